@@ -13,9 +13,14 @@ pub struct Cli {
 pub enum Commands {
     /// Initialize agman configuration
     Init {
-        /// Overwrite existing files with defaults
+        /// Overwrite existing files with defaults. Asks first when run from a
+        /// terminal and files have been customized; never asks otherwise
         #[arg(long, default_value_t = false)]
         force: bool,
+        /// Show how each customized file differs from the default and exit
+        /// without writing anything
+        #[arg(long, default_value_t = false, conflicts_with = "force")]
+        diff: bool,
     },
 
     /// Send a message to an agent's inbox
@@ -124,14 +129,21 @@ EXAMPLES:
   cat <<'EOF' | agman create-pm-task myproj myrepo fix-bug --first-prompt -
   Multi-line first prompt via stdin using the - sentinel.
   EOF
-  agman create-pm-task myproj myrepo fix-bug --first-prompt @./task-prompt.md")]
+  agman create-pm-task myproj myrepo fix-bug --first-prompt @./task-prompt.md
+  agman create-pm-task myproj myrepo --from-issue 42")]
     CreatePmTask {
         /// Project name
         project: String,
         /// Repository name
         repo: String,
-        /// Task name (becomes the branch name, e.g. 'fix-login-bug')
-        task_name: String,
+        /// Task name (becomes the branch name, e.g. 'fix-login-bug').
+        /// Derived from the issue title when --from-issue is given.
+        #[arg(required_unless_present = "from_issue")]
+        task_name: Option<String>,
+        /// Seed the task from a GitHub issue: its title names the branch and
+        /// its title/body become the first prompt
+        #[arg(long = "from-issue", value_name = "NUMBER")]
+        from_issue: Option<u64>,
         /// Optional first prompt sent to the attached engineer
         #[arg(
             long = "first-prompt",
@@ -155,6 +167,40 @@ EXAMPLES:
         task_id: String,
     },
 
+    /// Relaunch a task's engineer, recreating its tmux sessions if they were killed.
+    /// Same as `r` on a task in the TUI.
+    RestartTask {
+        /// Task identifier (repo--branch format)
+        task_id: String,
+    },
+
+    /// Tell a task's engineer to carry on, optionally with feedback.
+    /// Same as a quick answer (`m`) in the TUI.
+    #[command(after_help = "\
+EXAMPLES:
+  agman continue-task repo--branch
+  agman continue-task repo--branch \"Also cover the empty case\"
+  generate-review | agman continue-task repo--branch -
+  agman continue-task repo--branch @./review.md")]
+    ContinueTask {
+        /// Task identifier (repo--branch format)
+        task_id: String,
+        /// Feedback for the engineer (`-` reads stdin, `@path` a file).
+        /// Without it, or when it is blank, the engineer is told to carry on.
+        #[arg(allow_hyphen_values = true)]
+        feedback: Option<String>,
+        /// Read feedback from a file
+        #[arg(short = 'F', long)]
+        file: Option<std::path::PathBuf>,
+    },
+
+    /// Ask a task's engineer to compact its context (`compact_command` in
+    /// config, else the harness's `/compact`). Same as `K` in the TUI.
+    CompactTask {
+        /// Task identifier (repo--branch format)
+        task_id: String,
+    },
+
     /// Link a GitHub PR to a task so the TUI can display and open it
     #[command(after_help = "\
 EXAMPLES:
@@ -192,6 +238,19 @@ EXAMPLES:
         tail: usize,
     },
 
+    /// Export a task's agent log and engineer messages as markdown
+    #[command(after_help = "\
+EXAMPLES:
+  agman transcript backend--fix-login
+  agman transcript backend--fix-login --out fix-login.md")]
+    Transcript {
+        /// Task identifier (repo--branch format)
+        task_id: String,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        out: Option<std::path::PathBuf>,
+    },
+
     /// Show aggregated status across all projects and tasks
     Status,
 
@@ -466,8 +525,68 @@ EXAMPLES:
         #[arg(long, default_value_t = 120)]
         timeout: u64,
     },
+    /// Bundle a task's directory (meta, notes, logs) into a .tar.gz. The worktree is not included.
+    ExportTask {
+        /// Task ID (repo--branch format)
+        task_id: String,
+        /// Destination archive, e.g. task.tar.gz
+        file: std::path::PathBuf,
+    },
+    /// Recreate a task from an archive written by export-task and re-establish
+    /// its worktree when the repo and branch exist locally.
+    ImportTask {
+        /// Archive written by export-task
+        file: std::path::PathBuf,
+    },
+    /// Serve a read-only JSON API over HTTP (GET /tasks, /tasks/<id>, /tasks/<id>/log?tail=N)
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+        /// Address to bind; use 0.0.0.0 to expose beyond localhost
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: std::net::IpAddr,
+    },
     /// Restart the agman TUI binary itself to pick up a new version. Chat sessions are unaffected.
     Restart,
+
+    /// Tidy ~/.agman: purge expired archives, prune old dismissed notifications,
+    /// truncate oversized task logs and list orphan worktrees
+    Gc {
+        /// Also remove the orphan worktrees that were found
+        #[arg(long, default_value_t = false)]
+        yes: bool,
+    },
+
+    /// View or change ~/.agman/config.toml
+    #[command(after_help = "\
+EXAMPLES:
+  agman config list
+  agman config get harness
+  agman config set cleanup_on_merge keep_all
+  agman config set repos_dir ~/src")]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigAction {
+    /// Print every known key with its current value
+    List,
+    /// Print one key's value (empty when unset)
+    Get {
+        /// Config key, e.g. harness or archive_retention_days
+        key: String,
+    },
+    /// Validate and set one key
+    Set {
+        /// Config key, e.g. harness or archive_retention_days
+        key: String,
+        /// New value
+        value: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
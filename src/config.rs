@@ -1,8 +1,10 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use crate::harness::{Harness, HarnessKind};
+use crate::tmux::SessionWindow;
 
 /// Replace `/` with `-` in branch names so task directories stay flat.
 /// The real branch name is preserved in `meta.json`; the task ID is just a
@@ -25,20 +27,103 @@ pub struct Config {
     pub prompts_dir: PathBuf,
     pub repos_dir: PathBuf,
     pub notes_dir: PathBuf,
+    /// When set, worktrees live under `<worktrees_dir>/<repo>/` instead of
+    /// the `<repo>-wt/` directory beside each repo.
+    pub worktrees_dir: Option<PathBuf>,
 }
 
 /// On-disk config file (~/.agman/config.toml).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConfigFile {
     pub repos_dir: Option<String>,
+    /// Put worktrees under this directory (namespaced by repo) instead of
+    /// beside each repo. Useful for keeping worktrees on a different disk.
+    pub worktrees_dir: Option<String>,
     pub archive_retention_days: Option<u64>,
     pub telegram_bot_token: Option<String>,
     pub telegram_chat_id: Option<String>,
     /// Which agent harness to use for newly-spawned agents. `"claude"`,
     /// `"codex"`, `"goose"`, or `"pi"`. Defaults to `"claude"` when absent.
     pub harness: Option<String>,
+    /// Per-repo overrides of `harness`, keyed by repo name, for repos whose
+    /// tooling suits a different agent.
+    pub repo_harness: Option<BTreeMap<String, String>>,
+    /// Show the coffee-break badge in the TUI clock. Defaults to `false`.
+    pub break_enabled: Option<bool>,
+    /// Minutes between break reminders. Defaults to 50.
+    pub break_interval_mins: Option<u64>,
+    /// Seconds before the break is due at which the badge starts counting
+    /// down. Defaults to 300.
+    pub break_warning_secs: Option<u64>,
+    /// Regex used to scrape token/cost usage from agent.log. Named groups
+    /// `input`, `output` and `cost` (dollars) are summed per task.
+    pub usage_pattern: Option<String>,
+    /// Show the per-task COST column in the task list. Defaults to false.
+    pub show_cost_column: Option<bool>,
+    /// What `archive_task` reclaims for tasks with a linked PR:
+    /// `"worktree_only"`, `"branch_and_worktree"`, or `"keep_all"`.
+    /// Defaults to `"worktree_only"` when absent.
+    pub cleanup_on_merge: Option<String>,
+    /// Window layout for new task tmux sessions. Defaults to
+    /// nvim / lazygit / shell when absent.
+    pub session_windows: Option<Vec<SessionWindow>>,
+    /// Per-repo overrides of `session_windows`, keyed by repo name.
+    pub repo_session_windows: Option<BTreeMap<String, Vec<SessionWindow>>>,
+    /// Standing instructions prepended to every agent's system prompt
+    /// (coding conventions, "always run tests", ...).
+    pub system_preamble: Option<String>,
+    /// Per-repo overrides of `system_preamble`, keyed by repo name. A repo
+    /// entry replaces the global preamble rather than adding to it.
+    pub repo_system_preamble: Option<BTreeMap<String, String>>,
+    /// Shell command run in each fresh worktree of a repo (e.g.
+    /// `npm install`), keyed by repo name. Without an entry, a
+    /// `.agman-setup.sh` in the worktree is run instead when
+    /// `allow_setup_file` is on.
+    pub repo_setup_script: Option<BTreeMap<String, String>>,
+    /// Run a worktree's own `.agman-setup.sh` when its repo has no
+    /// `repo_setup_script` entry. The file comes from the checked-out
+    /// branch, which may be someone else's PR, so this defaults to false.
+    pub allow_setup_file: Option<bool>,
+    /// Flag a task "idle?" when its engineer's tmux window has been silent
+    /// this many seconds. Off when absent or 0.
+    pub agent_idle_secs: Option<u64>,
+    /// Also raise a desktop notification when a task goes idle. Defaults to
+    /// false.
+    pub agent_idle_notify: Option<bool>,
+    /// Default task list order: `"pinned_then_updated"`, `"updated_desc"`,
+    /// `"created_asc"`, or `"repo"`. Defaults to `"pinned_then_updated"`.
+    pub task_sort: Option<String>,
+    /// Niceness (0-19) agents are launched with, so they yield CPU to
+    /// interactive work. Off when absent.
+    pub agent_nice: Option<u64>,
+    /// Virtual memory cap in MiB applied with `ulimit -v` when an agent is
+    /// launched. Best-effort: not every platform enforces it. Off when absent.
+    pub agent_memory_limit_mb: Option<u64>,
+    /// Snapshot the worktrees of tasks with a running engineer to
+    /// `refs/agman/checkpoints/<branch>` this often. Off when absent or 0.
+    pub checkpoint_interval_mins: Option<u64>,
+    /// URL that receives a JSON POST whenever a task's engineer goes idle
+    /// (e.g. a Slack or Discord incoming webhook). Off when absent.
+    pub webhook_url: Option<String>,
+    /// Seconds between automatic refreshes of the project and task lists.
+    /// Defaults to 1.
+    pub refresh_secs: Option<u64>,
+    /// Open the TUI on a summary of what needs attention instead of the
+    /// project list. Defaults to false.
+    pub show_dashboard_on_start: Option<bool>,
+    /// Text sent to a task's engineer to compact its context. Defaults to
+    /// the harness's own command (`/compact`).
+    pub compact_command: Option<String>,
+    /// Regex matched against the last lines of each idle engineer's pane.
+    /// A match flags the task as waiting for input and raises a desktop
+    /// notification. Off when absent.
+    pub input_pattern: Option<String>,
 }
 
+/// Conventional per-repo setup script, run in fresh worktrees when config
+/// has no `repo_setup_script` entry for the repo and `allow_setup_file` is on.
+pub const SETUP_SCRIPT_FILE: &str = ".agman-setup.sh";
+
 /// Read `<base_dir>/config.toml`, returning defaults if missing or unparseable.
 pub fn load_config_file(base_dir: &Path) -> ConfigFile {
     let path = base_dir.join("config.toml");
@@ -76,6 +161,7 @@ impl Config {
             prompts_dir,
             repos_dir,
             notes_dir,
+            worktrees_dir: None,
         }
     }
 
@@ -89,7 +175,8 @@ impl Config {
             None => home_dir.join("repos"),
         };
 
-        let config = Self::new(base_dir, repos_dir);
+        let mut config = Self::new(base_dir, repos_dir);
+        config.worktrees_dir = config_file.worktrees_dir.map(PathBuf::from);
         tracing::debug!(base_dir = %config.base_dir.display(), repos_dir = %config.repos_dir.display(), "config loaded");
         Ok(config)
     }
@@ -160,16 +247,22 @@ impl Config {
         }
     }
 
-    /// Get worktree base path: ~/repos/<repo>-wt/
+    /// Get worktree base path: ~/repos/<repo>-wt/, or <worktrees_dir>/<repo>/
+    /// when `worktrees_dir` is configured.
     pub fn worktree_base(&self, repo_name: &str) -> PathBuf {
-        self.repos_dir.join(format!("{}-wt", repo_name))
+        match &self.worktrees_dir {
+            Some(dir) => dir.join(repo_name),
+            None => self.repos_dir.join(format!("{}-wt", repo_name)),
+        }
     }
 
     /// Get worktree base path, using `parent_dir` as base when provided (repos outside `repos_dir`).
+    /// A configured `worktrees_dir` takes precedence over both.
     pub fn worktree_base_for(&self, parent_dir: Option<&Path>, repo_name: &str) -> PathBuf {
-        match parent_dir {
-            Some(parent) => parent.join(format!("{}-wt", repo_name)),
-            None => self.worktree_base(repo_name),
+        match (parent_dir, &self.worktrees_dir) {
+            (_, Some(dir)) => dir.join(repo_name),
+            (Some(parent), None) => parent.join(format!("{}-wt", repo_name)),
+            (None, None) => self.worktree_base(repo_name),
         }
     }
 
@@ -205,6 +298,11 @@ impl Config {
         self.base_dir.join("repo_stats.json")
     }
 
+    /// Glob patterns of `repos_dir` entries hidden from repo selection.
+    pub fn repos_ignore_path(&self) -> PathBuf {
+        self.base_dir.join("repos-ignore")
+    }
+
     pub fn dismissed_notifications_path(&self) -> PathBuf {
         self.base_dir.join("dismissed_notifications.json")
     }
@@ -212,13 +310,58 @@ impl Config {
     /// Resolve the configured harness kind. Falls back to `Claude` when the
     /// `harness` config key is absent or unparseable.
     pub fn harness_kind(&self) -> HarnessKind {
+        self.harness_kind_for_repo(None)
+    }
+
+    /// Harness for an agent working in `repo_name`: the `repo_harness`
+    /// override, else the global `harness`, else `Claude`. Unparseable
+    /// values are skipped.
+    pub fn harness_kind_for_repo(&self, repo_name: Option<&str>) -> HarnessKind {
         let cf = load_config_file(&self.base_dir);
-        cf.harness
-            .as_deref()
-            .and_then(|raw| raw.parse().ok())
+        let repo_override = repo_name
+            .and_then(|repo| cf.repo_harness.and_then(|mut m| m.remove(repo)))
+            .and_then(|raw| raw.parse().ok());
+        repo_override
+            .or_else(|| cf.harness.as_deref().and_then(|raw| raw.parse().ok()))
             .unwrap_or(HarnessKind::Claude)
     }
 
+    /// Window layout for a task session in `repo_name`: the repo override,
+    /// else the global `session_windows`, else the built-in default.
+    pub fn session_windows(&self, repo_name: &str) -> Vec<SessionWindow> {
+        let cf = load_config_file(&self.base_dir);
+        cf.repo_session_windows
+            .and_then(|mut repos| repos.remove(repo_name))
+            .or(cf.session_windows)
+            .filter(|windows| !windows.is_empty())
+            .unwrap_or_else(SessionWindow::default_layout)
+    }
+
+    /// Resolve the standing agent preamble: the repo override wins over the
+    /// global `system_preamble`. Blank values count as unset.
+    pub fn system_preamble(&self, repo_name: Option<&str>) -> Option<String> {
+        let cf = load_config_file(&self.base_dir);
+        repo_name
+            .and_then(|repo| cf.repo_system_preamble.and_then(|mut m| m.remove(repo)))
+            .or(cf.system_preamble)
+            .filter(|p| !p.trim().is_empty())
+    }
+
+    /// Setup command for a fresh worktree of `repo_name`: the
+    /// `repo_setup_script` entry, else the worktree's own `.agman-setup.sh`
+    /// when `allow_setup_file` is on. Blank entries count as unset.
+    pub fn setup_script(&self, repo_name: &str, worktree_path: &Path) -> Option<String> {
+        let cf = load_config_file(&self.base_dir);
+        let allow_file = cf.allow_setup_file.unwrap_or(false);
+        cf.repo_setup_script
+            .and_then(|mut m| m.remove(repo_name))
+            .filter(|s| !s.trim().is_empty())
+            .or_else(|| {
+                (allow_file && worktree_path.join(SETUP_SCRIPT_FILE).is_file())
+                    .then(|| format!("sh {SETUP_SCRIPT_FILE}"))
+            })
+    }
+
     /// Return the configured harness as a trait object. Used at spawn sites
     /// for newly-launched long-lived agents.
     pub fn default_harness(&self) -> Box<dyn Harness> {
@@ -380,13 +523,26 @@ impl Config {
         self.templates_dir().join(format!("{name}.md"))
     }
 
+    /// Files `init_default_files` writes, with their default contents.
+    pub fn default_files(&self) -> Vec<(PathBuf, &'static str)> {
+        vec![(self.prompt_path("engineer"), ENGINEER_PROMPT)]
+    }
+
+    /// Default files that exist with contents other than the defaults, i.e.
+    /// the customizations `init_default_files(true)` would overwrite.
+    pub fn customized_default_files(&self) -> Vec<(PathBuf, &'static str)> {
+        self.default_files()
+            .into_iter()
+            .filter(|(path, content)| {
+                std::fs::read_to_string(path).is_ok_and(|current| current != *content)
+            })
+            .collect()
+    }
+
     pub fn init_default_files(&self, force: bool) -> Result<()> {
         self.ensure_dirs()?;
 
-        let prompts = [("engineer", ENGINEER_PROMPT)];
-
-        for (name, content) in prompts {
-            let path = self.prompt_path(name);
+        for (path, content) in self.default_files() {
             if force || !path.exists() {
                 std::fs::write(&path, content)?;
             }
@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 /// How many weeks to retain dismissed notification entries before pruning.
 pub const NOTIFICATION_RETENTION_WEEKS: i64 = 3;
 
+/// How long `s` hides a notification or task.
+pub const SNOOZE_HOURS: i64 = 4;
+
 /// A single dismissed notification entry, recording when the user dismissed it
 /// and the notification's `updated_at` at that moment. This allows detecting
 /// new activity: if a later poll shows a newer `updated_at`, the thread should
@@ -19,6 +22,11 @@ pub struct DismissedEntry {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DismissedNotifications {
     pub ids: HashMap<String, DismissedEntry>,
+    /// Snoozed thread IDs mapped to the RFC 3339 time they reappear. Unlike
+    /// dismissal this is local only and expires on its own, independent of
+    /// the retention window.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub snoozed: HashMap<String, String>,
 }
 
 /// Legacy format v1: `{ "ids": ["id1", "id2", ...] }` (HashSet<String>).
@@ -58,7 +66,10 @@ impl DismissedNotifications {
                     (id, entry)
                 })
                 .collect();
-            return Self { ids };
+            return Self {
+                ids,
+                snoozed: HashMap::new(),
+            };
         }
 
         // Fall back to v1 Vec format — migrate with current timestamp
@@ -75,7 +86,10 @@ impl DismissedNotifications {
                     (id, entry)
                 })
                 .collect();
-            return Self { ids };
+            return Self {
+                ids,
+                snoozed: HashMap::new(),
+            };
         }
 
         Self::empty()
@@ -127,6 +141,30 @@ impl DismissedNotifications {
         current_updated_at > entry.updated_at.as_str()
     }
 
+    /// Hide a notification until `until`, without dismissing it on GitHub.
+    pub fn snooze(&mut self, id: String, until: DateTime<Utc>) {
+        self.snoozed.insert(id, until.to_rfc3339());
+    }
+
+    pub fn is_snoozed(&self, id: &str) -> bool {
+        self.snoozed.get(id).is_some_and(|until| {
+            DateTime::parse_from_rfc3339(until).is_ok_and(|until| until > Utc::now())
+        })
+    }
+
+    /// Drop snoozes whose time has passed so those notifications reappear.
+    /// Returns the number of entries removed.
+    pub fn prune_expired_snoozes(&mut self) -> usize {
+        let now = Utc::now();
+        let before = self.snoozed.len();
+        self.snoozed.retain(|_id, until| {
+            DateTime::parse_from_rfc3339(until)
+                .map(|ts| ts > now)
+                .unwrap_or(false)
+        });
+        before - self.snoozed.len()
+    }
+
     /// Remove entries older than `max_age`. Returns the number of entries pruned.
     pub fn prune_older_than(&mut self, max_age: Duration) -> usize {
         let cutoff = Utc::now() - max_age;
@@ -146,6 +184,7 @@ impl DismissedNotifications {
     fn empty() -> Self {
         Self {
             ids: HashMap::new(),
+            snoozed: HashMap::new(),
         }
     }
 }
@@ -428,6 +428,134 @@ impl Git {
         Ok(())
     }
 
+    /// Clone `url` into `dest` (`git clone <url> <dest>`), passing each of
+    /// git's progress lines ("Receiving objects:  42% ...") to `on_progress`
+    /// as it arrives.
+    pub fn clone_repo(url: &str, dest: &Path, mut on_progress: impl FnMut(&str)) -> Result<()> {
+        use std::io::Read;
+
+        tracing::info!(url = url, dest = %dest.display(), "cloning repo");
+        let mut child = Command::new("git")
+            .args(["clone", "--progress", "--"])
+            .arg(url)
+            .arg(dest)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("Failed to run git clone")?;
+
+        // Progress lines are redrawn with `\r`, so split on both breaks.
+        let mut stderr = child.stderr.take().context("git clone has no stderr")?;
+        let mut lines = Vec::new();
+        let mut line = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = stderr
+                .read(&mut buf)
+                .context("Failed to read git clone output")?;
+            if n == 0 {
+                break;
+            }
+            for &byte in &buf[..n] {
+                if byte != b'\r' && byte != b'\n' {
+                    line.push(byte);
+                    continue;
+                }
+                let text = String::from_utf8_lossy(&line).trim().to_string();
+                line.clear();
+                if !text.is_empty() {
+                    on_progress(&text);
+                    lines.push(text);
+                }
+            }
+        }
+        let status = child.wait().context("Failed to run git clone")?;
+
+        if !status.success() {
+            let errors: Vec<String> = lines.into_iter().filter(|l| !l.contains('%')).collect();
+            anyhow::bail!("Failed to clone '{}': {}", url, errors.join("\n"));
+        }
+
+        Ok(())
+    }
+
+    /// Rebase the branch checked out in `worktree_path` onto `base_ref`.
+    /// On failure (typically conflicts or a dirty tree) the rebase is aborted
+    /// so the worktree is left as it was, and the git error is returned.
+    pub fn rebase_onto(worktree_path: &Path, base_ref: &str) -> Result<()> {
+        tracing::info!(worktree = %worktree_path.display(), base_ref, "rebasing worktree");
+        let output = Command::new("git")
+            .current_dir(worktree_path)
+            .args(["rebase", base_ref])
+            .output()
+            .context("Failed to run git rebase")?;
+
+        if !output.status.success() {
+            let _ = Command::new("git")
+                .current_dir(worktree_path)
+                .args(["rebase", "--abort"])
+                .output();
+            let detail = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            anyhow::bail!(
+                "Rebase onto {} failed and was aborted: {}",
+                base_ref,
+                detail.trim()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Rename a local branch (`git branch -m <old> <new>`).
+    pub fn rename_branch(repo_path: &PathBuf, old_name: &str, new_name: &str) -> Result<()> {
+        tracing::info!(old = old_name, new = new_name, "renaming branch");
+        let output = Command::new("git")
+            .current_dir(repo_path)
+            .args(["branch", "-m", old_name, new_name])
+            .output()
+            .context("Failed to rename branch")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to rename branch '{}' to '{}': {}",
+                old_name,
+                new_name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Move a worktree directory (`git worktree move <from> <to>`).
+    pub fn move_worktree(repo_path: &PathBuf, from: &Path, to: &Path) -> Result<()> {
+        tracing::info!(from = %from.display(), to = %to.display(), "moving worktree");
+        let output = Command::new("git")
+            .current_dir(repo_path)
+            .args([
+                "worktree",
+                "move",
+                from.to_str().unwrap(),
+                to.to_str().unwrap(),
+            ])
+            .output()
+            .context("Failed to move worktree")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to move worktree to {}: {}",
+                to.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+
     /// Run direnv allow in a directory
     pub fn direnv_allow(path: &Path) -> Result<()> {
         let output = Command::new("direnv")
@@ -449,6 +577,21 @@ impl Git {
         }
     }
 
+    /// Re-link a worktree with its repo after either moved on disk.
+    pub fn repair_worktree(repo_path: &Path, worktree_path: &Path) -> Result<()> {
+        let output = Command::new("git")
+            .current_dir(repo_path)
+            .args(["worktree", "repair", worktree_path.to_str().unwrap()])
+            .output()
+            .context("Failed to run git worktree repair")?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git worktree repair failed: {}", err.trim());
+        }
+        Ok(())
+    }
+
     /// Return the path of an existing worktree checked out for `branch`,
     /// if any. Wraps `list_worktrees` with a linear scan.
     pub fn find_worktree_for_branch(repo_path: &Path, branch: &str) -> Result<Option<PathBuf>> {
@@ -460,11 +603,156 @@ impl Git {
             .map(|(_, p)| p))
     }
 
+    /// True if `rev` resolves to a commit in the repo (branch, remote ref, tag or SHA).
+    pub fn commit_exists(repo_path: &Path, rev: &str) -> bool {
+        Self::ref_exists(&repo_path.to_path_buf(), &format!("{rev}^{{commit}}"))
+    }
+
+    /// Point the upstream of the branch checked out in `worktree_path` at
+    /// `upstream` (`git branch --set-upstream-to`).
+    pub fn set_upstream(worktree_path: &Path, upstream: &str) -> Result<()> {
+        tracing::info!(worktree = %worktree_path.display(), upstream, "setting branch upstream");
+        let output = Command::new("git")
+            .current_dir(worktree_path)
+            .args(["branch", &format!("--set-upstream-to={upstream}")])
+            .output()
+            .context("Failed to set branch upstream")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to set upstream to '{}': {}",
+                upstream,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Snapshot the worktree (tracked and untracked files) as a commit on
+    /// `ref_name` without touching the branch, HEAD or the real index. The
+    /// snapshot is built in a throwaway index and chained onto the previous
+    /// snapshot at `ref_name` (or HEAD for the first one). Returns the new
+    /// commit, or `None` when nothing changed since the last snapshot.
+    pub fn checkpoint(worktree_path: &Path, ref_name: &str) -> Result<Option<String>> {
+        // Unique per call so overlapping checkpoints of one worktree never
+        // share an index.
+        let index_name = format!("agman-checkpoint-index-{}", uuid::Uuid::new_v4());
+        let index_path = worktree_path.join(Self::git_stdout(
+            worktree_path,
+            &["rev-parse", "--git-path", &index_name],
+            None,
+        )?);
+        let result = Self::checkpoint_with_index(worktree_path, ref_name, &index_path);
+        let _ = std::fs::remove_file(&index_path);
+        result
+    }
+
+    fn checkpoint_with_index(
+        worktree_path: &Path,
+        ref_name: &str,
+        index_path: &Path,
+    ) -> Result<Option<String>> {
+        let index = Some(index_path);
+        Self::git_stdout(worktree_path, &["read-tree", "HEAD"], index)?;
+        Self::git_stdout(worktree_path, &["add", "-A"], index)?;
+        let tree = Self::git_stdout(worktree_path, &["write-tree"], index)?;
+
+        let parent = if Self::commit_exists(worktree_path, ref_name) {
+            ref_name
+        } else {
+            "HEAD"
+        };
+        let parent_tree = Self::git_stdout(
+            worktree_path,
+            &["rev-parse", &format!("{parent}^{{tree}}")],
+            None,
+        )?;
+        if parent_tree == tree {
+            return Ok(None);
+        }
+
+        let commit = Self::git_stdout(
+            worktree_path,
+            &["commit-tree", &tree, "-p", parent, "-m", "agman checkpoint"],
+            None,
+        )?;
+        Self::git_stdout(worktree_path, &["update-ref", ref_name, &commit], None)?;
+        tracing::info!(worktree = %worktree_path.display(), ref_name, commit = %commit, "wrote checkpoint");
+        Ok(Some(commit))
+    }
+
+    /// Create a lightweight tag `name` at the worktree's HEAD.
+    pub fn create_tag(worktree_path: &Path, name: &str) -> Result<()> {
+        Self::git_stdout(worktree_path, &["tag", name, "HEAD"], None)?;
+        tracing::info!(worktree = %worktree_path.display(), tag = name, "created tag");
+        Ok(())
+    }
+
+    /// Tag names matching the glob `pattern` (`git tag --list`).
+    pub fn list_tags(repo_path: &Path, pattern: &str) -> Result<Vec<String>> {
+        let out = Self::git_stdout(repo_path, &["tag", "--list", pattern], None)?;
+        Ok(out.lines().map(str::to_string).collect())
+    }
+
+    /// Move the worktree's branch, index and files to `rev`
+    /// (`git reset --hard`). Uncommitted changes are lost.
+    pub fn reset_hard(worktree_path: &Path, rev: &str) -> Result<()> {
+        tracing::info!(worktree = %worktree_path.display(), rev, "resetting worktree");
+        Self::git_stdout(worktree_path, &["reset", "--hard", rev], None)?;
+        Ok(())
+    }
+
+    /// True when the worktree has uncommitted changes or untracked files.
+    pub fn is_dirty(worktree_path: &Path) -> Result<bool> {
+        let out = Self::git_stdout(worktree_path, &["status", "--porcelain"], None)?;
+        Ok(!out.is_empty())
+    }
+
+    /// Run git in `dir` (optionally against another index file) and return
+    /// trimmed stdout, failing with stderr on a non-zero exit.
+    fn git_stdout(dir: &Path, args: &[&str], index_file: Option<&Path>) -> Result<String> {
+        let mut cmd = Command::new("git");
+        cmd.current_dir(dir).args(args);
+        if let Some(index_file) = index_file {
+            cmd.env("GIT_INDEX_FILE", index_file);
+        }
+        let output = cmd
+            .output()
+            .with_context(|| format!("Failed to run git {}", args[0]))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
     /// True if a local branch by this name exists in the repo.
     pub fn local_branch_exists(repo_path: &Path, branch: &str) -> bool {
         Self::ref_exists(&repo_path.to_path_buf(), &format!("refs/heads/{}", branch))
     }
 
+    /// True if `refs/remotes/origin/<branch>` exists in the repo (as of the
+    /// last fetch).
+    pub fn remote_branch_exists(repo_path: &Path, branch: &str) -> bool {
+        Self::ref_exists(
+            &repo_path.to_path_buf(),
+            &format!("refs/remotes/origin/{}", branch),
+        )
+    }
+
+    /// Number of commits reachable from `branch` but not from `base`
+    /// (`git rev-list --count base..branch`).
+    pub fn commits_ahead(repo_path: &Path, base: &str, branch: &str) -> Result<u64> {
+        let range = format!("{}..{}", base, branch);
+        let out = Self::git_stdout(repo_path, &["rev-list", "--count", &range], None)?;
+        out.parse::<u64>()
+            .with_context(|| format!("unexpected rev-list output: {}", out))
+    }
+
     /// Reviewer-only worktree creation helper.
     ///
     /// Always fetches origin first, verifies that `refs/remotes/origin/<branch>`
@@ -89,6 +89,10 @@ impl Harness for ClaudeHarness {
         ensure_workspace_trusted_in(&claude_trust_file_path(), cwd)
     }
 
+    fn compact_command(&self) -> Option<&'static str> {
+        Some("/compact")
+    }
+
     fn kill_pane(&self, session: &str, window: Option<&str>) -> Result<()> {
         kill_pane_via_slash(session, window, "/exit", 2)
     }
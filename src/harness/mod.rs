@@ -273,6 +273,12 @@ pub trait Harness: Send + Sync {
     /// - Pi: best-effort paste `/name <name>` + Enter.
     fn register_session_name(&self, ctx: &RegisterContext) -> Result<()>;
 
+    /// Slash command that compacts the running conversation, if the
+    /// harness has one. Goose has none.
+    fn compact_command(&self) -> Option<&'static str> {
+        None
+    }
+
     /// Tear down the foreground agent in a tmux pane gracefully.
     /// - Claude: `/exit` + Enter, fallback Ctrl-C × 2.
     /// - Codex:  `/quit` + Enter, fallback Ctrl-C × 3.
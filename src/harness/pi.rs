@@ -107,6 +107,10 @@ impl Harness for PiHarness {
         Ok(())
     }
 
+    fn compact_command(&self) -> Option<&'static str> {
+        Some("/compact")
+    }
+
     fn kill_pane(&self, session: &str, window: Option<&str>) -> Result<()> {
         super::claude::kill_pane_via_slash(session, window, "/quit", 3)
     }
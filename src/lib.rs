@@ -8,9 +8,11 @@ pub mod inbox;
 pub mod migration;
 pub mod project;
 pub mod repo_stats;
+pub mod serve;
 pub mod supervisor;
 pub mod task;
 pub mod telegram;
 pub mod templates;
 pub mod tmux;
+pub mod usage;
 pub mod use_cases;
@@ -11,7 +11,7 @@ use agman::supervisor;
 use agman::task::Task;
 use agman::tmux::Tmux;
 use agman::use_cases;
-use cli::{AgentKindArg, Cli, Commands};
+use cli::{AgentKindArg, Cli, Commands, ConfigAction};
 use tui::run_tui;
 
 fn resolve_text_arg(
@@ -72,11 +72,7 @@ fn main() -> Result<()> {
     use_cases::purge_chief_of_staff_agents(&config);
 
     match cli.command {
-        Some(Commands::Init { force }) => {
-            config.init_default_files(force)?;
-            println!("agman initialized at {}", config.base_dir.display());
-            Ok(())
-        }
+        Some(Commands::Init { force, diff }) => cmd_init(&config, force, diff),
 
         Some(Commands::SendMessage {
             target,
@@ -124,8 +120,16 @@ fn main() -> Result<()> {
             project,
             repo,
             task_name,
+            from_issue,
+            first_prompt,
+        }) => cmd_create_pm_task(
+            &config,
+            &project,
+            &repo,
+            task_name,
+            from_issue,
             first_prompt,
-        }) => cmd_create_pm_task(&config, &project, &repo, &task_name, first_prompt),
+        ),
 
         Some(Commands::ListPmTasks { project }) => cmd_list_pm_tasks(&config, &project),
 
@@ -133,6 +137,16 @@ fn main() -> Result<()> {
 
         Some(Commands::TaskInfo { task_id }) => cmd_task_info(&config, &task_id),
 
+        Some(Commands::RestartTask { task_id }) => cmd_restart_task(&config, &task_id),
+
+        Some(Commands::ContinueTask {
+            task_id,
+            feedback,
+            file,
+        }) => cmd_continue_task(&config, &task_id, feedback.as_deref(), file.as_deref()),
+
+        Some(Commands::CompactTask { task_id }) => cmd_compact_task(&config, &task_id),
+
         Some(Commands::LinkPr {
             task_id,
             pr,
@@ -153,6 +167,10 @@ fn main() -> Result<()> {
 
         Some(Commands::TaskLog { task_id, tail }) => cmd_task_log(&config, &task_id, tail),
 
+        Some(Commands::Transcript { task_id, out }) => {
+            cmd_transcript(&config, &task_id, out.as_deref())
+        }
+
         Some(Commands::CreateAgent {
             kind,
             name,
@@ -305,6 +323,18 @@ fn main() -> Result<()> {
 
         Some(Commands::Restart) => cmd_restart(),
 
+        Some(Commands::Gc { yes }) => cmd_gc(&config, yes),
+
+        Some(Commands::Serve { port, bind }) => {
+            agman::serve::run(config.clone(), std::net::SocketAddr::new(bind, port))
+        }
+
+        Some(Commands::ExportTask { task_id, file }) => cmd_export_task(&config, &task_id, &file),
+
+        Some(Commands::ImportTask { file }) => cmd_import_task(&config, &file),
+
+        Some(Commands::Config { action }) => cmd_config(&config, action),
+
         None => {
             // No subcommand - launch TUI
             config.ensure_dirs()?;
@@ -497,9 +527,22 @@ fn cmd_create_pm_task(
     config: &Config,
     project: &str,
     repo: &str,
-    task_name: &str,
+    task_name: Option<String>,
+    from_issue: Option<u64>,
     first_prompt: Option<String>,
 ) -> Result<()> {
+    let issue = match from_issue {
+        Some(number) => Some(use_cases::fetch_issue(&config.repo_path(repo), number)?),
+        None => None,
+    };
+    let task_name = match (task_name, &issue) {
+        (Some(name), _) => name,
+        (None, Some(issue)) => use_cases::issue_branch_name(issue),
+        (None, None) => anyhow::bail!("task-name is required unless --from-issue is given"),
+    };
+    let task_name = task_name.as_str();
+    use_cases::validate_branch_name(task_name)?;
+
     // Reject protected branch names
     if matches!(task_name, "main" | "master" | "develop") {
         anyhow::bail!(
@@ -524,7 +567,7 @@ fn cmd_create_pm_task(
 
     let first_prompt = match first_prompt {
         Some(prompt) => Some(resolve_text_arg(Some(&prompt), None, "first-prompt")?),
-        None => None,
+        None => issue.as_ref().map(use_cases::issue_first_prompt),
     };
 
     let mut task =
@@ -541,7 +584,8 @@ fn cmd_create_pm_task(
 }
 
 fn cmd_list_pm_tasks(config: &Config, project: &str) -> Result<()> {
-    let tasks = use_cases::list_project_tasks(config, project)?;
+    let mut tasks = use_cases::list_project_tasks(config, project)?;
+    use_cases::sort_tasks(&mut tasks, use_cases::load_task_sort(config));
 
     if tasks.is_empty() {
         println!("No tasks in project '{}'.", project);
@@ -575,6 +619,68 @@ fn cmd_task_info(config: &Config, task_id: &str) -> Result<()> {
     Ok(())
 }
 
+fn cmd_continue_task(
+    config: &Config,
+    task_id: &str,
+    feedback: Option<&str>,
+    file: Option<&std::path::Path>,
+) -> Result<()> {
+    let task = Task::load_by_id(config, task_id)?;
+    let display_id = task.meta.task_id();
+    // Only read stdin when asked to (`-`): a bare `continue-task` in a
+    // script must not block on a pipe that never closes.
+    let feedback = match (feedback, file) {
+        (None, None) => String::new(),
+        (feedback, file) => resolve_text_arg(feedback, file, "feedback")?,
+    };
+    if feedback.trim().is_empty() {
+        use_cases::continue_engineer(config, &display_id)?;
+        println!("Told {}'s engineer to carry on", display_id);
+    } else {
+        use_cases::answer_engineer(config, &display_id, feedback.trim_end())?;
+        println!("Feedback sent to {}'s engineer", display_id);
+    }
+    Ok(())
+}
+
+fn cmd_compact_task(config: &Config, task_id: &str) -> Result<()> {
+    let task = Task::load_by_id(config, task_id)?;
+    let display_id = task.meta.task_id();
+    let command = use_cases::compact_engineer(config, &display_id)?;
+    println!("Sent {} to {}'s engineer", command, display_id);
+    Ok(())
+}
+
+fn cmd_restart_task(config: &Config, task_id: &str) -> Result<()> {
+    let mut task = Task::load_by_id(config, task_id)?;
+    if task.meta.archived_at.is_some() {
+        anyhow::bail!("Task '{}' is archived", task.meta.task_id());
+    }
+    if !task.meta.has_repos() && !task.meta.is_multi_repo() {
+        anyhow::bail!("Task '{}' has no repos configured", task.meta.task_id());
+    }
+
+    let display_id = task.meta.task_id();
+    supervisor::ensure_task_tmux(config, &task)
+        .with_context(|| format!("failed to prepare tmux for task '{}'", display_id))?;
+    let outcome = supervisor::launch_next_step(config, &mut task)
+        .with_context(|| format!("failed to launch engineer for task '{}'", display_id))?;
+
+    tracing::info!(task_id = %display_id, "restarted task engineer via CLI");
+    match outcome {
+        supervisor::AdvanceOutcome::Launched { session_name } => {
+            println!(
+                "Restarted engineer for '{}' in {}",
+                display_id, session_name
+            );
+        }
+        supervisor::AdvanceOutcome::Stopped => {
+            println!("Task '{}' has nothing to launch", display_id);
+        }
+    }
+    Ok(())
+}
+
 fn cmd_link_pr(
     config: &Config,
     task_id: &str,
@@ -613,6 +719,19 @@ fn cmd_task_log(config: &Config, task_id: &str, tail: usize) -> Result<()> {
     Ok(())
 }
 
+fn cmd_transcript(config: &Config, task_id: &str, out: Option<&std::path::Path>) -> Result<()> {
+    let markdown = use_cases::task_transcript(config, task_id)?;
+    match out {
+        Some(path) => {
+            std::fs::write(path, &markdown)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("Transcript written to {}", path.display());
+        }
+        None => print!("{}", markdown),
+    }
+    Ok(())
+}
+
 fn format_relative_time(dt: chrono::DateTime<chrono::Utc>) -> String {
     let now = chrono::Utc::now();
     let duration = now.signed_duration_since(dt);
@@ -971,6 +1090,149 @@ fn cmd_respawn_agent(config: &Config, target: &str, force: bool, timeout: u64) -
     Ok(())
 }
 
+fn cmd_config(config: &Config, action: ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::List => {
+            for (key, value) in use_cases::list_config_values(config) {
+                println!("{} = {}", key, value.as_deref().unwrap_or("(unset)"));
+            }
+        }
+        ConfigAction::Get { key } => {
+            if let Some(value) = use_cases::get_config_value(config, &key)? {
+                println!("{}", value);
+            }
+        }
+        ConfigAction::Set { key, value } => {
+            use_cases::set_config_value(config, &key, &value)?;
+            println!("Set {} = {}", key, value);
+        }
+    }
+    Ok(())
+}
+
+fn cmd_export_task(config: &Config, task_id: &str, file: &std::path::Path) -> Result<()> {
+    use_cases::export_task(config, task_id, file)?;
+    println!("Exported '{}' to {}", task_id, file.display());
+    Ok(())
+}
+
+fn cmd_import_task(config: &Config, file: &std::path::Path) -> Result<()> {
+    let imported = use_cases::import_task(config, file)?;
+    for warning in &imported.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    println!("Imported '{}'", imported.task_id);
+    println!(
+        "Run `agman restart-task {}` to relaunch its engineer.",
+        imported.task_id
+    );
+    Ok(())
+}
+
+fn cmd_init(config: &Config, force: bool, diff: bool) -> Result<()> {
+    use std::io::{IsTerminal, Write as _};
+
+    let customized = config.customized_default_files();
+    if diff {
+        if customized.is_empty() {
+            println!("All default files match the defaults");
+        }
+        for (path, default) in &customized {
+            print_default_diff(path, default)?;
+        }
+        return Ok(());
+    }
+
+    if force && !customized.is_empty() && std::io::stdin().is_terminal() {
+        println!("--force will overwrite these customized files:");
+        for (path, _) in &customized {
+            println!("  {}", path.display());
+        }
+        print!("Overwrite them? [y/N] ");
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            println!("Aborted; nothing was written. Use --diff to review the changes.");
+            return Ok(());
+        }
+    }
+
+    config.init_default_files(force)?;
+    println!("agman initialized at {}", config.base_dir.display());
+    Ok(())
+}
+
+/// `diff -u` the file at `path` against `default`, which is fed on stdin.
+fn print_default_diff(path: &std::path::Path, default: &str) -> Result<()> {
+    use std::io::Write as _;
+
+    let current_label = path.display().to_string();
+    let mut child = std::process::Command::new("diff")
+        .args(["-u", "-L", &current_label, "-L", "default", "--"])
+        .arg(path)
+        .arg("-")
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .context("failed to run diff")?;
+    child
+        .stdin
+        .take()
+        .context("diff stdin unavailable")?
+        .write_all(default.as_bytes())?;
+    // diff exits 1 when the inputs differ, which is the expected case here.
+    child.wait().context("failed to wait for diff")?;
+    Ok(())
+}
+
+fn cmd_gc(config: &Config, remove_orphans: bool) -> Result<()> {
+    let report = use_cases::gc_store(config, remove_orphans)?;
+
+    println!("Purged {} expired archived task(s)", report.archives_purged);
+    println!(
+        "Pruned {} dismissed notification entr(ies)",
+        report.notifications_pruned
+    );
+    println!(
+        "Truncated {} task log(s) over {} MB",
+        report.logs_truncated,
+        use_cases::GC_LOG_MAX_BYTES / (1024 * 1024)
+    );
+    if report.orphan_worktrees.is_empty() {
+        println!("No orphan worktrees");
+    } else {
+        let verb = if report.orphans_removed {
+            "Removed"
+        } else {
+            "Found"
+        };
+        println!(
+            "{} {} orphan worktree(s):",
+            verb,
+            report.orphan_worktrees.len()
+        );
+        for (repo, path) in &report.orphan_worktrees {
+            if report.dirty_orphans.contains(path) {
+                println!(
+                    "  {}  {}  (kept: uncommitted changes)",
+                    repo,
+                    path.display()
+                );
+            } else {
+                println!("  {}  {}", repo, path.display());
+            }
+        }
+        if !report.orphans_removed {
+            println!("Re-run with --yes to remove them.");
+        }
+    }
+    println!(
+        "Reclaimed {:.1} MB",
+        report.reclaimed_bytes as f64 / (1024.0 * 1024.0)
+    );
+    Ok(())
+}
+
 fn cmd_restart() -> Result<()> {
     let signal_file = dirs::home_dir()
         .context("could not determine home directory")?
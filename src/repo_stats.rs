@@ -1,21 +1,27 @@
+use chrono::{DateTime, Duration, Utc};
 use std::collections::HashMap;
 use std::path::Path;
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+use crate::task::TaskMeta;
+use crate::usage::UsageTotals;
+
+/// How many recently used repos the new-task picker shows above favourites.
+pub const RECENT_REPOS_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct RepoStats {
     pub counts: HashMap<String, u64>,
+    /// When a task was last created in each repo.
+    #[serde(default)]
+    pub last_used: HashMap<String, DateTime<Utc>>,
 }
 
 impl RepoStats {
     pub fn load(path: &Path) -> Self {
         if let Ok(data) = std::fs::read_to_string(path) {
-            serde_json::from_str(&data).unwrap_or(Self {
-                counts: HashMap::new(),
-            })
+            serde_json::from_str(&data).unwrap_or_default()
         } else {
-            Self {
-                counts: HashMap::new(),
-            }
+            Self::default()
         }
     }
 
@@ -29,6 +35,22 @@ impl RepoStats {
         *self.counts.entry(repo_name.to_string()).or_insert(0) += 1;
     }
 
+    /// Record that a task was just created in `repo_name`.
+    pub fn touch(&mut self, repo_name: &str, at: DateTime<Utc>) {
+        self.last_used.insert(repo_name.to_string(), at);
+    }
+
+    /// Return up to `limit` repos, most recently used first.
+    pub fn recent(&self, limit: usize) -> Vec<String> {
+        let mut entries: Vec<(&String, &DateTime<Utc>)> = self.last_used.iter().collect();
+        entries.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        entries
+            .into_iter()
+            .take(limit)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
     /// Return repos sorted by count descending, only repos with count > 0
     pub fn favorites(&self) -> Vec<(String, u64)> {
         let mut entries: Vec<(String, u64)> = self
@@ -41,3 +63,138 @@ impl RepoStats {
         entries
     }
 }
+
+/// Per-repo task totals shown in the repo-stats dashboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoTaskTotals {
+    pub repo_name: String,
+    pub active: u64,
+    pub archived: u64,
+    /// Archived tasks that had a linked PR when they were archived.
+    pub merged: u64,
+    /// Mean `archived_at - created_at` across merged tasks, if any.
+    pub avg_time_to_merge: Option<Duration>,
+    /// Summed usage across all the repo's tasks (see [`crate::usage`]).
+    pub usage: UsageTotals,
+}
+
+/// Column the repo-stats dashboard is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepoStatsSort {
+    #[default]
+    Active,
+    Archived,
+    Merged,
+    TimeToMerge,
+    Cost,
+    Name,
+}
+
+impl RepoStatsSort {
+    /// Cycle to the next sort column.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Active => Self::Archived,
+            Self::Archived => Self::Merged,
+            Self::Merged => Self::TimeToMerge,
+            Self::TimeToMerge => Self::Cost,
+            Self::Cost => Self::Name,
+            Self::Name => Self::Active,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Archived => "archived",
+            Self::Merged => "merged",
+            Self::TimeToMerge => "time to merge",
+            Self::Cost => "cost",
+            Self::Name => "name",
+        }
+    }
+}
+
+/// Aggregate per-repo totals across active and archived tasks.
+///
+/// Multi-repo tasks count once for each repo they touch, usage included. A task counts as
+/// merged when it was archived with a linked PR; its time-to-merge is the
+/// span between `created_at` and `archived_at`.
+pub fn repo_task_totals(tasks: &[TaskMeta]) -> Vec<RepoTaskTotals> {
+    let mut by_repo: HashMap<String, (RepoTaskTotals, Vec<Duration>)> = HashMap::new();
+
+    for meta in tasks {
+        let repo_names: Vec<&str> = if meta.repos.is_empty() {
+            vec![meta.name.as_str()]
+        } else {
+            meta.repos.iter().map(|r| r.repo_name.as_str()).collect()
+        };
+
+        for repo_name in repo_names {
+            let (totals, merge_times) = by_repo.entry(repo_name.to_string()).or_insert_with(|| {
+                (
+                    RepoTaskTotals {
+                        repo_name: repo_name.to_string(),
+                        active: 0,
+                        archived: 0,
+                        merged: 0,
+                        avg_time_to_merge: None,
+                        usage: UsageTotals::default(),
+                    },
+                    Vec::new(),
+                )
+            });
+
+            totals.usage.input_tokens += meta.total_input_tokens;
+            totals.usage.output_tokens += meta.total_output_tokens;
+            totals.usage.cost_cents += meta.total_cost_cents;
+
+            match meta.archived_at {
+                None => totals.active += 1,
+                Some(archived_at) => {
+                    totals.archived += 1;
+                    if meta.linked_pr.is_some() {
+                        totals.merged += 1;
+                        merge_times.push(archived_at.signed_duration_since(meta.created_at));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut rows: Vec<RepoTaskTotals> = by_repo
+        .into_values()
+        .map(|(mut totals, merge_times)| {
+            if !merge_times.is_empty() {
+                let total: Duration = merge_times.iter().fold(Duration::zero(), |acc, d| acc + *d);
+                totals.avg_time_to_merge = Some(total / merge_times.len() as i32);
+            }
+            totals
+        })
+        .collect();
+    sort_repo_task_totals(&mut rows, RepoStatsSort::default());
+    rows
+}
+
+/// Sort dashboard rows by the given column. Counts and durations sort
+/// descending; names sort ascending. Ties fall back to the repo name.
+pub fn sort_repo_task_totals(rows: &mut [RepoTaskTotals], sort: RepoStatsSort) {
+    rows.sort_by(|a, b| {
+        let primary = match sort {
+            RepoStatsSort::Active => b.active.cmp(&a.active),
+            RepoStatsSort::Archived => b.archived.cmp(&a.archived),
+            RepoStatsSort::Merged => b.merged.cmp(&a.merged),
+            RepoStatsSort::TimeToMerge => b.avg_time_to_merge.cmp(&a.avg_time_to_merge),
+            RepoStatsSort::Cost => (
+                b.usage.cost_cents,
+                b.usage.input_tokens + b.usage.output_tokens,
+            )
+                .cmp(&(
+                    a.usage.cost_cents,
+                    a.usage.input_tokens + a.usage.output_tokens,
+                )),
+            RepoStatsSort::Name => std::cmp::Ordering::Equal,
+        };
+        primary.then(a.repo_name.cmp(&b.repo_name))
+    });
+}
@@ -0,0 +1,180 @@
+//! Read-only HTTP JSON API (`agman serve`).
+//!
+//! Exposes task state for dashboards without going through the TUI:
+//!
+//! - `GET /tasks` — every task's metadata
+//! - `GET /tasks/<id>` — one task's metadata
+//! - `GET /tasks/<id>/log?tail=N` — the last N lines of its agent.log
+//!
+//! The server speaks just enough HTTP/1.1 for `curl` and `fetch`: one request
+//! per connection, no keep-alive, no request bodies. It binds to localhost
+//! unless told otherwise.
+
+use std::net::SocketAddr;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::config::Config;
+use crate::task::Task;
+use crate::use_cases;
+
+/// Default number of log lines returned by `/tasks/<id>/log`.
+const DEFAULT_LOG_TAIL: usize = 50;
+
+/// Largest request head accepted before the connection is rejected.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// A routed response: HTTP status code plus JSON body.
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Run the API server until the process is interrupted.
+pub fn run(config: Config, addr: SocketAddr) -> Result<()> {
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(async move {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("failed to bind {addr}"))?;
+        tracing::info!(%addr, "serving read-only task API");
+        println!("Serving agman API on http://{addr}");
+
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let config = config.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(config, stream).await {
+                    tracing::debug!(%peer, error = %e, "api connection failed");
+                }
+            });
+        }
+    })
+}
+
+async fn handle_connection(config: Config, mut stream: TcpStream) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.len() > MAX_REQUEST_BYTES {
+            break;
+        }
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut parts = head.lines().next().unwrap_or("").split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        _ if buf.len() > MAX_REQUEST_BYTES => Response::error(431, "request too large"),
+        (Some(method), Some(target)) => {
+            let (method, target) = (method.to_string(), target.to_string());
+            tokio::task::spawn_blocking(move || route(&config, &method, &target))
+                .await
+                .unwrap_or_else(|_| Response::error(500, "internal error"))
+        }
+        _ => Response::error(400, "malformed request"),
+    };
+
+    let body = serde_json::to_string_pretty(&response.body)?;
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason_phrase(response.status),
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Map a request line's method and target to a response. Pure apart from
+/// reading task state, so it can be exercised without a socket.
+pub fn route(config: &Config, method: &str, target: &str) -> Response {
+    if method != "GET" {
+        return Response::error(405, "only GET is supported");
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["tasks"] => {
+            let tasks: Vec<Value> = Task::list_all(config).iter().map(task_json).collect();
+            Response::ok(Value::Array(tasks))
+        }
+        ["tasks", id] => match Task::load_by_id(config, id) {
+            Ok(task) => Response::ok(task_json(&task)),
+            Err(e) => Response::error(404, e.to_string()),
+        },
+        ["tasks", id, "log"] => {
+            let tail = match query_param(query, "tail") {
+                None => DEFAULT_LOG_TAIL,
+                Some(v) => match v.parse() {
+                    Ok(n) => n,
+                    Err(_) => return Response::error(400, "tail must be a number"),
+                },
+            };
+            let task = match Task::load_by_id(config, id) {
+                Ok(task) => task,
+                Err(e) => return Response::error(404, e.to_string()),
+            };
+            let task_id = task.meta.task_id();
+            match use_cases::get_task_log_tail(config, &task_id, tail) {
+                Ok(text) => Response::ok(json!({
+                    "id": task_id,
+                    "lines": text.lines().collect::<Vec<_>>(),
+                })),
+                Err(e) => Response::error(500, e.to_string()),
+            }
+        }
+        _ => Response::error(404, "not found"),
+    }
+}
+
+fn task_json(task: &Task) -> Value {
+    let mut value = serde_json::to_value(&task.meta).unwrap_or(Value::Null);
+    if let Value::Object(map) = &mut value {
+        map.insert("id".to_string(), Value::String(task.meta.task_id()));
+    }
+    value
+}
+
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
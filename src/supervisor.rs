@@ -33,10 +33,7 @@ pub enum AdvanceOutcome {
 
 pub fn supervisor_session(task: &Task) -> Result<String> {
     if task.meta.is_multi_repo() {
-        Ok(Config::tmux_session_name(
-            &task.meta.name,
-            &task.meta.branch_name,
-        ))
+        Ok(task.meta.parent_tmux_session())
     } else if task.meta.has_repos() {
         Ok(task.meta.primary_repo().tmux_session.clone())
     } else {
@@ -49,28 +46,34 @@ pub fn supervisor_session(task: &Task) -> Result<String> {
 
 pub fn ensure_task_tmux(config: &Config, task: &Task) -> Result<String> {
     for repo in &task.meta.repos {
-        Tmux::ensure_session(&repo.tmux_session, &repo.worktree_path).with_context(|| {
-            format!(
-                "failed to ensure tmux session for repo '{}'",
-                repo.repo_name
-            )
-        })?;
+        let windows = config.session_windows(&repo.repo_name);
+        Tmux::ensure_session(&repo.tmux_session, &repo.worktree_path, &windows).with_context(
+            || {
+                format!(
+                    "failed to ensure tmux session for repo '{}'",
+                    repo.repo_name
+                )
+            },
+        )?;
     }
     if task.meta.is_multi_repo() {
-        let parent_dir = task.meta.parent_dir.as_ref().ok_or_else(|| {
+        let parent_dir = task.meta.parent_session_dir().ok_or_else(|| {
             anyhow::anyhow!(
                 "multi-repo task '{}' has no parent_dir",
                 task.meta.task_id()
             )
         })?;
-        let session = Config::tmux_session_name(&task.meta.name, &task.meta.branch_name);
+        let session = task.meta.parent_tmux_session();
         if !Tmux::session_exists(&session) {
-            Tmux::create_session_with_windows(&session, parent_dir).with_context(|| {
-                format!(
-                    "failed to create parent-dir tmux session '{}' for multi-repo task",
-                    session
-                )
-            })?;
+            let windows = config.session_windows(&task.meta.name);
+            Tmux::create_session_with_windows(&session, &parent_dir, &windows).with_context(
+                || {
+                    format!(
+                        "failed to create parent-dir tmux session '{}' for multi-repo task",
+                        session
+                    )
+                },
+            )?;
         }
     }
     let task_id = task.meta.task_id();
@@ -147,6 +150,10 @@ pub fn launch_task_engineer(config: &Config, task: &Task) -> Result<String> {
 }
 
 pub fn launch_next_step(config: &Config, task: &mut Task) -> Result<AdvanceOutcome> {
+    if task.meta.auto_rebase {
+        crate::use_cases::rebase_task_onto_base(config, task)
+            .context("auto-rebase failed; engineer not launched")?;
+    }
     let session_name = launch_task_engineer(config, task)?;
     Ok(AdvanceOutcome::Launched { session_name })
 }
@@ -16,6 +16,58 @@ struct LogSection<'a> {
     lines: Vec<&'a str>,
 }
 
+/// Split agent.log into sections at the `--- Agent: ... started at/finished
+/// at ... ---` markers.
+fn log_sections(content: &str) -> Vec<LogSection<'_>> {
+    let mut sections: Vec<LogSection> = Vec::new();
+    let mut current_lines: Vec<&str> = Vec::new();
+    let mut current_kind = SectionKind::AgentOutput;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("--- Agent:")
+            && trimmed.ends_with("---")
+            && trimmed.contains("started at")
+        {
+            // Flush previous section
+            if !current_lines.is_empty() {
+                sections.push(LogSection {
+                    kind: current_kind,
+                    lines: std::mem::take(&mut current_lines),
+                });
+            }
+            current_kind = SectionKind::AgentOutput;
+            current_lines.push(line);
+        } else if trimmed.starts_with("--- Agent:")
+            && trimmed.ends_with("---")
+            && trimmed.contains("finished at")
+        {
+            current_lines.push(line);
+            sections.push(LogSection {
+                kind: current_kind,
+                lines: std::mem::take(&mut current_lines),
+            });
+            current_kind = SectionKind::AgentOutput;
+        } else {
+            current_lines.push(line);
+        }
+    }
+    // Flush last section
+    if !current_lines.is_empty() {
+        sections.push(LogSection {
+            kind: current_kind,
+            lines: current_lines,
+        });
+    }
+    sections
+}
+
+/// The lines of each agent.log section, in order.
+pub fn agent_log_sections(content: &str) -> Vec<Vec<&str>> {
+    log_sections(content).into_iter().map(|s| s.lines).collect()
+}
+
 /// A single repo entry within a task. For single-repo tasks there is exactly one;
 /// for multi-repo tasks there is one per repo.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +146,45 @@ pub struct TaskMeta {
     /// metadata; new task-attached agents own their canonical sessions.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub session_history: Vec<SessionEntry>,
+    /// Usage scraped from agent.log by [`crate::usage`]; zero when the agent
+    /// never reported any.
+    #[serde(default)]
+    pub total_input_tokens: u64,
+    #[serde(default)]
+    pub total_output_tokens: u64,
+    #[serde(default)]
+    pub total_cost_cents: u64,
+    /// Rebase the task branch onto `base_ref` before each engineer launch.
+    #[serde(default)]
+    pub auto_rebase: bool,
+    /// Ref the task branch was created from (e.g. `origin/main`). `None` for
+    /// tasks on existing branches; auto-rebase then falls back to the repo's
+    /// detected base ref.
+    #[serde(default)]
+    pub base_ref: Option<String>,
+    /// Pinned tasks are listed in their own section above the rest.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Hidden from the task list until this time (`s` in the TUI).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snoozed_until: Option<DateTime<Utc>>,
+    /// Human label shown instead of the branch in the TUI. Display only:
+    /// the task id and paths stay branch-based.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Multi-repo tasks: the repo whose worktree the user mostly works in.
+    /// Attaching preselects its session and the parent session starts in
+    /// its worktree once the repo has joined the task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_repo_hint: Option<String>,
+    /// Last time the engineer was asked to compact its context (`K`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_compacted_at: Option<DateTime<Utc>>,
+    /// Multi-repo tasks: the parent session's name when it had to be
+    /// suffixed to avoid another task's session. `None` means the name
+    /// derived from name and branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_tmux_session: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -111,6 +202,15 @@ pub struct LinkedPr {
 }
 
 impl TaskMeta {
+    /// Usage totals recorded on this task.
+    pub fn usage(&self) -> crate::usage::UsageTotals {
+        crate::usage::UsageTotals {
+            input_tokens: self.total_input_tokens,
+            output_tokens: self.total_output_tokens,
+            cost_cents: self.total_cost_cents,
+        }
+    }
+
     pub fn new(
         name: String,
         branch_name: String,
@@ -137,6 +237,17 @@ impl TaskMeta {
             saved: false,
             project: None,
             session_history: Vec::new(),
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            total_cost_cents: 0,
+            auto_rebase: false,
+            base_ref: None,
+            pinned: false,
+            snoozed_until: None,
+            label: None,
+            primary_repo_hint: None,
+            last_compacted_at: None,
+            parent_tmux_session: None,
         }
     }
 
@@ -161,6 +272,17 @@ impl TaskMeta {
             saved: false,
             project: None,
             session_history: Vec::new(),
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            total_cost_cents: 0,
+            auto_rebase: false,
+            base_ref: None,
+            pinned: false,
+            snoozed_until: None,
+            label: None,
+            primary_repo_hint: None,
+            last_compacted_at: None,
+            parent_tmux_session: None,
         }
     }
 
@@ -174,6 +296,35 @@ impl TaskMeta {
         &self.repos[0]
     }
 
+    /// Index into `repos` of the repo named by `primary_repo_hint`, once it
+    /// has joined the task.
+    pub fn hinted_repo_index(&self) -> Option<usize> {
+        let hint = self.primary_repo_hint.as_deref()?;
+        self.repos.iter().position(|r| r.repo_name == hint)
+    }
+
+    /// tmux session of a multi-repo task's parent directory.
+    pub fn parent_tmux_session(&self) -> String {
+        self.parent_tmux_session
+            .clone()
+            .unwrap_or_else(|| Config::tmux_session_name(&self.name, &self.branch_name))
+    }
+
+    /// Working directory for a multi-repo task's parent session: the hinted
+    /// repo's worktree when there is one, else `parent_dir`.
+    pub fn parent_session_dir(&self) -> Option<PathBuf> {
+        self.hinted_repo_index()
+            .map(|i| self.repos[i].worktree_path.clone())
+            .filter(|path| path.exists())
+            .or_else(|| self.parent_dir.clone())
+    }
+
+    /// Whether `repo` is this task's repo, or one of its repos for multi-repo
+    /// tasks. Exact name match, so `api` does not match `api-gateway`.
+    pub fn belongs_to_repo(&self, repo: &str) -> bool {
+        self.name == repo || self.repos.iter().any(|r| r.repo_name == repo)
+    }
+
     /// Whether this is a multi-repo task.
     /// Uses the explicit `multi_repo` field when present. Falls back to
     /// `parent_dir.is_some()` for tasks created before the field existed.
@@ -190,9 +341,29 @@ impl TaskMeta {
     pub fn has_repos(&self) -> bool {
         !self.repos.is_empty()
     }
+
+    /// True if the primary worktree directory was removed from disk
+    /// (e.g. deleted by hand) while the task still references it.
+    pub fn worktree_missing(&self) -> bool {
+        self.has_repos() && !self.primary_repo().worktree_path.exists()
+    }
+
+    /// True if the linked PR is someone else's, i.e. this task is review duty.
+    pub fn is_review_duty(&self) -> bool {
+        self.linked_pr.as_ref().is_some_and(|pr| !pr.owned)
+    }
+
+    pub fn is_snoozed(&self, now: DateTime<Utc>) -> bool {
+        self.snoozed_until.is_some_and(|until| until > now)
+    }
+
+    /// The label if one is set, otherwise the branch name.
+    pub fn display_name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.branch_name)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Task {
     pub meta: TaskMeta,
     pub dir: PathBuf,
@@ -461,50 +632,7 @@ impl Task {
             return Ok(content);
         }
 
-        let all_lines: Vec<&str> = content.lines().collect();
-
-        // Parse into sections
-        let mut sections: Vec<LogSection> = Vec::new();
-        let mut current_lines: Vec<&str> = Vec::new();
-        let mut current_kind = SectionKind::AgentOutput;
-
-        for line in &all_lines {
-            let trimmed = line.trim();
-
-            if trimmed.starts_with("--- Agent:")
-                && trimmed.ends_with("---")
-                && trimmed.contains("started at")
-            {
-                // Flush previous section
-                if !current_lines.is_empty() {
-                    sections.push(LogSection {
-                        kind: current_kind,
-                        lines: std::mem::take(&mut current_lines),
-                    });
-                }
-                current_kind = SectionKind::AgentOutput;
-                current_lines.push(line);
-            } else if trimmed.starts_with("--- Agent:")
-                && trimmed.ends_with("---")
-                && trimmed.contains("finished at")
-            {
-                current_lines.push(line);
-                sections.push(LogSection {
-                    kind: current_kind,
-                    lines: std::mem::take(&mut current_lines),
-                });
-                current_kind = SectionKind::AgentOutput;
-            } else {
-                current_lines.push(line);
-            }
-        }
-        // Flush last section
-        if !current_lines.is_empty() {
-            sections.push(LogSection {
-                kind: current_kind,
-                lines: current_lines,
-            });
-        }
+        let sections = log_sections(&content);
 
         // Now condense: keep structural lines and tail of agent output
         let per_agent_tail = 30;
@@ -521,12 +649,7 @@ impl Task {
                     // The first line might be an agent start marker
                     let mut in_body = false;
                     for line in &section.lines {
-                        let trimmed = line.trim();
-                        let is_marker = (trimmed.starts_with("--- Agent:")
-                            && trimmed.ends_with("---"))
-                            || trimmed.contains("AGENT_DONE")
-                            || trimmed.contains("TASK_COMPLETE")
-                            || trimmed.contains("INPUT_NEEDED");
+                        let is_marker = crate::use_cases::is_log_marker(line);
 
                         if is_marker && !in_body {
                             structural_head.push(line);
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::io::Write;
 use std::path::Path;
 use std::process::{Command, Stdio};
@@ -7,6 +8,32 @@ const SHELL_COMMANDS: &[&str] = &["zsh", "bash", "fish", "sh", "dash", "ksh", "c
 
 pub struct Tmux;
 
+/// One window of a task tmux session: its name and an optional command typed
+/// into it once created. Configured via `session_windows` in config.toml.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionWindow {
+    pub name: String,
+    #[serde(default)]
+    pub command: Option<String>,
+}
+
+impl SessionWindow {
+    /// The built-in layout used when nothing is configured.
+    pub fn default_layout() -> Vec<SessionWindow> {
+        [
+            ("nvim", "nvim"),
+            ("lazygit", "lazygit"),
+            ("shell", "git status && git branch --show-current"),
+        ]
+        .into_iter()
+        .map(|(name, command)| SessionWindow {
+            name: name.to_string(),
+            command: Some(command.to_string()),
+        })
+        .collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TmuxWindowActivity {
     pub session_name: String,
@@ -29,14 +56,14 @@ impl Tmux {
             .unwrap_or(false)
     }
 
-    /// Create a new task tmux session with multiple worktree windows:
-    /// - nvim: starts nvim
-    /// - lazygit: starts lazygit
-    /// - shell: runs git status
-    ///
-    /// Attached agents are linked in from their canonical sessions separately;
-    /// task sessions no longer own a manual `agman` window.
-    pub fn create_session_with_windows(session_name: &str, working_dir: &Path) -> Result<()> {
+    /// Create a detached session with one window per entry in `windows`
+    /// (falling back to [`SessionWindow::default_layout`] when empty), typing
+    /// each window's command into it. The first window is selected.
+    pub fn create_session_with_windows(
+        session_name: &str,
+        working_dir: &Path,
+        windows: &[SessionWindow],
+    ) -> Result<()> {
         if Self::session_exists(session_name) {
             tracing::debug!(
                 session = session_name,
@@ -46,9 +73,17 @@ impl Tmux {
         }
         tracing::debug!(session = session_name, dir = %working_dir.display(), "creating tmux session");
 
+        let default_layout;
+        let windows = if windows.is_empty() {
+            default_layout = SessionWindow::default_layout();
+            &default_layout
+        } else {
+            windows
+        };
         let wd = working_dir.to_str().unwrap();
 
-        // Create session with first window (nvim)
+        // Create session with the first window
+        let first = &windows[0];
         let output = Command::new("tmux")
             .args([
                 "new-session",
@@ -62,7 +97,7 @@ impl Tmux {
                 "-c",
                 wd,
                 "-n",
-                "nvim",
+                &first.name,
             ])
             .output()
             .context("Failed to create tmux session")?;
@@ -74,28 +109,32 @@ impl Tmux {
             );
         }
 
-        // Start nvim in first window
-        Self::send_keys_to_window(session_name, "nvim", "nvim")?;
-
-        // Create lazygit window
-        let _ = Command::new("tmux")
-            .args(["new-window", "-t", session_name, "-n", "lazygit", "-c", wd])
-            .output();
-        Self::send_keys_to_window(session_name, "lazygit", "lazygit")?;
-
-        // Create shell window
-        let _ = Command::new("tmux")
-            .args(["new-window", "-t", session_name, "-n", "shell", "-c", wd])
-            .output();
-        Self::send_keys_to_window(
-            session_name,
-            "shell",
-            "git status && git branch --show-current",
-        )?;
+        for (i, window) in windows.iter().enumerate() {
+            if i > 0 {
+                let _ = Command::new("tmux")
+                    .args([
+                        "new-window",
+                        "-t",
+                        session_name,
+                        "-n",
+                        &window.name,
+                        "-c",
+                        wd,
+                    ])
+                    .output();
+            }
+            if let Some(command) = &window.command {
+                Self::send_keys_to_window(session_name, &window.name, command)?;
+            }
+        }
 
-        // Select nvim window as default
+        // Select the first window as default
         let _ = Command::new("tmux")
-            .args(["select-window", "-t", &format!("{}:nvim", session_name)])
+            .args([
+                "select-window",
+                "-t",
+                &format!("{}:{}", session_name, first.name),
+            ])
             .output();
 
         Ok(())
@@ -120,6 +159,29 @@ impl Tmux {
         Ok(())
     }
 
+    /// Rename a tmux session. No-op when the session does not exist.
+    pub fn rename_session(old_name: &str, new_name: &str) -> Result<()> {
+        if !Self::session_exists(old_name) {
+            return Ok(());
+        }
+        tracing::debug!(old = old_name, new = new_name, "renaming tmux session");
+
+        let output = Command::new("tmux")
+            .args(["rename-session", "-t", old_name, new_name])
+            .output()
+            .context("Failed to rename tmux session")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to rename tmux session '{}': {}",
+                old_name,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+
     pub fn attach_session(session_name: &str) -> Result<()> {
         tracing::debug!(session = session_name, "attaching to tmux session");
 
@@ -228,16 +290,20 @@ impl Tmux {
         Ok(())
     }
 
-    /// Ensure a tmux session exists with all standard windows.
+    /// Ensure a tmux session exists with the given window layout.
     ///
     /// If the session already exists, this is a no-op. Otherwise, creates the
     /// session with `create_session_with_windows`.
-    pub fn ensure_session(session_name: &str, working_dir: &Path) -> Result<()> {
+    pub fn ensure_session(
+        session_name: &str,
+        working_dir: &Path,
+        windows: &[SessionWindow],
+    ) -> Result<()> {
         if Self::session_exists(session_name) {
             return Ok(());
         }
         tracing::info!(session = session_name, dir = %working_dir.display(), "recreating missing tmux session");
-        Self::create_session_with_windows(session_name, working_dir)?;
+        Self::create_session_with_windows(session_name, working_dir, windows)?;
         Ok(())
     }
 
@@ -1,11 +1,14 @@
 use anyhow::Result;
 use ratatui::crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, widgets::ListState, Terminal};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io;
 #[cfg(unix)]
 use std::os::unix::process::CommandExt as _;
@@ -18,16 +21,17 @@ use tui_textarea::{CursorMove, Input, Key, TextArea};
 
 use agman::agent_model::{AgentAttachment, AgentKind, AgentRecord, AgentStatus};
 use agman::config::Config;
-use agman::dismissed_notifications::DismissedNotifications;
+use agman::dismissed_notifications::{DismissedNotifications, SNOOZE_HOURS};
 use agman::git::Git;
-use agman::inbox;
+use agman::inbox::{self, InboxMessage};
 use agman::project::Project;
-use agman::repo_stats::RepoStats;
+use agman::repo_stats::{self, RepoStats, RepoStatsSort, RepoTaskTotals};
 use agman::supervisor;
-use agman::task::Task;
+use agman::task::{RepoEntry, Task};
 use agman::tmux::{Tmux, TmuxWindowActivity};
-use agman::use_cases;
+use agman::use_cases::{self, ArchiveMode};
 
+use super::keymap;
 use super::ui;
 use super::vim::{VimMode, VimTextArea};
 
@@ -41,6 +45,13 @@ const TELEGRAM_STALL_THRESHOLD_SECS: u64 = 60;
 /// Gives the new thread time to warm up and write its first heartbeat.
 const TELEGRAM_RESPAWN_COOLDOWN: Duration = Duration::from_secs(90);
 
+/// How often agent logs are re-scanned for usage when the COST column is on.
+const USAGE_SCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Tasks whose preview position is remembered; the least recently left one
+/// is forgotten first.
+const PREVIEW_POSITIONS_MAX: usize = 32;
+
 /// Open a URL in the default browser, cross-platform (macOS / Linux).
 fn open_url(url: &str) {
     let cmd = if cfg!(target_os = "macos") {
@@ -51,6 +62,72 @@ fn open_url(url: &str) {
     let _ = Command::new(cmd).arg(url).spawn();
 }
 
+/// Copy `text` to the system clipboard.
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text.to_string()))
+        .map_err(|e| anyhow::anyhow!("clipboard unavailable: {e}"))
+}
+
+/// Suspend the TUI, page `path` with `$PAGER` (default `less -R`), then
+/// restore the terminal.
+fn run_pager(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, path: &Path) -> Result<()> {
+    run_suspended(terminal, "PAGER", "less -R", path)
+}
+
+/// Suspend the TUI, open `path` in `$EDITOR` (default `vi`), then restore
+/// the terminal.
+fn run_editor(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, path: &Path) -> Result<()> {
+    run_suspended(terminal, "EDITOR", "vi", path)
+}
+
+/// Run the command named by `env_var` (or `default`) on `path` with the TUI
+/// suspended. Mirrors the raw-mode / alternate-screen cycle used around tmux
+/// attach.
+fn run_suspended(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    env_var: &str,
+    default: &str,
+    path: &Path,
+) -> Result<()> {
+    let command = std::env::var(env_var)
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| default.to_string());
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or(default);
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste
+    )?;
+    terminal.show_cursor()?;
+
+    let status = Command::new(program).args(parts).arg(path).status();
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    terminal.clear()?;
+
+    match status {
+        Ok(_) => Ok(()),
+        Err(e) => Err(anyhow::anyhow!(
+            "failed to run {} '{}': {}",
+            env_var.to_lowercase(),
+            program,
+            e
+        )),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum View {
     ProjectList,
@@ -70,6 +147,20 @@ pub enum View {
     ProjectDeleteConfirm,
     AgentWizard,
     RespawnConfirm,
+    RepoStats,
+    RenameBranch,
+    EditBaseRef,
+    EditLabel,
+    RelocateRepo,
+    QuickAnswer,
+    AddRepo,
+    MessageHistory,
+    Snapshots,
+    Dashboard,
+    CloneRepo,
+    Compare,
+    QuitConfirm,
+    Help,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -114,6 +205,8 @@ pub enum ProjectDetailRow<'a> {
     TasksSectionHeader,
     TasksColumnsHeader,
     EmptyTasks,
+    PinnedTasksHeader,
+    OtherTasksHeader,
     Task(ProjectTaskRow<'a>),
     AttachedAgentsHeader,
     AttachedAgent(ProjectTaskRow<'a>),
@@ -151,10 +244,16 @@ pub struct DirectoryPicker {
     pub entry_kinds: Vec<DirKind>,
     pub selected_index: usize,
     pub origin: DirPickerOrigin,
+    /// Repos most recently used for new tasks, shown above favourites in repo select modes.
+    pub recent_repos: Vec<String>,
     /// Favourite repos with task counts (loaded once at construction). Always shown in repo select modes.
     pub favorite_repos: Vec<(String, u64)>,
     /// The configured repos_dir, used to resolve favourite repo paths.
     pub repos_dir: PathBuf,
+    /// The configured worktrees_dir, hidden from repo selection like `-wt` dirs.
+    pub worktrees_dir: Option<PathBuf>,
+    /// Patterns from `repos-ignore`, hidden from repo selection.
+    pub ignore_patterns: Vec<String>,
 }
 
 impl DirectoryPicker {
@@ -165,8 +264,11 @@ impl DirectoryPicker {
             entry_kinds: Vec::new(),
             selected_index: 0,
             origin,
+            recent_repos: Vec::new(),
             favorite_repos: Vec::new(),
             repos_dir: PathBuf::new(),
+            worktrees_dir: None,
+            ignore_patterns: Vec::new(),
         };
         picker.refresh_entries();
         picker
@@ -177,8 +279,15 @@ impl DirectoryPicker {
         origin: DirPickerOrigin,
         stats_path: &std::path::Path,
         repos_dir: PathBuf,
+        worktrees_dir: Option<PathBuf>,
+        ignore_patterns: Vec<String>,
     ) -> Self {
         let stats = RepoStats::load(stats_path);
+        let recent_repos: Vec<String> = stats
+            .recent(repo_stats::RECENT_REPOS_LIMIT)
+            .into_iter()
+            .filter(|name| repos_dir.join(name).join(".git").exists())
+            .collect();
         let favorite_repos: Vec<(String, u64)> = stats
             .favorites()
             .into_iter()
@@ -191,13 +300,26 @@ impl DirectoryPicker {
             entry_kinds: Vec::new(),
             selected_index: 0,
             origin,
+            recent_repos,
             favorite_repos,
             repos_dir,
+            worktrees_dir,
+            ignore_patterns,
         };
         picker.refresh_entries();
         picker
     }
 
+    /// Reopen at `dir` with `selected_index` highlighted, as far as the
+    /// directory still exists and has that many items.
+    fn restore_position(&mut self, dir: PathBuf, selected_index: usize) {
+        if dir.is_dir() {
+            self.current_dir = dir;
+            self.refresh_entries();
+        }
+        self.selected_index = selected_index.min(self.total_items().saturating_sub(1));
+    }
+
     pub fn is_repo_select_mode(&self) -> bool {
         matches!(self.origin, DirPickerOrigin::RepoSelect)
     }
@@ -212,9 +334,12 @@ impl DirectoryPicker {
                 .filter(|e| e.path().is_dir())
                 .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
                 .filter(|e| {
-                    // In RepoSelect modes, filter out -wt worktree directories
+                    // In RepoSelect modes, filter out -wt, relocated worktree and ignored directories
                     if is_repo_select {
-                        !e.file_name().to_string_lossy().ends_with("-wt")
+                        let name = e.file_name().to_string_lossy().to_string();
+                        !name.ends_with("-wt")
+                            && self.worktrees_dir.as_deref() != Some(e.path().as_path())
+                            && !use_cases::is_repo_ignored(&name, &self.ignore_patterns)
                     } else {
                         true
                     }
@@ -242,18 +367,34 @@ impl DirectoryPicker {
         self.favorite_repos.len()
     }
 
-    /// Total number of selectable items (favourites + directory entries).
+    /// Number of quick-pick entries (recent repos followed by favourites).
+    pub fn quick_pick_len(&self) -> usize {
+        self.recent_repos.len() + self.favorites_len()
+    }
+
+    /// Repo name of the quick-pick entry at `index`, if it is one.
+    fn quick_pick_name(&self, index: usize) -> Option<&str> {
+        match index.checked_sub(self.recent_repos.len()) {
+            None => Some(&self.recent_repos[index]),
+            Some(fav_idx) => self
+                .favorite_repos
+                .get(fav_idx)
+                .map(|(name, _)| name.as_str()),
+        }
+    }
+
+    /// Total number of selectable items (recent + favourites + directory entries).
     pub fn total_items(&self) -> usize {
-        self.favorites_len() + self.entries.len()
+        self.quick_pick_len() + self.entries.len()
     }
 
     fn enter_selected(&mut self) {
-        let fav_len = self.favorites_len();
-        if self.selected_index < fav_len {
-            // Favourites are handled externally via select_repo_from_picker
+        let quick_len = self.quick_pick_len();
+        if self.selected_index < quick_len {
+            // Quick picks are handled externally via select_repo_from_picker
             return;
         }
-        let entry_idx = self.selected_index - fav_len;
+        let entry_idx = self.selected_index - quick_len;
         if let Some(name) = self.entries.get(entry_idx) {
             self.current_dir = self.current_dir.join(name);
             self.refresh_entries();
@@ -269,23 +410,21 @@ impl DirectoryPicker {
 
     /// Get the kind of the currently selected entry (for RepoSelect mode).
     pub fn selected_entry_kind(&self) -> Option<DirKind> {
-        let fav_len = self.favorites_len();
-        if self.selected_index < fav_len {
+        let quick_len = self.quick_pick_len();
+        if self.selected_index < quick_len {
             return Some(DirKind::GitRepo);
         }
-        self.entry_kinds.get(self.selected_index - fav_len).copied()
+        self.entry_kinds
+            .get(self.selected_index - quick_len)
+            .copied()
     }
 
     /// Get the full path to the currently selected entry.
     pub fn selected_path(&self) -> Option<PathBuf> {
-        let fav_len = self.favorites_len();
-        if self.selected_index < fav_len {
-            return Some(
-                self.repos_dir
-                    .join(&self.favorite_repos[self.selected_index].0),
-            );
+        if let Some(name) = self.quick_pick_name(self.selected_index) {
+            return Some(self.repos_dir.join(name));
         }
-        let entry_idx = self.selected_index - fav_len;
+        let entry_idx = self.selected_index - self.quick_pick_len();
         self.entries
             .get(entry_idx)
             .map(|name| self.current_dir.join(name))
@@ -293,17 +432,16 @@ impl DirectoryPicker {
 
     /// Get the name of the currently selected entry.
     pub fn selected_name(&self) -> Option<String> {
-        let fav_len = self.favorites_len();
-        if self.selected_index < fav_len {
-            return Some(self.favorite_repos[self.selected_index].0.clone());
+        if let Some(name) = self.quick_pick_name(self.selected_index) {
+            return Some(name.to_string());
         }
-        let entry_idx = self.selected_index - fav_len;
+        let entry_idx = self.selected_index - self.quick_pick_len();
         self.entries.get(entry_idx).cloned()
     }
 
-    /// Whether the currently selected item is a favourite.
-    pub fn is_favorite_selected(&self) -> bool {
-        self.selected_index < self.favorites_len()
+    /// Whether the currently selected item is a recent repo or a favourite.
+    pub fn is_quick_pick_selected(&self) -> bool {
+        self.selected_index < self.quick_pick_len()
     }
 }
 
@@ -335,10 +473,30 @@ pub struct NewTaskWizard {
     pub base_branch_editor: TextArea<'static>,
     /// Which field has focus in the NewBranch tab: false = branch name, true = base branch.
     pub base_branch_focus: bool,
+    /// In-progress Ctrl+N completion of the new branch name: the text as typed
+    /// before the first press, and how many presses have happened since.
+    pub branch_completion: Option<(String, usize)>,
+    /// Rebase the new task onto its base ref before each engineer launch.
+    pub auto_rebase: bool,
     pub first_prompt_editor: VimTextArea<'static>,
     pub error_message: Option<String>,
+    /// Set while `create_task` runs on the worker; keys are ignored until
+    /// it reports back.
+    pub creating: bool,
+    /// New branch name the user was told already exists; pressing Enter
+    /// again on the same name reuses the branch instead.
+    pub reuse_branch_confirm: Option<String>,
     /// True when a multi-repo parent directory was selected (not a git repo).
     pub is_multi_repo: bool,
+    /// Multi-repo only: git repos under the parent directory, and the one
+    /// (cycled with Ctrl+O) stored as the task's `primary_repo_hint`.
+    pub start_repo_choices: Vec<String>,
+    pub start_repo: Option<usize>,
+    /// Open GitHub issues to seed the new branch from (Ctrl+G); `None` when closed.
+    pub issue_picker: Option<Vec<use_cases::GithubIssue>>,
+    pub selected_issue_index: usize,
+    /// True while `gh issue list` runs on the worker for Ctrl+G.
+    pub loading_issues: bool,
 }
 
 impl NewTaskWizard {
@@ -348,6 +506,86 @@ impl NewTaskWizard {
     }
 }
 
+/// Open issues fetched for the wizard's Ctrl+G picker, keyed by repo path.
+type IssueListResult = (PathBuf, Result<Vec<use_cases::GithubIssue>, String>);
+
+/// Progress of a `git clone` started from the clone prompt.
+enum CloneEvent {
+    Progress(String),
+    Done(Result<(String, PathBuf), String>),
+}
+
+/// A wizard's `create_task` call, finished on the worker.
+struct CreatedTask {
+    name: String,
+    branch_name: String,
+    auto_rebase: bool,
+    result: Result<Task, String>,
+}
+
+/// Active `/` search in the Logs pane.
+pub struct LogSearch {
+    pub term: String,
+    /// `(row, char column)` of every match, refreshed on each jump.
+    pub matches: Vec<(usize, usize)>,
+    /// Index into `matches` of the highlighted match.
+    pub current: Option<usize>,
+}
+
+/// Paste into a single-line input. Line breaks are dropped: the input's
+/// key handler would treat them as submit.
+fn paste_into_line(textarea: &mut TextArea<'static>, text: &str) {
+    textarea.insert_str(text.trim().replace(['\r', '\n'], ""));
+}
+
+/// Case-insensitive (ASCII) positions of `term` in `lines`, as
+/// `(row, char column)` pairs in document order.
+pub fn find_log_matches(lines: &[String], term: &str) -> Vec<(usize, usize)> {
+    let needle: Vec<char> = term.chars().map(|c| c.to_ascii_lowercase()).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let mut matches = Vec::new();
+    for (row, line) in lines.iter().enumerate() {
+        let hay: Vec<char> = line.chars().map(|c| c.to_ascii_lowercase()).collect();
+        for (col, window) in hay.windows(needle.len()).enumerate() {
+            if window == needle.as_slice() {
+                matches.push((row, col));
+            }
+        }
+    }
+    matches
+}
+
+/// Cursor `(row, col)` the user left a task's Logs and Notes panes at.
+/// `None` for a pane left on its last line, so it keeps following the tail.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PreviewPosition {
+    pub logs: Option<(usize, usize)>,
+    pub notes: Option<(usize, usize)>,
+}
+
+/// What the startup dashboard summarizes, each count backed by a view the
+/// dashboard can jump into.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DashboardCounts {
+    /// Tasks whose engineer has gone quiet past `agent_idle_secs`.
+    pub idle_tasks: usize,
+    /// Tasks reviewing someone else's PR.
+    pub review_tasks: usize,
+    /// Open PRs on GitHub waiting on the user's review.
+    pub review_requests: usize,
+    pub unread_notifications: usize,
+}
+
+/// One side of the task comparison view.
+pub struct ComparePane {
+    pub task_id: String,
+    pub log: String,
+    /// First visible line.
+    pub scroll: u16,
+}
+
 /// What triggered the project picker modal.
 #[derive(Debug, Clone)]
 pub enum ProjectPickerAction {
@@ -496,6 +734,15 @@ pub struct AgentActivitySample {
     pub query_ok: bool,
 }
 
+/// Counts behind the task badge in the title bar.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TaskAttention {
+    /// Engineer alive but idle, i.e. waiting on the user.
+    pub waiting: usize,
+    /// Engineer session gone or back at a shell prompt.
+    pub stopped: usize,
+}
+
 impl AgentActivitySample {
     fn from_tmux_window(
         activity: &TmuxWindowActivity,
@@ -692,6 +939,19 @@ pub struct NotesView {
     pub confirm_delete: bool,
     /// Cut state: `(source_dir, file_name)` of the entry being moved.
     pub cut_entry: Option<(PathBuf, String)>,
+    /// Ctrl+P fuzzy finder, open while `Some`.
+    pub finder: Option<NoteFinder>,
+    /// Cached result of `list_notes_recursive`; dropped whenever notes are
+    /// created, deleted, renamed or moved so the finder re-indexes.
+    note_index: Option<Vec<PathBuf>>,
+}
+
+/// State of the Notes fuzzy finder.
+pub struct NoteFinder {
+    pub input: TextArea<'static>,
+    /// Paths (relative to the notes root) matching the current query.
+    pub matches: Vec<PathBuf>,
+    pub selected: usize,
 }
 
 impl NotesView {
@@ -711,6 +971,8 @@ impl NotesView {
             create_input: None,
             confirm_delete: false,
             cut_entry: None,
+            finder: None,
+            note_index: None,
         })
     }
 
@@ -722,6 +984,53 @@ impl NotesView {
         Ok(())
     }
 
+    /// Drop the cached finder index after notes are added, removed or moved.
+    pub fn invalidate_index(&mut self) {
+        self.note_index = None;
+    }
+
+    /// Open the fuzzy finder, indexing the notes tree if not cached.
+    pub fn open_finder(&mut self) -> Result<()> {
+        if self.note_index.is_none() {
+            self.note_index = Some(use_cases::list_notes_recursive(&self.root_dir)?);
+        }
+        self.finder = Some(NoteFinder {
+            input: TextArea::default(),
+            matches: self.note_index.clone().unwrap_or_default(),
+            selected: 0,
+        });
+        Ok(())
+    }
+
+    /// Re-run the finder query against the cached index.
+    pub fn update_finder_matches(&mut self) {
+        let index = self.note_index.as_deref().unwrap_or_default();
+        if let Some(finder) = self.finder.as_mut() {
+            finder.matches = use_cases::fuzzy_filter_notes(index, &finder.input.lines()[0]);
+            finder.selected = 0;
+        }
+    }
+
+    /// Open `rel_path` (relative to the notes root) from the finder: the
+    /// explorer moves to its directory and selects it.
+    pub fn open_from_finder(&mut self, rel_path: &std::path::Path) -> Result<()> {
+        let path = self.root_dir.join(rel_path);
+        self.save_current()?;
+        self.open_file(&path)?;
+        self.finder = None;
+        self.current_dir = path
+            .parent()
+            .unwrap_or(self.root_dir.as_path())
+            .to_path_buf();
+        self.refresh()?;
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string());
+        self.selected_index = file_name
+            .and_then(|name| self.entries.iter().position(|e| e.file_name == name))
+            .unwrap_or(0);
+        self.focus = NotesFocus::Editor;
+        Ok(())
+    }
+
     pub fn open_file(&mut self, path: &std::path::Path) -> Result<()> {
         let content = use_cases::read_note(path)?;
         self.editor = VimTextArea::from_lines(content.lines());
@@ -745,10 +1054,29 @@ impl NotesView {
 pub struct App {
     pub config: Config,
     pub tasks: Vec<Task>,
+    /// Every task before project scope and list filters. Attention, stall
+    /// and input checks read this so a filter toggle doesn't forget tasks.
+    pub all_tasks: Vec<Task>,
     pub selected_index: usize,
     pub view: View,
     pub preview_content: String,
     pub logs_editor: VimTextArea<'static>,
+    /// `/` prompt in the Logs pane; `Some` while the user is typing a term.
+    pub log_search_editor: Option<TextArea<'static>>,
+    pub log_search: Option<LogSearch>,
+    /// Show the whole agent.log in the Logs pane instead of the structured tail.
+    pub preview_full_log: bool,
+    /// Which lines of the log the Logs pane shows.
+    pub log_filter: use_cases::LogFilter,
+    /// `(ahead, behind)` of the previewed task against its base, sampled
+    /// when the preview loads.
+    pub preview_ahead_behind: Option<(u64, u64)>,
+    /// Per-task preview positions restored by `load_preview`, least recently
+    /// left first, capped at [`PREVIEW_POSITIONS_MAX`].
+    pub preview_positions: VecDeque<(String, PreviewPosition)>,
+    /// Engineer tmux session mirrored into the Logs pane while live view is
+    /// on (`L` in the preview).
+    pub preview_live: Option<String>,
     pub notes_content: String,
     pub notes_editor: VimTextArea<'static>,
     pub notes_editing: bool,
@@ -758,24 +1086,43 @@ pub struct App {
     pub wizard: Option<NewTaskWizard>,
     pub output_log: Vec<String>,
     pub output_scroll: u16,
+    /// Most recent error from the output pane or status bar, kept after both
+    /// clear so `Y` can copy it.
+    pub last_error: Option<String>,
+    /// Error copied with `Y`, shown in the status bar until dismissed.
+    pub pinned_error: Option<String>,
     pub last_output_time: Option<Instant>,
     pub should_restart: bool,
     // Tokio runtime for background async work
     rt: tokio::runtime::Runtime,
     // Directory picker for repos_dir
     pub dir_picker: Option<DirectoryPicker>,
+    /// Where the repo picker was left (`current_dir`, `selected_index`), so
+    /// reopening it after a cancel or wizard back-step lands in the same place.
+    pub repo_picker_position: Option<(PathBuf, usize)>,
     // Session picker for multi-repo attach
     pub session_picker_sessions: Vec<(String, String)>, // (repo_name, tmux_session)
     pub selected_session_index: usize,
     pub attach_session_name: Option<String>,
+    // Log file the main loop should open in $PAGER on its next iteration
+    pub pager_path: Option<PathBuf>,
+    /// Notes file to open in `$EDITOR` once the current event is handled.
+    pub editor_path: Option<PathBuf>,
     // GitHub notifications polling
     pub notifications: Vec<use_cases::GithubNotification>,
+    /// Index into the filtered (visible) notifications, not `notifications`.
     pub selected_notif_index: usize,
+    pub notif_filter: use_cases::NotifReasonFilter,
     pub last_gh_notif_poll: Instant,
     gh_notif_tx: tokio_mpsc::UnboundedSender<use_cases::NotifPollResult>,
     gh_notif_rx: tokio_mpsc::UnboundedReceiver<use_cases::NotifPollResult>,
     gh_notif_poll_active: bool,
     pub gh_notif_first_poll_done: bool,
+    /// GitHub polling (notifications and Show PRs) is paused until this
+    /// instant after `gh` reports a rate limit.
+    pub pr_poll_backoff_until: Option<Instant>,
+    /// Current backoff length; doubles on each consecutive rate limit.
+    pr_poll_backoff: Option<Duration>,
     /// Thread IDs dismissed by the user, persisted across restarts.
     dismissed_notifs: DismissedNotifications,
     // Notes view
@@ -796,6 +1143,26 @@ pub struct App {
     pub archive_retention_days: u64,
     pub telegram_token_editor: TextArea<'static>,
     pub telegram_chat_id_editor: TextArea<'static>,
+    // Coffee-break reminder shown in the clock title
+    pub break_settings: use_cases::BreakSettings,
+    pub last_break_reset: Instant,
+    // Usage / cost accounting
+    pub usage_settings: use_cases::UsageSettings,
+    last_usage_scan: Instant,
+    // Log scans run on the worker and send back changed totals; one at a time.
+    usage_scan_tx: tokio_mpsc::UnboundedSender<Vec<(String, agman::usage::UsageTotals)>>,
+    usage_scan_rx: tokio_mpsc::UnboundedReceiver<Vec<(String, agman::usage::UsageTotals)>>,
+    usage_scan_active: bool,
+    /// `checkpoint_interval_mins` from config, read once at startup.
+    checkpoint_interval: Option<Duration>,
+    last_checkpoint: Instant,
+    /// `refresh_secs` from config, read once at startup.
+    pub refresh_interval: Duration,
+    /// Set with Space to stop the lists rescanning (and reordering) while
+    /// the user reads them.
+    pub auto_refresh_paused: bool,
+    /// `show_dashboard_on_start` from config, read once at startup.
+    pub show_dashboard_on_start: bool,
     // Archive view
     pub archive_kind: ArchiveKind,
     pub archive_tasks: Vec<(Task, String)>,
@@ -805,6 +1172,10 @@ pub struct App {
     pub archive_list_state: ListState,
     pub archive_preview: Option<String>,
     pub archive_scroll: u16,
+    // Repo stats dashboard
+    pub repo_stats_rows: Vec<RepoTaskTotals>,
+    pub repo_stats_sort: RepoStatsSort,
+    pub repo_stats_selected: usize,
     // Project list (Chief of Staff/PM hierarchy)
     pub projects: Vec<Project>,
     pub selected_project_index: usize,
@@ -824,6 +1195,85 @@ pub struct App {
     pub project_picker: Option<ProjectPicker>,
     // Project deletion
     pub project_to_delete: Option<String>,
+    // Inline branch rename for the selected task
+    pub branch_rename_editor: TextArea<'static>,
+    pub branch_rename_error: Option<String>,
+    // Base ref correction for the previewed task
+    pub base_ref_editor: TextArea<'static>,
+    pub base_ref_error: Option<String>,
+    // Display label for the selected task
+    pub label_editor: TextArea<'static>,
+    /// New repo location typed for a task whose repo moved on disk.
+    pub relocate_editor: TextArea<'static>,
+    // One-line answer to the selected task's engineer, and the question
+    // found at the bottom of its pane when the modal opened
+    pub answer_editor: TextArea<'static>,
+    pub answer_question: Option<String>,
+    // Repos that can be added to the selected task, for the `+` picker
+    pub add_repo_choices: Vec<String>,
+    pub add_repo_selected: usize,
+    // Messages sent to the selected task's engineer, oldest first
+    pub message_history: Vec<InboxMessage>,
+    pub message_history_title: String,
+    pub message_history_scroll: u16,
+    // Snapshot tags of the task in `snapshot_task_id`, newest first.
+    // `snapshot_restore_confirm` is set while a restore awaits `y`.
+    pub snapshot_task_id: Option<String>,
+    pub snapshots: Vec<String>,
+    pub snapshot_selected: usize,
+    pub snapshot_restore_confirm: bool,
+    // Quit confirmation: tasks with live sessions at quit time, and the view
+    // to return to on cancel.
+    pub quit_live_tasks: Vec<String>,
+    pub quit_return_view: View,
+    // `?` help overlay: the view whose keys are shown, and the scroll offset.
+    pub help_return_view: View,
+    pub help_scroll: u16,
+    // Repo scope for the task list, toggled with `F` on a task. Matches on
+    // repo name (see `TaskMeta::belongs_to_repo`), not a substring search.
+    pub repo_filter: Option<String>,
+    /// List snoozed tasks instead of hiding them (`S`).
+    pub show_snoozed: bool,
+    /// Only list tasks whose linked PR is someone else's (`V`).
+    pub review_queue_only: bool,
+    /// Tasks hidden by snooze in the last refresh.
+    pub snoozed_task_count: usize,
+    // Side-by-side task comparison. `compare_mark` is the first task picked
+    // with `C`; the second `C` on another task opens the view.
+    pub compare_mark: Option<String>,
+    pub compare_panes: Vec<ComparePane>,
+    pub compare_focus: usize,
+    /// Task IDs whose primary worktree directory is gone, recomputed on refresh.
+    pub missing_worktrees: HashSet<String>,
+    // Stalled-engineer detection (`agent_idle_secs`). `stalled_tasks` holds
+    // tasks whose engineer has been silent past the threshold.
+    pub idle_settings: use_cases::IdleSettings,
+    pub stalled_tasks: HashSet<String>,
+    /// Task list order, cycled with `O` and saved as the config default.
+    pub task_sort: use_cases::TaskSort,
+    /// `webhook_url` from config, read once at startup.
+    webhook_url: Option<String>,
+    /// `input_pattern` from config, read once at startup.
+    input_pattern: Option<regex::Regex>,
+    /// Tasks whose idle engineer pane matches `input_pattern`, with the
+    /// matching line.
+    pub input_needed_tasks: HashMap<String, String>,
+    // Pane captures for `input_pattern` run on the worker; one at a time.
+    input_check_tx: tokio_mpsc::UnboundedSender<HashMap<String, String>>,
+    input_check_rx: tokio_mpsc::UnboundedReceiver<HashMap<String, String>>,
+    input_check_active: bool,
+    // Live-view pane captures run on the worker; one at a time. Each result
+    // carries the session it came from so a stale capture can be dropped.
+    live_capture_tx: tokio_mpsc::UnboundedSender<(String, Result<String, String>)>,
+    live_capture_rx: tokio_mpsc::UnboundedReceiver<(String, Result<String, String>)>,
+    live_capture_active: bool,
+    // Clone-a-repo prompt opened from the repo picker
+    pub clone_url_editor: TextArea<'static>,
+    pub clone_error: Option<String>,
+    // `git clone` runs on the worker and streams progress lines back.
+    pub clone_in_progress: bool,
+    clone_tx: tokio_mpsc::UnboundedSender<CloneEvent>,
+    clone_rx: tokio_mpsc::UnboundedReceiver<CloneEvent>,
     // Unattached project agents plus task-attached child rows.
     pub agents: Vec<AgentRecord>,
     pub attached_task_agents: HashMap<String, Vec<AgentRecord>>,
@@ -863,6 +1313,14 @@ pub struct App {
     pub archive_in_progress: HashSet<String>,
     archive_tx: tokio_mpsc::UnboundedSender<(String, Result<bool, String>)>,
     archive_rx: tokio_mpsc::UnboundedReceiver<(String, Result<bool, String>)>,
+    // Task creation and repo addition (async). Fresh worktrees run the
+    // repo's setup script, which can take as long as an `npm install`.
+    task_create_tx: tokio_mpsc::UnboundedSender<CreatedTask>,
+    task_create_rx: tokio_mpsc::UnboundedReceiver<CreatedTask>,
+    issue_list_tx: tokio_mpsc::UnboundedSender<IssueListResult>,
+    issue_list_rx: tokio_mpsc::UnboundedReceiver<IssueListResult>,
+    add_repo_tx: tokio_mpsc::UnboundedSender<(String, String, Result<RepoEntry, String>)>,
+    add_repo_rx: tokio_mpsc::UnboundedReceiver<(String, String, Result<RepoEntry, String>)>,
     // Respawn confirmation dialog
     pub respawn_confirm_target: Option<String>,
     pub respawn_confirm_index: usize,
@@ -916,6 +1374,13 @@ impl App {
         let (project_refresh_tx, project_refresh_rx) = tokio_mpsc::unbounded_channel();
         let (respawn_tx, respawn_rx) = tokio_mpsc::unbounded_channel();
         let (archive_tx, archive_rx) = tokio_mpsc::unbounded_channel();
+        let (task_create_tx, task_create_rx) = tokio_mpsc::unbounded_channel();
+        let (issue_list_tx, issue_list_rx) = tokio_mpsc::unbounded_channel();
+        let (clone_tx, clone_rx) = tokio_mpsc::unbounded_channel();
+        let (usage_scan_tx, usage_scan_rx) = tokio_mpsc::unbounded_channel();
+        let (input_check_tx, input_check_rx) = tokio_mpsc::unbounded_channel();
+        let (live_capture_tx, live_capture_rx) = tokio_mpsc::unbounded_channel();
+        let (add_repo_tx, add_repo_rx) = tokio_mpsc::unbounded_channel();
         let rt = tokio::runtime::Runtime::new()?;
         let mut dismissed_notifs =
             DismissedNotifications::load(&config.dismissed_notifications_path());
@@ -965,14 +1430,31 @@ impl App {
         }
 
         let archive_retention_days = use_cases::load_archive_retention(&config);
+        let break_settings = use_cases::load_break_settings(&config);
+        let usage_settings = use_cases::load_usage_settings(&config);
+        let idle_settings = use_cases::load_idle_settings(&config);
+        let webhook_url = use_cases::load_webhook_url(&config);
+        let input_pattern = use_cases::load_input_pattern(&config);
+        let task_sort = use_cases::load_task_sort(&config);
+        let checkpoint_interval = use_cases::load_checkpoint_interval(&config);
+        let refresh_interval = use_cases::load_refresh_interval(&config);
+        let show_dashboard_on_start = use_cases::load_show_dashboard_on_start(&config);
 
         Ok(Self {
             config,
+            all_tasks: tasks.clone(),
             tasks,
             selected_index: 0,
             view: View::ProjectList,
             preview_content: String::new(),
             logs_editor,
+            log_search_editor: None,
+            log_search: None,
+            preview_full_log: false,
+            log_filter: use_cases::LogFilter::default(),
+            preview_ahead_behind: None,
+            preview_positions: VecDeque::new(),
+            preview_live: None,
             notes_content: String::new(),
             notes_editor,
             notes_editing: false,
@@ -981,21 +1463,29 @@ impl App {
             status_message: None,
             wizard: None,
             output_log: Vec::new(),
+            last_error: None,
+            pinned_error: None,
             output_scroll: 0,
             last_output_time: None,
             should_restart: false,
             rt,
             dir_picker: None,
+            repo_picker_position: None,
             session_picker_sessions: Vec::new(),
             selected_session_index: 0,
             attach_session_name: None,
+            pager_path: None,
+            editor_path: None,
             notifications: Vec::new(),
             selected_notif_index: 0,
+            notif_filter: use_cases::NotifReasonFilter::default(),
             last_gh_notif_poll: Instant::now() - Duration::from_secs(60),
             gh_notif_tx,
             gh_notif_rx,
             gh_notif_poll_active: false,
             gh_notif_first_poll_done: false,
+            pr_poll_backoff_until: None,
+            pr_poll_backoff: None,
             dismissed_notifs,
             notes_view: None,
             notes_return_view: View::ProjectList,
@@ -1012,6 +1502,18 @@ impl App {
             archive_retention_days,
             telegram_token_editor,
             telegram_chat_id_editor,
+            break_settings,
+            last_break_reset: Instant::now(),
+            usage_settings,
+            last_usage_scan: Instant::now() - USAGE_SCAN_INTERVAL,
+            usage_scan_tx,
+            usage_scan_rx,
+            usage_scan_active: false,
+            checkpoint_interval,
+            last_checkpoint: Instant::now(),
+            refresh_interval,
+            auto_refresh_paused: false,
+            show_dashboard_on_start,
             archive_kind: ArchiveKind::Tasks,
             archive_tasks: Vec::new(),
             archive_agents: Vec::new(),
@@ -1020,6 +1522,9 @@ impl App {
             archive_list_state: ListState::default(),
             archive_preview: None,
             archive_scroll: 0,
+            repo_stats_rows: Vec::new(),
+            repo_stats_sort: RepoStatsSort::default(),
+            repo_stats_selected: 0,
             projects: Vec::new(),
             selected_project_index: 0,
             current_project: None,
@@ -1035,6 +1540,52 @@ impl App {
             agent_wizard: None,
             project_picker: None,
             project_to_delete: None,
+            branch_rename_editor: Self::create_plain_editor(),
+            branch_rename_error: None,
+            base_ref_editor: Self::create_plain_editor(),
+            base_ref_error: None,
+            label_editor: Self::create_plain_editor(),
+            relocate_editor: Self::create_plain_editor(),
+            answer_editor: Self::create_plain_editor(),
+            answer_question: None,
+            add_repo_choices: Vec::new(),
+            add_repo_selected: 0,
+            message_history: Vec::new(),
+            message_history_title: String::new(),
+            message_history_scroll: 0,
+            snapshot_task_id: None,
+            snapshots: Vec::new(),
+            snapshot_selected: 0,
+            snapshot_restore_confirm: false,
+            quit_live_tasks: Vec::new(),
+            quit_return_view: View::ProjectList,
+            help_return_view: View::ProjectList,
+            help_scroll: 0,
+            repo_filter: None,
+            show_snoozed: false,
+            review_queue_only: false,
+            snoozed_task_count: 0,
+            compare_mark: None,
+            compare_panes: Vec::new(),
+            compare_focus: 0,
+            missing_worktrees: HashSet::new(),
+            idle_settings,
+            stalled_tasks: HashSet::new(),
+            task_sort,
+            webhook_url,
+            input_pattern,
+            input_needed_tasks: HashMap::new(),
+            input_check_tx,
+            input_check_rx,
+            input_check_active: false,
+            live_capture_tx,
+            live_capture_rx,
+            live_capture_active: false,
+            clone_url_editor: Self::create_plain_editor(),
+            clone_error: None,
+            clone_in_progress: false,
+            clone_tx,
+            clone_rx,
             agents: Vec::new(),
             attached_task_agents: HashMap::new(),
             agent_activity: HashMap::new(),
@@ -1053,6 +1604,12 @@ impl App {
             archive_in_progress: HashSet::new(),
             archive_tx,
             archive_rx,
+            task_create_tx,
+            task_create_rx,
+            issue_list_tx,
+            issue_list_rx,
+            add_repo_tx,
+            add_repo_rx,
             respawn_confirm_target: None,
             respawn_confirm_index: 0,
             respawn_confirm_is_chief_of_staff: false,
@@ -1162,7 +1719,17 @@ impl App {
 
     pub fn refresh_tasks(&mut self) {
         let prev_row_key = self.selected_project_detail_row_key();
-        self.tasks = Task::list_all(&self.config);
+        self.all_tasks = Task::list_all(&self.config);
+        self.tasks = self.all_tasks.clone();
+        if let Some(repo) = &self.repo_filter {
+            self.tasks.retain(|t| t.meta.belongs_to_repo(repo));
+        }
+        if self.review_queue_only {
+            self.tasks.retain(|t| t.meta.is_review_duty());
+        }
+        self.hide_snoozed_tasks();
+        use_cases::sort_tasks(&mut self.tasks, self.task_sort);
+        self.refresh_missing_worktrees();
         self.refresh_attached_task_agents();
         if self.restore_project_detail_selection(prev_row_key.as_ref()) {
             return;
@@ -1170,6 +1737,63 @@ impl App {
         self.clamp_project_detail_selection();
     }
 
+    fn hide_snoozed_tasks(&mut self) {
+        let now = chrono::Utc::now();
+        let before = self.tasks.len();
+        if !self.show_snoozed {
+            self.tasks.retain(|t| !t.meta.is_snoozed(now));
+        }
+        self.snoozed_task_count = before - self.tasks.len();
+    }
+
+    fn refresh_missing_worktrees(&mut self) {
+        self.missing_worktrees = self
+            .tasks
+            .iter()
+            .filter(|t| t.meta.worktree_missing())
+            .map(|t| t.meta.task_id())
+            .collect();
+    }
+
+    /// Report and return true when the selected task's worktree is missing,
+    /// so actions that need it can bail with a clear hint.
+    fn selected_task_worktree_missing(&mut self) -> bool {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return false;
+        };
+        if !self.missing_worktrees.contains(&task_id) {
+            return false;
+        }
+        self.set_status(format!(
+            "Worktree for {task_id} is missing; press W to recreate it from the branch"
+        ));
+        true
+    }
+
+    fn recreate_selected_task_worktree(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        let task_id = task.meta.task_id();
+        // A moved repo has to be found again before its worktree can be.
+        if use_cases::task_repo_missing(&self.config, task) {
+            self.relocate_editor = Self::create_plain_editor();
+            self.view = View::RelocateRepo;
+            return;
+        }
+        match use_cases::recreate_task_worktrees(&self.config, &task_id) {
+            Ok(0) => self.set_status(format!("Worktree for {task_id} already exists")),
+            Ok(_) => {
+                self.set_status(format!("Recreated worktree for {task_id}"));
+                self.refresh_tasks_and_select(&task_id);
+            }
+            Err(e) => {
+                tracing::error!(task_id = %task_id, error = %e, "failed to recreate worktree");
+                self.set_status(format!("Failed to recreate worktree: {e}"));
+            }
+        }
+    }
+
     /// Refresh the task list and restore selection to the task with the given ID.
     /// If the task is no longer present, selection falls back to a valid index.
     fn refresh_tasks_and_select(&mut self, task_id: &str) {
@@ -1356,6 +1980,186 @@ impl App {
         self.agent_activity.get(session_name)
     }
 
+    /// tmux session of the engineer attached to `task_id`, if any.
+    fn task_engineer_session(&self, task_id: &str) -> Option<String> {
+        self.attached_task_agents
+            .get(task_id)
+            .and_then(|agents| agents.iter().find(|a| a.is_engineer()))
+            .map(Self::agent_session_name)
+    }
+
+    /// Re-evaluate which tasks' engineers have been silent past
+    /// `agent_idle_secs`, from the in-memory activity samples. Raises one
+    /// desktop notification per stall when `agent_idle_notify` is on, and
+    /// posts to `webhook_url` when one is configured.
+    fn refresh_stalled_tasks(&mut self) {
+        let Some(threshold) = self.idle_settings.threshold else {
+            self.stalled_tasks.clear();
+            return;
+        };
+        let now = Instant::now();
+        // Every task, not just the current project's, so switching
+        // projects neither hides stalls nor re-fires their notifications.
+        let stalled: HashSet<String> = self
+            .all_tasks
+            .iter()
+            .map(|t| t.meta.task_id())
+            .filter(|task_id| {
+                self.task_engineer_session(task_id)
+                    .and_then(|session| self.agent_activity_sample(&session))
+                    .is_some_and(|sample| {
+                        sample.query_ok
+                            && !sample.pane_dead
+                            && !sample.foreground_command_is_shell()
+                            && sample.activity_age(now) >= threshold
+                    })
+            })
+            .collect();
+
+        for task_id in stalled.difference(&self.stalled_tasks) {
+            tracing::info!(task_id = %task_id, "engineer looks idle");
+            if self.idle_settings.notify {
+                use_cases::send_desktop_notification(
+                    "agman: task idle?",
+                    &format!("{task_id}: no engineer output for {}s", threshold.as_secs()),
+                );
+            }
+            if let Some(url) = self.webhook_url.clone() {
+                let payload = use_cases::task_idle_webhook_payload(task_id, threshold.as_secs());
+                let task_id = task_id.clone();
+                self.rt.spawn(async move {
+                    let result =
+                        tokio::task::spawn_blocking(move || use_cases::post_webhook(&url, &payload))
+                            .await;
+                    match result {
+                        Ok(Err(e)) => {
+                            tracing::warn!(task_id = %task_id, error = %e, "idle webhook failed")
+                        }
+                        Err(e) => {
+                            tracing::warn!(task_id = %task_id, error = %e, "idle webhook task panicked")
+                        }
+                        Ok(Ok(())) => {}
+                    }
+                });
+            }
+        }
+        self.stalled_tasks = stalled;
+    }
+
+    /// Re-check idle engineers' panes against `input_pattern`, across all
+    /// tasks. The captures run on the worker; `apply_input_check_result`
+    /// takes the matches.
+    fn refresh_input_needed_tasks(&mut self) {
+        let Some(pattern) = self.input_pattern.clone() else {
+            self.input_needed_tasks.clear();
+            return;
+        };
+        if self.input_check_active {
+            return;
+        }
+        let now = Instant::now();
+        // Only idle panes are captured: a working engineer isn't asking.
+        let idle: Vec<(String, String)> = self
+            .all_tasks
+            .iter()
+            .map(|t| t.meta.task_id())
+            .filter_map(|task_id| {
+                let session = self.task_engineer_session(&task_id)?;
+                let idle = self.agent_activity_sample(&session).is_some_and(|sample| {
+                    sample.query_ok
+                        && !sample.pane_dead
+                        && !sample.foreground_command_is_shell()
+                        && ui::classify_agent_status(now, Some(sample)) == ui::WorkingIdle::Idle
+                });
+                idle.then_some((task_id, session))
+            })
+            .collect();
+
+        self.input_check_active = true;
+        let tx = self.input_check_tx.clone();
+        self.rt.spawn(async move {
+            let waiting = tokio::task::spawn_blocking(move || {
+                idle.into_iter()
+                    .filter_map(|(task_id, session)| {
+                        let pane = Tmux::capture_pane(&session).ok()?;
+                        let line = use_cases::input_request(&pane, &pattern)?;
+                        Some((task_id, line))
+                    })
+                    .collect::<HashMap<_, _>>()
+            })
+            .await
+            .unwrap_or_default();
+            let _ = tx.send(waiting);
+        });
+    }
+
+    /// Take the worker's pane matches. Newly matched tasks are logged and
+    /// raise one desktop notification each.
+    fn apply_input_check_result(&mut self) {
+        let Ok(waiting) = self.input_check_rx.try_recv() else {
+            return;
+        };
+        self.input_check_active = false;
+        for (task_id, line) in &waiting {
+            if self.input_needed_tasks.contains_key(task_id) {
+                continue;
+            }
+            tracing::info!(task_id = %task_id, line = %line, "engineer is asking for input");
+            self.log_output(format!("{task_id} needs input: {line}"));
+            use_cases::send_desktop_notification(
+                "agman: input needed",
+                &format!("{task_id}: {line}"),
+            );
+        }
+        self.input_needed_tasks = waiting;
+    }
+
+    /// Snapshot running tasks' worktrees in the background once
+    /// `checkpoint_interval` has passed.
+    fn maybe_checkpoint_tasks(&mut self) {
+        let Some(interval) = self.checkpoint_interval else {
+            return;
+        };
+        if self.last_checkpoint.elapsed() < interval {
+            return;
+        }
+        self.last_checkpoint = Instant::now();
+        let config = self.config.clone();
+        self.rt.spawn(async move {
+            let written =
+                tokio::task::spawn_blocking(move || use_cases::checkpoint_running_tasks(&config))
+                    .await
+                    .unwrap_or(0);
+            tracing::debug!(written, "checkpoint pass finished");
+        });
+    }
+
+    /// Summarize which tasks need the user, from the last agent
+    /// activity refresh. Cheap enough to call on every draw.
+    pub fn task_attention(&self) -> TaskAttention {
+        let now = Instant::now();
+        let mut attention = TaskAttention::default();
+        for task in &self.all_tasks {
+            let Some(session) = self.task_engineer_session(&task.meta.task_id()) else {
+                continue;
+            };
+            // Only engineers whose tmux session was sampled count; a missing
+            // or failed sample says nothing about the agent.
+            let Some(sample) = self
+                .agent_activity_sample(&session)
+                .filter(|sample| sample.query_ok)
+            else {
+                continue;
+            };
+            if sample.pane_dead || sample.foreground_command_is_shell() {
+                attention.stopped += 1;
+            } else if ui::classify_agent_status(now, Some(sample)) == ui::WorkingIdle::Idle {
+                attention.waiting += 1;
+            }
+        }
+        attention
+    }
+
     /// Total entries in the project list (projects + unassigned pseudo-entry).
     pub fn project_list_len(&self) -> usize {
         self.projects.len() + if self.unassigned_task_count > 0 { 1 } else { 0 }
@@ -1364,24 +2168,30 @@ impl App {
     /// Refresh tasks filtered by current_project.
     fn refresh_tasks_for_project(&mut self) {
         let prev_row_key = self.selected_project_detail_row_key();
-        let all = Task::list_all(&self.config);
         let in_progress = &self.archive_in_progress;
+        self.all_tasks = Task::list_all(&self.config)
+            .into_iter()
+            .filter(|t| !in_progress.contains(&t.meta.task_id()))
+            .collect();
+        let all = self.all_tasks.iter().cloned();
         self.tasks = match &self.current_project {
-            Some(name) if name == "(unassigned)" => all
-                .into_iter()
-                .filter(|t| t.meta.project.is_none())
-                .filter(|t| !in_progress.contains(&t.meta.task_id()))
-                .collect(),
+            Some(name) if name == "(unassigned)" => {
+                all.filter(|t| t.meta.project.is_none()).collect()
+            }
             Some(name) => all
-                .into_iter()
                 .filter(|t| t.meta.project.as_deref() == Some(name.as_str()))
-                .filter(|t| !in_progress.contains(&t.meta.task_id()))
-                .collect(),
-            None => all
-                .into_iter()
-                .filter(|t| !in_progress.contains(&t.meta.task_id()))
                 .collect(),
+            None => all.collect(),
         };
+        if let Some(repo) = &self.repo_filter {
+            self.tasks.retain(|t| t.meta.belongs_to_repo(repo));
+        }
+        if self.review_queue_only {
+            self.tasks.retain(|t| t.meta.is_review_duty());
+        }
+        self.hide_snoozed_tasks();
+        use_cases::sort_tasks(&mut self.tasks, self.task_sort);
+        self.refresh_missing_worktrees();
         self.refresh_attached_task_agents();
         if self.restore_project_detail_selection(prev_row_key.as_ref()) {
             return;
@@ -1391,7 +2201,7 @@ impl App {
 
     fn refresh_attached_task_agents(&mut self) {
         self.attached_task_agents.clear();
-        for task in &self.tasks {
+        for task in &self.all_tasks {
             let task_id = task.meta.task_id();
             match use_cases::attached_agents_for_task(&self.config, &task_id) {
                 Ok(agents) => {
@@ -1426,7 +2236,19 @@ impl App {
         if self.tasks.is_empty() {
             rows.push(ProjectDetailRow::EmptyTasks);
         } else {
+            let any_pinned =
+                self.task_sort.groups_pinned() && self.tasks.iter().any(|t| t.meta.pinned);
             for (task_index, task) in self.tasks.iter().enumerate() {
+                if any_pinned && task_index == 0 {
+                    rows.push(ProjectDetailRow::PinnedTasksHeader);
+                }
+                if any_pinned
+                    && !task.meta.pinned
+                    && task_index > 0
+                    && self.tasks[task_index - 1].meta.pinned
+                {
+                    rows.push(ProjectDetailRow::OtherTasksHeader);
+                }
                 rows.push(ProjectDetailRow::Task(ProjectTaskRow::Task {
                     task_index,
                     task,
@@ -1486,6 +2308,8 @@ impl App {
             | ProjectDetailRow::TasksSectionHeader
             | ProjectDetailRow::TasksColumnsHeader
             | ProjectDetailRow::EmptyTasks
+            | ProjectDetailRow::PinnedTasksHeader
+            | ProjectDetailRow::OtherTasksHeader
             | ProjectDetailRow::AttachedAgentsHeader => None,
         }
     }
@@ -1714,10 +2538,16 @@ impl App {
     }
 
     pub fn set_status(&mut self, message: String) {
+        if use_cases::is_error_line(&message) {
+            self.last_error = Some(message.clone());
+        }
         self.status_message = Some((message, Instant::now()));
     }
 
     pub fn log_output(&mut self, message: String) {
+        if use_cases::is_error_line(&message) {
+            self.last_error = Some(message.trim().to_string());
+        }
         self.output_log.push(message);
         self.last_output_time = Some(Instant::now());
         // Keep only the last 100 lines
@@ -1728,6 +2558,26 @@ impl App {
         self.output_scroll = self.output_log.len().saturating_sub(5) as u16;
     }
 
+    /// `Y`: dismiss a pinned error, or copy the last error to the clipboard
+    /// and pin it in the status bar.
+    fn copy_last_error(&mut self) {
+        if self.pinned_error.take().is_some() {
+            return;
+        }
+        let Some(error) = self.last_error.clone() else {
+            self.set_status("No recent error".to_string());
+            return;
+        };
+        match copy_to_clipboard(&error) {
+            Ok(()) => self.set_status("Copied last error".to_string()),
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to copy last error");
+                self.status_message = Some((format!("Could not copy: {e}"), Instant::now()));
+            }
+        }
+        self.pinned_error = Some(error);
+    }
+
     pub fn clear_old_status(&mut self) {
         if let Some((_, instant)) = &self.status_message {
             if instant.elapsed() > Duration::from_secs(3) {
@@ -1977,6 +2827,65 @@ impl App {
         self.view = View::Archive;
     }
 
+    /// Re-scan the listed tasks' agent logs for usage on the worker;
+    /// `apply_usage_scan_result` copies changed totals onto the list.
+    fn scan_task_usage(&mut self) {
+        if self.usage_scan_active {
+            return;
+        }
+        self.usage_scan_active = true;
+        let tasks = self.tasks.clone();
+        let pattern = self.usage_settings.pattern.clone();
+        let tx = self.usage_scan_tx.clone();
+        self.rt.spawn(async move {
+            let changed = tokio::task::spawn_blocking(move || {
+                tasks
+                    .into_iter()
+                    .filter_map(|mut task| {
+                        let task_id = task.meta.task_id();
+                        match use_cases::refresh_task_usage(&mut task, &pattern) {
+                            Ok(true) => Some((task_id, task.meta.usage())),
+                            Ok(false) => None,
+                            Err(e) => {
+                                tracing::warn!(task_id = %task_id, error = %e, "failed to refresh task usage");
+                                None
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .await
+            .unwrap_or_default();
+            let _ = tx.send(changed);
+        });
+    }
+
+    fn apply_usage_scan_result(&mut self) {
+        let Ok(changed) = self.usage_scan_rx.try_recv() else {
+            return;
+        };
+        self.usage_scan_active = false;
+        for (task_id, totals) in changed {
+            for task in self
+                .tasks
+                .iter_mut()
+                .chain(self.all_tasks.iter_mut())
+                .filter(|t| t.meta.task_id() == task_id)
+            {
+                task.meta.total_input_tokens = totals.input_tokens;
+                task.meta.total_output_tokens = totals.output_tokens;
+                task.meta.total_cost_cents = totals.cost_cents;
+            }
+        }
+    }
+
+    fn open_repo_stats(&mut self) {
+        self.repo_stats_rows = use_cases::repo_dashboard(&self.config);
+        repo_stats::sort_repo_task_totals(&mut self.repo_stats_rows, self.repo_stats_sort);
+        self.repo_stats_selected = 0;
+        self.view = View::RepoStats;
+    }
+
     fn return_from_agent_wizard(&mut self) {
         self.agent_wizard = None;
         self.refresh_agents();
@@ -1984,25 +2893,146 @@ impl App {
     }
 
     fn load_preview(&mut self) {
-        let (preview_content, notes_content) = if let Some(task) = self.selected_task() {
-            let preview = task
-                .read_agent_log_structured_tail(500)
-                .unwrap_or_else(|_| "No agent log available".to_string());
-            let notes = task.read_notes().unwrap_or_default();
-            (preview, notes)
-        } else {
+        let (preview_content, notes_content, ahead_behind) =
+            if let Some(task) = self.selected_task() {
+                let preview = self.read_preview_log(task);
+                let notes = task.read_notes().unwrap_or_default();
+                (preview, notes, use_cases::task_ahead_behind(task))
+            } else {
+                return;
+            };
+        self.preview_ahead_behind = ahead_behind;
+
+        self.load_logs_editor(preview_content);
+        self.log_search_editor = None;
+        self.log_search = None;
+        self.preview_live = None;
+
+        self.load_notes_editor(notes_content);
+    }
+
+    /// Record where the Logs and Notes panes of the selected task are, for
+    /// the next time its preview is opened.
+    fn remember_preview_position(&mut self) {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        let away_from_bottom = |textarea: &TextArea<'static>| {
+            let cursor = textarea.cursor();
+            (cursor.0 + 1 < textarea.lines().len()).then_some(cursor)
+        };
+        let position = PreviewPosition {
+            logs: away_from_bottom(&self.logs_editor.textarea),
+            notes: away_from_bottom(&self.notes_editor.textarea),
+        };
+        self.preview_positions.retain(|(id, _)| *id != task_id);
+        if position != PreviewPosition::default() {
+            self.preview_positions.push_back((task_id, position));
+        }
+        while self.preview_positions.len() > PREVIEW_POSITIONS_MAX {
+            self.preview_positions.pop_front();
+        }
+    }
+
+    fn restore_preview_position(&mut self) {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        let Some(position) = self
+            .preview_positions
+            .iter()
+            .find(|(id, _)| *id == task_id)
+            .map(|(_, position)| *position)
+        else {
             return;
         };
+        let jump = |(row, col): (usize, usize)| {
+            CursorMove::Jump(
+                row.min(u16::MAX as usize) as u16,
+                col.min(u16::MAX as usize) as u16,
+            )
+        };
+        if let Some(cursor) = position.logs {
+            self.logs_editor.move_cursor(jump(cursor));
+        }
+        if let Some(cursor) = position.notes {
+            self.notes_editor.move_cursor(jump(cursor));
+        }
+    }
 
-        self.preview_content = preview_content.clone();
+    /// The Logs pane content for `task`: the whole log when
+    /// `preview_full_log` is set, else the structured tail, narrowed by
+    /// `log_filter`.
+    fn read_preview_log(&self, task: &Task) -> String {
+        let log = if self.preview_full_log {
+            task.read_agent_log()
+        } else {
+            task.read_agent_log_structured_tail(500)
+        };
+        match log {
+            Ok(log) => use_cases::filter_log(&log, self.log_filter),
+            Err(_) => "No agent log available".to_string(),
+        }
+    }
 
-        // Setup logs editor (read-only VimTextArea)
-        self.logs_editor = VimTextArea::from_lines(preview_content.lines());
+    /// Setup logs editor (read-only VimTextArea), scrolled to the bottom
+    fn load_logs_editor(&mut self, content: String) {
+        self.logs_editor = VimTextArea::from_lines(content.lines());
         self.logs_editor.set_read_only(true);
         self.logs_editor.set_normal_mode();
         self.logs_editor.move_cursor(CursorMove::Bottom);
+        self.preview_content = content;
+    }
+
+    /// Switch the Logs pane between the structured tail and the whole log.
+    /// An active search is kept and re-anchored to its first match in the
+    /// new content, since matches often sit in the part the tail hides.
+    fn toggle_preview_full_log(&mut self) {
+        if self.selected_task().is_none() {
+            return;
+        }
+        self.preview_full_log = !self.preview_full_log;
+        let Some(content) = self.selected_task().map(|t| self.read_preview_log(t)) else {
+            return;
+        };
+        self.preview_live = None;
+        self.load_logs_editor(content);
+        self.set_status(
+            if self.preview_full_log {
+                "Showing full log"
+            } else {
+                "Showing log tail"
+            }
+            .to_string(),
+        );
+        if let Some(search) = self.log_search.as_mut() {
+            search.matches = find_log_matches(self.logs_editor.textarea.lines(), &search.term);
+            search.current = (!search.matches.is_empty()).then_some(0);
+            self.highlight_log_match();
+        }
+    }
+
+    /// Cycle the Logs pane filter and reload the log through it.
+    fn cycle_log_filter(&mut self) {
+        if self.selected_task().is_none() {
+            return;
+        }
+        self.log_filter = self.log_filter.next();
+        let Some(content) = self.selected_task().map(|t| self.read_preview_log(t)) else {
+            return;
+        };
+        self.preview_live = None;
+        self.load_logs_editor(content);
+        self.set_status(format!("Log filter: {}", self.log_filter.label()));
+        if let Some(search) = self.log_search.as_mut() {
+            search.matches = find_log_matches(self.logs_editor.textarea.lines(), &search.term);
+            search.current = (!search.matches.is_empty()).then_some(0);
+            self.highlight_log_match();
+        }
+    }
 
-        // Setup notes editor with vim mode (read-only until user starts editing)
+    /// Setup notes editor with vim mode (read-only until user starts editing)
+    fn load_notes_editor(&mut self, notes_content: String) {
         self.notes_content = notes_content.clone();
         self.notes_editor = VimTextArea::from_lines(notes_content.lines());
         self.notes_editor.set_read_only(true);
@@ -2012,6 +3042,17 @@ impl App {
         self.notes_editing = false;
     }
 
+    /// Pick up edits made to the selected task's notes outside the TUI.
+    fn reload_notes(&mut self) {
+        let Some(notes) = self
+            .selected_task()
+            .map(|t| t.read_notes().unwrap_or_default())
+        else {
+            return;
+        };
+        self.load_notes_editor(notes);
+    }
+
     fn save_notes(&mut self) -> Result<()> {
         if let Some(task) = self.selected_task() {
             let notes = self.notes_editor.lines_joined();
@@ -2023,6 +3064,10 @@ impl App {
     }
 
     fn archive_task(&mut self, saved: bool) -> Result<()> {
+        self.archive_task_with(saved, ArchiveMode::Full)
+    }
+
+    fn archive_task_with(&mut self, saved: bool, mode: ArchiveMode) -> Result<()> {
         if self.tasks.is_empty() {
             return Ok(());
         }
@@ -2033,7 +3078,7 @@ impl App {
         let task = self.tasks.remove(task_index);
         let task_id = task.meta.task_id();
 
-        tracing::info!(task_id = %task_id, saved, "TUI: archive task requested (async)");
+        tracing::info!(task_id = %task_id, saved, ?mode, "TUI: archive task requested (async)");
         self.log_output(format!("Archiving task {}...", task_id));
 
         // Optimistic UI updates: clear attached agent cache, clamp selection,
@@ -2043,10 +3088,10 @@ impl App {
         self.refresh_attached_task_agents();
         self.clamp_project_detail_selection_near(self.selected_index);
 
-        let label = if saved {
-            "Archiving & saving"
-        } else {
-            "Archiving"
+        let label = match (saved, mode) {
+            (true, _) => "Archiving & saving",
+            (false, ArchiveMode::KeepSession) => "Archiving (session kept)",
+            (false, ArchiveMode::Full) => "Archiving",
         };
         self.set_status(format!("{}: {}", label, task_id));
         self.view = View::TaskList;
@@ -2060,7 +3105,7 @@ impl App {
         let task_id_for_send = task_id.clone();
         self.rt.spawn(async move {
             let result = tokio::task::spawn_blocking(move || {
-                use_cases::archive_task(&config, &mut owned_task, saved).map(|_| saved)
+                use_cases::archive_task_with(&config, &mut owned_task, saved, mode).map(|_| saved)
             })
             .await
             .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
@@ -2091,16 +3136,31 @@ impl App {
         };
 
         // Launch the directory picker in RepoSelect mode, rooted at repos_dir, with favourites
-        self.dir_picker = Some(DirectoryPicker::new_with_favorites(
+        let mut picker = DirectoryPicker::new_with_favorites(
             start,
             DirPickerOrigin::RepoSelect,
             &self.config.repo_stats_path(),
             self.config.repos_dir.clone(),
-        ));
+            self.config.worktrees_dir.clone(),
+            use_cases::load_repos_ignore(&self.config),
+        );
+        if let Some((dir, index)) = self.repo_picker_position.clone() {
+            if dir.starts_with(&self.config.repos_dir) {
+                picker.restore_position(dir, index);
+            }
+        }
+        self.dir_picker = Some(picker);
         self.view = View::DirectoryPicker;
         Ok(())
     }
 
+    /// Remember where the repo picker is before it closes.
+    fn remember_repo_picker_position(&mut self) {
+        if let Some(picker) = self.dir_picker.as_ref().filter(|p| p.is_repo_select_mode()) {
+            self.repo_picker_position = Some((picker.current_dir.clone(), picker.selected_index));
+        }
+    }
+
     /// Create the wizard from a directory picker selection, starting at `SelectBranch`.
     fn create_wizard_from_picker(
         &mut self,
@@ -2126,6 +3186,11 @@ impl App {
         let mut first_prompt_editor = VimTextArea::new();
         first_prompt_editor.set_insert_mode();
 
+        let start_repo_choices = if is_multi {
+            Self::scan_git_subdirs(&repo_path)
+        } else {
+            Vec::new()
+        };
         let (branches, worktrees) = if is_multi {
             (Vec::new(), Vec::new())
         } else {
@@ -2148,7 +3213,16 @@ impl App {
             base_branch_focus: false,
             first_prompt_editor,
             error_message: None,
+            creating: false,
+            reuse_branch_confirm: None,
             is_multi_repo: is_multi,
+            start_repo_choices,
+            start_repo: None,
+            branch_completion: None,
+            auto_rebase: false,
+            issue_picker: None,
+            selected_issue_index: 0,
+            loading_issues: false,
         });
 
         self.view = View::NewTaskWizard;
@@ -2169,19 +3243,35 @@ impl App {
         let mut branches: Vec<String> = stdout.lines().map(|s| s.to_string()).collect();
 
         // Filter out branches that already have tasks
-        let existing_tasks: std::collections::HashSet<String> = self
-            .tasks
-            .iter()
-            .filter(|t| t.meta.name == repo_name)
-            .map(|t| t.meta.branch_name.clone())
-            .collect();
-
-        branches.retain(|b| !existing_tasks.contains(b));
+        branches.retain(|b| Self::task_owning_branch(&self.all_tasks, repo_name, b).is_none());
         branches.sort();
 
         Ok(branches)
     }
 
+    /// ID of the live task that has `branch` of `repo_name` checked out,
+    /// including multi-repo tasks that merely include the repo.
+    fn task_owning_branch(tasks: &[Task], repo_name: &str, branch: &str) -> Option<String> {
+        tasks
+            .iter()
+            .find(|t| t.meta.branch_name == branch && t.meta.belongs_to_repo(repo_name))
+            .map(|t| t.meta.task_id())
+    }
+
+    /// Sorted names of the git repos directly under `dir`.
+    fn scan_git_subdirs(dir: &Path) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut repos: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.path().join(".git").exists())
+            .filter_map(|e| e.file_name().to_str().map(str::to_string))
+            .collect();
+        repos.sort();
+        repos
+    }
+
     fn scan_existing_worktrees(
         &self,
         repo_name: &str,
@@ -2276,6 +3366,48 @@ impl App {
                     return Ok(());
                 }
 
+                // A "new" branch that already exists (e.g. left behind by a
+                // deleted task) is reused only once the user confirms.
+                if wizard.branch_source == BranchSource::NewBranch && !wizard.is_multi_repo {
+                    if let Some(owner) =
+                        Self::task_owning_branch(&self.all_tasks, &repo_name, &branch_name)
+                    {
+                        wizard.error_message = Some(format!(
+                            "Branch '{}' belongs to task '{}'; pick another name",
+                            branch_name, owner
+                        ));
+                        return Ok(());
+                    }
+                    let repo_path = &wizard.selected_repo_path;
+                    let exists = wizard.existing_branches.contains(&branch_name)
+                        || Git::local_branch_exists(repo_path, &branch_name)
+                        || Git::remote_branch_exists(repo_path, &branch_name);
+                    if exists {
+                        if wizard.reuse_branch_confirm.as_deref() != Some(branch_name.as_str()) {
+                            wizard.error_message = Some(format!(
+                                "Branch '{}' already exists. Enter again to reuse it, or pick another name",
+                                branch_name
+                            ));
+                            wizard.reuse_branch_confirm = Some(branch_name);
+                            return Ok(());
+                        }
+                        tracing::info!(repo = %repo_name, branch = %branch_name, "reusing existing branch from new-branch wizard");
+                        let index = match wizard
+                            .existing_branches
+                            .iter()
+                            .position(|b| *b == branch_name)
+                        {
+                            Some(index) => index,
+                            None => {
+                                wizard.existing_branches.push(branch_name);
+                                wizard.existing_branches.len() - 1
+                            }
+                        };
+                        wizard.branch_source = BranchSource::ExistingBranch;
+                        wizard.selected_branch_index = index;
+                    }
+                }
+
                 wizard.step = WizardStep::EnterFirstPrompt;
             }
             WizardStep::EnterFirstPrompt => {
@@ -2320,6 +3452,10 @@ impl App {
         let name = wizard.selected_repo_name().to_string();
         let repo_path = wizard.selected_repo_path.clone();
         let is_multi = wizard.is_multi_repo;
+        let auto_rebase = wizard.auto_rebase;
+        let start_repo = wizard
+            .start_repo
+            .and_then(|i| wizard.start_repo_choices.get(i).cloned());
 
         let (branch_name, worktree_source) = match wizard.branch_source {
             BranchSource::ExistingWorktree => {
@@ -2381,6 +3517,13 @@ impl App {
             };
 
             let task_id = task.meta.task_id();
+            if auto_rebase || start_repo.is_some() {
+                task.meta.auto_rebase = auto_rebase;
+                task.meta.primary_repo_hint = start_repo;
+                if let Err(e) = task.save_meta() {
+                    tracing::warn!(task_id = %task_id, error = %e, "failed to save wizard options");
+                }
+            }
             self.log_output("  Launching engineer via supervisor...".to_string());
             if let Err(e) = supervisor::ensure_task_tmux(&self.config, &task)
                 .and_then(|_| supervisor::launch_next_step(&self.config, &mut task).map(|_| ()))
@@ -2402,6 +3545,7 @@ impl App {
                 self.refresh_tasks_and_select(&task_id);
             }
             self.set_status(format!("Created multi-repo task: {}", task_id));
+            self.report_tmux_session_collisions(&task_id);
         } else {
             // Single-repo path: compute parent_dir when repo is outside repos_dir
             let parent_dir = repo_path.parent().and_then(|p| {
@@ -2412,49 +3556,38 @@ impl App {
                 }
             });
 
-            let mut task = match use_cases::create_task(
-                &self.config,
-                &name,
-                &branch_name,
-                first_prompt,
-                "new",
-                worktree_source,
-                parent_dir,
-                project,
-            ) {
-                Ok(t) => t,
-                Err(e) => {
-                    tracing::error!(repo = %name, branch = %branch_name, error = %e, "failed to create task");
-                    self.log_output(format!("  Error: {}", e));
-                    if let Some(w) = &mut self.wizard {
-                        w.error_message = Some(format!("Failed to create task: {}", e));
-                    }
-                    return Ok(());
-                }
-            };
-
-            let task_id = task.meta.task_id();
-            self.log_output("  Launching engineer via supervisor...".to_string());
-            if let Err(e) = supervisor::ensure_task_tmux(&self.config, &task)
-                .and_then(|_| supervisor::launch_next_step(&self.config, &mut task).map(|_| ()))
-            {
-                tracing::error!(repo = %name, branch = %branch_name, error = %e, "failed to launch task engineer");
-                self.log_output(format!("  Error: {}", e));
-                if let Some(w) = &mut self.wizard {
-                    w.error_message = Some(format!("Failed to launch engineer: {}", e));
-                }
-                return Ok(());
-            }
-
-            // Success - close wizard and refresh
-            self.wizard = None;
-            self.view = View::TaskList;
-            if self.current_project.is_some() {
-                self.refresh_tasks_for_project();
-            } else {
-                self.refresh_tasks_and_select(&task_id);
-            }
-            self.set_status(format!("Created task: {}", task_id));
+            // The worktree's setup script can take minutes, so creation
+            // runs on the worker; `apply_task_create_results` launches the
+            // engineer or puts the error back in the wizard.
+            if let Some(w) = &mut self.wizard {
+                w.creating = true;
+            }
+            let first_prompt = first_prompt.map(str::to_string);
+            let tx = self.task_create_tx.clone();
+            let config = self.config.clone();
+            self.rt.spawn(async move {
+                let (task_name, task_branch) = (name.clone(), branch_name.clone());
+                let result = tokio::task::spawn_blocking(move || {
+                    use_cases::create_task(
+                        &config,
+                        &task_name,
+                        &task_branch,
+                        first_prompt.as_deref(),
+                        "new",
+                        worktree_source,
+                        parent_dir,
+                        project,
+                    )
+                })
+                .await
+                .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
+                let _ = tx.send(CreatedTask {
+                    name,
+                    branch_name,
+                    auto_rebase,
+                    result: result.map_err(|e| format!("{e}")),
+                });
+            });
         }
 
         Ok(())
@@ -2463,7 +3596,21 @@ impl App {
     pub fn handle_event(&mut self, event: Event) -> Result<bool> {
         self.clear_old_status();
 
-        match self.view {
+        if let Event::Paste(ref text) = event {
+            self.paste_into_active_editor(text);
+            return Ok(false);
+        }
+
+        if let Event::Key(key) = &event {
+            if key.code == KeyCode::Char('?') && self.help_available() {
+                self.help_return_view = self.view;
+                self.help_scroll = 0;
+                self.view = View::Help;
+                return Ok(false);
+            }
+        }
+
+        let result = match self.view {
             View::ProjectList => self.handle_project_list_event(event),
             View::TaskList => self.handle_task_list_event(event),
             View::Preview => self.handle_preview_event(event),
@@ -2481,7 +3628,199 @@ impl App {
             View::ProjectDeleteConfirm => self.handle_project_delete_confirm_event(event),
             View::AgentWizard => self.handle_agent_wizard_event(event),
             View::RespawnConfirm => self.handle_respawn_confirm_event(event),
+            View::RepoStats => self.handle_repo_stats_event(event),
+            View::RenameBranch => self.handle_rename_branch_event(event),
+            View::EditBaseRef => self.handle_edit_base_ref_event(event),
+            View::EditLabel => self.handle_edit_label_event(event),
+            View::RelocateRepo => self.handle_relocate_repo_event(event),
+            View::QuickAnswer => self.handle_quick_answer_event(event),
+            View::AddRepo => self.handle_add_repo_event(event),
+            View::MessageHistory => self.handle_message_history_event(event),
+            View::Snapshots => self.handle_snapshots_event(event),
+            View::Dashboard => self.handle_dashboard_event(event),
+            View::CloneRepo => self.handle_clone_repo_event(event),
+            View::Compare => self.handle_compare_event(event),
+            View::QuitConfirm => self.handle_quit_confirm_event(event),
+            View::Help => self.handle_help_event(event),
+        };
+        if self.should_quit && self.view != View::QuitConfirm {
+            self.confirm_quit_if_tasks_live();
+        }
+        result
+    }
+
+    /// `?` opens help only from navigation states, so it can still be typed
+    /// into editors, search prompts and inline inputs.
+    fn help_available(&self) -> bool {
+        if !keymap::has_help(self.view) {
+            return false;
+        }
+        match self.view {
+            View::Preview => !self.notes_editing && self.log_search_editor.is_none(),
+            View::Notes => self.notes_view.as_ref().is_some_and(|nv| {
+                nv.focus == NotesFocus::Explorer
+                    && nv.rename_input.is_none()
+                    && nv.create_input.is_none()
+                    && nv.finder.is_none()
+            }),
+            _ => true,
+        }
+    }
+
+    fn handle_help_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.should_quit = true;
+                }
+                KeyCode::Char('?') | KeyCode::Char('q') | KeyCode::Esc => {
+                    self.view = self.help_return_view;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.help_scroll = self.help_scroll.saturating_add(1);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.help_scroll = self.help_scroll.saturating_sub(1);
+                }
+                KeyCode::Char('g') => {
+                    self.help_scroll = 0;
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    /// Route a bracketed paste to whichever editor or input currently has
+    /// focus. Pastes elsewhere are dropped rather than replayed as keys.
+    fn paste_into_active_editor(&mut self, text: &str) {
+        match self.view {
+            View::Preview if self.notes_editing && self.preview_pane == PreviewPane::Notes => {
+                self.notes_editor.paste_text(text);
+            }
+            View::Preview => {
+                if let Some(search) = self.log_search_editor.as_mut() {
+                    paste_into_line(search, text);
+                }
+            }
+            View::NewTaskWizard => {
+                if let Some(wizard) = self.wizard.as_mut() {
+                    match wizard.step {
+                        WizardStep::EnterFirstPrompt => wizard.first_prompt_editor.paste_text(text),
+                        WizardStep::SelectBranch
+                            if wizard.branch_source == BranchSource::NewBranch
+                                && wizard.issue_picker.is_none() =>
+                        {
+                            if wizard.base_branch_focus {
+                                paste_into_line(&mut wizard.base_branch_editor, text);
+                            } else {
+                                paste_into_line(&mut wizard.new_branch_editor, text);
+                                wizard.branch_completion = None;
+                            }
+                        }
+                        WizardStep::SelectBranch => {}
+                    }
+                }
+            }
+            View::ProjectWizard => {
+                if let Some(wizard) = self.project_wizard.as_mut() {
+                    if wizard.description_focus {
+                        wizard.description_editor.paste_text(text);
+                    } else {
+                        paste_into_line(&mut wizard.name_editor, text);
+                    }
+                }
+            }
+            View::AgentWizard => {
+                if let Some(wizard) = self.agent_wizard.as_mut() {
+                    match wizard.step {
+                        AgentWizardStep::FirstPrompt => wizard.first_prompt_editor.paste_text(text),
+                        AgentWizardStep::Name => paste_into_line(&mut wizard.name_editor, text),
+                        AgentWizardStep::Worktrees => {
+                            if let Some(row) = wizard.worktree_rows.get_mut(wizard.selected_row) {
+                                if row.branch_focus {
+                                    paste_into_line(&mut row.branch_editor, text);
+                                } else {
+                                    paste_into_line(&mut row.repo_editor, text);
+                                }
+                            }
+                        }
+                        AgentWizardStep::Kind | AgentWizardStep::Capabilities => {}
+                    }
+                }
+            }
+            View::Notes => {
+                if let Some(nv) = self.notes_view.as_mut() {
+                    if let Some(finder) = nv.finder.as_mut() {
+                        paste_into_line(&mut finder.input, text);
+                        nv.update_finder_matches();
+                    } else if let Some(input) = nv.rename_input.as_mut() {
+                        paste_into_line(input, text);
+                    } else if let Some((input, _)) = nv.create_input.as_mut() {
+                        paste_into_line(input, text);
+                    } else if nv.focus == NotesFocus::Editor && nv.open_file.is_some() {
+                        nv.editor.paste_text(text);
+                        nv.modified = true;
+                    }
+                }
+            }
+            View::Settings if self.settings_editing => match self.settings_selected {
+                2 => paste_into_line(&mut self.telegram_token_editor, text),
+                3 => paste_into_line(&mut self.telegram_chat_id_editor, text),
+                _ => {}
+            },
+            View::Archive if self.archive_preview.is_none() => {
+                paste_into_line(&mut self.archive_search, text);
+                self.archive_selected = 0;
+            }
+            View::RenameBranch => paste_into_line(&mut self.branch_rename_editor, text),
+            View::EditBaseRef => paste_into_line(&mut self.base_ref_editor, text),
+            View::EditLabel => paste_into_line(&mut self.label_editor, text),
+            View::RelocateRepo => paste_into_line(&mut self.relocate_editor, text),
+            View::QuickAnswer => paste_into_line(&mut self.answer_editor, text),
+            View::CloneRepo => paste_into_line(&mut self.clone_url_editor, text),
+            _ => {}
+        }
+    }
+
+    /// Intercept a quit request while tasks still have live tmux sessions so
+    /// they aren't forgotten; otherwise let it through.
+    fn confirm_quit_if_tasks_live(&mut self) {
+        let live = use_cases::live_task_ids(&self.config);
+        if live.is_empty() {
+            return;
+        }
+        self.should_quit = false;
+        self.quit_live_tasks = live;
+        self.quit_return_view = self.view;
+        self.view = View::QuitConfirm;
+    }
+
+    fn handle_quit_confirm_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.should_quit = true;
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.should_quit = true;
+                }
+                KeyCode::Char('s') => {
+                    for task_id in &self.quit_live_tasks {
+                        if let Err(e) = use_cases::stop_task_sessions(&self.config, task_id) {
+                            tracing::error!(task_id = %task_id, error = %e, "failed to stop task sessions");
+                        }
+                    }
+                    self.should_quit = true;
+                }
+                KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q') => {
+                    self.quit_live_tasks.clear();
+                    self.view = self.quit_return_view;
+                }
+                _ => {}
+            }
         }
+        Ok(false)
     }
 
     fn handle_project_list_event(&mut self, event: Event) -> Result<bool> {
@@ -2509,6 +3848,9 @@ impl App {
                 KeyCode::Char('g') => {
                     self.selected_project_index = 0;
                 }
+                KeyCode::Char('Y') => {
+                    self.copy_last_error();
+                }
                 KeyCode::Char('G') => {
                     let total = self.project_list_len();
                     if total > 0 {
@@ -2645,6 +3987,15 @@ impl App {
                     self.settings_selected = 0;
                     self.view = View::Settings;
                 }
+                KeyCode::Char('r') => {
+                    self.open_repo_stats();
+                }
+                KeyCode::Char('b') => {
+                    self.reset_break_timer();
+                }
+                KeyCode::Char(' ') => {
+                    self.toggle_auto_refresh();
+                }
                 _ => {}
             }
         }
@@ -2689,6 +4040,7 @@ impl App {
             match key.code {
                 KeyCode::Char('q') | KeyCode::Esc => {
                     self.current_project = None;
+                    self.repo_filter = None;
                     self.refresh_projects();
                     self.view = View::ProjectList;
                 }
@@ -2720,6 +4072,7 @@ impl App {
             KeyCode::Enter => match self.selected_project_detail_row() {
                 Some(ProjectDetailRow::Task(_)) => {
                     self.load_preview();
+                    self.restore_preview_position();
                     self.preview_pane = PreviewPane::Logs;
                     self.view = View::Preview;
                 }
@@ -2735,6 +4088,15 @@ impl App {
             KeyCode::Char('d') => {
                 self.start_focused_archive_confirm();
             }
+            KeyCode::Char('A') => {
+                // Archive without the confirm: finished work, full cleanup.
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.archive_task(false)?;
+                }
+            }
             KeyCode::Char('G') => {
                 self.select_last_project_detail_row();
             }
@@ -2779,7 +4141,8 @@ impl App {
                 if matches!(
                     self.selected_project_detail_row(),
                     Some(ProjectDetailRow::Task(_))
-                ) {
+                ) && !self.selected_task_worktree_missing()
+                {
                     self.restart_selected_task()?;
                 }
             }
@@ -2797,803 +4160,1087 @@ impl App {
             KeyCode::Char('e') => {
                 self.start_project_respawn_confirm();
             }
-            _ => {}
-        }
-        Ok(false)
-    }
-
-    fn handle_settings_event(&mut self, event: Event) -> Result<bool> {
-        if let Event::Key(key) = event {
-            // When editing a text field, route keys to the TextArea
-            if self.settings_editing {
-                match key.code {
-                    KeyCode::Enter => {
-                        // Save the edited value
-                        let token_text: String = self.telegram_token_editor.lines().join("");
-                        let chat_id_text: String = self.telegram_chat_id_editor.lines().join("");
-                        let token = if token_text.is_empty() {
-                            None
-                        } else {
-                            Some(token_text)
-                        };
-                        let chat_id = if chat_id_text.is_empty() {
-                            None
-                        } else {
-                            Some(chat_id_text)
-                        };
-                        if let Err(e) =
-                            use_cases::save_telegram_config(&self.config, token, chat_id)
-                        {
-                            tracing::error!(error = %e, "failed to save telegram config");
-                            self.set_status(format!("Failed to save: {e}"));
-                        } else {
-                            self.set_status("Telegram settings saved".to_string());
-                            self.restart_telegram_bot();
-                        }
-                        self.settings_editing = false;
-                    }
-                    KeyCode::Esc => {
-                        // Discard edit, restore from config
-                        let (token, chat_id) = use_cases::load_telegram_config(&self.config);
-                        self.telegram_token_editor = TextArea::new(vec![token.unwrap_or_default()]);
-                        self.telegram_token_editor
-                            .set_cursor_line_style(ratatui::style::Style::default());
-                        self.telegram_chat_id_editor =
-                            TextArea::new(vec![chat_id.unwrap_or_default()]);
-                        self.telegram_chat_id_editor
-                            .set_cursor_line_style(ratatui::style::Style::default());
-                        self.settings_editing = false;
-                    }
-                    _ => match self.settings_selected {
-                        2 => {
-                            self.telegram_token_editor.input(event);
-                        }
-                        3 => {
-                            self.telegram_chat_id_editor.input(event);
-                        }
-                        _ => {}
-                    },
-                }
-                return Ok(false);
+            KeyCode::Char('b') => {
+                self.reset_break_timer();
             }
-
-            match key.code {
-                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.should_quit = true;
+            KeyCode::Char(' ') => {
+                self.toggle_auto_refresh();
+            }
+            KeyCode::Char('R') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) && !self.selected_task_worktree_missing()
+                {
+                    self.start_branch_rename();
                 }
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    self.view = View::TaskList;
+            }
+            KeyCode::Char('L') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.start_label_edit();
                 }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    if self.settings_selected < 3 {
-                        self.settings_selected += 1;
-                    }
+            }
+            KeyCode::Char('H') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.open_message_history();
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    if self.settings_selected > 0 {
-                        self.settings_selected -= 1;
-                    }
+            }
+            KeyCode::Char('m') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.start_quick_answer();
                 }
-                KeyCode::Enter => {
-                    // Enter editing mode for text fields
-                    if self.settings_selected == 2 || self.settings_selected == 3 {
-                        self.settings_editing = true;
-                        // Move cursor to end of current value
-                        let editor = if self.settings_selected == 2 {
-                            &mut self.telegram_token_editor
-                        } else {
-                            &mut self.telegram_chat_id_editor
-                        };
-                        let line_len = editor.lines().first().map(|l| l.len()).unwrap_or(0);
-                        editor.move_cursor(tui_textarea::CursorMove::Jump(0, line_len as u16));
+            }
+            KeyCode::Char('+') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.start_add_repo();
+                }
+            }
+            KeyCode::Char('W') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.recreate_selected_task_worktree();
+                }
+            }
+            KeyCode::Char('K') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    if let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) {
+                        self.compact_task(&task_id);
                     }
                 }
-                KeyCode::Char('h') | KeyCode::Left => {
-                    match self.settings_selected {
-                        0 => {
-                            // Decrease archive retention by 7 days (min 7)
-                            if self.archive_retention_days > 7 {
-                                let new_days = self.archive_retention_days - 7;
-                                tracing::info!(
-                                    old_days = self.archive_retention_days,
-                                    new_days,
-                                    "archive retention changed"
-                                );
-                                self.archive_retention_days = new_days;
-                                if let Err(e) =
-                                    use_cases::save_archive_retention(&self.config, new_days)
-                                {
-                                    tracing::error!(error = %e, "failed to save archive retention");
-                                    self.set_status(format!("Failed to save: {e}"));
-                                }
-                            }
-                        }
-                        1 => {
-                            // Cycle harness left through HarnessKind::ALL
-                            self.cycle_harness(-1);
-                        }
-                        _ => {}
+            }
+            KeyCode::Char('Z') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) && !self.selected_task_worktree_missing()
+                {
+                    if let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) {
+                        self.snapshot_task(&task_id);
                     }
                 }
-                KeyCode::Char('l') | KeyCode::Right => {
-                    match self.settings_selected {
-                        0 => {
-                            // Increase archive retention by 7 days (max 365)
-                            if self.archive_retention_days < 365 {
-                                let new_days = self.archive_retention_days + 7;
-                                tracing::info!(
-                                    old_days = self.archive_retention_days,
-                                    new_days,
-                                    "archive retention changed"
-                                );
-                                self.archive_retention_days = new_days;
-                                if let Err(e) =
-                                    use_cases::save_archive_retention(&self.config, new_days)
-                                {
-                                    tracing::error!(error = %e, "failed to save archive retention");
-                                    self.set_status(format!("Failed to save: {e}"));
-                                }
-                            }
-                        }
-                        1 => {
-                            // Cycle harness right through HarnessKind::ALL
-                            self.cycle_harness(1);
-                        }
-                        _ => {}
+            }
+            KeyCode::Char('U') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) && !self.selected_task_worktree_missing()
+                {
+                    self.open_snapshots();
+                }
+            }
+            KeyCode::Char('C') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.mark_or_open_compare();
+                }
+            }
+            KeyCode::Char('P') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.toggle_selected_task_pin();
+                }
+            }
+            KeyCode::Char('s') => {
+                if matches!(
+                    self.selected_project_detail_row(),
+                    Some(ProjectDetailRow::Task(_))
+                ) {
+                    self.toggle_selected_task_snooze();
+                }
+            }
+            KeyCode::Char('S') => {
+                self.show_snoozed = !self.show_snoozed;
+                self.refresh_tasks_for_project();
+                self.set_status(
+                    if self.show_snoozed {
+                        "Showing snoozed tasks"
+                    } else {
+                        "Hiding snoozed tasks"
                     }
+                    .to_string(),
+                );
+            }
+            KeyCode::Char('O') => {
+                self.cycle_task_sort();
+            }
+            KeyCode::Char('V') => {
+                self.toggle_review_queue();
+            }
+            KeyCode::Char('Y') => {
+                self.copy_last_error();
+            }
+            KeyCode::Char('F') => {
+                if self.repo_filter.is_some()
+                    || matches!(
+                        self.selected_project_detail_row(),
+                        Some(ProjectDetailRow::Task(_))
+                    )
+                {
+                    self.toggle_repo_filter();
                 }
-                _ => {}
             }
+            _ => {}
         }
         Ok(false)
     }
 
-    /// Cycle the configured harness by `delta` (typically -1 / +1).
-    fn cycle_harness(&mut self, delta: i32) {
-        use agman::harness::HarnessKind;
-        let all = HarnessKind::ALL;
-        let current = self.config.harness_kind();
-        let pos = all.iter().position(|k| *k == current).unwrap_or(0);
-        let new_pos = ((pos as i32 + delta).rem_euclid(all.len() as i32)) as usize;
-        let new_kind = all[new_pos];
-        if new_kind == current {
-            return;
+    /// Switch the task list to the next ordering and keep it as the default.
+    fn cycle_task_sort(&mut self) {
+        self.task_sort = self.task_sort.next();
+        if let Err(e) = use_cases::save_task_sort(&self.config, self.task_sort) {
+            tracing::error!(error = %e, "failed to save task sort");
         }
-        match use_cases::save_harness(&self.config, new_kind) {
-            Ok(()) => {
-                tracing::info!(harness = %new_kind, "harness setting changed");
-                self.set_status(format!(
-                    "Harness set to {} (applies to newly-spawned agents)",
-                    new_kind
-                ));
+        tracing::info!(sort = self.task_sort.as_str(), "task sort changed");
+        self.refresh_tasks_for_project();
+        self.set_status(format!("Sorting tasks by {}", self.task_sort.label()));
+    }
+
+    /// Snooze the selected task for [`SNOOZE_HOURS`], or wake it if it is
+    /// already snoozed.
+    fn toggle_selected_task_snooze(&mut self) {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        let mut task = match Task::load_by_id(&self.config, &task_id) {
+            Ok(task) => task,
+            Err(e) => {
+                self.set_status(format!("Failed to load task: {e}"));
+                return;
             }
+        };
+        let now = chrono::Utc::now();
+        task.meta.snoozed_until = if task.meta.is_snoozed(now) {
+            None
+        } else {
+            Some(now + chrono::Duration::hours(SNOOZE_HOURS))
+        };
+        if let Err(e) = task.save_meta() {
+            tracing::error!(task_id = %task_id, error = %e, "failed to save task snooze");
+            self.set_status(format!("Failed to snooze task: {e}"));
+            return;
+        }
+        tracing::info!(task_id = %task_id, until = ?task.meta.snoozed_until, "task snooze toggled");
+        match task.meta.snoozed_until {
+            Some(until) => self.set_status(format!(
+                "Snoozed {task_id} until {}",
+                until.with_timezone(&chrono::Local).format("%H:%M")
+            )),
+            None => self.set_status(format!("Woke {task_id}")),
+        }
+        self.refresh_tasks_and_select(&task_id);
+    }
+
+    fn toggle_selected_task_pin(&mut self) {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        let mut task = match Task::load_by_id(&self.config, &task_id) {
+            Ok(task) => task,
             Err(e) => {
-                tracing::error!(error = %e, "failed to save harness");
-                self.set_status(format!("Failed to save harness: {e}"));
+                self.set_status(format!("Failed to load task: {e}"));
+                return;
             }
+        };
+        task.meta.pinned = !task.meta.pinned;
+        if let Err(e) = task.save_meta() {
+            tracing::error!(task_id = %task_id, error = %e, "failed to save task pin");
+            self.set_status(format!("Failed to pin task: {e}"));
+            return;
         }
+        tracing::info!(task_id = %task_id, pinned = task.meta.pinned, "task pin toggled");
+        self.set_status(format!(
+            "{} {}",
+            if task.meta.pinned {
+                "Pinned"
+            } else {
+                "Unpinned"
+            },
+            task_id
+        ));
+        self.refresh_tasks_and_select(&task_id);
     }
 
-    /// Return indices into the active archive list that match the current search query.
-    pub fn archive_filtered_indices(&self) -> Vec<usize> {
-        let query: String = self.archive_search.lines().join("").to_lowercase();
-        let terms: Vec<&str> = query.split_whitespace().collect();
-        match self.archive_kind {
-            ArchiveKind::Tasks => {
-                if terms.is_empty() {
-                    return (0..self.archive_tasks.len()).collect();
-                }
-                self.archive_tasks
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, (task, content))| {
-                        let id_lower = task.meta.task_id().to_lowercase();
-                        let content_lower = content.to_lowercase();
-                        terms
-                            .iter()
-                            .all(|term| id_lower.contains(term) || content_lower.contains(term))
-                    })
-                    .map(|(i, _)| i)
-                    .collect()
+    /// Scope the task list to the selected task's repo, or clear the scope if
+    /// one is already applied.
+    fn toggle_repo_filter(&mut self) {
+        if self.repo_filter.take().is_some() {
+            self.set_status("Repo filter cleared".to_string());
+        } else if let Some(repo) = self.selected_task().map(|t| t.meta.name.clone()) {
+            self.set_status(format!("Showing tasks in {}", repo));
+            self.repo_filter = Some(repo);
+        } else {
+            return;
+        }
+        self.refresh_tasks_for_project();
+    }
+
+    /// Narrow the task list to tasks reviewing someone else's PR, or back to
+    /// all tasks.
+    fn toggle_review_queue(&mut self) {
+        self.review_queue_only = !self.review_queue_only;
+        self.set_status(
+            if self.review_queue_only {
+                "Showing review queue (others' PRs)"
+            } else {
+                "Showing all tasks"
             }
-            ArchiveKind::Agents => {
-                if terms.is_empty() {
-                    return (0..self.archive_agents.len()).collect();
-                }
-                self.archive_agents
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, (agent, content))| {
-                        let name_lower = agent.meta.name.to_lowercase();
-                        let project_lower = agent.meta.project.to_lowercase();
-                        let kind_lower = Self::agent_kind_label(&agent.meta.kind).to_lowercase();
-                        let content_lower = content.to_lowercase();
-                        terms.iter().all(|term| {
-                            name_lower.contains(term)
-                                || project_lower.contains(term)
-                                || kind_lower.contains(term)
-                                || content_lower.contains(term)
+            .to_string(),
+        );
+        self.refresh_tasks_for_project();
+    }
+
+    /// First `C` marks the selected task; `C` on a different task opens the
+    /// side-by-side comparison; `C` on the marked task clears the mark.
+    fn mark_or_open_compare(&mut self) {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        match self.compare_mark.take() {
+            None => {
+                self.set_status(format!(
+                    "Marked {} for compare — press C on another task",
+                    task_id
+                ));
+                self.compare_mark = Some(task_id);
+            }
+            Some(marked) if marked == task_id => {
+                self.set_status("Compare mark cleared".to_string());
+            }
+            Some(marked) => {
+                let panes: Vec<ComparePane> = [marked, task_id]
+                    .into_iter()
+                    .filter_map(|id| {
+                        let task = self.tasks.iter().find(|t| t.meta.task_id() == id)?;
+                        let log = task
+                            .read_agent_log_structured_tail(500)
+                            .unwrap_or_else(|_| "No agent log available".to_string());
+                        // Open near the tail, like the Logs pane.
+                        let scroll = log.lines().count().saturating_sub(30) as u16;
+                        Some(ComparePane {
+                            task_id: id,
+                            log,
+                            scroll,
                         })
                     })
-                    .map(|(i, _)| i)
-                    .collect()
+                    .collect();
+                if panes.len() == 2 {
+                    self.compare_panes = panes;
+                    self.compare_focus = 0;
+                    self.view = View::Compare;
+                } else {
+                    self.set_status("Marked task no longer exists".to_string());
+                }
             }
         }
     }
 
-    fn handle_project_wizard_event(&mut self, event: Event) -> Result<bool> {
+    fn handle_compare_event(&mut self, event: Event) -> Result<bool> {
         if let Event::Key(key) = event {
             if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
                 self.should_quit = true;
                 return Ok(false);
             }
-
-            // Ctrl+S to submit
-            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
-                if let Some(wizard) = &self.project_wizard {
-                    let name = wizard.name_editor.lines().join("").trim().to_string();
-                    let desc = wizard.description_editor.lines_joined();
-                    let desc = desc.trim().to_string();
-
-                    if name.is_empty() {
-                        if let Some(w) = &mut self.project_wizard {
-                            w.error_message = Some("Project name is required".to_string());
-                        }
-                        return Ok(false);
-                    }
-
-                    match use_cases::create_project(&self.config, &name, &desc, None) {
-                        Ok(_project) => {
-                            tracing::info!(project = %name, "created project via wizard");
-                            self.set_status(format!("Created project: {name}"));
-                            self.project_wizard = None;
-                            self.view = View::ProjectList;
-                            self.refresh_projects();
+            let Some(pane) = self.compare_panes.get_mut(self.compare_focus) else {
+                self.view = View::TaskList;
+                return Ok(false);
+            };
+            let max_scroll = pane.log.lines().count().saturating_sub(1) as u16;
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.compare_panes.clear();
+                    self.view = View::TaskList;
+                }
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Char('h') | KeyCode::Char('l') => {
+                    self.compare_focus = 1 - self.compare_focus;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    pane.scroll = (pane.scroll + 1).min(max_scroll);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    pane.scroll = pane.scroll.saturating_sub(1);
+                }
+                KeyCode::Char('d') | KeyCode::PageDown => {
+                    pane.scroll = (pane.scroll + 20).min(max_scroll);
+                }
+                KeyCode::Char('u') | KeyCode::PageUp => {
+                    pane.scroll = pane.scroll.saturating_sub(20);
+                }
+                KeyCode::Char('g') => {
+                    pane.scroll = 0;
+                }
+                KeyCode::Char('G') => {
+                    pane.scroll = max_scroll;
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    fn start_branch_rename(&mut self) {
+        let Some(branch) = self.selected_task().map(|t| t.meta.branch_name.clone()) else {
+            return;
+        };
+        let mut editor = Self::create_plain_editor();
+        editor.insert_str(&branch);
+        self.branch_rename_editor = editor;
+        self.branch_rename_error = None;
+        self.view = View::RenameBranch;
+    }
+
+    fn handle_rename_branch_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Esc => {
+                    self.branch_rename_error = None;
+                    self.view = View::TaskList;
+                }
+                KeyCode::Enter => {
+                    let new_branch = self.branch_rename_editor.lines().join("");
+                    let Some(task_index) = self.selected_task_index() else {
+                        self.view = View::TaskList;
+                        return Ok(false);
+                    };
+                    let task = &mut self.tasks[task_index];
+                    let old_id = task.meta.task_id();
+                    match use_cases::rename_task_branch(&self.config, task, &new_branch) {
+                        Ok(()) => {
+                            let new_id = task.meta.task_id();
+                            tracing::info!(old_id = %old_id, new_id = %new_id, "renamed task branch");
+                            self.branch_rename_error = None;
+                            self.view = View::TaskList;
+                            self.refresh_tasks_and_select(&new_id);
+                            self.set_status(format!("Renamed {old_id} → {new_id}"));
+                            self.report_tmux_session_collisions(&new_id);
                         }
                         Err(e) => {
-                            tracing::warn!(project = %name, error = %e, "failed to create project");
-                            if let Some(w) = &mut self.project_wizard {
-                                w.error_message = Some(format!("{e}"));
-                            }
+                            tracing::error!(task_id = %old_id, error = %e, "failed to rename task branch");
+                            self.branch_rename_error = Some(e.to_string());
                         }
                     }
                 }
-                return Ok(false);
+                _ => {
+                    let input: Input = key.into();
+                    self.branch_rename_editor.input(input);
+                }
             }
+        }
+        Ok(false)
+    }
 
-            if let Some(wizard) = &mut self.project_wizard {
-                wizard.error_message = None;
-                let input = Input::from(event.clone());
+    fn start_base_ref_edit(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        let mut editor = Self::create_plain_editor();
+        if let Some(base_ref) = &task.meta.base_ref {
+            editor.insert_str(base_ref);
+        }
+        self.base_ref_editor = editor;
+        self.base_ref_error = None;
+        self.view = View::EditBaseRef;
+    }
 
-                // Tab to switch focus between name and description
-                if key.code == KeyCode::Tab || key.code == KeyCode::BackTab {
-                    wizard.description_focus = !wizard.description_focus;
-                    if wizard.description_focus {
-                        wizard.description_editor.set_insert_mode();
+    fn handle_edit_base_ref_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            let set_upstream =
+                key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('t');
+            match key.code {
+                KeyCode::Esc => {
+                    self.base_ref_error = None;
+                    self.view = View::Preview;
+                }
+                _ if key.code == KeyCode::Enter || set_upstream => {
+                    let base_ref = self.base_ref_editor.lines().join("");
+                    let Some(task_index) = self.selected_task_index() else {
+                        self.view = View::Preview;
+                        return Ok(false);
+                    };
+                    let task = &mut self.tasks[task_index];
+                    let task_id = task.meta.task_id();
+                    match use_cases::set_task_base_ref(&self.config, task, &base_ref, set_upstream)
+                    {
+                        Ok(()) => {
+                            let msg = match &task.meta.base_ref {
+                                Some(base) if set_upstream => {
+                                    format!("Base ref set to {base} (upstream updated)")
+                                }
+                                Some(base) => format!("Base ref set to {base}"),
+                                None => "Base ref cleared".to_string(),
+                            };
+                            self.base_ref_error = None;
+                            self.view = View::Preview;
+                            self.set_status(msg);
+                        }
+                        Err(e) => {
+                            tracing::error!(task_id = %task_id, error = %e, "failed to set task base ref");
+                            self.base_ref_error = Some(e.to_string());
+                        }
                     }
-                    return Ok(false);
                 }
+                _ => {
+                    let input: Input = key.into();
+                    self.base_ref_editor.input(input);
+                }
+            }
+        }
+        Ok(false)
+    }
 
-                if wizard.description_focus {
-                    let was_insert = wizard.description_editor.mode() == VimMode::Insert;
-                    wizard.description_editor.input(input.clone());
-                    let is_normal_now = wizard.description_editor.mode() == VimMode::Normal;
+    fn start_label_edit(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        let mut editor = Self::create_plain_editor();
+        if let Some(label) = &task.meta.label {
+            editor.insert_str(label);
+        }
+        self.label_editor = editor;
+        self.view = View::EditLabel;
+    }
 
-                    // Esc in normal mode goes back to name field
-                    if input.key == Key::Esc && !was_insert && is_normal_now {
-                        wizard.description_focus = false;
+    fn handle_edit_label_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Esc => {
+                    self.view = View::TaskList;
+                }
+                KeyCode::Enter => {
+                    let label = self.label_editor.lines().join("");
+                    self.view = View::TaskList;
+                    let Some(task_index) = self.selected_task_index() else {
+                        return Ok(false);
+                    };
+                    let task = &mut self.tasks[task_index];
+                    let task_id = task.meta.task_id();
+                    match use_cases::set_task_label(task, &label) {
+                        Ok(()) => {
+                            let msg = match &task.meta.label {
+                                Some(label) => format!("Labelled {task_id} \"{label}\""),
+                                None => format!("Cleared label on {task_id}"),
+                            };
+                            self.set_status(msg);
+                        }
+                        Err(e) => {
+                            tracing::error!(task_id = %task_id, error = %e, "failed to set task label");
+                            self.set_status(format!("Failed to set label: {e}"));
+                        }
                     }
-                } else {
-                    // Name field: Esc cancels wizard
-                    if key.code == KeyCode::Esc {
-                        self.project_wizard = None;
-                        self.view = View::ProjectList;
+                }
+                _ => {
+                    let input: Input = key.into();
+                    self.label_editor.input(input);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_relocate_repo_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Esc => {
+                    self.view = View::TaskList;
+                }
+                KeyCode::Enter => {
+                    let path = self.relocate_editor.lines().join("");
+                    let path = path.trim();
+                    if path.is_empty() {
                         return Ok(false);
                     }
-                    // Enter in name field moves to description
-                    if key.code == KeyCode::Enter {
-                        wizard.description_focus = true;
-                        wizard.description_editor.set_insert_mode();
+                    let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+                        self.view = View::TaskList;
                         return Ok(false);
+                    };
+                    let location = match path.strip_prefix("~/") {
+                        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+                        None => PathBuf::from(path),
+                    };
+                    match use_cases::relocate_task_repo(&self.config, &task_id, &location) {
+                        Ok(resolved) => {
+                            self.view = View::TaskList;
+                            self.log_output(format!(
+                                "Pointed {task_id} at {} ({resolved} worktree(s) re-linked)",
+                                location.display()
+                            ));
+                            self.set_status(format!("Relocated repo for {task_id}"));
+                            self.refresh_tasks_and_select(&task_id);
+                        }
+                        // Stay in the prompt so a mistyped path can be fixed.
+                        Err(e) => self.set_status(format!("Failed to relocate repo: {e}")),
                     }
-                    wizard.name_editor.input(input);
+                }
+                _ => {
+                    let input: Input = key.into();
+                    self.relocate_editor.input(input);
                 }
             }
         }
         Ok(false)
     }
 
-    fn handle_agent_wizard_event(&mut self, event: Event) -> Result<bool> {
+    /// Open the one-line answer modal, showing the engineer's latest
+    /// question when its pane has one.
+    fn start_quick_answer(&mut self) {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        self.answer_question = use_cases::task_engineer_session(&self.config, &task_id)
+            .and_then(|session| Tmux::capture_pane(&session))
+            .ok()
+            .and_then(|pane| use_cases::pending_question(&pane));
+        self.answer_editor = Self::create_plain_editor();
+        self.view = View::QuickAnswer;
+    }
+
+    fn handle_quick_answer_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Esc => {
+                    self.view = View::TaskList;
+                }
+                KeyCode::Enter => {
+                    let answer = self.answer_editor.lines().join("");
+                    self.view = View::TaskList;
+                    let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+                        return Ok(false);
+                    };
+                    // An empty answer means "carry on" rather than an error.
+                    let (result, sent) = if answer.trim().is_empty() {
+                        (
+                            use_cases::continue_engineer(&self.config, &task_id),
+                            format!("Told {task_id}'s engineer to carry on"),
+                        )
+                    } else {
+                        (
+                            use_cases::answer_engineer(&self.config, &task_id, &answer),
+                            format!("Answer sent to {task_id}'s engineer"),
+                        )
+                    };
+                    match result {
+                        Ok(()) => self.set_status(sent),
+                        Err(e) => {
+                            tracing::error!(task_id = %task_id, error = %e, "failed to send answer");
+                            self.set_status(format!("Failed to send answer: {e}"));
+                        }
+                    }
+                }
+                _ => {
+                    let input: Input = key.into();
+                    self.answer_editor.input(input);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Open the picker of sibling repos that can join the selected task.
+    fn start_add_repo(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        let choices = use_cases::addable_repos_for_task(&self.config, task);
+        if choices.is_empty() {
+            self.set_status("No other repos next to this task's repos".to_string());
+            return;
+        }
+        self.add_repo_choices = choices;
+        self.add_repo_selected = 0;
+        self.view = View::AddRepo;
+    }
+
+    fn handle_add_repo_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.add_repo_choices.clear();
+                    self.view = View::TaskList;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if self.add_repo_selected + 1 < self.add_repo_choices.len() {
+                        self.add_repo_selected += 1;
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.add_repo_selected = self.add_repo_selected.saturating_sub(1);
+                }
+                KeyCode::Enter => {
+                    let repo = self.add_repo_choices.get(self.add_repo_selected).cloned();
+                    self.add_repo_choices.clear();
+                    self.view = View::TaskList;
+                    if let Some(repo) = repo {
+                        self.add_repo_to_selected_task(&repo);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    fn add_repo_to_selected_task(&mut self, repo: &str) {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        // Runs the repo's setup script; `apply_add_repo_results` reports back.
+        self.set_status(format!("Adding {repo} to {task_id}..."));
+        let tx = self.add_repo_tx.clone();
+        let config = self.config.clone();
+        let repo = repo.to_string();
+        self.rt.spawn(async move {
+            let (load_id, add_repo) = (task_id.clone(), repo.clone());
+            let result = tokio::task::spawn_blocking(move || {
+                Task::load_by_id(&config, &load_id)
+                    .and_then(|mut task| use_cases::add_repo_to_task(&config, &mut task, &add_repo))
+            })
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
+            let _ = tx.send((task_id, repo, result.map_err(|e| format!("{e}"))));
+        });
+    }
+
+    fn open_message_history(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        let task_id = task.meta.task_id();
+        let title = task.meta.display_name().to_string();
+        match use_cases::task_message_history(&self.config, &task_id) {
+            Ok(messages) => {
+                self.message_history = messages;
+                self.message_history_title = title;
+                // Open at the bottom so the latest guidance is in view; the
+                // draw pass clamps this to the real maximum.
+                self.message_history_scroll = u16::MAX;
+                self.view = View::MessageHistory;
+            }
+            Err(e) => {
+                tracing::error!(task_id = %task_id, error = %e, "failed to read message history");
+                self.set_status(format!("Failed to read message history: {e}"));
+            }
+        }
+    }
+
+    fn handle_message_history_event(&mut self, event: Event) -> Result<bool> {
         if let Event::Key(key) = event {
             if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
                 self.should_quit = true;
                 return Ok(false);
             }
 
-            // Ctrl+S submits from any step. The wizard validates required
-            // fields and surfaces inline errors so the user never has to
-            // play guess-the-step.
-            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
-                self.submit_agent_wizard();
-                return Ok(false);
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.message_history.clear();
+                    self.view = View::TaskList;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.message_history_scroll = self.message_history_scroll.saturating_add(1);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.message_history_scroll = self.message_history_scroll.saturating_sub(1);
+                }
+                KeyCode::Char('g') => {
+                    self.message_history_scroll = 0;
+                }
+                KeyCode::Char('G') => {
+                    self.message_history_scroll = u16::MAX;
+                }
+                _ => {}
             }
+        }
+        Ok(false)
+    }
 
-            let global_harness = self.config.harness_kind();
-            let Some(wizard) = self.agent_wizard.as_mut() else {
-                return Ok(false);
-            };
-            wizard.error_message = None;
+    /// Ask the task's engineer to compact its context.
+    fn compact_task(&mut self, task_id: &str) {
+        self.log_output(format!("Compacting {task_id}'s engineer context..."));
+        match use_cases::compact_engineer(&self.config, task_id) {
+            Ok(command) => {
+                self.log_output(format!("Sent {command} to {task_id}'s engineer"));
+                self.set_status(format!("Compacting {task_id}'s engineer context"));
+                self.refresh_tasks();
+            }
+            Err(e) => {
+                tracing::error!(task_id, error = %e, "failed to compact engineer context");
+                self.log_output(format!("Error: failed to compact {task_id}: {e}"));
+                self.set_status(format!("Failed to compact: {e}"));
+            }
+        }
+    }
 
-            match wizard.step {
-                AgentWizardStep::Kind => match key.code {
-                    KeyCode::Esc => {
-                        self.return_from_agent_wizard();
-                    }
-                    KeyCode::Char('j')
-                    | KeyCode::Down
-                    | KeyCode::Char('l')
-                    | KeyCode::Right
-                    | KeyCode::Tab => {
-                        wizard.kind = match wizard.kind {
-                            AgentWizardKind::Researcher => AgentWizardKind::Reviewer,
-                            AgentWizardKind::Reviewer => AgentWizardKind::Tester,
-                            AgentWizardKind::Tester => AgentWizardKind::Operator,
-                            AgentWizardKind::Operator => AgentWizardKind::Researcher,
-                        };
-                    }
-                    KeyCode::Char('k')
-                    | KeyCode::Up
-                    | KeyCode::Char('h')
-                    | KeyCode::Left
-                    | KeyCode::BackTab => {
-                        wizard.kind = match wizard.kind {
-                            AgentWizardKind::Researcher => AgentWizardKind::Operator,
-                            AgentWizardKind::Reviewer => AgentWizardKind::Researcher,
-                            AgentWizardKind::Tester => AgentWizardKind::Reviewer,
-                            AgentWizardKind::Operator => AgentWizardKind::Tester,
-                        };
-                    }
-                    KeyCode::Enter => {
-                        wizard.step = AgentWizardStep::Name;
-                    }
-                    _ => {}
-                },
-                AgentWizardStep::Name => {
-                    if key.code == KeyCode::Esc {
-                        // First content step — Esc cancels the wizard.
-                        self.return_from_agent_wizard();
-                        return Ok(false);
-                    }
-                    if key.code == KeyCode::Enter {
-                        // Advance: reviewers go to worktrees, researchers
-                        // skip straight to first prompt.
-                        wizard.step = match wizard.kind {
-                            AgentWizardKind::Researcher | AgentWizardKind::Operator => {
-                                AgentWizardStep::FirstPrompt
-                            }
-                            AgentWizardKind::Reviewer | AgentWizardKind::Tester => {
-                                AgentWizardStep::Worktrees
-                            }
-                        };
-                        if matches!(wizard.step, AgentWizardStep::FirstPrompt) {
-                            wizard.first_prompt_editor.set_insert_mode();
-                        }
-                        return Ok(false);
-                    }
-                    let input = Input::from(event.clone());
-                    wizard.name_editor.input(input);
+    /// Tag the task's worktrees at HEAD as a named restore point.
+    fn snapshot_task(&mut self, task_id: &str) {
+        let result = Task::load_by_id(&self.config, task_id)
+            .and_then(|task| use_cases::snapshot_task(&task));
+        match result {
+            Ok(tag) => self.set_status(format!("Snapshot {tag} created")),
+            Err(e) => {
+                tracing::error!(task_id, error = %e, "failed to snapshot task");
+                self.set_status(format!("Failed to snapshot: {e}"));
+            }
+        }
+    }
+
+    fn open_snapshots(&mut self) {
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        self.snapshot_task_id = Some(task_id);
+        self.snapshot_selected = 0;
+        self.snapshot_restore_confirm = false;
+        self.reload_snapshots();
+        self.view = View::Snapshots;
+    }
+
+    fn reload_snapshots(&mut self) {
+        let Some(task_id) = self.snapshot_task_id.clone() else {
+            return;
+        };
+        let result = Task::load_by_id(&self.config, &task_id)
+            .and_then(|task| use_cases::list_task_snapshots(&task));
+        match result {
+            Ok(tags) => self.snapshots = tags,
+            Err(e) => {
+                tracing::error!(task_id = %task_id, error = %e, "failed to list snapshots");
+                self.snapshots.clear();
+                self.set_status(format!("Failed to list snapshots: {e}"));
+            }
+        }
+        self.snapshot_selected = self
+            .snapshot_selected
+            .min(self.snapshots.len().saturating_sub(1));
+    }
+
+    fn restore_selected_snapshot(&mut self) {
+        let (Some(task_id), Some(tag)) = (
+            self.snapshot_task_id.clone(),
+            self.snapshots.get(self.snapshot_selected).cloned(),
+        ) else {
+            return;
+        };
+        let result = Task::load_by_id(&self.config, &task_id)
+            .and_then(|task| use_cases::restore_task_snapshot(&task, &tag));
+        match result {
+            Ok(()) => self.set_status(format!("Restored {task_id} to {tag}")),
+            Err(e) => {
+                tracing::error!(task_id = %task_id, tag = %tag, error = %e, "failed to restore snapshot");
+                self.set_status(format!("Failed to restore snapshot: {e}"));
+            }
+        }
+    }
+
+    fn handle_snapshots_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
+            }
+
+            if self.snapshot_restore_confirm {
+                if key.code == KeyCode::Char('y') {
+                    self.restore_selected_snapshot();
                 }
-                AgentWizardStep::Worktrees => {
-                    match key.code {
-                        KeyCode::Esc => {
-                            wizard.step = AgentWizardStep::Name;
-                        }
-                        KeyCode::Tab => {
-                            // Tab cycles repo → branch → next row's repo.
-                            let len = wizard.worktree_rows.len();
-                            let cur = wizard.selected_row;
-                            let row = &mut wizard.worktree_rows[cur];
-                            if !row.branch_focus {
-                                row.branch_focus = true;
-                            } else if cur + 1 < len {
-                                row.branch_focus = false;
-                                wizard.selected_row += 1;
-                            } else {
-                                // Last field of last row — testers get an
-                                // optional capabilities step first.
-                                wizard.step = match wizard.kind {
-                                    AgentWizardKind::Tester => AgentWizardStep::Capabilities,
-                                    AgentWizardKind::Researcher
-                                    | AgentWizardKind::Operator
-                                    | AgentWizardKind::Reviewer => {
-                                        wizard.first_prompt_editor.set_insert_mode();
-                                        AgentWizardStep::FirstPrompt
-                                    }
-                                };
-                            }
-                        }
-                        KeyCode::BackTab => {
-                            let row = &mut wizard.worktree_rows[wizard.selected_row];
-                            if row.branch_focus {
-                                row.branch_focus = false;
-                            } else if wizard.selected_row > 0 {
-                                wizard.selected_row -= 1;
-                                wizard.worktree_rows[wizard.selected_row].branch_focus = true;
-                            }
-                        }
-                        KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            // Add a new row after the selected one.
-                            wizard
-                                .worktree_rows
-                                .insert(wizard.selected_row + 1, ReviewerWorktreeRow::new());
-                            wizard.selected_row += 1;
-                            wizard.worktree_rows[wizard.selected_row].branch_focus = false;
-                        }
-                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            // Remove the selected row, but never the last one.
-                            if wizard.worktree_rows.len() > 1 {
-                                wizard.worktree_rows.remove(wizard.selected_row);
-                                if wizard.selected_row >= wizard.worktree_rows.len() {
-                                    wizard.selected_row = wizard.worktree_rows.len() - 1;
-                                }
-                            }
-                        }
-                        KeyCode::Enter => {
-                            // Enter advances field-by-field, mirroring Tab.
-                            let len = wizard.worktree_rows.len();
-                            let cur = wizard.selected_row;
-                            let row = &mut wizard.worktree_rows[cur];
-                            if !row.branch_focus {
-                                row.branch_focus = true;
-                            } else if cur + 1 < len {
-                                row.branch_focus = false;
-                                wizard.selected_row += 1;
-                            } else {
-                                wizard.step = match wizard.kind {
-                                    AgentWizardKind::Tester => AgentWizardStep::Capabilities,
-                                    AgentWizardKind::Researcher
-                                    | AgentWizardKind::Operator
-                                    | AgentWizardKind::Reviewer => {
-                                        wizard.first_prompt_editor.set_insert_mode();
-                                        AgentWizardStep::FirstPrompt
-                                    }
-                                };
-                            }
-                        }
-                        _ => {
-                            let input = Input::from(event.clone());
-                            let row = &mut wizard.worktree_rows[wizard.selected_row];
-                            if row.branch_focus {
-                                row.branch_editor.input(input);
-                            } else {
-                                row.repo_editor.input(input);
-                            }
-                        }
-                    }
+                self.snapshot_restore_confirm = false;
+                return Ok(false);
+            }
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.snapshot_task_id = None;
+                    self.snapshots.clear();
+                    self.view = View::TaskList;
                 }
-                AgentWizardStep::Capabilities => match key.code {
-                    KeyCode::Esc | KeyCode::BackTab => {
-                        wizard.step = AgentWizardStep::Worktrees;
-                    }
-                    KeyCode::Enter | KeyCode::Tab => {
-                        wizard.step = AgentWizardStep::FirstPrompt;
-                        wizard.first_prompt_editor.set_insert_mode();
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if self.snapshot_selected + 1 < self.snapshots.len() {
+                        self.snapshot_selected += 1;
                     }
-                    KeyCode::Char(' ')
-                    | KeyCode::Char('h')
-                    | KeyCode::Char('l')
-                    | KeyCode::Left
-                    | KeyCode::Right => {
-                        if !matches!(
-                            global_harness,
-                            agman::harness::HarnessKind::Goose | agman::harness::HarnessKind::Pi
-                        ) {
-                            wizard.browser_capability = !wizard.browser_capability;
-                        }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.snapshot_selected = self.snapshot_selected.saturating_sub(1);
+                }
+                KeyCode::Char('n') => {
+                    if let Some(task_id) = self.snapshot_task_id.clone() {
+                        self.snapshot_task(&task_id);
+                        self.snapshot_selected = 0;
+                        self.reload_snapshots();
                     }
-                    _ => {}
-                },
-                AgentWizardStep::FirstPrompt => {
-                    let input = Input::from(event.clone());
-                    let was_insert = wizard.first_prompt_editor.mode() == VimMode::Insert;
-                    wizard.first_prompt_editor.input(input.clone());
-                    let is_normal_now = wizard.first_prompt_editor.mode() == VimMode::Normal;
-                    // Esc in normal mode steps back to the previous step.
-                    if input.key == Key::Esc && !was_insert && is_normal_now {
-                        wizard.step = match wizard.kind {
-                            AgentWizardKind::Researcher | AgentWizardKind::Operator => {
-                                AgentWizardStep::Name
-                            }
-                            AgentWizardKind::Reviewer => AgentWizardStep::Worktrees,
-                            AgentWizardKind::Tester => AgentWizardStep::Capabilities,
-                        };
+                }
+                KeyCode::Enter => {
+                    if !self.snapshots.is_empty() {
+                        self.snapshot_restore_confirm = true;
                     }
                 }
+                _ => {}
             }
         }
         Ok(false)
     }
 
-    /// Validate and submit the agent wizard. On success the wizard closes
-    /// and the new agent is started; on failure the error message is
-    /// surfaced inline so the user can correct and resubmit.
-    fn submit_agent_wizard(&mut self) {
-        let Some(wizard) = self.agent_wizard.as_ref() else {
-            return;
-        };
-        let kind = wizard.kind;
-        let project = wizard.project.clone();
-        let name = wizard.name_editor.lines().join("").trim().to_string();
-        let first_prompt_text = wizard.first_prompt_editor.lines_joined();
-        let first_prompt = first_prompt_text.trim();
-        let first_prompt = if first_prompt.is_empty() {
-            None
+    /// Pause or resume the periodic list refresh. Resuming refreshes the
+    /// current list right away.
+    fn toggle_auto_refresh(&mut self) {
+        self.auto_refresh_paused = !self.auto_refresh_paused;
+        if self.auto_refresh_paused {
+            self.set_status("Auto-refresh paused — Space to resume".to_string());
         } else {
-            Some(first_prompt)
-        };
-        let browser_capability = wizard.browser_capability;
-
-        if name.is_empty() {
-            if let Some(w) = self.agent_wizard.as_mut() {
-                w.error_message = Some("AgentRecord name is required".to_string());
-                w.step = AgentWizardStep::Name;
+            if self.view == View::TaskList {
+                self.refresh_tasks_for_project();
+            } else {
+                self.refresh_projects();
             }
-            return;
+            self.set_status("Auto-refresh resumed".to_string());
         }
+    }
 
-        match kind {
-            AgentWizardKind::Researcher | AgentWizardKind::Operator => {
-                let (kind_name, create_result) = match kind {
-                    AgentWizardKind::Researcher => (
-                        "researcher",
-                        use_cases::create_researcher(
-                            &self.config,
-                            &project,
-                            &name,
-                            first_prompt,
-                            None,
-                            None,
-                            None,
-                        ),
-                    ),
-                    AgentWizardKind::Operator => (
-                        "operator",
-                        use_cases::create_operator(
-                            &self.config,
-                            &project,
-                            &name,
-                            first_prompt,
-                            None,
-                            None,
-                            None,
-                        ),
-                    ),
-                    _ => unreachable!(),
-                };
-                match create_result {
-                    Ok(_agent) => {
-                        tracing::info!(project = %project, name = %name, kind = kind_name, "created agent via wizard");
-                        if let Err(e) =
-                            use_cases::start_agent_session(&self.config, &project, &name, false)
-                        {
-                            tracing::warn!(
-                                project = %project, name = %name, error = %e,
-                                "failed to start agent session"
-                            );
-                        }
-                        self.set_status(format!("Created {kind_name}: {name}"));
-                        self.return_from_agent_wizard();
-                    }
-                    Err(e) => {
-                        tracing::warn!(project = %project, name = %name, kind = kind_name, error = %e, "failed to create agent");
-                        if let Some(w) = self.agent_wizard.as_mut() {
-                            w.error_message = Some(format!("{e}"));
-                        }
-                    }
-                }
+    pub fn dashboard_counts(&self) -> DashboardCounts {
+        DashboardCounts {
+            idle_tasks: self.stalled_tasks.len(),
+            review_tasks: self
+                .all_tasks
+                .iter()
+                .filter(|t| t.meta.is_review_duty())
+                .count(),
+            review_requests: self.show_prs_data.review_requests.len(),
+            unread_notifications: self.notifications.iter().filter(|n| n.unread).count(),
+        }
+    }
+
+    /// Show the pending-actions dashboard, kicking off the Show PRs poll so
+    /// its review-request count fills in.
+    pub fn open_dashboard(&mut self) {
+        self.refresh_tasks();
+        self.refresh_agents();
+        if !self.show_prs_first_poll_done && !self.show_prs_poll_active {
+            self.start_show_prs_poll();
+        }
+        self.view = View::Dashboard;
+    }
+
+    /// Leave the dashboard for the task list across all projects.
+    fn open_all_tasks(&mut self, review_queue_only: bool) {
+        self.current_project = None;
+        self.review_queue_only = review_queue_only;
+        self.selected_index = 0;
+        self.refresh_tasks_for_project();
+        self.refresh_agents();
+        self.clamp_project_detail_selection();
+        self.view = View::TaskList;
+    }
+
+    fn handle_dashboard_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
             }
-            AgentWizardKind::Reviewer => {
-                // Collect (repo, branch) rows; reject empties so we don't
-                // hand a half-filled row to the use-case.
-                let mut branches: Vec<(String, String)> = Vec::new();
-                for row in &wizard.worktree_rows {
-                    let r = row.repo();
-                    let b = row.branch();
-                    if r.is_empty() && b.is_empty() {
-                        continue;
-                    }
-                    if r.is_empty() || b.is_empty() {
-                        if let Some(w) = self.agent_wizard.as_mut() {
-                            w.error_message = Some(
-                                "Each row needs both a repo and a branch (Ctrl+D to remove)"
-                                    .to_string(),
-                            );
-                            w.step = AgentWizardStep::Worktrees;
-                        }
-                        return;
-                    }
-                    branches.push((r, b));
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                    self.view = View::ProjectList;
+                    self.refresh_projects();
                 }
-                if branches.is_empty() {
-                    if let Some(w) = self.agent_wizard.as_mut() {
-                        w.error_message =
-                            Some("Reviewer needs at least one (repo, branch) pair".to_string());
-                        w.step = AgentWizardStep::Worktrees;
+                KeyCode::Char('w') => {
+                    let idle = self.stalled_tasks.clone();
+                    self.open_all_tasks(false);
+                    let first_idle = self
+                        .tasks
+                        .iter()
+                        .map(|t| t.meta.task_id())
+                        .find(|task_id| idle.contains(task_id));
+                    if let Some(task_id) = first_idle {
+                        self.restore_project_detail_selection(Some(&ProjectDetailRowKey::Task(
+                            ProjectTaskRowKey::Task { task_id },
+                        )));
                     }
-                    return;
                 }
-
-                let spec = use_cases::WorktreeSpec {
-                    branches,
-                    parent_dir: None,
-                };
-                match use_cases::create_reviewer(&self.config, &project, &name, first_prompt, spec)
-                {
-                    Ok(_agent) => {
-                        tracing::info!(project = %project, name = %name, "created reviewer via wizard");
-                        if let Err(e) =
-                            use_cases::start_agent_session(&self.config, &project, &name, false)
-                        {
-                            tracing::warn!(
-                                project = %project, name = %name, error = %e,
-                                "failed to start agent session"
-                            );
-                        }
-                        self.set_status(format!("Created reviewer: {name}"));
-                        self.return_from_agent_wizard();
-                    }
-                    Err(e) => {
-                        tracing::warn!(project = %project, name = %name, error = %e, "failed to create reviewer");
-                        // The three-step decision tree raises a loud,
-                        // user-actionable error for the local-branch case;
-                        // surface it verbatim so the user can either fix
-                        // the branch state or pick a different one.
-                        if let Some(w) = self.agent_wizard.as_mut() {
-                            w.error_message = Some(format!("{e}"));
-                            w.step = AgentWizardStep::Worktrees;
-                        }
-                    }
+                KeyCode::Char('r') => {
+                    self.open_all_tasks(true);
+                    self.set_status("Showing review queue (others' PRs)".to_string());
                 }
-            }
-            AgentWizardKind::Tester => {
-                let mut branches: Vec<(String, String)> = Vec::new();
-                for row in &wizard.worktree_rows {
-                    let r = row.repo();
-                    let b = row.branch();
-                    if r.is_empty() && b.is_empty() {
-                        continue;
-                    }
-                    if r.is_empty() || b.is_empty() {
-                        if let Some(w) = self.agent_wizard.as_mut() {
-                            w.error_message = Some(
-                                "Each row needs both a repo and a branch (Ctrl+D to remove)"
-                                    .to_string(),
-                            );
-                            w.step = AgentWizardStep::Worktrees;
-                        }
-                        return;
-                    }
-                    branches.push((r, b));
+                KeyCode::Char('p') => {
+                    self.show_prs_selected = 0;
+                    self.view = View::ShowPrs;
                 }
-                if branches.is_empty() {
-                    if let Some(w) = self.agent_wizard.as_mut() {
-                        w.error_message =
-                            Some("Tester needs at least one (repo, branch) pair".to_string());
-                        w.step = AgentWizardStep::Worktrees;
-                    }
-                    return;
+                KeyCode::Char('i') => {
+                    self.selected_notif_index = 0;
+                    self.view = View::Notifications;
                 }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
 
-                let spec = use_cases::WorktreeSpec {
-                    branches,
-                    parent_dir: None,
-                };
-                let capabilities = agman::agent_model::TesterCapabilities {
-                    browser: browser_capability,
-                };
-                match use_cases::create_tester(
-                    &self.config,
-                    &project,
-                    &name,
-                    first_prompt,
-                    spec,
-                    capabilities,
-                ) {
-                    Ok(_agent) => {
-                        tracing::info!(project = %project, name = %name, "created tester via wizard");
+    /// Restart the coffee-break countdown without touching anything else.
+    fn reset_break_timer(&mut self) {
+        if !self.break_settings.enabled {
+            return;
+        }
+        self.last_break_reset = Instant::now();
+        self.set_status("Break timer reset".to_string());
+    }
+
+    fn handle_settings_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            // When editing a text field, route keys to the TextArea
+            if self.settings_editing {
+                match key.code {
+                    KeyCode::Enter => {
+                        // Save the edited value
+                        let token_text: String = self.telegram_token_editor.lines().join("");
+                        let chat_id_text: String = self.telegram_chat_id_editor.lines().join("");
+                        let token = if token_text.is_empty() {
+                            None
+                        } else {
+                            Some(token_text)
+                        };
+                        let chat_id = if chat_id_text.is_empty() {
+                            None
+                        } else {
+                            Some(chat_id_text)
+                        };
                         if let Err(e) =
-                            use_cases::start_agent_session(&self.config, &project, &name, false)
+                            use_cases::save_telegram_config(&self.config, token, chat_id)
                         {
-                            tracing::warn!(
-                                project = %project, name = %name, error = %e,
-                                "failed to start agent session"
-                            );
+                            tracing::error!(error = %e, "failed to save telegram config");
+                            self.set_status(format!("Failed to save: {e}"));
+                        } else {
+                            self.set_status("Telegram settings saved".to_string());
+                            self.restart_telegram_bot();
                         }
-                        self.set_status(format!("Created tester: {name}"));
-                        self.return_from_agent_wizard();
+                        self.settings_editing = false;
                     }
-                    Err(e) => {
-                        tracing::warn!(project = %project, name = %name, error = %e, "failed to create tester");
-                        if let Some(w) = self.agent_wizard.as_mut() {
-                            w.error_message = Some(format!("{e}"));
-                            w.step = AgentWizardStep::Worktrees;
-                        }
+                    KeyCode::Esc => {
+                        // Discard edit, restore from config
+                        let (token, chat_id) = use_cases::load_telegram_config(&self.config);
+                        self.telegram_token_editor = TextArea::new(vec![token.unwrap_or_default()]);
+                        self.telegram_token_editor
+                            .set_cursor_line_style(ratatui::style::Style::default());
+                        self.telegram_chat_id_editor =
+                            TextArea::new(vec![chat_id.unwrap_or_default()]);
+                        self.telegram_chat_id_editor
+                            .set_cursor_line_style(ratatui::style::Style::default());
+                        self.settings_editing = false;
                     }
+                    _ => match self.settings_selected {
+                        2 => {
+                            self.telegram_token_editor.input(event);
+                        }
+                        3 => {
+                            self.telegram_chat_id_editor.input(event);
+                        }
+                        _ => {}
+                    },
                 }
+                return Ok(false);
             }
-        }
-    }
 
-    fn handle_project_picker_event(&mut self, event: Event) -> Result<bool> {
-        if let Event::Key(key) = event {
             match key.code {
                 KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     self.should_quit = true;
                 }
                 KeyCode::Esc | KeyCode::Char('q') => {
-                    let was_migrate_all = self.project_picker.as_ref().is_some_and(|p| {
-                        matches!(p.action, ProjectPickerAction::MigrateAllUnassigned)
-                    });
-                    self.project_picker = None;
-                    self.view = if was_migrate_all {
-                        View::ProjectList
-                    } else {
-                        View::TaskList
-                    };
+                    self.view = View::TaskList;
                 }
-                KeyCode::Char('j') => {
-                    if let Some(picker) = &mut self.project_picker {
-                        if !picker.projects.is_empty() {
-                            picker.selected = (picker.selected + 1) % picker.projects.len();
-                        }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if self.settings_selected < 4 {
+                        self.settings_selected += 1;
                     }
                 }
-                KeyCode::Char('k') => {
-                    if let Some(picker) = &mut self.project_picker {
-                        if !picker.projects.is_empty() {
-                            picker.selected = if picker.selected == 0 {
-                                picker.projects.len() - 1
-                            } else {
-                                picker.selected - 1
-                            };
-                        }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    if self.settings_selected > 0 {
+                        self.settings_selected -= 1;
                     }
                 }
                 KeyCode::Enter => {
-                    if let Some(picker) = self.project_picker.take() {
-                        if let Some(project_name) = picker.projects.get(picker.selected) {
-                            let project_name = project_name.clone();
-                            match &picker.action {
-                                ProjectPickerAction::MigrateAllUnassigned => {
-                                    let unassigned = use_cases::list_unassigned_tasks(&self.config)
-                                        .unwrap_or_default();
-                                    let task_ids: Vec<String> =
-                                        unassigned.iter().map(|t| t.meta.task_id()).collect();
-                                    match use_cases::migrate_tasks_to_project(
-                                        &self.config,
-                                        &project_name,
-                                        &task_ids,
-                                    ) {
-                                        Ok(count) => {
-                                            tracing::info!(
-                                                project = %project_name,
-                                                count,
-                                                "migrated unassigned tasks"
-                                            );
-                                            self.set_status(format!(
-                                                "Migrated {count} tasks to {project_name}"
-                                            ));
-                                        }
-                                        Err(e) => {
-                                            tracing::error!(error = %e, "failed to migrate tasks");
-                                            self.set_status(format!("Migration failed: {e}"));
-                                        }
-                                    }
-                                    self.refresh_projects();
-                                    self.view = View::ProjectList;
+                    // Enter editing mode for text fields
+                    if self.settings_selected == 2 || self.settings_selected == 3 {
+                        self.settings_editing = true;
+                        // Move cursor to end of current value
+                        let editor = if self.settings_selected == 2 {
+                            &mut self.telegram_token_editor
+                        } else {
+                            &mut self.telegram_chat_id_editor
+                        };
+                        let line_len = editor.lines().first().map(|l| l.len()).unwrap_or(0);
+                        editor.move_cursor(tui_textarea::CursorMove::Jump(0, line_len as u16));
+                    }
+                }
+                KeyCode::Char('h') | KeyCode::Left => {
+                    match self.settings_selected {
+                        0 => {
+                            // Decrease archive retention by 7 days (min 7)
+                            if self.archive_retention_days > 7 {
+                                let new_days = self.archive_retention_days - 7;
+                                tracing::info!(
+                                    old_days = self.archive_retention_days,
+                                    new_days,
+                                    "archive retention changed"
+                                );
+                                self.archive_retention_days = new_days;
+                                if let Err(e) =
+                                    use_cases::save_archive_retention(&self.config, new_days)
+                                {
+                                    tracing::error!(error = %e, "failed to save archive retention");
+                                    self.set_status(format!("Failed to save: {e}"));
+                                }
+                            }
+                        }
+                        1 => {
+                            // Cycle harness left through HarnessKind::ALL
+                            self.cycle_harness(-1);
+                        }
+                        4 => self.cycle_merge_cleanup(-1),
+                        _ => {}
+                    }
+                }
+                KeyCode::Char('l') | KeyCode::Right => {
+                    match self.settings_selected {
+                        0 => {
+                            // Increase archive retention by 7 days (max 365)
+                            if self.archive_retention_days < 365 {
+                                let new_days = self.archive_retention_days + 7;
+                                tracing::info!(
+                                    old_days = self.archive_retention_days,
+                                    new_days,
+                                    "archive retention changed"
+                                );
+                                self.archive_retention_days = new_days;
+                                if let Err(e) =
+                                    use_cases::save_archive_retention(&self.config, new_days)
+                                {
+                                    tracing::error!(error = %e, "failed to save archive retention");
+                                    self.set_status(format!("Failed to save: {e}"));
                                 }
                             }
                         }
+                        1 => {
+                            // Cycle harness right through HarnessKind::ALL
+                            self.cycle_harness(1);
+                        }
+                        4 => self.cycle_merge_cleanup(1),
+                        _ => {}
                     }
                 }
                 _ => {}
@@ -3602,73 +5249,132 @@ impl App {
         Ok(false)
     }
 
-    fn handle_archive_event(&mut self, event: Event) -> Result<bool> {
+    /// Cycle the configured harness by `delta` (typically -1 / +1).
+    fn cycle_harness(&mut self, delta: i32) {
+        use agman::harness::HarnessKind;
+        let all = HarnessKind::ALL;
+        let current = self.config.harness_kind();
+        let pos = all.iter().position(|k| *k == current).unwrap_or(0);
+        let new_pos = ((pos as i32 + delta).rem_euclid(all.len() as i32)) as usize;
+        let new_kind = all[new_pos];
+        if new_kind == current {
+            return;
+        }
+        match use_cases::save_harness(&self.config, new_kind) {
+            Ok(()) => {
+                tracing::info!(harness = %new_kind, "harness setting changed");
+                self.set_status(format!(
+                    "Harness set to {} (applies to newly-spawned agents)",
+                    new_kind
+                ));
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "failed to save harness");
+                self.set_status(format!("Failed to save harness: {e}"));
+            }
+        }
+    }
+
+    /// Cycle the post-merge cleanup policy by `delta` through `MergeCleanup::ALL`.
+    fn cycle_merge_cleanup(&mut self, delta: i32) {
+        let all = use_cases::MergeCleanup::ALL;
+        let current = use_cases::load_merge_cleanup(&self.config);
+        let pos = all.iter().position(|c| *c == current).unwrap_or(0);
+        let new_pos = ((pos as i32 + delta).rem_euclid(all.len() as i32)) as usize;
+        let new_cleanup = all[new_pos];
+        match use_cases::save_merge_cleanup(&self.config, new_cleanup) {
+            Ok(()) => {
+                tracing::info!(
+                    cleanup = new_cleanup.as_str(),
+                    "merge cleanup setting changed"
+                );
+                self.set_status(format!("Cleanup on merge set to {}", new_cleanup.as_str()));
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "failed to save merge cleanup");
+                self.set_status(format!("Failed to save cleanup policy: {e}"));
+            }
+        }
+    }
+
+    /// Return indices into the active archive list that match the current search query.
+    pub fn archive_filtered_indices(&self) -> Vec<usize> {
+        let query: String = self.archive_search.lines().join("").to_lowercase();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        match self.archive_kind {
+            ArchiveKind::Tasks => {
+                if terms.is_empty() {
+                    return (0..self.archive_tasks.len()).collect();
+                }
+                self.archive_tasks
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (task, content))| {
+                        let id_lower = task.meta.task_id().to_lowercase();
+                        let content_lower = content.to_lowercase();
+                        terms
+                            .iter()
+                            .all(|term| id_lower.contains(term) || content_lower.contains(term))
+                    })
+                    .map(|(i, _)| i)
+                    .collect()
+            }
+            ArchiveKind::Agents => {
+                if terms.is_empty() {
+                    return (0..self.archive_agents.len()).collect();
+                }
+                self.archive_agents
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (agent, content))| {
+                        let name_lower = agent.meta.name.to_lowercase();
+                        let project_lower = agent.meta.project.to_lowercase();
+                        let kind_lower = Self::agent_kind_label(&agent.meta.kind).to_lowercase();
+                        let content_lower = content.to_lowercase();
+                        terms.iter().all(|term| {
+                            name_lower.contains(term)
+                                || project_lower.contains(term)
+                                || kind_lower.contains(term)
+                                || content_lower.contains(term)
+                        })
+                    })
+                    .map(|(i, _)| i)
+                    .collect()
+            }
+        }
+    }
+
+    fn handle_repo_stats_event(&mut self, event: Event) -> Result<bool> {
         if let Event::Key(key) = event {
             if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
                 self.should_quit = true;
                 return Ok(false);
             }
 
-            if self.archive_preview.is_some() {
-                // Preview mode
-                return self.handle_archive_preview_event(key.code, key.modifiers);
-            }
-
-            // Search mode
             match key.code {
-                KeyCode::Esc => {
-                    self.view = View::TaskList;
-                }
-                KeyCode::Up | KeyCode::Down => {
-                    let filtered = self.archive_filtered_indices();
-                    if !filtered.is_empty() {
-                        if key.code == KeyCode::Up {
-                            self.archive_selected = self.archive_selected.saturating_sub(1);
-                        } else {
-                            self.archive_selected =
-                                (self.archive_selected + 1).min(filtered.len() - 1);
-                        }
-                    }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.view = View::ProjectList;
                 }
-                KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    let filtered = self.archive_filtered_indices();
-                    if !filtered.is_empty() {
-                        self.archive_selected = (self.archive_selected + 1).min(filtered.len() - 1);
+                KeyCode::Char('j') | KeyCode::Down => {
+                    let total = self.repo_stats_rows.len();
+                    if total > 0 && self.repo_stats_selected < total - 1 {
+                        self.repo_stats_selected += 1;
                     }
                 }
-                KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.archive_selected = self.archive_selected.saturating_sub(1);
-                }
-                KeyCode::Enter => {
-                    let filtered = self.archive_filtered_indices();
-                    if let Some(&archive_idx) = filtered.get(self.archive_selected) {
-                        let content = match self.archive_kind {
-                            ArchiveKind::Tasks => self.archive_tasks[archive_idx].1.clone(),
-                            ArchiveKind::Agents => self.archive_agents[archive_idx].1.clone(),
-                        };
-                        self.archive_preview = Some(content);
-                        self.archive_scroll = 0;
-                    }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.repo_stats_selected = self.repo_stats_selected.saturating_sub(1);
                 }
-                KeyCode::Backspace => {
-                    self.archive_search.input(Input {
-                        key: Key::Backspace,
-                        ctrl: false,
-                        alt: false,
-                        shift: false,
-                    });
-                    // Reset selection after search change
-                    self.archive_selected = 0;
+                KeyCode::Char('s') => {
+                    self.repo_stats_sort = self.repo_stats_sort.next();
+                    repo_stats::sort_repo_task_totals(
+                        &mut self.repo_stats_rows,
+                        self.repo_stats_sort,
+                    );
+                    self.repo_stats_selected = 0;
+                    self.set_status(format!("Sorted by {}", self.repo_stats_sort.label()));
                 }
-                KeyCode::Char(c) => {
-                    self.archive_search.input(Input {
-                        key: Key::Char(c),
-                        ctrl: false,
-                        alt: false,
-                        shift: false,
-                    });
-                    // Reset selection after search change
-                    self.archive_selected = 0;
+                KeyCode::Char('r') => {
+                    self.open_repo_stats();
                 }
                 _ => {}
             }
@@ -3676,535 +5382,580 @@ impl App {
         Ok(false)
     }
 
-    fn handle_archive_preview_event(
-        &mut self,
-        code: KeyCode,
-        modifiers: KeyModifiers,
-    ) -> Result<bool> {
-        match code {
-            KeyCode::Esc | KeyCode::Char('q') => {
-                self.archive_preview = None;
-            }
-            KeyCode::Char('j') => {
-                self.archive_scroll = self.archive_scroll.saturating_add(1);
-            }
-            KeyCode::Char('k') => {
-                self.archive_scroll = self.archive_scroll.saturating_sub(1);
-            }
-            KeyCode::Char('g') => {
-                self.archive_scroll = 0;
-            }
-            KeyCode::Char('G') => {
-                // Jump to bottom — use a large value, clamped during rendering
-                self.archive_scroll = u16::MAX;
-            }
-            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
-                self.archive_scroll = self.archive_scroll.saturating_add(15);
-            }
-            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
-                self.archive_scroll = self.archive_scroll.saturating_sub(15);
-            }
-            KeyCode::Char('s') => {
-                if self.archive_kind == ArchiveKind::Tasks {
-                    // Toggle saved
-                    let filtered = self.archive_filtered_indices();
-                    if let Some(&task_idx) = filtered.get(self.archive_selected) {
-                        let task = &mut self.archive_tasks[task_idx].0;
-                        match use_cases::toggle_archive_saved(&self.config, task) {
-                            Ok(()) => {
-                                let label = if task.meta.saved { "Saved" } else { "Unsaved" };
-                                let task_id = task.meta.task_id();
-                                self.set_status(format!("{}: {}", label, task_id));
-                            }
-                            Err(e) => {
-                                tracing::error!(error = %e, "failed to toggle archive saved");
-                                self.set_status(format!("Failed to toggle saved: {e}"));
-                            }
-                        }
-                    };
-                }
+    fn handle_project_wizard_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
             }
-            KeyCode::Char('d') => {
-                match self.archive_kind {
-                    ArchiveKind::Tasks => {
-                        // Permanently delete
-                        let filtered = self.archive_filtered_indices();
-                        if let Some(&task_idx) = filtered.get(self.archive_selected) {
-                            let (task, _) = self.archive_tasks.remove(task_idx);
-                            let task_id = task.meta.task_id();
-                            if let Err(e) =
-                                use_cases::permanently_delete_archived_task(&self.config, task)
-                            {
-                                tracing::error!(task_id = %task_id, error = %e, "failed to permanently delete archived task");
-                                self.set_status(format!("Delete failed: {e}"));
-                            } else {
-                                self.set_status(format!("Deleted: {}", task_id));
-                            }
+
+            // Ctrl+S to submit
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
+                if let Some(wizard) = &self.project_wizard {
+                    let name = wizard.name_editor.lines().join("").trim().to_string();
+                    let desc = wizard.description_editor.lines_joined();
+                    let desc = desc.trim().to_string();
+
+                    if name.is_empty() {
+                        if let Some(w) = &mut self.project_wizard {
+                            w.error_message = Some("Project name is required".to_string());
                         }
+                        return Ok(false);
                     }
-                    ArchiveKind::Agents => {
-                        let filtered = self.archive_filtered_indices();
-                        if let Some(&agent_idx) = filtered.get(self.archive_selected) {
-                            let (agent, _) = self.archive_agents.remove(agent_idx);
-                            let project = agent.meta.project.clone();
-                            let name = agent.meta.name.clone();
-                            if let Err(e) =
-                                use_cases::permanently_delete_archived_agent(&self.config, agent)
-                            {
-                                tracing::error!(project = %project, name = %name, error = %e, "failed to permanently delete archived agent");
-                                self.set_status(format!("Delete failed: {e}"));
-                            } else {
-                                self.set_status(format!("Deleted: {project}--{name}"));
+
+                    match use_cases::create_project(&self.config, &name, &desc, None) {
+                        Ok(_project) => {
+                            tracing::info!(project = %name, "created project via wizard");
+                            self.set_status(format!("Created project: {name}"));
+                            self.project_wizard = None;
+                            self.view = View::ProjectList;
+                            self.refresh_projects();
+                        }
+                        Err(e) => {
+                            tracing::warn!(project = %name, error = %e, "failed to create project");
+                            if let Some(w) = &mut self.project_wizard {
+                                w.error_message = Some(format!("{e}"));
                             }
                         }
                     }
                 }
-                self.archive_preview = None;
-                // Clamp selection
-                let filtered = self.archive_filtered_indices();
-                if self.archive_selected >= filtered.len() && !filtered.is_empty() {
-                    self.archive_selected = filtered.len() - 1;
-                }
+                return Ok(false);
             }
-            KeyCode::Char('n') => {
-                match self.archive_kind {
-                    ArchiveKind::Tasks => {
-                        // New task from archived task
-                        let filtered = self.archive_filtered_indices();
-                        if let Some(&task_idx) = filtered.get(self.archive_selected) {
-                            let (task, _content) = &self.archive_tasks[task_idx];
-                            let repo_name = task.meta.name.clone();
-                            let task_id = task.meta.task_id();
-                            let branch_name = task.meta.branch_name.clone();
-                            let repo_path = self
-                                .config
-                                .repo_path_for(task.meta.parent_dir.as_deref(), &repo_name);
 
-                            if !repo_path.exists() {
-                                self.set_status(format!(
-                                    "Repo path not found: {}",
-                                    repo_path.display()
-                                ));
-                            } else {
-                                tracing::info!(task_id = %task_id, repo = %repo_name, "starting new task from archived task");
-                                self.archive_preview = None;
-                                self.create_wizard_from_picker(repo_name, repo_path, false)?;
+            if let Some(wizard) = &mut self.project_wizard {
+                wizard.error_message = None;
+                let input = Input::from(event.clone());
 
-                                let prefill = format!(
-                                    "Reference: archived task \"{}\" (branch: {}). Examine the git branch/PR if needed.\n\n\n",
-                                    task_id, branch_name,
-                                );
-                                if let Some(wizard) = self.wizard.as_mut() {
-                                    wizard.first_prompt_editor.textarea.insert_str(&prefill);
-                                }
-                            }
-                        }
+                // Tab to switch focus between name and description
+                if key.code == KeyCode::Tab || key.code == KeyCode::BackTab {
+                    wizard.description_focus = !wizard.description_focus;
+                    if wizard.description_focus {
+                        wizard.description_editor.set_insert_mode();
                     }
-                    ArchiveKind::Agents => {
-                        let filtered = self.archive_filtered_indices();
-                        if let Some(&agent_idx) = filtered.get(self.archive_selected) {
-                            let agent = &self.archive_agents[agent_idx].0;
-                            let project = agent.meta.project.clone();
-                            let name = agent.meta.name.clone();
-                            let key = Self::project_detail_agent_key(agent);
-                            match use_cases::resume_agent(&self.config, &project, &name) {
-                                Ok(()) => {
-                                    self.set_status(format!("Restored agent '{name}'"));
-                                    self.archive_preview = None;
-                                    self.view = View::TaskList;
-                                    self.refresh_tasks_for_project();
-                                    self.refresh_agents();
-                                    self.restore_project_detail_selection(Some(&key));
-                                }
-                                Err(e) => {
-                                    tracing::error!(project = %project, name = %name, error = %e, "failed to restore archived agent");
-                                    self.set_status(format!("Restore failed: {e}"));
-                                }
-                            }
-                        }
+                    return Ok(false);
+                }
+
+                if wizard.description_focus {
+                    let was_insert = wizard.description_editor.mode() == VimMode::Insert;
+                    wizard.description_editor.input(input.clone());
+                    let is_normal_now = wizard.description_editor.mode() == VimMode::Normal;
+
+                    // Esc in normal mode goes back to name field
+                    if input.key == Key::Esc && !was_insert && is_normal_now {
+                        wizard.description_focus = false;
+                    }
+                } else {
+                    // Name field: Esc cancels wizard
+                    if key.code == KeyCode::Esc {
+                        self.project_wizard = None;
+                        self.view = View::ProjectList;
+                        return Ok(false);
                     }
+                    // Enter in name field moves to description
+                    if key.code == KeyCode::Enter {
+                        wizard.description_focus = true;
+                        wizard.description_editor.set_insert_mode();
+                        return Ok(false);
+                    }
+                    wizard.name_editor.input(input);
                 }
             }
-            _ => {}
         }
         Ok(false)
     }
 
-    fn handle_preview_event(&mut self, event: Event) -> Result<bool> {
-        // If editing notes, handle vim-style input with auto-wrap and save logic
-        if self.notes_editing && self.preview_pane == PreviewPane::Notes {
-            return self.handle_notes_editing(event);
-        }
-
+    fn handle_agent_wizard_event(&mut self, event: Event) -> Result<bool> {
         if let Event::Key(key) = event {
-            // Ctrl+C to quit
             if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
                 self.should_quit = true;
                 return Ok(false);
             }
 
-            // Tab/BackTab to switch preview panes
-            if key.code == KeyCode::Tab || key.code == KeyCode::BackTab {
-                self.preview_pane = match self.preview_pane {
-                    PreviewPane::Logs => PreviewPane::Notes,
-                    PreviewPane::Notes => PreviewPane::Logs,
-                };
+            // Ctrl+S submits from any step. The wizard validates required
+            // fields and surfaces inline errors so the user never has to
+            // play guess-the-step.
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
+                self.submit_agent_wizard();
                 return Ok(false);
             }
 
-            // Esc: forward to VimTextArea first (cancels Visual/Operator), then exit if already Normal
-            if key.code == KeyCode::Esc {
-                let editor = match self.preview_pane {
-                    PreviewPane::Logs => &self.logs_editor,
-                    PreviewPane::Notes => &self.notes_editor,
-                };
-                let was_normal = editor.mode() == VimMode::Normal;
-
-                let input = Input::from(event.clone());
-                match self.preview_pane {
-                    PreviewPane::Logs => self.logs_editor.input(input),
-                    PreviewPane::Notes => self.notes_editor.input(input),
-                }
-
-                if was_normal {
-                    self.view = View::TaskList;
-                }
+            let global_harness = self.config.harness_kind();
+            let Some(wizard) = self.agent_wizard.as_mut() else {
                 return Ok(false);
-            }
-
-            // q: exit preview if in Normal mode, otherwise forward to editor
-            if key.code == KeyCode::Char('q') && !key.modifiers.contains(KeyModifiers::CONTROL) {
-                let editor = match self.preview_pane {
-                    PreviewPane::Logs => &self.logs_editor,
-                    PreviewPane::Notes => &self.notes_editor,
-                };
-                if editor.mode() == VimMode::Normal {
-                    self.view = View::TaskList;
-                    return Ok(false);
-                }
-                // Otherwise fall through to forward to editor
-            }
+            };
+            wizard.error_message = None;
 
-            // Enter: Logs → attach tmux; Notes → start editing
-            if key.code == KeyCode::Enter {
-                match self.preview_pane {
-                    PreviewPane::Logs => {
-                        if let Some(task) = self.selected_task() {
-                            if task.meta.is_multi_repo() && task.meta.repos.len() > 1 {
-                                // Ensure all repo sessions exist before showing picker
-                                for repo in &task.meta.repos {
-                                    let _ = Tmux::ensure_session(
-                                        &repo.tmux_session,
-                                        &repo.worktree_path,
-                                    );
-                                }
-                                let sessions: Vec<(String, String)> = task
-                                    .meta
-                                    .repos
-                                    .iter()
-                                    .filter(|r| Tmux::session_exists(&r.tmux_session))
-                                    .map(|r| (r.repo_name.clone(), r.tmux_session.clone()))
-                                    .collect();
-                                if !sessions.is_empty() {
-                                    self.session_picker_sessions = sessions;
-                                    self.selected_session_index = 0;
-                                    self.view = View::SessionPicker;
-                                }
-                            } else if task.meta.has_repos() {
-                                let _ = Tmux::ensure_session(
-                                    &task.meta.primary_repo().tmux_session,
-                                    &task.meta.primary_repo().worktree_path,
-                                );
-                                if Tmux::session_exists(&task.meta.primary_repo().tmux_session) {
-                                    return Ok(true);
-                                }
-                            } else if task.meta.is_multi_repo() {
-                                let parent_session = Config::tmux_session_name(
-                                    &task.meta.name,
-                                    &task.meta.branch_name,
-                                );
-                                if let Some(ref parent_dir) = task.meta.parent_dir {
-                                    if !Tmux::session_exists(&parent_session) {
-                                        let _ = Tmux::create_session_with_windows(
-                                            &parent_session,
-                                            parent_dir,
-                                        );
-                                    }
-                                }
-                                if Tmux::session_exists(&parent_session) {
-                                    return Ok(true);
-                                }
-                            }
-                        }
+            match wizard.step {
+                AgentWizardStep::Kind => match key.code {
+                    KeyCode::Esc => {
+                        self.return_from_agent_wizard();
+                    }
+                    KeyCode::Char('j')
+                    | KeyCode::Down
+                    | KeyCode::Char('l')
+                    | KeyCode::Right
+                    | KeyCode::Tab => {
+                        wizard.kind = match wizard.kind {
+                            AgentWizardKind::Researcher => AgentWizardKind::Reviewer,
+                            AgentWizardKind::Reviewer => AgentWizardKind::Tester,
+                            AgentWizardKind::Tester => AgentWizardKind::Operator,
+                            AgentWizardKind::Operator => AgentWizardKind::Researcher,
+                        };
+                    }
+                    KeyCode::Char('k')
+                    | KeyCode::Up
+                    | KeyCode::Char('h')
+                    | KeyCode::Left
+                    | KeyCode::BackTab => {
+                        wizard.kind = match wizard.kind {
+                            AgentWizardKind::Researcher => AgentWizardKind::Operator,
+                            AgentWizardKind::Reviewer => AgentWizardKind::Researcher,
+                            AgentWizardKind::Tester => AgentWizardKind::Reviewer,
+                            AgentWizardKind::Operator => AgentWizardKind::Tester,
+                        };
+                    }
+                    KeyCode::Enter => {
+                        wizard.step = AgentWizardStep::Name;
+                    }
+                    _ => {}
+                },
+                AgentWizardStep::Name => {
+                    if key.code == KeyCode::Esc {
+                        // First content step — Esc cancels the wizard.
+                        self.return_from_agent_wizard();
                         return Ok(false);
                     }
-                    PreviewPane::Notes => {
-                        self.start_notes_editing();
+                    if key.code == KeyCode::Enter {
+                        // Advance: reviewers go to worktrees, researchers
+                        // skip straight to first prompt.
+                        wizard.step = match wizard.kind {
+                            AgentWizardKind::Researcher | AgentWizardKind::Operator => {
+                                AgentWizardStep::FirstPrompt
+                            }
+                            AgentWizardKind::Reviewer | AgentWizardKind::Tester => {
+                                AgentWizardStep::Worktrees
+                            }
+                        };
+                        if matches!(wizard.step, AgentWizardStep::FirstPrompt) {
+                            wizard.first_prompt_editor.set_insert_mode();
+                        }
                         return Ok(false);
                     }
+                    let input = Input::from(event.clone());
+                    wizard.name_editor.input(input);
                 }
-            }
-
-            // Action keys — handled before forwarding to VimTextArea
-            match key.code {
-                KeyCode::Char('p') => {
-                    let pr_info = self.selected_task().and_then(|t| {
-                        t.meta
-                            .linked_pr
-                            .as_ref()
-                            .map(|pr| (pr.number, pr.url.clone()))
-                    });
-                    if let Some((number, url)) = pr_info {
-                        open_url(&url);
-                        self.set_status(format!("Opening PR #{}...", number));
-                    } else {
-                        self.set_status("No linked PR".to_string());
+                AgentWizardStep::Worktrees => {
+                    match key.code {
+                        KeyCode::Esc => {
+                            wizard.step = AgentWizardStep::Name;
+                        }
+                        KeyCode::Tab => {
+                            // Tab cycles repo → branch → next row's repo.
+                            let len = wizard.worktree_rows.len();
+                            let cur = wizard.selected_row;
+                            let row = &mut wizard.worktree_rows[cur];
+                            if !row.branch_focus {
+                                row.branch_focus = true;
+                            } else if cur + 1 < len {
+                                row.branch_focus = false;
+                                wizard.selected_row += 1;
+                            } else {
+                                // Last field of last row — testers get an
+                                // optional capabilities step first.
+                                wizard.step = match wizard.kind {
+                                    AgentWizardKind::Tester => AgentWizardStep::Capabilities,
+                                    AgentWizardKind::Researcher
+                                    | AgentWizardKind::Operator
+                                    | AgentWizardKind::Reviewer => {
+                                        wizard.first_prompt_editor.set_insert_mode();
+                                        AgentWizardStep::FirstPrompt
+                                    }
+                                };
+                            }
+                        }
+                        KeyCode::BackTab => {
+                            let row = &mut wizard.worktree_rows[wizard.selected_row];
+                            if row.branch_focus {
+                                row.branch_focus = false;
+                            } else if wizard.selected_row > 0 {
+                                wizard.selected_row -= 1;
+                                wizard.worktree_rows[wizard.selected_row].branch_focus = true;
+                            }
+                        }
+                        KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Add a new row after the selected one.
+                            wizard
+                                .worktree_rows
+                                .insert(wizard.selected_row + 1, ReviewerWorktreeRow::new());
+                            wizard.selected_row += 1;
+                            wizard.worktree_rows[wizard.selected_row].branch_focus = false;
+                        }
+                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            // Remove the selected row, but never the last one.
+                            if wizard.worktree_rows.len() > 1 {
+                                wizard.worktree_rows.remove(wizard.selected_row);
+                                if wizard.selected_row >= wizard.worktree_rows.len() {
+                                    wizard.selected_row = wizard.worktree_rows.len() - 1;
+                                }
+                            }
+                        }
+                        KeyCode::Enter => {
+                            // Enter advances field-by-field, mirroring Tab.
+                            let len = wizard.worktree_rows.len();
+                            let cur = wizard.selected_row;
+                            let row = &mut wizard.worktree_rows[cur];
+                            if !row.branch_focus {
+                                row.branch_focus = true;
+                            } else if cur + 1 < len {
+                                row.branch_focus = false;
+                                wizard.selected_row += 1;
+                            } else {
+                                wizard.step = match wizard.kind {
+                                    AgentWizardKind::Tester => AgentWizardStep::Capabilities,
+                                    AgentWizardKind::Researcher
+                                    | AgentWizardKind::Operator
+                                    | AgentWizardKind::Reviewer => {
+                                        wizard.first_prompt_editor.set_insert_mode();
+                                        AgentWizardStep::FirstPrompt
+                                    }
+                                };
+                            }
+                        }
+                        _ => {
+                            let input = Input::from(event.clone());
+                            let row = &mut wizard.worktree_rows[wizard.selected_row];
+                            if row.branch_focus {
+                                row.branch_editor.input(input);
+                            } else {
+                                row.repo_editor.input(input);
+                            }
+                        }
                     }
-                    return Ok(false);
-                }
-                KeyCode::Char('r') => {
-                    self.restart_selected_task()?;
-                    return Ok(false);
                 }
-                _ => {}
-            }
-
-            // Insert-mode-entry keys in Notes pane start editing
-            if self.preview_pane == PreviewPane::Notes {
-                match key.code {
-                    KeyCode::Char('i')
-                    | KeyCode::Char('I')
-                    | KeyCode::Char('o')
-                    | KeyCode::Char('O') => {
-                        self.start_notes_editing();
-                        return Ok(false);
+                AgentWizardStep::Capabilities => match key.code {
+                    KeyCode::Esc | KeyCode::BackTab => {
+                        wizard.step = AgentWizardStep::Worktrees;
                     }
-                    KeyCode::Char('a') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        self.start_notes_editing();
-                        return Ok(false);
+                    KeyCode::Enter | KeyCode::Tab => {
+                        wizard.step = AgentWizardStep::FirstPrompt;
+                        wizard.first_prompt_editor.set_insert_mode();
                     }
-                    KeyCode::Char('A') => {
-                        self.start_notes_editing();
-                        return Ok(false);
+                    KeyCode::Char(' ')
+                    | KeyCode::Char('h')
+                    | KeyCode::Char('l')
+                    | KeyCode::Left
+                    | KeyCode::Right => {
+                        if !matches!(
+                            global_harness,
+                            agman::harness::HarnessKind::Goose | agman::harness::HarnessKind::Pi
+                        ) {
+                            wizard.browser_capability = !wizard.browser_capability;
+                        }
                     }
                     _ => {}
+                },
+                AgentWizardStep::FirstPrompt => {
+                    let input = Input::from(event.clone());
+                    let was_insert = wizard.first_prompt_editor.mode() == VimMode::Insert;
+                    wizard.first_prompt_editor.input(input.clone());
+                    let is_normal_now = wizard.first_prompt_editor.mode() == VimMode::Normal;
+                    // Esc in normal mode steps back to the previous step.
+                    if input.key == Key::Esc && !was_insert && is_normal_now {
+                        wizard.step = match wizard.kind {
+                            AgentWizardKind::Researcher | AgentWizardKind::Operator => {
+                                AgentWizardStep::Name
+                            }
+                            AgentWizardKind::Reviewer => AgentWizardStep::Worktrees,
+                            AgentWizardKind::Tester => AgentWizardStep::Capabilities,
+                        };
+                    }
                 }
             }
-
-            // Forward all remaining keys to the focused VimTextArea
-            let input = Input::from(event.clone());
-            match self.preview_pane {
-                PreviewPane::Logs => self.logs_editor.input(input),
-                PreviewPane::Notes => self.notes_editor.input(input),
-            }
         }
         Ok(false)
     }
 
-    fn start_notes_editing(&mut self) {
-        self.notes_editing = true;
-        self.notes_editor.set_read_only(false);
-        self.notes_editor.set_insert_mode();
-        self.set_status("Editing notes (vim mode, Ctrl+S or Esc twice to save)".to_string());
-    }
+    /// Validate and submit the agent wizard. On success the wizard closes
+    /// and the new agent is started; on failure the error message is
+    /// surfaced inline so the user can correct and resubmit.
+    fn submit_agent_wizard(&mut self) {
+        let Some(wizard) = self.agent_wizard.as_ref() else {
+            return;
+        };
+        let kind = wizard.kind;
+        let project = wizard.project.clone();
+        let name = wizard.name_editor.lines().join("").trim().to_string();
+        let first_prompt_text = wizard.first_prompt_editor.lines_joined();
+        let first_prompt = first_prompt_text.trim();
+        let first_prompt = if first_prompt.is_empty() {
+            None
+        } else {
+            Some(first_prompt)
+        };
+        let browser_capability = wizard.browser_capability;
 
-    fn handle_notes_editing(&mut self, event: Event) -> Result<bool> {
-        if let Event::Key(key) = event {
-            // Check for Ctrl+S to save in any mode
-            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
-                self.notes_editing = false;
-                self.notes_editor.set_normal_mode();
-                self.notes_editor.set_read_only(true);
-                self.save_notes()?;
-                return Ok(false);
+        if name.is_empty() {
+            if let Some(w) = self.agent_wizard.as_mut() {
+                w.error_message = Some("AgentRecord name is required".to_string());
+                w.step = AgentWizardStep::Name;
             }
+            return;
+        }
 
-            // In Normal mode, Esc exits editing; in Insert mode, Esc goes to Normal
-            let input = Input::from(event.clone());
-            let was_insert = self.notes_editor.mode() == VimMode::Insert;
-
-            self.notes_editor.input(input.clone());
-
-            // If we were in insert mode and now in normal, or got Esc in normal, might exit
-            let is_normal_now = self.notes_editor.mode() == VimMode::Normal;
+        match kind {
+            AgentWizardKind::Researcher | AgentWizardKind::Operator => {
+                let (kind_name, create_result) = match kind {
+                    AgentWizardKind::Researcher => (
+                        "researcher",
+                        use_cases::create_researcher(
+                            &self.config,
+                            &project,
+                            &name,
+                            first_prompt,
+                            None,
+                            None,
+                            None,
+                        ),
+                    ),
+                    AgentWizardKind::Operator => (
+                        "operator",
+                        use_cases::create_operator(
+                            &self.config,
+                            &project,
+                            &name,
+                            first_prompt,
+                            None,
+                            None,
+                            None,
+                        ),
+                    ),
+                    _ => unreachable!(),
+                };
+                match create_result {
+                    Ok(_agent) => {
+                        tracing::info!(project = %project, name = %name, kind = kind_name, "created agent via wizard");
+                        if let Err(e) =
+                            use_cases::start_agent_session(&self.config, &project, &name, false)
+                        {
+                            tracing::warn!(
+                                project = %project, name = %name, error = %e,
+                                "failed to start agent session"
+                            );
+                        }
+                        self.set_status(format!("Created {kind_name}: {name}"));
+                        self.return_from_agent_wizard();
+                    }
+                    Err(e) => {
+                        tracing::warn!(project = %project, name = %name, kind = kind_name, error = %e, "failed to create agent");
+                        if let Some(w) = self.agent_wizard.as_mut() {
+                            w.error_message = Some(format!("{e}"));
+                        }
+                    }
+                }
+            }
+            AgentWizardKind::Reviewer => {
+                // Collect (repo, branch) rows; reject empties so we don't
+                // hand a half-filled row to the use-case.
+                let mut branches: Vec<(String, String)> = Vec::new();
+                for row in &wizard.worktree_rows {
+                    let r = row.repo();
+                    let b = row.branch();
+                    if r.is_empty() && b.is_empty() {
+                        continue;
+                    }
+                    if r.is_empty() || b.is_empty() {
+                        if let Some(w) = self.agent_wizard.as_mut() {
+                            w.error_message = Some(
+                                "Each row needs both a repo and a branch (Ctrl+D to remove)"
+                                    .to_string(),
+                            );
+                            w.step = AgentWizardStep::Worktrees;
+                        }
+                        return;
+                    }
+                    branches.push((r, b));
+                }
+                if branches.is_empty() {
+                    if let Some(w) = self.agent_wizard.as_mut() {
+                        w.error_message =
+                            Some("Reviewer needs at least one (repo, branch) pair".to_string());
+                        w.step = AgentWizardStep::Worktrees;
+                    }
+                    return;
+                }
 
-            // If we pressed Esc and are now in normal mode after being in normal, exit editing
-            if input.key == Key::Esc && !was_insert && is_normal_now {
-                self.notes_editing = false;
-                self.notes_editor.set_read_only(true);
-                self.save_notes()?;
-                return Ok(false);
+                let spec = use_cases::WorktreeSpec {
+                    branches,
+                    parent_dir: None,
+                };
+                match use_cases::create_reviewer(&self.config, &project, &name, first_prompt, spec)
+                {
+                    Ok(_agent) => {
+                        tracing::info!(project = %project, name = %name, "created reviewer via wizard");
+                        if let Err(e) =
+                            use_cases::start_agent_session(&self.config, &project, &name, false)
+                        {
+                            tracing::warn!(
+                                project = %project, name = %name, error = %e,
+                                "failed to start agent session"
+                            );
+                        }
+                        self.set_status(format!("Created reviewer: {name}"));
+                        self.return_from_agent_wizard();
+                    }
+                    Err(e) => {
+                        tracing::warn!(project = %project, name = %name, error = %e, "failed to create reviewer");
+                        // The three-step decision tree raises a loud,
+                        // user-actionable error for the local-branch case;
+                        // surface it verbatim so the user can either fix
+                        // the branch state or pick a different one.
+                        if let Some(w) = self.agent_wizard.as_mut() {
+                            w.error_message = Some(format!("{e}"));
+                            w.step = AgentWizardStep::Worktrees;
+                        }
+                    }
+                }
             }
-        }
-        Ok(false)
-    }
+            AgentWizardKind::Tester => {
+                let mut branches: Vec<(String, String)> = Vec::new();
+                for row in &wizard.worktree_rows {
+                    let r = row.repo();
+                    let b = row.branch();
+                    if r.is_empty() && b.is_empty() {
+                        continue;
+                    }
+                    if r.is_empty() || b.is_empty() {
+                        if let Some(w) = self.agent_wizard.as_mut() {
+                            w.error_message = Some(
+                                "Each row needs both a repo and a branch (Ctrl+D to remove)"
+                                    .to_string(),
+                            );
+                            w.step = AgentWizardStep::Worktrees;
+                        }
+                        return;
+                    }
+                    branches.push((r, b));
+                }
+                if branches.is_empty() {
+                    if let Some(w) = self.agent_wizard.as_mut() {
+                        w.error_message =
+                            Some("Tester needs at least one (repo, branch) pair".to_string());
+                        w.step = AgentWizardStep::Worktrees;
+                    }
+                    return;
+                }
 
-    fn handle_delete_confirm_event(&mut self, event: Event) -> Result<bool> {
-        if let Event::Key(key) = event {
-            match key.code {
-                KeyCode::Enter => self.archive_focused_project_row()?,
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    self.view = View::TaskList;
+                let spec = use_cases::WorktreeSpec {
+                    branches,
+                    parent_dir: None,
+                };
+                let capabilities = agman::agent_model::TesterCapabilities {
+                    browser: browser_capability,
+                };
+                match use_cases::create_tester(
+                    &self.config,
+                    &project,
+                    &name,
+                    first_prompt,
+                    spec,
+                    capabilities,
+                ) {
+                    Ok(_agent) => {
+                        tracing::info!(project = %project, name = %name, "created tester via wizard");
+                        if let Err(e) =
+                            use_cases::start_agent_session(&self.config, &project, &name, false)
+                        {
+                            tracing::warn!(
+                                project = %project, name = %name, error = %e,
+                                "failed to start agent session"
+                            );
+                        }
+                        self.set_status(format!("Created tester: {name}"));
+                        self.return_from_agent_wizard();
+                    }
+                    Err(e) => {
+                        tracing::warn!(project = %project, name = %name, error = %e, "failed to create tester");
+                        if let Some(w) = self.agent_wizard.as_mut() {
+                            w.error_message = Some(format!("{e}"));
+                            w.step = AgentWizardStep::Worktrees;
+                        }
+                    }
                 }
-                _ => {}
             }
         }
-        Ok(false)
     }
 
-    fn handle_respawn_confirm_event(&mut self, event: Event) -> Result<bool> {
+    fn handle_project_picker_event(&mut self, event: Event) -> Result<bool> {
         if let Event::Key(key) = event {
             match key.code {
-                KeyCode::Char('j') | KeyCode::Down => {
-                    self.respawn_confirm_index = (self.respawn_confirm_index + 1) % 2;
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.should_quit = true;
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    self.respawn_confirm_index = if self.respawn_confirm_index == 0 {
-                        1
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    let was_migrate_all = self.project_picker.as_ref().is_some_and(|p| {
+                        matches!(p.action, ProjectPickerAction::MigrateAllUnassigned)
+                    });
+                    self.project_picker = None;
+                    self.view = if was_migrate_all {
+                        View::ProjectList
                     } else {
-                        0
+                        View::TaskList
                     };
                 }
+                KeyCode::Char('j') => {
+                    if let Some(picker) = &mut self.project_picker {
+                        if !picker.projects.is_empty() {
+                            picker.selected = (picker.selected + 1) % picker.projects.len();
+                        }
+                    }
+                }
+                KeyCode::Char('k') => {
+                    if let Some(picker) = &mut self.project_picker {
+                        if !picker.projects.is_empty() {
+                            picker.selected = if picker.selected == 0 {
+                                picker.projects.len() - 1
+                            } else {
+                                picker.selected - 1
+                            };
+                        }
+                    }
+                }
                 KeyCode::Enter => {
-                    if self.respawn_confirm_is_chief_of_staff {
-                        match self.respawn_confirm_index {
-                            0 => {
-                                // Chief of Staff only
-                                let target = "chief-of-staff".to_string();
-                                tracing::info!(target = %target, "respawn confirmed: chief-of-staff only");
-                                self.respawn_in_progress = Some(target.clone());
-                                self.set_status("respawning chief-of-staff...".to_string());
-                                let tx = self.respawn_tx.clone();
-                                let config = self.config.clone();
-                                self.rt.spawn(async move {
-                                    let result = tokio::task::spawn_blocking(move || {
-                                        use_cases::respawn_agent(
-                                            &config,
-                                            "chief-of-staff",
-                                            false,
-                                            120,
-                                        )
-                                    })
-                                    .await
-                                    .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
-                                    let msg = match result {
-                                        Ok(()) => Ok("chief-of-staff".to_string()),
-                                        Err(e) => Err(format!("{e}")),
-                                    };
-                                    let _ = tx.send(msg);
-                                });
-                            }
-                            1 => {
-                                // Chief of Staff + all PMs
-                                let project_names: Vec<String> =
-                                    self.projects.iter().map(|p| p.meta.name.clone()).collect();
-                                let pm_count = project_names.len();
-                                tracing::info!(
-                                    pm_count = pm_count,
-                                    "respawn confirmed: cos + all pms"
-                                );
-                                self.respawn_in_progress = Some("cos+pms".to_string());
-                                self.set_status(
-                                    "respawning chief-of-staff + all pms...".to_string(),
-                                );
-                                let tx = self.respawn_tx.clone();
-                                let config = self.config.clone();
-                                self.rt.spawn(async move {
-                                    // Respawn Chief of Staff first
-                                    let cos_result = tokio::task::spawn_blocking({
-                                        let config = config.clone();
-                                        move || {
-                                            use_cases::respawn_agent(
-                                                &config,
-                                                "chief-of-staff",
-                                                false,
-                                                120,
-                                            )
+                    if let Some(picker) = self.project_picker.take() {
+                        if let Some(project_name) = picker.projects.get(picker.selected) {
+                            let project_name = project_name.clone();
+                            match &picker.action {
+                                ProjectPickerAction::MigrateAllUnassigned => {
+                                    let unassigned = use_cases::list_unassigned_tasks(&self.config)
+                                        .unwrap_or_default();
+                                    let task_ids: Vec<String> =
+                                        unassigned.iter().map(|t| t.meta.task_id()).collect();
+                                    match use_cases::migrate_tasks_to_project(
+                                        &self.config,
+                                        &project_name,
+                                        &task_ids,
+                                    ) {
+                                        Ok(count) => {
+                                            tracing::info!(
+                                                project = %project_name,
+                                                count,
+                                                "migrated unassigned tasks"
+                                            );
+                                            self.set_status(format!(
+                                                "Migrated {count} tasks to {project_name}"
+                                            ));
                                         }
-                                    })
-                                    .await
-                                    .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
-
-                                    if let Err(e) = cos_result {
-                                        let _ = tx.send(Err(format!(
-                                            "chief-of-staff respawn failed: {e}"
-                                        )));
-                                        return;
-                                    }
-
-                                    // Respawn all PMs concurrently
-                                    let mut handles = Vec::new();
-                                    for name in &project_names {
-                                        let config = config.clone();
-                                        let name = name.clone();
-                                        handles.push(tokio::task::spawn_blocking(move || {
-                                            use_cases::respawn_agent(&config, &name, false, 120)
-                                        }));
-                                    }
-
-                                    let mut failures = Vec::new();
-                                    for (i, handle) in handles.into_iter().enumerate() {
-                                        match handle.await {
-                                            Ok(Ok(())) => {}
-                                            Ok(Err(e)) => {
-                                                failures.push(format!("{}: {e}", project_names[i]))
-                                            }
-                                            Err(e) => {
-                                                failures.push(format!("{}: {e}", project_names[i]))
-                                            }
+                                        Err(e) => {
+                                            tracing::error!(error = %e, "failed to migrate tasks");
+                                            self.set_status(format!("Migration failed: {e}"));
                                         }
                                     }
-
-                                    if failures.is_empty() {
-                                        let _ = tx.send(Ok(format!("cos+pms:{pm_count}")));
-                                    } else {
-                                        let _ = tx.send(Err(format!(
-                                            "some respawns failed: {}",
-                                            failures.join(", ")
-                                        )));
-                                    }
-                                });
-                            }
-                            _ => {}
-                        }
-                    } else {
-                        // PM confirmation
-                        match self.respawn_confirm_index {
-                            0 => {
-                                // Respawn
-                                if let Some(ref target) = self.respawn_confirm_target {
-                                    let target = target.clone();
-                                    tracing::info!(target = %target, "respawn confirmed: pm");
-                                    self.respawn_in_progress = Some(target.clone());
-                                    self.set_status(format!("respawning {target}..."));
-                                    let tx = self.respawn_tx.clone();
-                                    let config = self.config.clone();
-                                    self.rt.spawn(async move {
-                                        let result = tokio::task::spawn_blocking(move || {
-                                            use_cases::respawn_agent(&config, &target, false, 120)
-                                        })
-                                        .await
-                                        .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
-                                        let msg = match result {
-                                            Ok(()) => Ok("pm".to_string()),
-                                            Err(e) => Err(format!("{e}")),
-                                        };
-                                        let _ = tx.send(msg);
-                                    });
+                                    self.refresh_projects();
+                                    self.view = View::ProjectList;
                                 }
                             }
-                            1 => {
-                                // Cancel — just return to previous view
-                            }
-                            _ => {}
                         }
                     }
-                    self.view = self.respawn_confirm_return_view;
-                    self.respawn_confirm_target = None;
-                }
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    self.view = self.respawn_confirm_return_view;
-                    self.respawn_confirm_target = None;
                 }
                 _ => {}
             }
@@ -4212,180 +5963,73 @@ impl App {
         Ok(false)
     }
 
-    fn handle_wizard_event(&mut self, event: Event) -> Result<bool> {
+    fn handle_archive_event(&mut self, event: Event) -> Result<bool> {
         if let Event::Key(key) = event {
-            // Check for Ctrl+C to quit
             if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
                 self.should_quit = true;
                 return Ok(false);
             }
 
-            let wizard = match &mut self.wizard {
-                Some(w) => w,
-                None => {
+            if self.archive_preview.is_some() {
+                // Preview mode
+                return self.handle_archive_preview_event(key.code, key.modifiers);
+            }
+
+            // Search mode
+            match key.code {
+                KeyCode::Esc => {
                     self.view = View::TaskList;
-                    return Ok(false);
                 }
-            };
-
-            // Clear error on any keypress
-            wizard.error_message = None;
-
-            match wizard.step {
-                WizardStep::SelectBranch => {
-                    match key.code {
-                        KeyCode::Esc => {
-                            self.wizard_prev_step();
-                        }
-                        KeyCode::Tab => {
-                            // Multi-repo: locked to NewBranch, no cycling
-                            if !wizard.is_multi_repo {
-                                // Cycle forward: NewBranch → ExistingBranch → ExistingWorktree → NewBranch
-                                wizard.branch_source = match wizard.branch_source {
-                                    BranchSource::NewBranch => BranchSource::ExistingBranch,
-                                    BranchSource::ExistingBranch => BranchSource::ExistingWorktree,
-                                    BranchSource::ExistingWorktree => BranchSource::NewBranch,
-                                };
-                            }
-                        }
-                        KeyCode::BackTab => {
-                            if !wizard.is_multi_repo {
-                                // Cycle backward
-                                wizard.branch_source = match wizard.branch_source {
-                                    BranchSource::NewBranch => BranchSource::ExistingWorktree,
-                                    BranchSource::ExistingBranch => BranchSource::NewBranch,
-                                    BranchSource::ExistingWorktree => BranchSource::ExistingBranch,
-                                };
-                            }
-                        }
-                        KeyCode::Enter => {
-                            self.wizard_next_step()?;
-                        }
-                        _ => {
-                            match wizard.branch_source {
-                                BranchSource::NewBranch => {
-                                    // Ctrl+B or Up/Down toggles focus between branch name and base branch
-                                    if key.modifiers.contains(KeyModifiers::CONTROL)
-                                        && key.code == KeyCode::Char('b')
-                                        || key.code == KeyCode::Up
-                                        || key.code == KeyCode::Down
-                                    {
-                                        wizard.base_branch_focus = !wizard.base_branch_focus;
-                                    } else if wizard.base_branch_focus {
-                                        let input = Input::from(event.clone());
-                                        wizard.base_branch_editor.input(input);
-                                    } else {
-                                        let input = Input::from(event.clone());
-                                        wizard.new_branch_editor.input(input);
-                                    }
-                                }
-                                BranchSource::ExistingBranch => match key.code {
-                                    KeyCode::Char('j') | KeyCode::Down => {
-                                        if !wizard.existing_branches.is_empty() {
-                                            wizard.selected_branch_index =
-                                                (wizard.selected_branch_index + 1)
-                                                    % wizard.existing_branches.len();
-                                        }
-                                    }
-                                    KeyCode::Char('k') | KeyCode::Up => {
-                                        if !wizard.existing_branches.is_empty() {
-                                            wizard.selected_branch_index =
-                                                if wizard.selected_branch_index == 0 {
-                                                    wizard.existing_branches.len() - 1
-                                                } else {
-                                                    wizard.selected_branch_index - 1
-                                                };
-                                        }
-                                    }
-                                    _ => {}
-                                },
-                                BranchSource::ExistingWorktree => match key.code {
-                                    KeyCode::Char('j') | KeyCode::Down => {
-                                        if !wizard.existing_worktrees.is_empty() {
-                                            wizard.selected_worktree_index =
-                                                (wizard.selected_worktree_index + 1)
-                                                    % wizard.existing_worktrees.len();
-                                        }
-                                    }
-                                    KeyCode::Char('k') | KeyCode::Up => {
-                                        if !wizard.existing_worktrees.is_empty() {
-                                            wizard.selected_worktree_index =
-                                                if wizard.selected_worktree_index == 0 {
-                                                    wizard.existing_worktrees.len() - 1
-                                                } else {
-                                                    wizard.selected_worktree_index - 1
-                                                };
-                                        }
-                                    }
-                                    _ => {}
-                                },
-                            }
+                KeyCode::Up | KeyCode::Down => {
+                    let filtered = self.archive_filtered_indices();
+                    if !filtered.is_empty() {
+                        if key.code == KeyCode::Up {
+                            self.archive_selected = self.archive_selected.saturating_sub(1);
+                        } else {
+                            self.archive_selected =
+                                (self.archive_selected + 1).min(filtered.len() - 1);
                         }
                     }
                 }
-                WizardStep::EnterFirstPrompt => {
-                    // Check for Ctrl+S to submit in any mode
-                    if key.modifiers.contains(KeyModifiers::CONTROL)
-                        && key.code == KeyCode::Char('s')
-                    {
-                        wizard.first_prompt_editor.set_normal_mode();
-                        self.wizard_next_step()?;
-                        return Ok(false);
-                    }
-
-                    let input = Input::from(event.clone());
-                    let was_insert = wizard.first_prompt_editor.mode() == VimMode::Insert;
-
-                    wizard.first_prompt_editor.input(input.clone());
-
-                    let is_normal_now = wizard.first_prompt_editor.mode() == VimMode::Normal;
-
-                    // If we pressed Esc while already in normal mode, go back
-                    if input.key == Key::Esc && !was_insert && is_normal_now {
-                        self.wizard_prev_step();
-                        return Ok(false);
+                KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    let filtered = self.archive_filtered_indices();
+                    if !filtered.is_empty() {
+                        self.archive_selected = (self.archive_selected + 1).min(filtered.len() - 1);
                     }
                 }
-            }
-        }
-        Ok(false)
-    }
-
-    fn handle_session_picker_event(&mut self, event: Event) -> Result<bool> {
-        if let Event::Key(key) = event {
-            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-                self.should_quit = true;
-                return Ok(false);
-            }
-
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    self.view = View::Preview;
-                }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    if !self.session_picker_sessions.is_empty() {
-                        self.selected_session_index =
-                            (self.selected_session_index + 1) % self.session_picker_sessions.len();
-                    }
+                KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.archive_selected = self.archive_selected.saturating_sub(1);
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    if !self.session_picker_sessions.is_empty() {
-                        self.selected_session_index = if self.selected_session_index == 0 {
-                            self.session_picker_sessions.len() - 1
-                        } else {
-                            self.selected_session_index - 1
+                KeyCode::Enter => {
+                    let filtered = self.archive_filtered_indices();
+                    if let Some(&archive_idx) = filtered.get(self.archive_selected) {
+                        let content = match self.archive_kind {
+                            ArchiveKind::Tasks => self.archive_tasks[archive_idx].1.clone(),
+                            ArchiveKind::Agents => self.archive_agents[archive_idx].1.clone(),
                         };
+                        self.archive_preview = Some(content);
+                        self.archive_scroll = 0;
                     }
                 }
-                KeyCode::Enter => {
-                    if let Some((_, session)) = self
-                        .session_picker_sessions
-                        .get(self.selected_session_index)
-                    {
-                        self.attach_session_name = Some(session.clone());
-                        self.view = View::Preview;
-                        return Ok(true);
-                    }
+                KeyCode::Backspace => {
+                    self.archive_search.input(Input {
+                        key: Key::Backspace,
+                        ctrl: false,
+                        alt: false,
+                        shift: false,
+                    });
+                    // Reset selection after search change
+                    self.archive_selected = 0;
+                }
+                KeyCode::Char(c) => {
+                    self.archive_search.input(Input {
+                        key: Key::Char(c),
+                        ctrl: false,
+                        alt: false,
+                        shift: false,
+                    });
+                    // Reset selection after search change
+                    self.archive_selected = 0;
                 }
                 _ => {}
             }
@@ -4393,1789 +6037,4030 @@ impl App {
         Ok(false)
     }
 
-    fn handle_notifications_event(&mut self, event: Event) -> Result<bool> {
-        if let Event::Key(key) = event {
-            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-                self.should_quit = true;
-                return Ok(false);
+    fn handle_archive_preview_event(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Result<bool> {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.archive_preview = None;
             }
-
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    self.view = View::TaskList;
-                }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    if !self.notifications.is_empty()
-                        && self.selected_notif_index < self.notifications.len() - 1
-                    {
-                        self.selected_notif_index += 1;
-                    }
-                }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    if self.selected_notif_index > 0 {
-                        self.selected_notif_index -= 1;
-                    }
+            KeyCode::Char('j') => {
+                self.archive_scroll = self.archive_scroll.saturating_add(1);
+            }
+            KeyCode::Char('k') => {
+                self.archive_scroll = self.archive_scroll.saturating_sub(1);
+            }
+            KeyCode::Char('g') => {
+                self.archive_scroll = 0;
+            }
+            KeyCode::Char('G') => {
+                // Jump to bottom — use a large value, clamped during rendering
+                self.archive_scroll = u16::MAX;
+            }
+            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.archive_scroll = self.archive_scroll.saturating_add(15);
+            }
+            KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+                self.archive_scroll = self.archive_scroll.saturating_sub(15);
+            }
+            KeyCode::Char('s') => {
+                if self.archive_kind == ArchiveKind::Tasks {
+                    // Toggle saved
+                    let filtered = self.archive_filtered_indices();
+                    if let Some(&task_idx) = filtered.get(self.archive_selected) {
+                        let task = &mut self.archive_tasks[task_idx].0;
+                        match use_cases::toggle_archive_saved(&self.config, task) {
+                            Ok(()) => {
+                                let label = if task.meta.saved { "Saved" } else { "Unsaved" };
+                                let task_id = task.meta.task_id();
+                                self.set_status(format!("{}: {}", label, task_id));
+                            }
+                            Err(e) => {
+                                tracing::error!(error = %e, "failed to toggle archive saved");
+                                self.set_status(format!("Failed to toggle saved: {e}"));
+                            }
+                        }
+                    };
                 }
-                KeyCode::Char('d') => {
-                    if let Some(notif) = self.notifications.get(self.selected_notif_index) {
-                        let thread_id = notif.id.clone();
-                        let updated_at = notif.updated_at.clone();
-                        tracing::info!(thread_id = %thread_id, "dismissing github notification");
-
-                        // Track dismissed ID so polls don't reintroduce it (persisted to disk)
-                        self.dismissed_notifs.insert(thread_id.clone(), updated_at);
-                        self.dismissed_notifs
-                            .save(&self.config.dismissed_notifications_path());
-                        tracing::info!(thread_id = %thread_id, "persisted dismissed notification");
-
-                        // Optimistic removal
-                        self.notifications.remove(self.selected_notif_index);
-                        if self.selected_notif_index >= self.notifications.len()
-                            && !self.notifications.is_empty()
-                        {
-                            self.selected_notif_index = self.notifications.len() - 1;
+            }
+            KeyCode::Char('d') => {
+                match self.archive_kind {
+                    ArchiveKind::Tasks => {
+                        // Permanently delete
+                        let filtered = self.archive_filtered_indices();
+                        if let Some(&task_idx) = filtered.get(self.archive_selected) {
+                            let (task, _) = self.archive_tasks.remove(task_idx);
+                            let task_id = task.meta.task_id();
+                            if let Err(e) =
+                                use_cases::permanently_delete_archived_task(&self.config, task)
+                            {
+                                tracing::error!(task_id = %task_id, error = %e, "failed to permanently delete archived task");
+                                self.set_status(format!("Delete failed: {e}"));
+                            } else {
+                                self.set_status(format!("Deleted: {}", task_id));
+                            }
                         }
-
-                        // Fire-and-forget background dismiss
-                        self.rt.spawn(async move {
-                            let _ = tokio::task::spawn_blocking(move || {
-                                if let Err(e) = use_cases::dismiss_github_notification(&thread_id) {
-                                    tracing::warn!(thread_id = %thread_id, error = %e, "failed to dismiss notification");
-                                }
-                            })
-                            .await;
-                        });
-
-                        self.set_status("Notification dismissed".to_string());
                     }
-                }
-                KeyCode::Char('o') | KeyCode::Enter => {
-                    if let Some(notif) = self.notifications.get_mut(self.selected_notif_index) {
-                        let url = notif.browser_url.clone();
-                        let thread_id = notif.id.clone();
-                        tracing::info!(url = %url, thread_id = %thread_id, "opening notification in browser");
-                        open_url(&url);
-
-                        // Optimistic mark-as-read
-                        if notif.unread {
-                            notif.unread = false;
-                            tracing::info!(thread_id = %thread_id, "marking notification as read");
-
-                            // Fire-and-forget background PATCH
-                            self.rt.spawn(async move {
-                                let _ = tokio::task::spawn_blocking(move || {
-                                    if let Err(e) =
-                                        use_cases::mark_notification_read(&thread_id)
-                                    {
-                                        tracing::warn!(thread_id = %thread_id, error = %e, "failed to mark notification as read");
-                                    }
-                                })
-                                .await;
-                            });
+                    ArchiveKind::Agents => {
+                        let filtered = self.archive_filtered_indices();
+                        if let Some(&agent_idx) = filtered.get(self.archive_selected) {
+                            let (agent, _) = self.archive_agents.remove(agent_idx);
+                            let project = agent.meta.project.clone();
+                            let name = agent.meta.name.clone();
+                            if let Err(e) =
+                                use_cases::permanently_delete_archived_agent(&self.config, agent)
+                            {
+                                tracing::error!(project = %project, name = %name, error = %e, "failed to permanently delete archived agent");
+                                self.set_status(format!("Delete failed: {e}"));
+                            } else {
+                                self.set_status(format!("Deleted: {project}--{name}"));
+                            }
                         }
-
-                        self.set_status("Opening notification...".to_string());
                     }
                 }
-                _ => {}
+                self.archive_preview = None;
+                // Clamp selection
+                let filtered = self.archive_filtered_indices();
+                if self.archive_selected >= filtered.len() && !filtered.is_empty() {
+                    self.archive_selected = filtered.len() - 1;
+                }
             }
-        }
-        Ok(false)
-    }
+            KeyCode::Char('n') => {
+                match self.archive_kind {
+                    ArchiveKind::Tasks => {
+                        // New task from archived task
+                        let filtered = self.archive_filtered_indices();
+                        if let Some(&task_idx) = filtered.get(self.archive_selected) {
+                            let (task, _content) = &self.archive_tasks[task_idx];
+                            let repo_name = task.meta.name.clone();
+                            let task_id = task.meta.task_id();
+                            let branch_name = task.meta.branch_name.clone();
+                            let repo_path = self
+                                .config
+                                .repo_path_for(task.meta.parent_dir.as_deref(), &repo_name);
 
-    fn handle_show_prs_event(&mut self, event: Event) -> Result<bool> {
-        if let Event::Key(key) = event {
-            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-                self.should_quit = true;
-                return Ok(false);
-            }
+                            if !repo_path.exists() {
+                                self.set_status(format!(
+                                    "Repo path not found: {}",
+                                    repo_path.display()
+                                ));
+                            } else {
+                                tracing::info!(task_id = %task_id, repo = %repo_name, "starting new task from archived task");
+                                self.archive_preview = None;
+                                self.create_wizard_from_picker(repo_name, repo_path, false)?;
 
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    self.view = View::TaskList;
-                }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    let total = self.show_prs_total_items();
-                    if total > 0 && self.show_prs_selected < total - 1 {
-                        self.show_prs_selected += 1;
-                    }
-                }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    if self.show_prs_selected > 0 {
-                        self.show_prs_selected -= 1;
+                                let prefill = format!(
+                                    "Reference: archived task \"{}\" (branch: {}). Examine the git branch/PR if needed.\n\n\n",
+                                    task_id, branch_name,
+                                );
+                                if let Some(wizard) = self.wizard.as_mut() {
+                                    wizard.first_prompt_editor.textarea.insert_str(&prefill);
+                                }
+                            }
+                        }
                     }
-                }
-                KeyCode::Char('o') | KeyCode::Enter => {
-                    if let Some(item) = self.show_prs_selected_item() {
-                        let url = item.url.clone();
-                        tracing::info!(url = %url, "opening GitHub item from show-prs");
-                        open_url(&url);
-                        self.set_status("Opening in browser...".to_string());
+                    ArchiveKind::Agents => {
+                        let filtered = self.archive_filtered_indices();
+                        if let Some(&agent_idx) = filtered.get(self.archive_selected) {
+                            let agent = &self.archive_agents[agent_idx].0;
+                            let project = agent.meta.project.clone();
+                            let name = agent.meta.name.clone();
+                            let key = Self::project_detail_agent_key(agent);
+                            match use_cases::resume_agent(&self.config, &project, &name) {
+                                Ok(()) => {
+                                    self.set_status(format!("Restored agent '{name}'"));
+                                    self.archive_preview = None;
+                                    self.view = View::TaskList;
+                                    self.refresh_tasks_for_project();
+                                    self.refresh_agents();
+                                    self.restore_project_detail_selection(Some(&key));
+                                }
+                                Err(e) => {
+                                    tracing::error!(project = %project, name = %name, error = %e, "failed to restore archived agent");
+                                    self.set_status(format!("Restore failed: {e}"));
+                                }
+                            }
+                        }
                     }
                 }
-                KeyCode::Char('r') => {
-                    self.show_prs_poll_active = false; // force allow re-poll
-                    self.start_show_prs_poll();
-                    self.last_show_prs_poll = Instant::now();
-                    self.set_status("Refreshing...".to_string());
-                }
-                _ => {}
             }
+            _ => {}
         }
         Ok(false)
     }
 
-    fn handle_notes_event(&mut self, event: Event) -> Result<bool> {
+    fn handle_preview_event(&mut self, event: Event) -> Result<bool> {
+        // If editing notes, handle vim-style input with auto-wrap and save logic
+        if self.notes_editing && self.preview_pane == PreviewPane::Notes {
+            return self.handle_notes_editing(event);
+        }
+
         if let Event::Key(key) = event {
+            // Ctrl+C to quit
             if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
                 self.should_quit = true;
                 return Ok(false);
             }
 
-            let nv = match &mut self.notes_view {
-                Some(nv) => nv,
-                None => return Ok(false),
-            };
-
-            // Handle confirm_delete modal
-            if nv.confirm_delete {
+            if let Some(editor) = self.log_search_editor.as_mut() {
                 match key.code {
-                    KeyCode::Char('y') => {
-                        if let Some(entry) = nv.entries.get(nv.selected_index) {
-                            let path = nv.current_dir.join(&entry.file_name);
-                            if let Err(e) = use_cases::delete_note(&path) {
-                                self.set_status(format!("Delete failed: {e}"));
-                            }
+                    KeyCode::Enter => {
+                        let term = editor.lines().join("");
+                        self.log_search_editor = None;
+                        if term.is_empty() {
+                            self.log_search = None;
+                            self.logs_editor.textarea.cancel_selection();
+                        } else {
+                            self.log_search = Some(LogSearch {
+                                term,
+                                matches: Vec::new(),
+                                current: None,
+                            });
+                            self.jump_to_log_match(true);
                         }
-                        let nv = self.notes_view.as_mut().unwrap();
-                        nv.confirm_delete = false;
-                        let _ = nv.refresh();
+                    }
+                    KeyCode::Esc => {
+                        self.log_search_editor = None;
                     }
                     _ => {
-                        nv.confirm_delete = false;
+                        editor.input(Input::from(event.clone()));
                     }
                 }
                 return Ok(false);
             }
 
-            // Handle create_input modal
-            if nv.create_input.is_some() {
-                match key.code {
-                    KeyCode::Enter => {
-                        let (ref input, is_dir) = nv.create_input.as_ref().unwrap();
-                        let name = input.lines()[0].trim().to_string();
-                        if !name.is_empty() {
-                            let current_dir = nv.current_dir.clone();
-                            if *is_dir {
-                                if let Err(e) = use_cases::create_note_dir(&current_dir, &name) {
-                                    self.set_status(format!("Create dir failed: {e}"));
+            // Tab/BackTab to switch preview panes
+            if key.code == KeyCode::Tab || key.code == KeyCode::BackTab {
+                self.preview_pane = match self.preview_pane {
+                    PreviewPane::Logs => PreviewPane::Notes,
+                    PreviewPane::Notes => PreviewPane::Logs,
+                };
+                return Ok(false);
+            }
+
+            // Esc: forward to VimTextArea first (cancels Visual/Operator), then exit if already Normal
+            if key.code == KeyCode::Esc {
+                let editor = match self.preview_pane {
+                    PreviewPane::Logs => &self.logs_editor,
+                    PreviewPane::Notes => &self.notes_editor,
+                };
+                let was_normal = editor.mode() == VimMode::Normal;
+
+                let input = Input::from(event.clone());
+                match self.preview_pane {
+                    PreviewPane::Logs => self.logs_editor.input(input),
+                    PreviewPane::Notes => self.notes_editor.input(input),
+                }
+
+                if was_normal {
+                    self.remember_preview_position();
+                    self.view = View::TaskList;
+                }
+                return Ok(false);
+            }
+
+            // q: exit preview if in Normal mode, otherwise forward to editor
+            if key.code == KeyCode::Char('q') && !key.modifiers.contains(KeyModifiers::CONTROL) {
+                let editor = match self.preview_pane {
+                    PreviewPane::Logs => &self.logs_editor,
+                    PreviewPane::Notes => &self.notes_editor,
+                };
+                if editor.mode() == VimMode::Normal {
+                    self.remember_preview_position();
+                    self.view = View::TaskList;
+                    return Ok(false);
+                }
+                // Otherwise fall through to forward to editor
+            }
+
+            // Enter: Logs → attach tmux; Notes → start editing
+            if key.code == KeyCode::Enter {
+                match self.preview_pane {
+                    PreviewPane::Logs => {
+                        if let Some(task) = self.selected_task() {
+                            if task.meta.is_multi_repo() && task.meta.repos.len() > 1 {
+                                // Ensure all repo sessions exist before showing picker
+                                for repo in &task.meta.repos {
+                                    let _ = Tmux::ensure_session(
+                                        &repo.tmux_session,
+                                        &repo.worktree_path,
+                                        &self.config.session_windows(&repo.repo_name),
+                                    );
                                 }
-                            } else {
-                                match use_cases::create_note(&current_dir, &name) {
-                                    Ok(path) => {
-                                        let nv = self.notes_view.as_mut().unwrap();
-                                        nv.create_input = None;
-                                        let _ = nv.refresh();
-                                        let _ = nv.open_file(&path);
-                                        nv.focus = NotesFocus::Editor;
-                                        return Ok(false);
-                                    }
-                                    Err(e) => {
-                                        self.set_status(format!("Create note failed: {e}"));
+                                let sessions: Vec<(String, String)> = task
+                                    .meta
+                                    .repos
+                                    .iter()
+                                    .filter(|r| Tmux::session_exists(&r.tmux_session))
+                                    .map(|r| (r.repo_name.clone(), r.tmux_session.clone()))
+                                    .collect();
+                                let hinted = task.meta.hinted_repo_index().and_then(|i| {
+                                    let name = &task.meta.repos[i].repo_name;
+                                    sessions.iter().position(|(repo, _)| repo == name)
+                                });
+                                if !sessions.is_empty() {
+                                    self.session_picker_sessions = sessions;
+                                    self.selected_session_index = hinted.unwrap_or(0);
+                                    self.view = View::SessionPicker;
+                                }
+                            } else if task.meta.has_repos() {
+                                let _ = Tmux::ensure_session(
+                                    &task.meta.primary_repo().tmux_session,
+                                    &task.meta.primary_repo().worktree_path,
+                                    &self
+                                        .config
+                                        .session_windows(&task.meta.primary_repo().repo_name),
+                                );
+                                if Tmux::session_exists(&task.meta.primary_repo().tmux_session) {
+                                    return Ok(true);
+                                }
+                            } else if task.meta.is_multi_repo() {
+                                let parent_session = task.meta.parent_tmux_session();
+                                if let Some(parent_dir) = task.meta.parent_session_dir() {
+                                    if !Tmux::session_exists(&parent_session) {
+                                        let _ = Tmux::create_session_with_windows(
+                                            &parent_session,
+                                            &parent_dir,
+                                            &self.config.session_windows(&task.meta.name),
+                                        );
                                     }
                                 }
+                                if Tmux::session_exists(&parent_session) {
+                                    return Ok(true);
+                                }
                             }
                         }
-                        let nv = self.notes_view.as_mut().unwrap();
-                        nv.create_input = None;
-                        let _ = nv.refresh();
-                    }
-                    KeyCode::Esc => {
-                        nv.create_input = None;
+                        return Ok(false);
                     }
-                    _ => {
-                        let input_event: Input = key.into();
-                        nv.create_input.as_mut().unwrap().0.input(input_event);
+                    PreviewPane::Notes => {
+                        self.start_notes_editing();
+                        return Ok(false);
                     }
                 }
-                return Ok(false);
             }
 
-            // Handle rename_input modal
-            if nv.rename_input.is_some() {
-                match key.code {
-                    KeyCode::Enter => {
-                        let new_name = nv.rename_input.as_ref().unwrap().lines()[0]
-                            .trim()
-                            .to_string();
-                        if !new_name.is_empty() {
-                            if let Some(entry) = nv.entries.get(nv.selected_index) {
-                                let old_path = nv.current_dir.join(&entry.file_name);
-                                if let Err(e) = use_cases::rename_note(&old_path, &new_name) {
-                                    self.set_status(format!("Rename failed: {e}"));
-                                }
-                            }
-                        }
-                        let nv = self.notes_view.as_mut().unwrap();
-                        nv.rename_input = None;
-                        let _ = nv.refresh();
-                    }
-                    KeyCode::Esc => {
+            // Action keys — handled before forwarding to VimTextArea
+            match key.code {
+                KeyCode::Char('p') => {
+                    let pr_info = self.selected_task().and_then(|t| {
+                        t.meta
+                            .linked_pr
+                            .as_ref()
+                            .map(|pr| (pr.number, pr.url.clone()))
+                    });
+                    if let Some((number, url)) = pr_info {
+                        open_url(&url);
+                        self.set_status(format!("Opening PR #{}...", number));
+                    } else {
+                        self.set_status("No linked PR".to_string());
+                    }
+                    return Ok(false);
+                }
+                KeyCode::Char('r') => {
+                    self.restart_selected_task()?;
+                    return Ok(false);
+                }
+                KeyCode::Char('O') if self.preview_pane == PreviewPane::Logs => {
+                    self.open_log_in_pager();
+                    return Ok(false);
+                }
+                KeyCode::Char('B')
+                    if self.preview_pane == PreviewPane::Logs
+                        && self.logs_editor.mode() == VimMode::Normal =>
+                {
+                    self.start_base_ref_edit();
+                    return Ok(false);
+                }
+                KeyCode::Char('E') if self.preview_pane == PreviewPane::Notes => {
+                    self.editor_path = self.selected_task().map(|t| t.dir.join("notes.md"));
+                    return Ok(false);
+                }
+                KeyCode::Char('L') if self.preview_pane == PreviewPane::Logs => {
+                    self.toggle_preview_live();
+                    return Ok(false);
+                }
+                KeyCode::Char('T') if self.preview_pane == PreviewPane::Logs => {
+                    self.toggle_preview_full_log();
+                    return Ok(false);
+                }
+                KeyCode::Char('M') if self.preview_pane == PreviewPane::Logs => {
+                    self.cycle_log_filter();
+                    return Ok(false);
+                }
+                KeyCode::Char('/')
+                    if self.preview_pane == PreviewPane::Logs
+                        && self.logs_editor.mode() == VimMode::Normal =>
+                {
+                    self.log_search_editor = Some(Self::create_plain_editor());
+                    return Ok(false);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N')
+                    if self.preview_pane == PreviewPane::Logs
+                        && self.logs_editor.mode() == VimMode::Normal
+                        && self.log_search.is_some() =>
+                {
+                    self.jump_to_log_match(key.code == KeyCode::Char('n'));
+                    return Ok(false);
+                }
+                KeyCode::Char('F')
+                    if self.preview_pane == PreviewPane::Logs
+                        && self.logs_editor.mode() == VimMode::Normal =>
+                {
+                    self.toggle_repo_filter();
+                    self.view = View::TaskList;
+                    return Ok(false);
+                }
+                KeyCode::Char('A')
+                    if self.preview_pane == PreviewPane::Logs
+                        && self.logs_editor.mode() == VimMode::Normal =>
+                {
+                    self.archive_task(false)?;
+                    return Ok(false);
+                }
+                _ => {}
+            }
+
+            // Insert-mode-entry keys in Notes pane start editing
+            if self.preview_pane == PreviewPane::Notes {
+                match key.code {
+                    KeyCode::Char('i')
+                    | KeyCode::Char('I')
+                    | KeyCode::Char('o')
+                    | KeyCode::Char('O') => {
+                        self.start_notes_editing();
+                        return Ok(false);
+                    }
+                    KeyCode::Char('a') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.start_notes_editing();
+                        return Ok(false);
+                    }
+                    KeyCode::Char('A') => {
+                        self.start_notes_editing();
+                        return Ok(false);
+                    }
+                    _ => {}
+                }
+            }
+
+            // Forward all remaining keys to the focused VimTextArea
+            let input = Input::from(event.clone());
+            match self.preview_pane {
+                PreviewPane::Logs => self.logs_editor.input(input),
+                PreviewPane::Notes => self.notes_editor.input(input),
+            }
+        }
+        Ok(false)
+    }
+
+    /// Toggle mirroring the engineer's tmux pane into the Logs pane, for
+    /// watching a running task without attaching.
+    fn toggle_preview_live(&mut self) {
+        if self.preview_live.take().is_some() {
+            self.load_preview();
+            self.set_status("Live view off".to_string());
+            return;
+        }
+        let Some(task_id) = self.selected_task().map(|t| t.meta.task_id()) else {
+            return;
+        };
+        match use_cases::task_engineer_session(&self.config, &task_id) {
+            Ok(session) if Tmux::session_exists(&session) => {
+                self.preview_live = Some(session);
+                self.refresh_preview_live();
+                self.set_status("Live view on — L to stop".to_string());
+            }
+            Ok(_) => self.set_status("Engineer is not running".to_string()),
+            Err(e) => self.set_status(format!("No live view: {e}")),
+        }
+    }
+
+    /// Re-capture the engineer pane while live view is on. Called every main
+    /// loop tick, so it does nothing unless the Logs pane is on screen. The
+    /// capture runs on the worker; `apply_preview_live_result` shows it.
+    fn refresh_preview_live(&mut self) {
+        if !self.preview_live_visible() || self.live_capture_active {
+            return;
+        }
+        let Some(session) = self.preview_live.clone() else {
+            return;
+        };
+        self.live_capture_active = true;
+        let tx = self.live_capture_tx.clone();
+        self.rt.spawn(async move {
+            let capture_session = session.clone();
+            let result = tokio::task::spawn_blocking(move || Tmux::capture_pane(&capture_session))
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|r| r)
+                .map_err(|e| e.to_string());
+            let _ = tx.send((session, result));
+        });
+    }
+
+    /// Whether a live capture may replace the Logs pane right now.
+    fn preview_live_visible(&self) -> bool {
+        // Don't yank the text out from under a selection or search.
+        self.view == View::Preview
+            && self.preview_pane == PreviewPane::Logs
+            && self.logs_editor.mode() == VimMode::Normal
+            && self.log_search.is_none()
+    }
+
+    /// Take the worker's live capture. Captures of a session that is no
+    /// longer being watched, or that land while the pane is busy, are dropped.
+    fn apply_preview_live_result(&mut self) {
+        let Ok((session, result)) = self.live_capture_rx.try_recv() else {
+            return;
+        };
+        self.live_capture_active = false;
+        if self.preview_live.as_deref() != Some(session.as_str()) || !self.preview_live_visible() {
+            return;
+        }
+        let content = match result {
+            Ok(content) => content.trim_end().to_string(),
+            Err(e) => {
+                tracing::debug!(session = %session, error = %e, "live preview capture failed");
+                self.load_preview();
+                self.set_status("Engineer stopped — live view off".to_string());
+                return;
+            }
+        };
+        if content == self.preview_content {
+            return;
+        }
+        self.load_logs_editor(content);
+    }
+
+    /// Move to the next (or previous) match of the active log search,
+    /// wrapping at either end, and highlight it with the textarea selection.
+    fn jump_to_log_match(&mut self, forward: bool) {
+        let Some(search) = self.log_search.as_mut() else {
+            return;
+        };
+        let anchor = search
+            .current
+            .and_then(|i| search.matches.get(i).copied())
+            .unwrap_or_else(|| self.logs_editor.textarea.cursor());
+        search.matches = find_log_matches(self.logs_editor.textarea.lines(), &search.term);
+        search.current = if search.matches.is_empty() {
+            None
+        } else if forward {
+            Some(search.matches.iter().position(|m| *m > anchor).unwrap_or(0))
+        } else {
+            Some(
+                search
+                    .matches
+                    .iter()
+                    .rposition(|m| *m < anchor)
+                    .unwrap_or(search.matches.len() - 1),
+            )
+        };
+        self.highlight_log_match();
+    }
+
+    /// Select the current match of the active log search, or report that
+    /// the term does not occur.
+    fn highlight_log_match(&mut self) {
+        let Some(search) = self.log_search.as_ref() else {
+            return;
+        };
+        let Some((row, col)) = search.current.and_then(|i| search.matches.get(i).copied()) else {
+            let msg = format!("Pattern not found: {}", search.term);
+            self.logs_editor.textarea.cancel_selection();
+            self.set_status(msg);
+            return;
+        };
+        let len = search.term.chars().count();
+
+        let textarea = &mut self.logs_editor.textarea;
+        textarea.cancel_selection();
+        textarea.move_cursor(CursorMove::Jump(row as u16, col as u16));
+        textarea.start_selection();
+        textarea.move_cursor(CursorMove::Jump(row as u16, (col + len) as u16));
+    }
+
+    fn open_log_in_pager(&mut self) {
+        let Some(path) = self.selected_task().map(|t| t.dir.join("agent.log")) else {
+            return;
+        };
+        if path.exists() {
+            self.pager_path = Some(path);
+        } else {
+            self.set_status("No agent log available".to_string());
+        }
+    }
+
+    fn start_notes_editing(&mut self) {
+        self.notes_editing = true;
+        self.notes_editor.set_read_only(false);
+        self.notes_editor.set_insert_mode();
+        self.set_status("Editing notes (vim mode, Ctrl+S or Esc twice to save)".to_string());
+    }
+
+    fn handle_notes_editing(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            // Check for Ctrl+S to save in any mode
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('s') {
+                self.notes_editing = false;
+                self.notes_editor.set_normal_mode();
+                self.notes_editor.set_read_only(true);
+                self.save_notes()?;
+                return Ok(false);
+            }
+
+            // In Normal mode, Esc exits editing; in Insert mode, Esc goes to Normal
+            let input = Input::from(event.clone());
+            let was_insert = self.notes_editor.mode() == VimMode::Insert;
+
+            self.notes_editor.input(input.clone());
+
+            // If we were in insert mode and now in normal, or got Esc in normal, might exit
+            let is_normal_now = self.notes_editor.mode() == VimMode::Normal;
+
+            // If we pressed Esc and are now in normal mode after being in normal, exit editing
+            if input.key == Key::Esc && !was_insert && is_normal_now {
+                self.notes_editing = false;
+                self.notes_editor.set_read_only(true);
+                self.save_notes()?;
+                return Ok(false);
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_delete_confirm_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Enter => self.archive_focused_project_row()?,
+                KeyCode::Char('s')
+                    if matches!(
+                        self.selected_project_detail_row(),
+                        Some(ProjectDetailRow::Task(_))
+                    ) =>
+                {
+                    self.archive_task_with(false, ArchiveMode::KeepSession)?
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.view = View::TaskList;
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_respawn_confirm_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.respawn_confirm_index = (self.respawn_confirm_index + 1) % 2;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.respawn_confirm_index = if self.respawn_confirm_index == 0 {
+                        1
+                    } else {
+                        0
+                    };
+                }
+                KeyCode::Enter => {
+                    if self.respawn_confirm_is_chief_of_staff {
+                        match self.respawn_confirm_index {
+                            0 => {
+                                // Chief of Staff only
+                                let target = "chief-of-staff".to_string();
+                                tracing::info!(target = %target, "respawn confirmed: chief-of-staff only");
+                                self.respawn_in_progress = Some(target.clone());
+                                self.set_status("respawning chief-of-staff...".to_string());
+                                let tx = self.respawn_tx.clone();
+                                let config = self.config.clone();
+                                self.rt.spawn(async move {
+                                    let result = tokio::task::spawn_blocking(move || {
+                                        use_cases::respawn_agent(
+                                            &config,
+                                            "chief-of-staff",
+                                            false,
+                                            120,
+                                        )
+                                    })
+                                    .await
+                                    .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
+                                    let msg = match result {
+                                        Ok(()) => Ok("chief-of-staff".to_string()),
+                                        Err(e) => Err(format!("{e}")),
+                                    };
+                                    let _ = tx.send(msg);
+                                });
+                            }
+                            1 => {
+                                // Chief of Staff + all PMs
+                                let project_names: Vec<String> =
+                                    self.projects.iter().map(|p| p.meta.name.clone()).collect();
+                                let pm_count = project_names.len();
+                                tracing::info!(
+                                    pm_count = pm_count,
+                                    "respawn confirmed: cos + all pms"
+                                );
+                                self.respawn_in_progress = Some("cos+pms".to_string());
+                                self.set_status(
+                                    "respawning chief-of-staff + all pms...".to_string(),
+                                );
+                                let tx = self.respawn_tx.clone();
+                                let config = self.config.clone();
+                                self.rt.spawn(async move {
+                                    // Respawn Chief of Staff first
+                                    let cos_result = tokio::task::spawn_blocking({
+                                        let config = config.clone();
+                                        move || {
+                                            use_cases::respawn_agent(
+                                                &config,
+                                                "chief-of-staff",
+                                                false,
+                                                120,
+                                            )
+                                        }
+                                    })
+                                    .await
+                                    .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
+
+                                    if let Err(e) = cos_result {
+                                        let _ = tx.send(Err(format!(
+                                            "chief-of-staff respawn failed: {e}"
+                                        )));
+                                        return;
+                                    }
+
+                                    // Respawn all PMs concurrently
+                                    let mut handles = Vec::new();
+                                    for name in &project_names {
+                                        let config = config.clone();
+                                        let name = name.clone();
+                                        handles.push(tokio::task::spawn_blocking(move || {
+                                            use_cases::respawn_agent(&config, &name, false, 120)
+                                        }));
+                                    }
+
+                                    let mut failures = Vec::new();
+                                    for (i, handle) in handles.into_iter().enumerate() {
+                                        match handle.await {
+                                            Ok(Ok(())) => {}
+                                            Ok(Err(e)) => {
+                                                failures.push(format!("{}: {e}", project_names[i]))
+                                            }
+                                            Err(e) => {
+                                                failures.push(format!("{}: {e}", project_names[i]))
+                                            }
+                                        }
+                                    }
+
+                                    if failures.is_empty() {
+                                        let _ = tx.send(Ok(format!("cos+pms:{pm_count}")));
+                                    } else {
+                                        let _ = tx.send(Err(format!(
+                                            "some respawns failed: {}",
+                                            failures.join(", ")
+                                        )));
+                                    }
+                                });
+                            }
+                            _ => {}
+                        }
+                    } else {
+                        // PM confirmation
+                        match self.respawn_confirm_index {
+                            0 => {
+                                // Respawn
+                                if let Some(ref target) = self.respawn_confirm_target {
+                                    let target = target.clone();
+                                    tracing::info!(target = %target, "respawn confirmed: pm");
+                                    self.respawn_in_progress = Some(target.clone());
+                                    self.set_status(format!("respawning {target}..."));
+                                    let tx = self.respawn_tx.clone();
+                                    let config = self.config.clone();
+                                    self.rt.spawn(async move {
+                                        let result = tokio::task::spawn_blocking(move || {
+                                            use_cases::respawn_agent(&config, &target, false, 120)
+                                        })
+                                        .await
+                                        .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
+                                        let msg = match result {
+                                            Ok(()) => Ok("pm".to_string()),
+                                            Err(e) => Err(format!("{e}")),
+                                        };
+                                        let _ = tx.send(msg);
+                                    });
+                                }
+                            }
+                            1 => {
+                                // Cancel — just return to previous view
+                            }
+                            _ => {}
+                        }
+                    }
+                    self.view = self.respawn_confirm_return_view;
+                    self.respawn_confirm_target = None;
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.view = self.respawn_confirm_return_view;
+                    self.respawn_confirm_target = None;
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_wizard_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            // Check for Ctrl+C to quit
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
+            }
+
+            let wizard = match &mut self.wizard {
+                Some(w) => w,
+                None => {
+                    self.view = View::TaskList;
+                    return Ok(false);
+                }
+            };
+
+            if wizard.creating {
+                return Ok(false);
+            }
+
+            // Clear error on any keypress
+            wizard.error_message = None;
+
+            match wizard.step {
+                WizardStep::SelectBranch if wizard.issue_picker.is_some() => {
+                    Self::handle_issue_picker_key(wizard, key.code);
+                }
+                WizardStep::SelectBranch => {
+                    match key.code {
+                        KeyCode::Esc => {
+                            self.wizard_prev_step();
+                        }
+                        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            wizard.auto_rebase = !wizard.auto_rebase;
+                        }
+                        KeyCode::Char('o')
+                            if key.modifiers.contains(KeyModifiers::CONTROL)
+                                && !wizard.start_repo_choices.is_empty() =>
+                        {
+                            // parent dir → each repo → parent dir
+                            wizard.start_repo = match wizard.start_repo {
+                                None => Some(0),
+                                Some(i) if i + 1 < wizard.start_repo_choices.len() => Some(i + 1),
+                                Some(_) => None,
+                            };
+                        }
+                        KeyCode::Char('g')
+                            if key.modifiers.contains(KeyModifiers::CONTROL)
+                                && !wizard.is_multi_repo
+                                && !wizard.loading_issues =>
+                        {
+                            // `gh` hits the network; `apply_issue_list_results`
+                            // opens the picker when it returns.
+                            wizard.loading_issues = true;
+                            let repo_path = wizard.selected_repo_path.clone();
+                            let tx = self.issue_list_tx.clone();
+                            self.rt.spawn(async move {
+                                let path = repo_path.clone();
+                                let result = tokio::task::spawn_blocking(move || {
+                                    use_cases::list_open_issues(&path)
+                                })
+                                .await
+                                .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
+                                let _ = tx.send((repo_path, result.map_err(|e| e.to_string())));
+                            });
+                        }
+                        KeyCode::Tab => {
+                            // Multi-repo: locked to NewBranch, no cycling
+                            if !wizard.is_multi_repo {
+                                // Cycle forward: NewBranch → ExistingBranch → ExistingWorktree → NewBranch
+                                wizard.branch_source = match wizard.branch_source {
+                                    BranchSource::NewBranch => BranchSource::ExistingBranch,
+                                    BranchSource::ExistingBranch => BranchSource::ExistingWorktree,
+                                    BranchSource::ExistingWorktree => BranchSource::NewBranch,
+                                };
+                            }
+                        }
+                        KeyCode::BackTab => {
+                            if !wizard.is_multi_repo {
+                                // Cycle backward
+                                wizard.branch_source = match wizard.branch_source {
+                                    BranchSource::NewBranch => BranchSource::ExistingWorktree,
+                                    BranchSource::ExistingBranch => BranchSource::NewBranch,
+                                    BranchSource::ExistingWorktree => BranchSource::ExistingBranch,
+                                };
+                            }
+                        }
+                        KeyCode::Enter => {
+                            self.wizard_next_step()?;
+                        }
+                        _ => {
+                            match wizard.branch_source {
+                                BranchSource::NewBranch => {
+                                    // Ctrl+B or Up/Down toggles focus between branch name and base branch
+                                    if key.modifiers.contains(KeyModifiers::CONTROL)
+                                        && key.code == KeyCode::Char('b')
+                                        || key.code == KeyCode::Up
+                                        || key.code == KeyCode::Down
+                                    {
+                                        wizard.base_branch_focus = !wizard.base_branch_focus;
+                                    } else if wizard.base_branch_focus {
+                                        let input = Input::from(event.clone());
+                                        wizard.base_branch_editor.input(input);
+                                    } else if key.modifiers.contains(KeyModifiers::CONTROL)
+                                        && key.code == KeyCode::Char('n')
+                                    {
+                                        let (typed, press) =
+                                            wizard.branch_completion.take().unwrap_or_else(|| {
+                                                (wizard.new_branch_editor.lines().join(""), 0)
+                                            });
+                                        match use_cases::complete_branch_name(
+                                            &typed,
+                                            &wizard.existing_branches,
+                                            press,
+                                        ) {
+                                            Some(completed) => {
+                                                let mut editor = Self::create_plain_editor();
+                                                editor.set_cursor_line_style(
+                                                    ratatui::style::Style::default(),
+                                                );
+                                                editor.insert_str(completed);
+                                                wizard.new_branch_editor = editor;
+                                                wizard.branch_completion = Some((typed, press + 1));
+                                                wizard.error_message = None;
+                                            }
+                                            None => {
+                                                wizard.error_message =
+                                                    Some("No matching branches".to_string());
+                                            }
+                                        }
+                                    } else {
+                                        wizard.branch_completion = None;
+                                        let input = Input::from(event.clone());
+                                        wizard.new_branch_editor.input(input);
+                                    }
+                                }
+                                BranchSource::ExistingBranch => match key.code {
+                                    KeyCode::Char('j') | KeyCode::Down => {
+                                        if !wizard.existing_branches.is_empty() {
+                                            wizard.selected_branch_index =
+                                                (wizard.selected_branch_index + 1)
+                                                    % wizard.existing_branches.len();
+                                        }
+                                    }
+                                    KeyCode::Char('k') | KeyCode::Up => {
+                                        if !wizard.existing_branches.is_empty() {
+                                            wizard.selected_branch_index =
+                                                if wizard.selected_branch_index == 0 {
+                                                    wizard.existing_branches.len() - 1
+                                                } else {
+                                                    wizard.selected_branch_index - 1
+                                                };
+                                        }
+                                    }
+                                    _ => {}
+                                },
+                                BranchSource::ExistingWorktree => match key.code {
+                                    KeyCode::Char('j') | KeyCode::Down => {
+                                        if !wizard.existing_worktrees.is_empty() {
+                                            wizard.selected_worktree_index =
+                                                (wizard.selected_worktree_index + 1)
+                                                    % wizard.existing_worktrees.len();
+                                        }
+                                    }
+                                    KeyCode::Char('k') | KeyCode::Up => {
+                                        if !wizard.existing_worktrees.is_empty() {
+                                            wizard.selected_worktree_index =
+                                                if wizard.selected_worktree_index == 0 {
+                                                    wizard.existing_worktrees.len() - 1
+                                                } else {
+                                                    wizard.selected_worktree_index - 1
+                                                };
+                                        }
+                                    }
+                                    _ => {}
+                                },
+                            }
+                        }
+                    }
+                }
+                WizardStep::EnterFirstPrompt => {
+                    // Check for Ctrl+S to submit in any mode
+                    if key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('s')
+                    {
+                        wizard.first_prompt_editor.set_normal_mode();
+                        self.wizard_next_step()?;
+                        return Ok(false);
+                    }
+
+                    let input = Input::from(event.clone());
+                    let was_insert = wizard.first_prompt_editor.mode() == VimMode::Insert;
+
+                    wizard.first_prompt_editor.input(input.clone());
+
+                    let is_normal_now = wizard.first_prompt_editor.mode() == VimMode::Normal;
+
+                    // If we pressed Esc while already in normal mode, go back
+                    if input.key == Key::Esc && !was_insert && is_normal_now {
+                        self.wizard_prev_step();
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Navigate the wizard's issue picker. Enter fills the new-branch name and
+    /// first prompt from the selected issue; Esc closes the picker.
+    fn handle_issue_picker_key(wizard: &mut NewTaskWizard, code: KeyCode) {
+        let Some(issues) = &wizard.issue_picker else {
+            return;
+        };
+        match code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                wizard.selected_issue_index = (wizard.selected_issue_index + 1) % issues.len();
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                wizard.selected_issue_index = if wizard.selected_issue_index == 0 {
+                    issues.len() - 1
+                } else {
+                    wizard.selected_issue_index - 1
+                };
+            }
+            KeyCode::Enter => {
+                let issue = &issues[wizard.selected_issue_index];
+                let mut branch_editor = Self::create_plain_editor();
+                branch_editor.set_cursor_line_style(ratatui::style::Style::default());
+                branch_editor.insert_str(use_cases::issue_branch_name(issue));
+                let prompt = use_cases::issue_first_prompt(issue);
+                let mut prompt_editor = VimTextArea::from_lines(prompt.lines());
+                prompt_editor.set_insert_mode();
+                prompt_editor.move_cursor(CursorMove::Bottom);
+                prompt_editor.move_cursor(CursorMove::End);
+
+                wizard.new_branch_editor = branch_editor;
+                wizard.first_prompt_editor = prompt_editor;
+                wizard.base_branch_focus = false;
+                wizard.issue_picker = None;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                wizard.issue_picker = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_session_picker_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
+            }
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.view = View::Preview;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if !self.session_picker_sessions.is_empty() {
+                        self.selected_session_index =
+                            (self.selected_session_index + 1) % self.session_picker_sessions.len();
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    if !self.session_picker_sessions.is_empty() {
+                        self.selected_session_index = if self.selected_session_index == 0 {
+                            self.session_picker_sessions.len() - 1
+                        } else {
+                            self.selected_session_index - 1
+                        };
+                    }
+                }
+                KeyCode::Enter => {
+                    if let Some((_, session)) = self
+                        .session_picker_sessions
+                        .get(self.selected_session_index)
+                    {
+                        self.attach_session_name = Some(session.clone());
+                        self.view = View::Preview;
+                        return Ok(true);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    /// Indices into `notifications` that pass the current reason filter.
+    pub fn visible_notif_indices(&self) -> Vec<usize> {
+        self.notifications
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| self.notif_filter.matches(&n.reason))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn clamp_notif_selection(&mut self) {
+        let visible = self.visible_notif_indices().len();
+        if self.selected_notif_index >= visible {
+            self.selected_notif_index = visible.saturating_sub(1);
+        }
+    }
+
+    /// Dismiss the given notifications: persist them as dismissed so polls
+    /// don't reintroduce them, remove them optimistically, and fire the API
+    /// calls in the background.
+    fn dismiss_notifications(&mut self, indices: &[usize]) {
+        let threads: Vec<(String, String)> = indices
+            .iter()
+            .filter_map(|&i| self.notifications.get(i))
+            .map(|n| (n.id.clone(), n.updated_at.clone()))
+            .collect();
+        if threads.is_empty() {
+            return;
+        }
+        tracing::info!(count = threads.len(), "dismissing github notifications");
+
+        for (thread_id, updated_at) in &threads {
+            self.dismissed_notifs
+                .insert(thread_id.clone(), updated_at.clone());
+        }
+        self.dismissed_notifs
+            .save(&self.config.dismissed_notifications_path());
+
+        let dismissed: HashSet<&str> = threads.iter().map(|(id, _)| id.as_str()).collect();
+        self.notifications
+            .retain(|n| !dismissed.contains(n.id.as_str()));
+        self.clamp_notif_selection();
+
+        let thread_ids: Vec<String> = threads.into_iter().map(|(id, _)| id).collect();
+        self.rt.spawn(async move {
+            let _ = tokio::task::spawn_blocking(move || {
+                for thread_id in thread_ids {
+                    if let Err(e) = use_cases::dismiss_github_notification(&thread_id) {
+                        tracing::warn!(thread_id = %thread_id, error = %e, "failed to dismiss notification");
+                    }
+                }
+            })
+            .await;
+        });
+    }
+
+    /// Hide a notification locally for [`SNOOZE_HOURS`]; it comes back on
+    /// the first poll after that.
+    fn snooze_notification(&mut self, index: usize) {
+        let Some(id) = self.notifications.get(index).map(|n| n.id.clone()) else {
+            return;
+        };
+        let until = chrono::Utc::now() + chrono::Duration::hours(SNOOZE_HOURS);
+        tracing::info!(thread_id = %id, until = %until, "snoozing github notification");
+        self.dismissed_notifs.snooze(id.clone(), until);
+        self.dismissed_notifs
+            .save(&self.config.dismissed_notifications_path());
+        self.notifications.retain(|n| n.id != id);
+        self.clamp_notif_selection();
+        self.set_status(format!(
+            "Snoozed until {}",
+            until.with_timezone(&chrono::Local).format("%H:%M")
+        ));
+    }
+
+    /// Optimistically mark the given notifications read and PATCH them in the background.
+    fn mark_notifications_read(&mut self, indices: &[usize]) -> usize {
+        let mut thread_ids = Vec::new();
+        for &i in indices {
+            if let Some(notif) = self.notifications.get_mut(i) {
+                if notif.unread {
+                    notif.unread = false;
+                    thread_ids.push(notif.id.clone());
+                }
+            }
+        }
+        let count = thread_ids.len();
+        if count == 0 {
+            return 0;
+        }
+        tracing::info!(count, "marking github notifications as read");
+        self.rt.spawn(async move {
+            let _ = tokio::task::spawn_blocking(move || {
+                for thread_id in thread_ids {
+                    if let Err(e) = use_cases::mark_notification_read(&thread_id) {
+                        tracing::warn!(thread_id = %thread_id, error = %e, "failed to mark notification as read");
+                    }
+                }
+            })
+            .await;
+        });
+        count
+    }
+
+    fn handle_notifications_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
+            }
+
+            let visible = self.visible_notif_indices();
+            let selected = visible.get(self.selected_notif_index).copied();
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.view = View::TaskList;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if !visible.is_empty() && self.selected_notif_index < visible.len() - 1 {
+                        self.selected_notif_index += 1;
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    if self.selected_notif_index > 0 {
+                        self.selected_notif_index -= 1;
+                    }
+                }
+                KeyCode::Char('f') => {
+                    self.notif_filter = self.notif_filter.next();
+                    self.selected_notif_index = 0;
+                    self.set_status(format!("Filter: {}", self.notif_filter.label()));
+                }
+                KeyCode::Char('d') => {
+                    if let Some(index) = selected {
+                        self.dismiss_notifications(&[index]);
+                        self.set_status("Notification dismissed".to_string());
+                    }
+                }
+                KeyCode::Char('D') => {
+                    if !visible.is_empty() {
+                        let count = visible.len();
+                        self.dismiss_notifications(&visible);
+                        self.set_status(format!("Dismissed {} notifications", count));
+                    }
+                }
+                KeyCode::Char('s') => {
+                    if let Some(index) = selected {
+                        self.snooze_notification(index);
+                    }
+                }
+                KeyCode::Char('m') => {
+                    let count = self.mark_notifications_read(&visible);
+                    self.set_status(format!("Marked {} notifications read", count));
+                }
+                KeyCode::Char('o') | KeyCode::Enter => {
+                    if let Some(index) = selected {
+                        let url = self.notifications[index].browser_url.clone();
+                        tracing::info!(url = %url, thread_id = %self.notifications[index].id, "opening notification in browser");
+                        open_url(&url);
+                        self.mark_notifications_read(&[index]);
+                        self.set_status("Opening notification...".to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_show_prs_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
+            }
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.view = View::TaskList;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    let total = self.show_prs_total_items();
+                    if total > 0 && self.show_prs_selected < total - 1 {
+                        self.show_prs_selected += 1;
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    if self.show_prs_selected > 0 {
+                        self.show_prs_selected -= 1;
+                    }
+                }
+                KeyCode::Char('o') | KeyCode::Enter => {
+                    if let Some(item) = self.show_prs_selected_item() {
+                        let url = item.url.clone();
+                        tracing::info!(url = %url, "opening GitHub item from show-prs");
+                        open_url(&url);
+                        self.set_status("Opening in browser...".to_string());
+                    }
+                }
+                KeyCode::Char('r') => {
+                    self.show_prs_poll_active = false; // force allow re-poll
+                    self.start_show_prs_poll();
+                    self.last_show_prs_poll = Instant::now();
+                    self.set_status("Refreshing...".to_string());
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_notes_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
+            }
+
+            let nv = match &mut self.notes_view {
+                Some(nv) => nv,
+                None => return Ok(false),
+            };
+
+            // Handle confirm_delete modal
+            if nv.confirm_delete {
+                match key.code {
+                    KeyCode::Char('y') => {
+                        if let Some(entry) = nv.entries.get(nv.selected_index) {
+                            let path = nv.current_dir.join(&entry.file_name);
+                            if let Err(e) = use_cases::delete_note(&path) {
+                                self.set_status(format!("Delete failed: {e}"));
+                            }
+                        }
+                        let nv = self.notes_view.as_mut().unwrap();
+                        nv.confirm_delete = false;
+                        nv.invalidate_index();
+                        let _ = nv.refresh();
+                    }
+                    _ => {
+                        nv.confirm_delete = false;
+                    }
+                }
+                return Ok(false);
+            }
+
+            // Handle create_input modal
+            if nv.create_input.is_some() {
+                match key.code {
+                    KeyCode::Enter => {
+                        let (ref input, is_dir) = nv.create_input.as_ref().unwrap();
+                        let name = input.lines()[0].trim().to_string();
+                        if !name.is_empty() {
+                            let current_dir = nv.current_dir.clone();
+                            if *is_dir {
+                                if let Err(e) = use_cases::create_note_dir(&current_dir, &name) {
+                                    self.set_status(format!("Create dir failed: {e}"));
+                                }
+                            } else {
+                                match use_cases::create_note(&current_dir, &name) {
+                                    Ok(path) => {
+                                        let nv = self.notes_view.as_mut().unwrap();
+                                        nv.create_input = None;
+                                        nv.invalidate_index();
+                                        let _ = nv.refresh();
+                                        let _ = nv.open_file(&path);
+                                        nv.focus = NotesFocus::Editor;
+                                        return Ok(false);
+                                    }
+                                    Err(e) => {
+                                        self.set_status(format!("Create note failed: {e}"));
+                                    }
+                                }
+                            }
+                        }
+                        let nv = self.notes_view.as_mut().unwrap();
+                        nv.create_input = None;
+                        nv.invalidate_index();
+                        let _ = nv.refresh();
+                    }
+                    KeyCode::Esc => {
+                        nv.create_input = None;
+                    }
+                    _ => {
+                        let input_event: Input = key.into();
+                        nv.create_input.as_mut().unwrap().0.input(input_event);
+                    }
+                }
+                return Ok(false);
+            }
+
+            // Handle rename_input modal
+            if nv.rename_input.is_some() {
+                match key.code {
+                    KeyCode::Enter => {
+                        let new_name = nv.rename_input.as_ref().unwrap().lines()[0]
+                            .trim()
+                            .to_string();
+                        if !new_name.is_empty() {
+                            if let Some(entry) = nv.entries.get(nv.selected_index) {
+                                let old_path = nv.current_dir.join(&entry.file_name);
+                                if let Err(e) = use_cases::rename_note(&old_path, &new_name) {
+                                    self.set_status(format!("Rename failed: {e}"));
+                                }
+                            }
+                        }
+                        let nv = self.notes_view.as_mut().unwrap();
+                        nv.rename_input = None;
+                        nv.invalidate_index();
+                        let _ = nv.refresh();
+                    }
+                    KeyCode::Esc => {
                         nv.rename_input = None;
                     }
-                    _ => {
-                        let input_event: Input = key.into();
-                        nv.rename_input.as_mut().unwrap().input(input_event);
+                    _ => {
+                        let input_event: Input = key.into();
+                        nv.rename_input.as_mut().unwrap().input(input_event);
+                    }
+                }
+                return Ok(false);
+            }
+
+            // Handle the fuzzy finder
+            if let Some(finder) = nv.finder.as_mut() {
+                match key.code {
+                    KeyCode::Esc => {
+                        nv.finder = None;
+                    }
+                    KeyCode::Enter => {
+                        if let Some(rel) = finder.matches.get(finder.selected).cloned() {
+                            if let Err(e) = nv.open_from_finder(&rel) {
+                                self.set_status(format!("Open failed: {e}"));
+                            }
+                        }
+                    }
+                    KeyCode::Down => {
+                        if finder.selected + 1 < finder.matches.len() {
+                            finder.selected += 1;
+                        }
+                    }
+                    KeyCode::Up => {
+                        finder.selected = finder.selected.saturating_sub(1);
+                    }
+                    KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if finder.selected + 1 < finder.matches.len() {
+                            finder.selected += 1;
+                        }
+                    }
+                    KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        finder.selected = finder.selected.saturating_sub(1);
+                    }
+                    _ => {
+                        let input_event: Input = key.into();
+                        if finder.input.input(input_event) {
+                            nv.update_finder_matches();
+                        }
+                    }
+                }
+                return Ok(false);
+            }
+
+            let ctrl_p =
+                key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('p');
+            if ctrl_p && (nv.focus == NotesFocus::Explorer || nv.editor.mode() == VimMode::Normal) {
+                if let Err(e) = nv.open_finder() {
+                    self.set_status(format!("Indexing notes failed: {e}"));
+                }
+                return Ok(false);
+            }
+
+            // Main key handling based on focus
+            match nv.focus {
+                NotesFocus::Explorer => match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        if !nv.entries.is_empty() && nv.selected_index < nv.entries.len() - 1 {
+                            nv.selected_index += 1;
+                        }
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        if nv.selected_index > 0 {
+                            nv.selected_index -= 1;
+                        }
+                    }
+                    KeyCode::Char('l') | KeyCode::Enter => {
+                        if let Some(entry) = nv.entries.get(nv.selected_index).cloned() {
+                            if entry.is_dir {
+                                let new_dir = nv.current_dir.join(&entry.file_name);
+                                nv.current_dir = new_dir;
+                                nv.selected_index = 0;
+                                let _ = nv.refresh();
+                            } else {
+                                let path = nv.current_dir.join(&entry.file_name);
+                                let _ = nv.save_current();
+                                if let Err(e) = nv.open_file(&path) {
+                                    self.set_status(format!("Open failed: {e}"));
+                                } else {
+                                    let nv = self.notes_view.as_mut().unwrap();
+                                    nv.focus = NotesFocus::Editor;
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Char('h') | KeyCode::Backspace => {
+                        if nv.current_dir != nv.root_dir {
+                            if let Some(parent) = nv.current_dir.parent() {
+                                let child_name = nv
+                                    .current_dir
+                                    .file_name()
+                                    .map(|n| n.to_string_lossy().to_string());
+                                nv.current_dir = parent.to_path_buf();
+                                let _ = nv.refresh();
+                                nv.selected_index = child_name
+                                    .and_then(|name| {
+                                        nv.entries.iter().position(|e| e.file_name == name)
+                                    })
+                                    .unwrap_or(0);
+                            }
+                        }
+                    }
+                    KeyCode::Char('a') => {
+                        nv.create_input = Some((TextArea::default(), false));
+                    }
+                    KeyCode::Char('A') => {
+                        nv.create_input = Some((TextArea::default(), true));
+                    }
+                    KeyCode::Char('d') => {
+                        if !nv.entries.is_empty() {
+                            nv.confirm_delete = true;
+                        }
+                    }
+                    KeyCode::Char('r') => {
+                        if let Some(entry) = nv.entries.get(nv.selected_index) {
+                            let mut ta = TextArea::default();
+                            ta.insert_str(&entry.name);
+                            nv.rename_input = Some(ta);
+                        }
+                    }
+                    KeyCode::Tab => {
+                        if nv.open_file.is_some() {
+                            nv.focus = NotesFocus::Editor;
+                        }
+                    }
+                    KeyCode::Char('J') => {
+                        if !nv.entries.is_empty() && nv.selected_index < nv.entries.len() - 1 {
+                            let entry_name = nv.entries[nv.selected_index].file_name.clone();
+                            let dir = nv.current_dir.clone();
+                            match use_cases::move_note(
+                                &dir,
+                                &entry_name,
+                                use_cases::MoveDirection::Down,
+                            ) {
+                                Ok(new_idx) => {
+                                    let _ = nv.refresh();
+                                    nv.selected_index = new_idx;
+                                }
+                                Err(e) => {
+                                    self.set_status(format!("Move failed: {e}"));
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Char('K') => {
+                        if nv.selected_index > 0 {
+                            let entry_name = nv.entries[nv.selected_index].file_name.clone();
+                            let dir = nv.current_dir.clone();
+                            match use_cases::move_note(
+                                &dir,
+                                &entry_name,
+                                use_cases::MoveDirection::Up,
+                            ) {
+                                Ok(new_idx) => {
+                                    let _ = nv.refresh();
+                                    nv.selected_index = new_idx;
+                                }
+                                Err(e) => {
+                                    self.set_status(format!("Move failed: {e}"));
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Char('x') => {
+                        if let Some(entry) = nv.entries.get(nv.selected_index) {
+                            let cut = (nv.current_dir.clone(), entry.file_name.clone());
+                            let status_msg = format!("Cut: {}", entry.name);
+                            tracing::info!(dir = %cut.0.display(), file = %cut.1, "cut note");
+                            nv.cut_entry = Some(cut);
+                            self.set_status(status_msg);
+                        }
+                    }
+                    KeyCode::Char('p') => {
+                        if let Some((ref src_dir, ref file_name)) = nv.cut_entry.clone() {
+                            let dest_dir = nv.current_dir.clone();
+                            match use_cases::paste_note(src_dir, &dest_dir, file_name) {
+                                Ok(()) => {
+                                    let nv = self.notes_view.as_mut().unwrap();
+                                    nv.cut_entry = None;
+                                    nv.invalidate_index();
+                                    let _ = nv.refresh();
+                                    self.set_status(format!("Pasted: {}", file_name));
+                                }
+                                Err(e) => {
+                                    self.set_status(format!("Paste failed: {e}"));
+                                }
+                            }
+                        }
+                    }
+                    KeyCode::Esc => {
+                        if nv.cut_entry.is_some() {
+                            nv.cut_entry = None;
+                            self.set_status("Cut cancelled".to_string());
+                        } else {
+                            let _ = nv.save_current();
+                            self.close_notes();
+                        }
+                    }
+                    KeyCode::Char('q') => {
+                        let _ = nv.save_current();
+                        self.close_notes();
+                    }
+                    _ => {}
+                },
+                NotesFocus::Editor => {
+                    let vim_mode = nv.editor.mode();
+                    let is_normal = vim_mode == VimMode::Normal;
+
+                    if key.code == KeyCode::Tab && is_normal {
+                        let _ = nv.save_current();
+                        nv.focus = NotesFocus::Explorer;
+                    } else if key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('s')
+                    {
+                        let _ = nv.save_current();
+                        self.set_status("Saved".to_string());
+                    } else if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) && is_normal {
+                        let _ = nv.save_current();
+                        self.close_notes();
+                    } else {
+                        let before = nv.editor.lines_joined();
+                        nv.editor.input(key.into());
+                        let after = nv.editor.lines_joined();
+                        if before != after {
+                            nv.modified = true;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    fn restart_selected_task(&mut self) -> Result<()> {
+        if self.tasks.is_empty() {
+            return Ok(());
+        }
+        let task_id = match self.selected_task() {
+            Some(task) if task.meta.has_repos() || task.meta.is_multi_repo() => task.meta.task_id(),
+            Some(task) => {
+                self.set_status(format!(
+                    "Task {} has no repos configured",
+                    task.meta.task_id()
+                ));
+                return Ok(());
+            }
+            None => return Ok(()),
+        };
+        let task_idx = match self
+            .tasks
+            .iter()
+            .position(|task| task.meta.task_id() == task_id)
+        {
+            Some(idx) => idx,
+            None => return Ok(()),
+        };
+        let launch_error = supervisor::ensure_task_tmux(&self.config, &self.tasks[task_idx])
+            .and_then(|_| {
+                supervisor::launch_next_step(&self.config, &mut self.tasks[task_idx]).map(|_| ())
+            })
+            .err();
+        match launch_error {
+            None => self.set_status(format!("Restarted engineer for {}", task_id)),
+            Some(e) => self.set_status(format!("Restart failed: {}", e)),
+        }
+        self.refresh_tasks_and_select(&task_id);
+        Ok(())
+    }
+
+    fn handle_directory_picker_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                self.should_quit = true;
+                return Ok(false);
+            }
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.remember_repo_picker_position();
+                    self.dir_picker = None;
+                    self.view = View::TaskList;
+                    self.set_status("Directory selection cancelled".to_string());
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if let Some(picker) = &mut self.dir_picker {
+                        let total = picker.total_items();
+                        if total > 0 {
+                            picker.selected_index = (picker.selected_index + 1) % total;
+                        }
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    if let Some(picker) = &mut self.dir_picker {
+                        let total = picker.total_items();
+                        if total > 0 {
+                            picker.selected_index = if picker.selected_index == 0 {
+                                total - 1
+                            } else {
+                                picker.selected_index - 1
+                            };
+                        }
+                    }
+                }
+                KeyCode::Char('l') | KeyCode::Enter => {
+                    // In RepoSelect mode: Enter on a git repo or quick pick selects it directly
+                    let should_select = self
+                        .dir_picker
+                        .as_ref()
+                        .map(|p| {
+                            p.is_repo_select_mode()
+                                && (p.is_quick_pick_selected()
+                                    || p.selected_entry_kind() == Some(DirKind::GitRepo))
+                        })
+                        .unwrap_or(false);
+
+                    if should_select {
+                        self.select_repo_from_picker()?;
+                    } else if let Some(picker) = &mut self.dir_picker {
+                        picker.enter_selected();
+                    }
+                }
+                KeyCode::Char('h') | KeyCode::Backspace => {
+                    // Go up one directory
+                    if let Some(picker) = &mut self.dir_picker {
+                        picker.go_up();
+                    }
+                }
+                KeyCode::Char('c') => {
+                    if self
+                        .dir_picker
+                        .as_ref()
+                        .is_some_and(|p| p.is_repo_select_mode())
+                    {
+                        self.clone_url_editor = Self::create_plain_editor();
+                        self.clone_error = None;
+                        self.view = View::CloneRepo;
+                    }
+                }
+                KeyCode::Char('s') => {
+                    let origin = self.dir_picker.as_ref().map(|p| p.origin);
+                    match origin {
+                        Some(DirPickerOrigin::RepoSelect) => {
+                            // Select the highlighted entry as a repo or multi-repo parent
+                            self.select_repo_from_picker()?;
+                        }
+                        Some(DirPickerOrigin::NewTask) => {
+                            // Select current directory as repos_dir (fallback mode)
+                            if let Some(picker) = self.dir_picker.take() {
+                                let selected_dir = picker.current_dir.clone();
+
+                                let mut config_file =
+                                    agman::config::load_config_file(&self.config.base_dir);
+                                config_file.repos_dir =
+                                    Some(selected_dir.to_string_lossy().to_string());
+                                if let Err(e) = agman::config::save_config_file(
+                                    &self.config.base_dir,
+                                    &config_file,
+                                ) {
+                                    self.set_status(format!("Failed to save config: {}", e));
+                                    self.view = View::TaskList;
+                                    return Ok(false);
+                                }
+
+                                self.config.repos_dir = selected_dir;
+                                tracing::info!(repos_dir = %self.config.repos_dir.display(), "repos_dir updated via directory picker");
+
+                                self.start_wizard()?;
+                            }
+                        }
+                        None => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_clone_repo_event(&mut self, event: Event) -> Result<bool> {
+        if let Event::Key(key) = event {
+            match key.code {
+                KeyCode::Esc => {
+                    self.clone_error = None;
+                    self.view = View::DirectoryPicker;
+                }
+                KeyCode::Enter if self.clone_in_progress => {}
+                KeyCode::Enter => {
+                    let url = self.clone_url_editor.lines().join("").trim().to_string();
+                    if url.is_empty() {
+                        self.clone_error = Some("Git URL cannot be empty".to_string());
+                        return Ok(false);
                     }
+                    self.start_clone(url);
+                }
+                _ => {
+                    let input: Input = key.into();
+                    self.clone_url_editor.input(input);
                 }
-                return Ok(false);
             }
+        }
+        Ok(false)
+    }
 
-            // Main key handling based on focus
-            match nv.focus {
-                NotesFocus::Explorer => match key.code {
-                    KeyCode::Char('j') | KeyCode::Down => {
-                        if !nv.entries.is_empty() && nv.selected_index < nv.entries.len() - 1 {
-                            nv.selected_index += 1;
-                        }
+    /// Clone `url` on the worker; `apply_clone_events` reports progress in
+    /// the status line and opens the wizard once the clone lands.
+    fn start_clone(&mut self, url: String) {
+        self.log_output(format!("Cloning {}...", url));
+        self.set_status(format!("Cloning {url}..."));
+        self.clone_error = None;
+        self.clone_in_progress = true;
+        let tx = self.clone_tx.clone();
+        let config = self.config.clone();
+        self.rt.spawn(async move {
+            let progress_tx = tx.clone();
+            let clone_url = url.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                use_cases::clone_repo_into_repos_dir(&config, &clone_url, |line| {
+                    let _ = progress_tx.send(CloneEvent::Progress(line.to_string()));
+                })
+            })
+            .await
+            .unwrap_or_else(|e| Err(anyhow::anyhow!("{e}")));
+            if let Err(e) = &result {
+                tracing::error!(url = %url, error = %e, "failed to clone repo");
+            }
+            let _ = tx.send(CloneEvent::Done(result.map_err(|e| e.to_string())));
+        });
+    }
+
+    fn apply_clone_events(&mut self) -> Result<()> {
+        while let Ok(event) = self.clone_rx.try_recv() {
+            match event {
+                CloneEvent::Progress(line) => self.set_status(line),
+                CloneEvent::Done(Ok((name, path))) => {
+                    self.clone_in_progress = false;
+                    self.log_output(format!("  Cloned into {}", path.display()));
+                    self.set_status(format!("Cloned {name}"));
+                    // Only open the wizard if the user is still waiting on it.
+                    if self.view == View::CloneRepo {
+                        self.dir_picker = None;
+                        self.create_wizard_from_picker(name, path, false)?;
                     }
-                    KeyCode::Char('k') | KeyCode::Up => {
-                        if nv.selected_index > 0 {
-                            nv.selected_index -= 1;
-                        }
+                }
+                CloneEvent::Done(Err(e)) => {
+                    self.clone_in_progress = false;
+                    self.log_output(format!("  Error: {}", e));
+                    self.set_status(format!("Clone failed: {e}"));
+                    self.clone_error = Some(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a repo selection from the directory picker (RepoSelect mode).
+    fn select_repo_from_picker(&mut self) -> Result<()> {
+        self.remember_repo_picker_position();
+        let (entry_kind, entry_path, entry_name, origin, is_quick) = match &self.dir_picker {
+            Some(picker) => {
+                let kind = picker.selected_entry_kind().unwrap_or(DirKind::Plain);
+                let path = match picker.selected_path() {
+                    Some(p) => p,
+                    None => return Ok(()),
+                };
+                let name = picker.selected_name().unwrap_or_default();
+                let is_quick = picker.is_quick_pick_selected();
+                (kind, path, name, picker.origin, is_quick)
+            }
+            None => return Ok(()),
+        };
+
+        if is_quick {
+            tracing::info!(repo = %entry_name, "selected quick-pick repo from picker");
+        }
+
+        match origin {
+            DirPickerOrigin::RepoSelect => match entry_kind {
+                DirKind::GitRepo => {
+                    self.dir_picker = None;
+                    tracing::info!(repo = %entry_name, path = %entry_path.display(), "selected git repo from picker");
+                    self.create_wizard_from_picker(entry_name, entry_path, false)?;
+                }
+                DirKind::MultiRepoParent => {
+                    self.dir_picker = None;
+                    tracing::info!(parent = %entry_name, path = %entry_path.display(), "selected multi-repo parent from picker");
+                    self.create_wizard_from_picker(entry_name, entry_path, true)?;
+                }
+                DirKind::Plain => {
+                    if let Some(picker) = &mut self.dir_picker {
+                        picker.enter_selected();
                     }
-                    KeyCode::Char('l') | KeyCode::Enter => {
-                        if let Some(entry) = nv.entries.get(nv.selected_index).cloned() {
-                            if entry.is_dir {
-                                let new_dir = nv.current_dir.join(&entry.file_name);
-                                nv.current_dir = new_dir;
-                                nv.selected_index = 0;
-                                let _ = nv.refresh();
-                            } else {
-                                let path = nv.current_dir.join(&entry.file_name);
-                                let _ = nv.save_current();
-                                if let Err(e) = nv.open_file(&path) {
-                                    self.set_status(format!("Open failed: {e}"));
-                                } else {
-                                    let nv = self.notes_view.as_mut().unwrap();
-                                    nv.focus = NotesFocus::Editor;
-                                }
-                            }
+                }
+            },
+            _ => {
+                // Fallback origins shouldn't reach here
+                if let Some(picker) = &mut self.dir_picker {
+                    picker.enter_selected();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Inbox polling (Chief of Staff & PM message delivery)
+    // -----------------------------------------------------------------------
+
+    /// Targets whose consecutive-skip count has reached `STALL_THRESHOLD`.
+    /// Surfaced in the TUI as `⚠ stalled` indicators.
+    pub fn stalled_targets(&self) -> Vec<&str> {
+        use_cases::stalled_targets_from_counts(&self.stuck_skip_counts, STALL_THRESHOLD)
+    }
+
+    fn start_inbox_poll(&mut self) {
+        if self.inbox_poll_active {
+            return;
+        }
+
+        // Enumerate delivery targets from disk so polling does not depend on
+        // whichever TUI view the user has visited.
+        let targets: Vec<_> =
+            use_cases::collect_inbox_poll_targets(&self.config, Tmux::session_exists)
+                .into_iter()
+                .map(|t| (t.name, t.inbox_path, t.seq_path, t.session_name, t.window))
+                .collect();
+
+        if targets.is_empty() {
+            return;
+        }
+
+        self.inbox_poll_active = true;
+        let tx = self.inbox_poll_tx.clone();
+        let mut stuck_skip_counts = self.stuck_skip_counts.clone();
+        let mut first_ready_at = self.first_ready_at.clone();
+        let mut verify_fail_counts = self.verify_fail_counts.clone();
+
+        self.rt.spawn(async move {
+            let output = tokio::task::spawn_blocking(move || {
+                const MAX_RETRIES: usize = 3;
+                const RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+                let mut results = Vec::new();
+                for (target, inbox_path, seq_path, session_name, window) in targets {
+                    let window_ref = window.as_deref();
+
+                    let undelivered = match inbox::read_undelivered(&inbox_path, &seq_path) {
+                        Ok(msgs) => msgs,
+                        Err(e) => {
+                            results.push(InboxPollResult {
+                                target,
+                                delivered: 0,
+                                errors: vec![format!("read error: {e}")],
+                            });
+                            continue;
                         }
+                    };
+
+                    if undelivered.is_empty() {
+                        stuck_skip_counts.remove(&target);
+                        verify_fail_counts.remove(&target);
+                        results.push(InboxPollResult {
+                            target,
+                            delivered: 0,
+                            errors: vec![],
+                        });
+                        continue;
                     }
-                    KeyCode::Char('h') | KeyCode::Backspace => {
-                        if nv.current_dir != nv.root_dir {
-                            if let Some(parent) = nv.current_dir.parent() {
-                                let child_name = nv
-                                    .current_dir
-                                    .file_name()
-                                    .map(|n| n.to_string_lossy().to_string());
-                                nv.current_dir = parent.to_path_buf();
-                                let _ = nv.refresh();
-                                nv.selected_index = child_name
-                                    .and_then(|name| {
-                                        nv.entries.iter().position(|e| e.file_name == name)
-                                    })
-                                    .unwrap_or(0);
-                            }
+
+                    let mut delivered = 0;
+                    let mut errors = Vec::new();
+
+                    // Readiness gate (process-only check; no UI scraping) +
+                    // 3-second cold-start buffer. Runs BEFORE the already-pasted
+                    // rescue so a paste that landed during cold-start mounting
+                    // still gets a chance to settle before we send Enter.
+                    match Tmux::is_session_ready_in(&session_name, window_ref) {
+                        Ok((false, cmd)) => {
+                            tracing::info!(
+                                target_name = &target,
+                                session = &session_name,
+                                cmd = %cmd,
+                                "session not ready (foreground: {cmd}), skipping delivery this cycle"
+                            );
+
+                            // Re-arm the cold-start buffer: when claude restarts
+                            // we want a fresh 3s window once it next flips ready.
+                            first_ready_at.remove(&target);
+
+                            let counter = stuck_skip_counts.entry(target.clone()).or_insert(0);
+                            *counter += 1;
+
+                            results.push(InboxPollResult {
+                                target,
+                                delivered: 0,
+                                errors: vec![],
+                            });
+                            continue;
                         }
-                    }
-                    KeyCode::Char('a') => {
-                        nv.create_input = Some((TextArea::default(), false));
-                    }
-                    KeyCode::Char('A') => {
-                        nv.create_input = Some((TextArea::default(), true));
-                    }
-                    KeyCode::Char('d') => {
-                        if !nv.entries.is_empty() {
-                            nv.confirm_delete = true;
+                        Err(e) => {
+                            tracing::debug!(
+                                target_name = &target,
+                                session = &session_name,
+                                error = %e,
+                                "session readiness check failed, skipping"
+                            );
+                            results.push(InboxPollResult {
+                                target,
+                                delivered: 0,
+                                errors: vec![],
+                            });
+                            continue;
+                        }
+                        Ok((true, _)) => {
+                            let ready_since = *first_ready_at
+                                .entry(target.clone())
+                                .or_insert_with(Instant::now);
+                            if ready_since.elapsed() < Duration::from_secs(5) {
+                                results.push(InboxPollResult {
+                                    target,
+                                    delivered: 0,
+                                    errors: vec![],
+                                });
+                                continue;
+                            }
+                            stuck_skip_counts.remove(&target);
                         }
                     }
-                    KeyCode::Char('r') => {
-                        if let Some(entry) = nv.entries.get(nv.selected_index) {
-                            let mut ta = TextArea::default();
-                            ta.insert_str(&entry.name);
-                            nv.rename_input = Some(ta);
+
+                    let visibility = match Tmux::is_window_visible_to_any_client(
+                        &session_name,
+                        window_ref,
+                    ) {
+                        Ok(visible) => Some(visible),
+                        Err(e) => {
+                            tracing::debug!(
+                                target_name = &target,
+                                session = &session_name,
+                                error = %e,
+                                "target visibility check failed; treating fresh messages as visible"
+                            );
+                            None
                         }
+                    };
+
+                    // Decision 5: already-pasted rescue (after readiness+buffer)
+                    let first_msg = &undelivered[0];
+                    if use_cases::should_defer_visible_fresh_inbox_message(
+                        first_msg,
+                        chrono::Utc::now(),
+                        visibility,
+                    ) {
+                        tracing::debug!(
+                            target_name = &target,
+                            session = &session_name,
+                            seq = first_msg.seq,
+                            "target window visible and inbox message is fresh; deferring delivery"
+                        );
+                        results.push(InboxPollResult {
+                            target,
+                            delivered: 0,
+                            errors: vec![],
+                        });
+                        continue;
                     }
-                    KeyCode::Tab => {
-                        if nv.open_file.is_some() {
-                            nv.focus = NotesFocus::Editor;
+
+                    let first_snippet = format!("[msg:{}:{}]", first_msg.from, first_msg.seq);
+                    let already_pasted = Tmux::capture_pane_window(&session_name, window_ref)
+                        .map(|content| use_cases::snippet_in_capture(&content, &first_snippet))
+                        .unwrap_or(false);
+
+                    if already_pasted {
+                        tracing::info!(
+                            target_name = &target,
+                            session = &session_name,
+                            seq = first_msg.seq,
+                            "already-pasted rescue: message visible in pane, sending Enter"
+                        );
+                        let _ = Tmux::send_enter_to(&session_name, window_ref);
+                        std::thread::sleep(std::time::Duration::from_millis(200));
+                        let verified = Tmux::capture_pane_window(&session_name, window_ref)
+                            .map(|content| use_cases::snippet_in_capture(&content, &first_snippet))
+                            .unwrap_or(false);
+                        if verified {
+                            if let Err(e) = inbox::mark_delivered(&seq_path, first_msg.seq) {
+                                errors.push(format!("mark_delivered error: {e}"));
+                            } else {
+                                delivered += 1;
+                                verify_fail_counts.remove(&target);
+                            }
+                        } else if use_cases::record_inbox_verify_failure(
+                            &mut verify_fail_counts,
+                            &target,
+                            first_msg.seq,
+                            VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
+                        ) {
+                            tracing::warn!(
+                                target_name = &target,
+                                session = &session_name,
+                                seq = first_msg.seq,
+                                threshold = VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
+                                "snippet verification failed for {} consecutive cycles; \
+                                 force-advancing inbox cursor past seq {} (message remains \
+                                 in inbox.jsonl; it was pasted to the pane but could not \
+                                 be verified)",
+                                VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
+                                first_msg.seq
+                            );
+                            if let Err(e) = inbox::mark_delivered(&seq_path, first_msg.seq) {
+                                errors.push(format!("mark_delivered error: {e}"));
+                            } else {
+                                verify_fail_counts.remove(&target);
+                            }
                         }
+                        // Even if rescue handled one message, skip to next target this cycle
+                        // (subsequent messages will be delivered in future poll cycles)
+                        stuck_skip_counts.remove(&target);
+                        results.push(InboxPollResult {
+                            target,
+                            delivered,
+                            errors,
+                        });
+                        continue;
                     }
-                    KeyCode::Char('J') => {
-                        if !nv.entries.is_empty() && nv.selected_index < nv.entries.len() - 1 {
-                            let entry_name = nv.entries[nv.selected_index].file_name.clone();
-                            let dir = nv.current_dir.clone();
-                            match use_cases::move_note(
-                                &dir,
-                                &entry_name,
-                                use_cases::MoveDirection::Down,
-                            ) {
-                                Ok(new_idx) => {
-                                    let _ = nv.refresh();
-                                    nv.selected_index = new_idx;
+
+                    'msg_loop: for msg in &undelivered {
+                        if use_cases::should_defer_visible_fresh_inbox_message(
+                            msg,
+                            chrono::Utc::now(),
+                            visibility,
+                        ) {
+                            tracing::debug!(
+                                target_name = &target,
+                                session = &session_name,
+                                seq = msg.seq,
+                                "target window visible and inbox message is fresh; deferring delivery"
+                            );
+                            break 'msg_loop;
+                        }
+
+                        let formatted_snippet = format!("[msg:{}:{}]", msg.from, msg.seq);
+                        // True once the message text is known to be in the
+                        // pane this cycle: inject_message_to succeeded, or the
+                        // snippet was observed in a capture. After that, later
+                        // attempts must only nudge Enter — pasting again would
+                        // deliver duplicate copies (verification can't see a
+                        // collapsed paste or text scrolled off the alternate
+                        // screen, so its absence proves nothing).
+                        let mut in_pane_this_cycle = false;
+
+                        for attempt in 0..MAX_RETRIES {
+                            let already_pasted = Tmux::capture_pane_window(&session_name, window_ref)
+                                .map(|content| use_cases::snippet_in_capture(&content, &formatted_snippet))
+                                .unwrap_or(false);
+
+                            if already_pasted || in_pane_this_cycle {
+                                in_pane_this_cycle = true;
+                                tracing::debug!(
+                                    target = &target, seq = msg.seq, attempt = attempt,
+                                    "message already pasted (visible in pane or earlier this cycle), retrying Enter only"
+                                );
+                                if let Err(e) = Tmux::send_enter_to(&session_name, window_ref) {
+                                    tracing::warn!(
+                                        target = &target, seq = msg.seq, error = %e,
+                                        "failed to send Enter retry"
+                                    );
+                                    std::thread::sleep(RETRY_DELAY);
+                                    continue;
                                 }
-                                Err(e) => {
-                                    self.set_status(format!("Move failed: {e}"));
+                            } else {
+                                match Tmux::inject_message_to(&session_name, window_ref, &msg.from, &msg.message, msg.seq) {
+                                    Ok(()) => in_pane_this_cycle = true,
+                                    Err(e) => {
+                                        tracing::warn!(
+                                            target = &target, seq = msg.seq, attempt = attempt, error = %e,
+                                            "inject_message failed"
+                                        );
+                                        std::thread::sleep(RETRY_DELAY);
+                                        continue;
+                                    }
                                 }
                             }
-                        }
-                    }
-                    KeyCode::Char('K') => {
-                        if nv.selected_index > 0 {
-                            let entry_name = nv.entries[nv.selected_index].file_name.clone();
-                            let dir = nv.current_dir.clone();
-                            match use_cases::move_note(
-                                &dir,
-                                &entry_name,
-                                use_cases::MoveDirection::Up,
-                            ) {
-                                Ok(new_idx) => {
-                                    let _ = nv.refresh();
-                                    nv.selected_index = new_idx;
-                                }
-                                Err(e) => {
-                                    self.set_status(format!("Move failed: {e}"));
+
+                            std::thread::sleep(std::time::Duration::from_millis(200));
+                            let verified = Tmux::capture_pane_window(&session_name, window_ref)
+                                .map(|content| use_cases::snippet_in_capture(&content, &formatted_snippet))
+                                .unwrap_or(false);
+
+                            if verified {
+                                if let Err(e) = inbox::mark_delivered(&seq_path, msg.seq) {
+                                    errors.push(format!("mark_delivered error: {e}"));
+                                } else {
+                                    delivered += 1;
+                                    verify_fail_counts.remove(&target);
                                 }
+                                continue 'msg_loop;
                             }
+
+                            tracing::debug!(
+                                target = &target, seq = msg.seq, attempt = attempt,
+                                "delivery verification failed, retrying"
+                            );
+                            std::thread::sleep(RETRY_DELAY);
                         }
-                    }
-                    KeyCode::Char('x') => {
-                        if let Some(entry) = nv.entries.get(nv.selected_index) {
-                            let cut = (nv.current_dir.clone(), entry.file_name.clone());
-                            let status_msg = format!("Cut: {}", entry.name);
-                            tracing::info!(dir = %cut.0.display(), file = %cut.1, "cut note");
-                            nv.cut_entry = Some(cut);
-                            self.set_status(status_msg);
-                        }
-                    }
-                    KeyCode::Char('p') => {
-                        if let Some((ref src_dir, ref file_name)) = nv.cut_entry.clone() {
-                            let dest_dir = nv.current_dir.clone();
-                            match use_cases::paste_note(src_dir, &dest_dir, file_name) {
-                                Ok(()) => {
-                                    let nv = self.notes_view.as_mut().unwrap();
-                                    nv.cut_entry = None;
-                                    let _ = nv.refresh();
-                                    self.set_status(format!("Pasted: {}", file_name));
-                                }
-                                Err(e) => {
-                                    self.set_status(format!("Paste failed: {e}"));
-                                }
+
+                        // Bound the cross-cycle loop: without a terminal state
+                        // an unverifiable head message would be re-attempted
+                        // every poll cycle forever, blocking all later seqs.
+                        // Only cycles where the message reached the pane count
+                        // toward the threshold — pure inject/tmux failures must
+                        // never force-advance past an undelivered message.
+                        let force_advance = in_pane_this_cycle
+                            && use_cases::record_inbox_verify_failure(
+                                &mut verify_fail_counts,
+                                &target,
+                                msg.seq,
+                                VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
+                            );
+                        if force_advance {
+                            tracing::warn!(
+                                target_name = &target,
+                                session = &session_name,
+                                seq = msg.seq,
+                                threshold = VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
+                                "snippet verification failed for {} consecutive cycles; \
+                                 force-advancing inbox cursor past seq {} (message remains \
+                                 in inbox.jsonl; it was pasted to the pane but could not \
+                                 be verified)",
+                                VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
+                                msg.seq
+                            );
+                            if let Err(e) = inbox::mark_delivered(&seq_path, msg.seq) {
+                                errors.push(format!("mark_delivered error: {e}"));
+                            } else {
+                                verify_fail_counts.remove(&target);
                             }
-                        }
-                    }
-                    KeyCode::Esc => {
-                        if nv.cut_entry.is_some() {
-                            nv.cut_entry = None;
-                            self.set_status("Cut cancelled".to_string());
                         } else {
-                            let _ = nv.save_current();
-                            self.close_notes();
-                        }
-                    }
-                    KeyCode::Char('q') => {
-                        let _ = nv.save_current();
-                        self.close_notes();
-                    }
-                    _ => {}
-                },
-                NotesFocus::Editor => {
-                    let vim_mode = nv.editor.mode();
-                    let is_normal = vim_mode == VimMode::Normal;
-
-                    if key.code == KeyCode::Tab && is_normal {
-                        let _ = nv.save_current();
-                        nv.focus = NotesFocus::Explorer;
-                    } else if key.modifiers.contains(KeyModifiers::CONTROL)
-                        && key.code == KeyCode::Char('s')
-                    {
-                        let _ = nv.save_current();
-                        self.set_status("Saved".to_string());
-                    } else if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) && is_normal {
-                        let _ = nv.save_current();
-                        self.close_notes();
-                    } else {
-                        let before = nv.editor.lines_joined();
-                        nv.editor.input(key.into());
-                        let after = nv.editor.lines_joined();
-                        if before != after {
-                            nv.modified = true;
+                            // Skipped on the force-advance cycle: the WARN
+                            // above already tells the whole story, and this
+                            // error would otherwise be logged after it.
+                            errors.push(format!(
+                                "delivery failed after {} attempts for seq {}",
+                                MAX_RETRIES, msg.seq
+                            ));
                         }
+                        break;
                     }
+                    results.push(InboxPollResult {
+                        target,
+                        delivered,
+                        errors,
+                    });
                 }
-            }
-        }
-        Ok(false)
+                InboxPollOutput {
+                    results,
+                    stuck_skip_counts,
+                    first_ready_at,
+                    verify_fail_counts,
+                }
+            })
+            .await
+            .unwrap_or_else(|_| InboxPollOutput {
+                results: Vec::new(),
+                stuck_skip_counts: std::collections::HashMap::new(),
+                first_ready_at: std::collections::HashMap::new(),
+                verify_fail_counts: std::collections::HashMap::new(),
+            });
+            let _ = tx.send(output);
+        });
     }
 
-    fn restart_selected_task(&mut self) -> Result<()> {
-        if self.tasks.is_empty() {
-            return Ok(());
-        }
-        let task_id = match self.selected_task() {
-            Some(task) if task.meta.has_repos() || task.meta.is_multi_repo() => task.meta.task_id(),
-            Some(task) => {
-                self.set_status(format!(
-                    "Task {} has no repos configured",
-                    task.meta.task_id()
-                ));
-                return Ok(());
-            }
-            None => return Ok(()),
+    fn apply_inbox_poll_results(&mut self) {
+        let output = match self.inbox_poll_rx.try_recv() {
+            Ok(r) => r,
+            Err(_) => return,
         };
-        let task_idx = match self
-            .tasks
+        self.inbox_poll_active = false;
+
+        self.stuck_skip_counts = output.stuck_skip_counts;
+        self.first_ready_at = output.first_ready_at;
+        self.verify_fail_counts = output.verify_fail_counts;
+
+        // One-shot warn the first time a target crosses STALL_THRESHOLD; clear
+        // the warned marker when it recovers, so a later stall episode warns again.
+        let currently_stalled: std::collections::HashSet<String> = self
+            .stuck_skip_counts
             .iter()
-            .position(|task| task.meta.task_id() == task_id)
-        {
-            Some(idx) => idx,
-            None => return Ok(()),
-        };
-        let launch_error = supervisor::ensure_task_tmux(&self.config, &self.tasks[task_idx])
-            .and_then(|_| {
-                supervisor::launch_next_step(&self.config, &mut self.tasks[task_idx]).map(|_| ())
-            })
-            .err();
-        match launch_error {
-            None => self.set_status(format!("Restarted engineer for {}", task_id)),
-            Some(e) => self.set_status(format!("Restart failed: {}", e)),
+            .filter(|(_, n)| **n >= STALL_THRESHOLD)
+            .map(|(t, _)| t.clone())
+            .collect();
+        for target in &currently_stalled {
+            if self.stall_warned.insert(target.clone()) {
+                tracing::warn!(
+                    target = %target,
+                    threshold = STALL_THRESHOLD,
+                    "message delivery stalled: session not ready for {} consecutive cycles",
+                    STALL_THRESHOLD
+                );
+            }
+        }
+        self.stall_warned.retain(|t| currently_stalled.contains(t));
+
+        for result in &output.results {
+            if result.delivered > 0 {
+                tracing::info!(
+                    target = %result.target,
+                    delivered = result.delivered,
+                    "delivered inbox messages"
+                );
+                self.stuck_skip_counts.remove(&result.target);
+            }
+            for err in &result.errors {
+                tracing::warn!(target = %result.target, error = %err, "inbox poll error");
+            }
         }
-        self.refresh_tasks_and_select(&task_id);
-        Ok(())
     }
 
-    fn handle_directory_picker_event(&mut self, event: Event) -> Result<bool> {
-        if let Event::Key(key) = event {
-            if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
-                self.should_quit = true;
-                return Ok(false);
+    fn apply_respawn_results(&mut self) {
+        let result = match self.respawn_rx.try_recv() {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        let target = self.respawn_in_progress.take().unwrap_or_default();
+        match result {
+            Ok(msg) if msg.starts_with("cos+pms:") => {
+                let pm_count = msg.strip_prefix("cos+pms:").unwrap_or("0");
+                tracing::info!(target = %target, pm_count = %pm_count, "cos + all pms respawned successfully");
+                self.set_status(format!("respawned chief-of-staff + {pm_count} pms"));
+            }
+            Ok(_) => {
+                tracing::info!(target = %target, "agent respawned successfully");
+                self.set_status(format!("respawned {target}"));
+            }
+            Err(e) => {
+                tracing::error!(target = %target, error = %e, "failed to respawn agent");
+                self.set_status(format!("respawn failed: {e}"));
             }
+        }
+    }
 
-            match key.code {
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    self.dir_picker = None;
-                    self.view = View::TaskList;
-                    self.set_status("Directory selection cancelled".to_string());
-                }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    if let Some(picker) = &mut self.dir_picker {
-                        let total = picker.total_items();
-                        if total > 0 {
-                            picker.selected_index = (picker.selected_index + 1) % total;
-                        }
-                    }
-                }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    if let Some(picker) = &mut self.dir_picker {
-                        let total = picker.total_items();
-                        if total > 0 {
-                            picker.selected_index = if picker.selected_index == 0 {
-                                total - 1
-                            } else {
-                                picker.selected_index - 1
-                            };
-                        }
-                    }
+    /// Drain wizard task creations finished on the worker: launch the
+    /// engineer and close the wizard, or show the error in the wizard.
+    fn apply_issue_list_results(&mut self) {
+        while let Ok((repo_path, result)) = self.issue_list_rx.try_recv() {
+            // Drop results for a wizard that was closed or reopened on another repo.
+            let Some(wizard) = self
+                .wizard
+                .as_mut()
+                .filter(|w| w.loading_issues && w.selected_repo_path == repo_path)
+            else {
+                continue;
+            };
+            wizard.loading_issues = false;
+            match result {
+                Ok(issues) if issues.is_empty() => {
+                    wizard.error_message = Some("No open issues".to_string());
                 }
-                KeyCode::Char('l') | KeyCode::Enter => {
-                    // In RepoSelect mode: Enter on a git repo or favourite selects it directly
-                    let should_select = self
-                        .dir_picker
-                        .as_ref()
-                        .map(|p| {
-                            p.is_repo_select_mode()
-                                && (p.is_favorite_selected()
-                                    || p.selected_entry_kind() == Some(DirKind::GitRepo))
-                        })
-                        .unwrap_or(false);
-
-                    if should_select {
-                        self.select_repo_from_picker()?;
-                    } else if let Some(picker) = &mut self.dir_picker {
-                        picker.enter_selected();
-                    }
+                Ok(issues) => {
+                    wizard.branch_source = BranchSource::NewBranch;
+                    wizard.issue_picker = Some(issues);
+                    wizard.selected_issue_index = 0;
                 }
-                KeyCode::Char('h') | KeyCode::Backspace => {
-                    // Go up one directory
-                    if let Some(picker) = &mut self.dir_picker {
-                        picker.go_up();
-                    }
+                Err(e) => {
+                    tracing::error!(repo = %wizard.selected_repo, error = %e, "failed to list GitHub issues");
+                    wizard.error_message = Some(e);
                 }
-                KeyCode::Char('s') => {
-                    let origin = self.dir_picker.as_ref().map(|p| p.origin);
-                    match origin {
-                        Some(DirPickerOrigin::RepoSelect) => {
-                            // Select the highlighted entry as a repo or multi-repo parent
-                            self.select_repo_from_picker()?;
-                        }
-                        Some(DirPickerOrigin::NewTask) => {
-                            // Select current directory as repos_dir (fallback mode)
-                            if let Some(picker) = self.dir_picker.take() {
-                                let selected_dir = picker.current_dir.clone();
-
-                                let mut config_file =
-                                    agman::config::load_config_file(&self.config.base_dir);
-                                config_file.repos_dir =
-                                    Some(selected_dir.to_string_lossy().to_string());
-                                if let Err(e) = agman::config::save_config_file(
-                                    &self.config.base_dir,
-                                    &config_file,
-                                ) {
-                                    self.set_status(format!("Failed to save config: {}", e));
-                                    self.view = View::TaskList;
-                                    return Ok(false);
-                                }
-
-                                self.config.repos_dir = selected_dir;
-                                tracing::info!(repos_dir = %self.config.repos_dir.display(), "repos_dir updated via directory picker");
+            }
+        }
+    }
 
-                                self.start_wizard()?;
-                            }
-                        }
-                        None => {}
+    fn apply_task_create_results(&mut self) {
+        while let Ok(created) = self.task_create_rx.try_recv() {
+            let CreatedTask {
+                name,
+                branch_name,
+                auto_rebase,
+                result,
+            } = created;
+            if let Some(w) = &mut self.wizard {
+                w.creating = false;
+            }
+            let mut task = match result {
+                Ok(t) => t,
+                Err(e) => {
+                    tracing::error!(repo = %name, branch = %branch_name, error = %e, "failed to create task");
+                    self.log_output(format!("  Error: {}", e));
+                    match &mut self.wizard {
+                        Some(w) => w.error_message = Some(format!("Failed to create task: {}", e)),
+                        None => self.set_status(format!("Failed to create task: {}", e)),
                     }
+                    continue;
                 }
-                _ => {}
+            };
+
+            let task_id = task.meta.task_id();
+            if auto_rebase {
+                task.meta.auto_rebase = true;
+                if let Err(e) = task.save_meta() {
+                    tracing::warn!(task_id = %task_id, error = %e, "failed to save auto_rebase");
+                }
+            }
+            self.log_output("  Launching engineer via supervisor...".to_string());
+            if let Err(e) = supervisor::ensure_task_tmux(&self.config, &task)
+                .and_then(|_| supervisor::launch_next_step(&self.config, &mut task).map(|_| ()))
+            {
+                tracing::error!(repo = %name, branch = %branch_name, error = %e, "failed to launch task engineer");
+                self.log_output(format!("  Error: {}", e));
+                if let Some(w) = &mut self.wizard {
+                    w.error_message = Some(format!("Failed to launch engineer: {}", e));
+                }
+                continue;
+            }
+
+            // Success - close wizard and refresh
+            self.wizard = None;
+            if self.view == View::NewTaskWizard {
+                self.view = View::TaskList;
+            }
+            if self.current_project.is_some() {
+                self.refresh_tasks_for_project();
+            } else {
+                self.refresh_tasks_and_select(&task_id);
             }
+            self.set_status(format!("Created task: {}", task_id));
+            self.report_tmux_session_collisions(&task_id);
         }
-        Ok(false)
     }
 
-    /// Handle a repo selection from the directory picker (RepoSelect mode).
-    fn select_repo_from_picker(&mut self) -> Result<()> {
-        let (entry_kind, entry_path, entry_name, origin, is_fav) = match &self.dir_picker {
-            Some(picker) => {
-                let kind = picker.selected_entry_kind().unwrap_or(DirKind::Plain);
-                let path = match picker.selected_path() {
-                    Some(p) => p,
-                    None => return Ok(()),
-                };
-                let name = picker.selected_name().unwrap_or_default();
-                let is_fav = picker.is_favorite_selected();
-                (kind, path, name, picker.origin, is_fav)
-            }
-            None => return Ok(()),
+    /// Tell the user when one of the task's tmux sessions got a suffixed
+    /// name because another task already owned the derived one.
+    fn report_tmux_session_collisions(&mut self, task_id: &str) {
+        let Some(task) = self.all_tasks.iter().find(|t| t.meta.task_id() == task_id) else {
+            return;
         };
-
-        if is_fav {
-            tracing::info!(repo = %entry_name, "selected favourite repo from picker");
+        let warnings = use_cases::tmux_session_collision_warnings(task);
+        for warning in warnings {
+            self.log_output(format!("  Warning: {warning}"));
+            self.set_status(format!("Warning: {warning}"));
         }
+    }
 
-        match origin {
-            DirPickerOrigin::RepoSelect => match entry_kind {
-                DirKind::GitRepo => {
-                    self.dir_picker = None;
-                    tracing::info!(repo = %entry_name, path = %entry_path.display(), "selected git repo from picker");
-                    self.create_wizard_from_picker(entry_name, entry_path, false)?;
+    /// Drain repo additions finished on the worker.
+    fn apply_add_repo_results(&mut self) {
+        while let Ok((task_id, repo, result)) = self.add_repo_rx.try_recv() {
+            match result {
+                Ok(entry) => {
+                    let windows = self.config.session_windows(&entry.repo_name);
+                    if let Err(e) =
+                        Tmux::ensure_session(&entry.tmux_session, &entry.worktree_path, &windows)
+                    {
+                        tracing::warn!(session = %entry.tmux_session, error = %e, "failed to create session for added repo");
+                    }
+                    self.set_status(format!("Added {repo} to {task_id}"));
+                    self.refresh_tasks_and_select(&task_id);
+                    self.report_tmux_session_collisions(&task_id);
                 }
-                DirKind::MultiRepoParent => {
-                    self.dir_picker = None;
-                    tracing::info!(parent = %entry_name, path = %entry_path.display(), "selected multi-repo parent from picker");
-                    self.create_wizard_from_picker(entry_name, entry_path, true)?;
+                Err(e) => {
+                    tracing::error!(task_id = %task_id, repo = %repo, error = %e, "failed to add repo to task");
+                    self.set_status(format!("Failed to add {repo}: {e}"));
                 }
-                DirKind::Plain => {
-                    if let Some(picker) = &mut self.dir_picker {
-                        picker.enter_selected();
-                    }
+            }
+        }
+    }
+
+    /// Drain any completed background archive jobs. The worker reports the
+    /// task id and either `Ok(saved)` or an error string. On success we clear
+    /// the in-progress entry so subsequent refreshes treat the task as
+    /// archived; on failure we still clear the entry (so the task can re-
+    /// appear from disk if the worker only partially completed), surface the
+    /// error to the status line, and log it.
+    fn apply_archive_results(&mut self) {
+        loop {
+            let (task_id, result) = match self.archive_rx.try_recv() {
+                Ok(r) => r,
+                Err(_) => return,
+            };
+            self.archive_in_progress.remove(&task_id);
+            match result {
+                Ok(saved) => {
+                    let label = if saved {
+                        "Archived & saved"
+                    } else {
+                        "Archived"
+                    };
+                    tracing::info!(task_id = %task_id, saved, "TUI: archive task completed");
+                    self.log_output(format!("  Archived task {task_id}"));
+                    self.set_status(format!("{label}: {task_id}"));
                 }
-            },
-            _ => {
-                // Fallback origins shouldn't reach here
-                if let Some(picker) = &mut self.dir_picker {
-                    picker.enter_selected();
+                Err(e) => {
+                    tracing::error!(task_id = %task_id, error = %e, "TUI: archive task failed");
+                    self.log_output(format!("  Archive failed for {task_id}: {e}"));
+                    self.set_status(format!("archive failed: {task_id}: {e}"));
                 }
             }
         }
-        Ok(())
     }
 
-    // -----------------------------------------------------------------------
-    // Inbox polling (Chief of Staff & PM message delivery)
-    // -----------------------------------------------------------------------
+    /// True while GitHub polling is paused after a rate limit.
+    pub fn gh_polling_backed_off(&self) -> bool {
+        self.pr_poll_backoff_until
+            .is_some_and(|until| Instant::now() < until)
+    }
 
-    /// Targets whose consecutive-skip count has reached `STALL_THRESHOLD`.
-    /// Surfaced in the TUI as `⚠ stalled` indicators.
-    pub fn stalled_targets(&self) -> Vec<&str> {
-        use_cases::stalled_targets_from_counts(&self.stuck_skip_counts, STALL_THRESHOLD)
+    /// Extend the GitHub poll backoff after a rate-limited response.
+    fn note_gh_rate_limited(&mut self) {
+        let backoff = use_cases::next_gh_backoff(self.pr_poll_backoff);
+        self.pr_poll_backoff = Some(backoff);
+        self.pr_poll_backoff_until = Some(Instant::now() + backoff);
+        tracing::warn!(
+            backoff_secs = backoff.as_secs(),
+            "GitHub rate-limited, backing off"
+        );
+        self.set_status("GitHub rate-limited, backing off".to_string());
     }
 
-    fn start_inbox_poll(&mut self) {
-        if self.inbox_poll_active {
+    fn clear_gh_backoff(&mut self) {
+        if self.pr_poll_backoff.take().is_some() {
+            tracing::info!("GitHub polling recovered from rate limit");
+        }
+        self.pr_poll_backoff_until = None;
+    }
+
+    /// Spawn a background task to poll GitHub notifications.
+    fn start_gh_notif_poll(&mut self) {
+        if self.gh_notif_poll_active {
             return;
         }
 
-        // Enumerate delivery targets from disk so polling does not depend on
-        // whichever TUI view the user has visited.
-        let targets: Vec<_> =
-            use_cases::collect_inbox_poll_targets(&self.config, Tmux::session_exists)
-                .into_iter()
-                .map(|t| (t.name, t.inbox_path, t.seq_path, t.session_name, t.window))
-                .collect();
+        self.gh_notif_poll_active = true;
+        let tx = self.gh_notif_tx.clone();
+
+        tracing::debug!("starting github notification poll");
+        self.rt.spawn(async move {
+            let result = tokio::task::spawn_blocking(use_cases::fetch_github_notifications)
+                .await
+                .unwrap_or_else(|_| use_cases::NotifPollResult {
+                    notifications: Vec::new(),
+                    rate_limited: false,
+                });
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Check for completed notification poll results (non-blocking) and apply.
+    fn apply_gh_notif_results(&mut self) {
+        let result = match self.gh_notif_rx.try_recv() {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        self.gh_notif_poll_active = false;
+
+        if result.rate_limited {
+            // Keep the last good notifications rather than showing a partial list
+            self.note_gh_rate_limited();
+            return;
+        }
+        self.clear_gh_backoff();
+
+        if !self.gh_notif_first_poll_done {
+            self.gh_notif_first_poll_done = true;
+            tracing::debug!("first github notification poll completed");
+        }
+
+        self.notifications = result.notifications;
+
+        // Auto-dismiss CI/workagent failure notifications
+        let ci_notifs: Vec<(String, String, String)> = self
+            .notifications
+            .iter()
+            .filter(|n| n.reason == "ci_activity")
+            .map(|n| (n.id.clone(), n.updated_at.clone(), n.title.clone()))
+            .collect();
+        if !ci_notifs.is_empty() {
+            tracing::info!(
+                count = ci_notifs.len(),
+                titles = ?ci_notifs.iter().map(|(_, _, t)| t.as_str()).collect::<Vec<_>>(),
+                "auto-dismissing ci_activity notifications"
+            );
+            for (thread_id, updated_at, _title) in &ci_notifs {
+                self.dismissed_notifs
+                    .insert(thread_id.clone(), updated_at.clone());
+                let tid = thread_id.clone();
+                self.rt.spawn(async move {
+                    let _ = tokio::task::spawn_blocking(move || {
+                        if let Err(e) = use_cases::dismiss_github_notification(&tid) {
+                            tracing::warn!(thread_id = %tid, error = %e, "failed to auto-dismiss ci_activity notification");
+                        }
+                    })
+                    .await;
+                });
+            }
+            self.dismissed_notifs
+                .save(&self.config.dismissed_notifications_path());
+        }
+
+        // Filter out notifications that were dismissed but may not yet be reflected by the API.
+        // Dismissed entries are only removed by retention-based pruning or explicit un-dismiss
+        // when there's genuine new activity (unread + newer updated_at).
+        if !self.dismissed_notifs.ids.is_empty() {
+            // Prune entries older than the retention window
+            let retention = chrono::Duration::weeks(
+                agman::dismissed_notifications::NOTIFICATION_RETENTION_WEEKS,
+            );
+            let pruned = self.dismissed_notifs.prune_older_than(retention);
+
+            // Un-dismiss threads that have new activity since they were dismissed
+            let mut undismissed: Vec<(String, String, String)> = Vec::new();
+            for notif in &self.notifications {
+                if self.dismissed_notifs.should_undismiss(
+                    &notif.id,
+                    &notif.updated_at,
+                    notif.unread,
+                ) {
+                    let old_updated_at = self
+                        .dismissed_notifs
+                        .ids
+                        .get(&notif.id)
+                        .map(|e| e.updated_at.clone())
+                        .unwrap_or_default();
+                    undismissed.push((notif.id.clone(), old_updated_at, notif.updated_at.clone()));
+                }
+            }
+            for (thread_id, old_updated_at, new_updated_at) in &undismissed {
+                tracing::info!(
+                    thread_id = %thread_id,
+                    old_updated_at = %old_updated_at,
+                    new_updated_at = %new_updated_at,
+                    "un-dismissing notification due to new activity"
+                );
+                self.dismissed_notifs.remove(thread_id);
+            }
+
+            if pruned > 0 || !undismissed.is_empty() {
+                self.dismissed_notifs
+                    .save(&self.config.dismissed_notifications_path());
+                tracing::debug!(
+                    pruned,
+                    undismissed = undismissed.len(),
+                    "cleaned up dismissed notification entries"
+                );
+            }
+            // Filter out still-dismissed notifications from the displayed list
+            let before = self.notifications.len();
+            self.notifications
+                .retain(|n| !self.dismissed_notifs.contains(&n.id));
+            let filtered = before - self.notifications.len();
+            if filtered > 0 {
+                tracing::debug!(
+                    filtered_count = filtered,
+                    "filtered dismissed notifications from poll results"
+                );
+            }
+        }
+
+        // Snoozed notifications stay hidden until the snooze runs out
+        if self.dismissed_notifs.prune_expired_snoozes() > 0 {
+            self.dismissed_notifs
+                .save(&self.config.dismissed_notifications_path());
+        }
+        self.notifications
+            .retain(|n| !self.dismissed_notifs.is_snoozed(&n.id));
+
+        self.clamp_notif_selection();
+
+        tracing::debug!(
+            notification_count = self.notifications.len(),
+            "applied github notification poll results"
+        );
+    }
 
-        if targets.is_empty() {
+    /// Spawn a background task to poll GitHub issues & PRs for the Show PRs view.
+    fn start_show_prs_poll(&mut self) {
+        if self.show_prs_poll_active {
             return;
         }
 
-        self.inbox_poll_active = true;
-        let tx = self.inbox_poll_tx.clone();
-        let mut stuck_skip_counts = self.stuck_skip_counts.clone();
-        let mut first_ready_at = self.first_ready_at.clone();
-        let mut verify_fail_counts = self.verify_fail_counts.clone();
+        self.show_prs_poll_active = true;
+        let tx = self.show_prs_poll_tx.clone();
 
+        tracing::debug!("starting show-prs poll");
         self.rt.spawn(async move {
-            let output = tokio::task::spawn_blocking(move || {
-                const MAX_RETRIES: usize = 3;
-                const RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
-
-                let mut results = Vec::new();
-                for (target, inbox_path, seq_path, session_name, window) in targets {
-                    let window_ref = window.as_deref();
+            let result = tokio::task::spawn_blocking(use_cases::fetch_show_prs_data)
+                .await
+                .unwrap_or_else(|_| use_cases::ShowPrsData::default());
+            let _ = tx.send(result);
+        });
+    }
 
-                    let undelivered = match inbox::read_undelivered(&inbox_path, &seq_path) {
-                        Ok(msgs) => msgs,
-                        Err(e) => {
-                            results.push(InboxPollResult {
-                                target,
-                                delivered: 0,
-                                errors: vec![format!("read error: {e}")],
-                            });
-                            continue;
-                        }
-                    };
+    /// Check for completed Show PRs poll results (non-blocking) and apply.
+    fn apply_show_prs_results(&mut self) {
+        let result = match self.show_prs_poll_rx.try_recv() {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+        self.show_prs_poll_active = false;
 
-                    if undelivered.is_empty() {
-                        stuck_skip_counts.remove(&target);
-                        verify_fail_counts.remove(&target);
-                        results.push(InboxPollResult {
-                            target,
-                            delivered: 0,
-                            errors: vec![],
-                        });
-                        continue;
-                    }
+        if result.rate_limited {
+            self.note_gh_rate_limited();
+            if self.show_prs_first_poll_done {
+                return;
+            }
+        } else {
+            self.clear_gh_backoff();
+        }
 
-                    let mut delivered = 0;
-                    let mut errors = Vec::new();
+        if !self.show_prs_first_poll_done {
+            self.show_prs_first_poll_done = true;
+            tracing::debug!("first show-prs poll completed");
+        }
 
-                    // Readiness gate (process-only check; no UI scraping) +
-                    // 3-second cold-start buffer. Runs BEFORE the already-pasted
-                    // rescue so a paste that landed during cold-start mounting
-                    // still gets a chance to settle before we send Enter.
-                    match Tmux::is_session_ready_in(&session_name, window_ref) {
-                        Ok((false, cmd)) => {
-                            tracing::info!(
-                                target_name = &target,
-                                session = &session_name,
-                                cmd = %cmd,
-                                "session not ready (foreground: {cmd}), skipping delivery this cycle"
-                            );
+        self.show_prs_data = result;
 
-                            // Re-arm the cold-start buffer: when claude restarts
-                            // we want a fresh 3s window once it next flips ready.
-                            first_ready_at.remove(&target);
+        // Clamp selection index
+        let total = self.show_prs_total_items();
+        if self.show_prs_selected >= total && total > 0 {
+            self.show_prs_selected = total - 1;
+        }
 
-                            let counter = stuck_skip_counts.entry(target.clone()).or_insert(0);
-                            *counter += 1;
+        tracing::debug!(
+            issues = self.show_prs_data.issues.len(),
+            my_prs = self.show_prs_data.my_prs.len(),
+            review_requests = self.show_prs_data.review_requests.len(),
+            "applied show-prs poll results"
+        );
+    }
 
-                            results.push(InboxPollResult {
-                                target,
-                                delivered: 0,
-                                errors: vec![],
-                            });
-                            continue;
-                        }
-                        Err(e) => {
-                            tracing::debug!(
-                                target_name = &target,
-                                session = &session_name,
-                                error = %e,
-                                "session readiness check failed, skipping"
-                            );
-                            results.push(InboxPollResult {
-                                target,
-                                delivered: 0,
-                                errors: vec![],
-                            });
-                            continue;
-                        }
-                        Ok((true, _)) => {
-                            let ready_since = *first_ready_at
-                                .entry(target.clone())
-                                .or_insert_with(Instant::now);
-                            if ready_since.elapsed() < Duration::from_secs(5) {
-                                results.push(InboxPollResult {
-                                    target,
-                                    delivered: 0,
-                                    errors: vec![],
-                                });
-                                continue;
-                            }
-                            stuck_skip_counts.remove(&target);
-                        }
-                    }
+    fn show_prs_total_items(&self) -> usize {
+        self.show_prs_data.issues.len()
+            + self.show_prs_data.my_prs.len()
+            + self.show_prs_data.review_requests.len()
+    }
 
-                    let visibility = match Tmux::is_window_visible_to_any_client(
-                        &session_name,
-                        window_ref,
-                    ) {
-                        Ok(visible) => Some(visible),
-                        Err(e) => {
-                            tracing::debug!(
-                                target_name = &target,
-                                session = &session_name,
-                                error = %e,
-                                "target visibility check failed; treating fresh messages as visible"
-                            );
-                            None
-                        }
-                    };
+    fn show_prs_selected_item(&self) -> Option<&use_cases::GithubItem> {
+        let idx = self.show_prs_selected;
+        let issues_len = self.show_prs_data.issues.len();
+        let my_prs_len = self.show_prs_data.my_prs.len();
 
-                    // Decision 5: already-pasted rescue (after readiness+buffer)
-                    let first_msg = &undelivered[0];
-                    if use_cases::should_defer_visible_fresh_inbox_message(
-                        first_msg,
-                        chrono::Utc::now(),
-                        visibility,
-                    ) {
-                        tracing::debug!(
-                            target_name = &target,
-                            session = &session_name,
-                            seq = first_msg.seq,
-                            "target window visible and inbox message is fresh; deferring delivery"
-                        );
-                        results.push(InboxPollResult {
-                            target,
-                            delivered: 0,
-                            errors: vec![],
-                        });
-                        continue;
-                    }
+        if idx < issues_len {
+            self.show_prs_data.issues.get(idx)
+        } else if idx < issues_len + my_prs_len {
+            self.show_prs_data.my_prs.get(idx - issues_len)
+        } else {
+            self.show_prs_data
+                .review_requests
+                .get(idx - issues_len - my_prs_len)
+        }
+    }
+}
 
-                    let first_snippet = format!("[msg:{}:{}]", first_msg.from, first_msg.seq);
-                    let already_pasted = Tmux::capture_pane_window(&session_name, window_ref)
-                        .map(|content| use_cases::snippet_in_capture(&content, &first_snippet))
-                        .unwrap_or(false);
+impl Drop for App {
+    fn drop(&mut self) {
+        self.stop_caffeinate();
+    }
+}
 
-                    if already_pasted {
-                        tracing::info!(
-                            target_name = &target,
-                            session = &session_name,
-                            seq = first_msg.seq,
-                            "already-pasted rescue: message visible in pane, sending Enter"
-                        );
-                        let _ = Tmux::send_enter_to(&session_name, window_ref);
-                        std::thread::sleep(std::time::Duration::from_millis(200));
-                        let verified = Tmux::capture_pane_window(&session_name, window_ref)
-                            .map(|content| use_cases::snippet_in_capture(&content, &first_snippet))
-                            .unwrap_or(false);
-                        if verified {
-                            if let Err(e) = inbox::mark_delivered(&seq_path, first_msg.seq) {
-                                errors.push(format!("mark_delivered error: {e}"));
-                            } else {
-                                delivered += 1;
-                                verify_fail_counts.remove(&target);
-                            }
-                        } else if use_cases::record_inbox_verify_failure(
-                            &mut verify_fail_counts,
-                            &target,
-                            first_msg.seq,
-                            VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
-                        ) {
-                            tracing::warn!(
-                                target_name = &target,
-                                session = &session_name,
-                                seq = first_msg.seq,
-                                threshold = VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
-                                "snippet verification failed for {} consecutive cycles; \
-                                 force-advancing inbox cursor past seq {} (message remains \
-                                 in inbox.jsonl; it was pasted to the pane but could not \
-                                 be verified)",
-                                VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
-                                first_msg.seq
-                            );
-                            if let Err(e) = inbox::mark_delivered(&seq_path, first_msg.seq) {
-                                errors.push(format!("mark_delivered error: {e}"));
-                            } else {
-                                verify_fail_counts.remove(&target);
-                            }
-                        }
-                        // Even if rescue handled one message, skip to next target this cycle
-                        // (subsequent messages will be delivered in future poll cycles)
-                        stuck_skip_counts.remove(&target);
-                        results.push(InboxPollResult {
-                            target,
-                            delivered,
-                            errors,
-                        });
-                        continue;
-                    }
+pub fn run_tui(config: Config) -> Result<()> {
+    // Remove any stale restart signal files left over from a previous run.
+    // This prevents a "double restart" if the TUI missed the signal (e.g. it
+    // crashed or was not running when release.sh created the file).
+    #[cfg(unix)]
+    {
+        let base = dirs::home_dir().unwrap_or_default().join(".agman");
+        let name = ".agman-restart";
+        let path = base.join(name);
+        if path.exists() {
+            tracing::info!(file = name, "removing stale restart signal file at startup");
+            let _ = std::fs::remove_file(&path);
+        }
+    }
 
-                    'msg_loop: for msg in &undelivered {
-                        if use_cases::should_defer_visible_fresh_inbox_message(
-                            msg,
-                            chrono::Utc::now(),
-                            visibility,
-                        ) {
-                            tracing::debug!(
-                                target_name = &target,
-                                session = &session_name,
-                                seq = msg.seq,
-                                "target window visible and inbox message is fresh; deferring delivery"
-                            );
-                            break 'msg_loop;
-                        }
+    // Create app once (persists across attach/return cycles)
+    let mut app = App::new(config)?;
+    let mut first_launch = true;
 
-                        let formatted_snippet = format!("[msg:{}:{}]", msg.from, msg.seq);
-                        // True once the message text is known to be in the
-                        // pane this cycle: inject_message_to succeeded, or the
-                        // snippet was observed in a capture. After that, later
-                        // attempts must only nudge Enter — pasting again would
-                        // deliver duplicate copies (verification can't see a
-                        // collapsed paste or text scrolled off the alternate
-                        // screen, so its absence proves nothing).
-                        let mut in_pane_this_cycle = false;
+    loop {
+        // Setup terminal
+        enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
+        let backend = CrosstermBackend::new(stdout);
+        let mut terminal = Terminal::new(backend)?;
 
-                        for attempt in 0..MAX_RETRIES {
-                            let already_pasted = Tmux::capture_pane_window(&session_name, window_ref)
-                                .map(|content| use_cases::snippet_in_capture(&content, &formatted_snippet))
-                                .unwrap_or(false);
+        // Reset view state when returning from attach
+        app.current_project = None;
+        app.view = View::ProjectList;
+        app.should_quit = false;
+        app.refresh_projects();
+        app.refresh_tasks();
+        if first_launch && app.show_dashboard_on_start {
+            app.open_dashboard();
+        }
+        first_launch = false;
 
-                            if already_pasted || in_pane_this_cycle {
-                                in_pane_this_cycle = true;
-                                tracing::debug!(
-                                    target = &target, seq = msg.seq, attempt = attempt,
-                                    "message already pasted (visible in pane or earlier this cycle), retrying Enter only"
-                                );
-                                if let Err(e) = Tmux::send_enter_to(&session_name, window_ref) {
-                                    tracing::warn!(
-                                        target = &target, seq = msg.seq, error = %e,
-                                        "failed to send Enter retry"
-                                    );
-                                    std::thread::sleep(RETRY_DELAY);
-                                    continue;
-                                }
-                            } else {
-                                match Tmux::inject_message_to(&session_name, window_ref, &msg.from, &msg.message, msg.seq) {
-                                    Ok(()) => in_pane_this_cycle = true,
-                                    Err(e) => {
-                                        tracing::warn!(
-                                            target = &target, seq = msg.seq, attempt = attempt, error = %e,
-                                            "inject_message failed"
-                                        );
-                                        std::thread::sleep(RETRY_DELAY);
-                                        continue;
-                                    }
-                                }
-                            }
+        // Main loop
+        let mut attach_session: Option<String> = None;
+        let mut last_refresh = Instant::now();
+        let refresh_interval = app.refresh_interval;
 
-                            std::thread::sleep(std::time::Duration::from_millis(200));
-                            let verified = Tmux::capture_pane_window(&session_name, window_ref)
-                                .map(|content| use_cases::snippet_in_capture(&content, &formatted_snippet))
-                                .unwrap_or(false);
+        loop {
+            // Poll any active tmux popup so inbox and PR polling keep ticking
+            // while the user is interacting with the popup. When the popup
+            // process exits, dispatch close side-effects via `on_popup_closed`.
+            let popup_open = match app.popup.as_mut() {
+                Some(p) => match p.child.try_wait() {
+                    Ok(None) => true,
+                    Ok(Some(_)) => {
+                        app.on_popup_closed();
+                        false
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "popup try_wait failed, treating as closed");
+                        app.on_popup_closed();
+                        false
+                    }
+                },
+                None => false,
+            };
 
-                            if verified {
-                                if let Err(e) = inbox::mark_delivered(&seq_path, msg.seq) {
-                                    errors.push(format!("mark_delivered error: {e}"));
-                                } else {
-                                    delivered += 1;
-                                    verify_fail_counts.remove(&target);
-                                }
-                                continue 'msg_loop;
-                            }
+            if !popup_open {
+                terminal.draw(|f| ui::draw(f, &mut app))?;
+            }
 
-                            tracing::debug!(
-                                target = &target, seq = msg.seq, attempt = attempt,
-                                "delivery verification failed, retrying"
-                            );
-                            std::thread::sleep(RETRY_DELAY);
+            if event::poll(Duration::from_millis(250))? {
+                let event = event::read()?;
+                if !popup_open {
+                    let should_attach = app.handle_event(event)?;
+
+                    if let Some(path) = app.pager_path.take() {
+                        if let Err(e) = run_pager(&mut terminal, &path) {
+                            tracing::error!(error = %e, "failed to open agent log in pager");
+                            app.set_status(format!("Error: {e}"));
                         }
+                    }
 
-                        // Bound the cross-cycle loop: without a terminal state
-                        // an unverifiable head message would be re-attempted
-                        // every poll cycle forever, blocking all later seqs.
-                        // Only cycles where the message reached the pane count
-                        // toward the threshold — pure inject/tmux failures must
-                        // never force-advance past an undelivered message.
-                        let force_advance = in_pane_this_cycle
-                            && use_cases::record_inbox_verify_failure(
-                                &mut verify_fail_counts,
-                                &target,
-                                msg.seq,
-                                VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
-                            );
-                        if force_advance {
-                            tracing::warn!(
-                                target_name = &target,
-                                session = &session_name,
-                                seq = msg.seq,
-                                threshold = VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
-                                "snippet verification failed for {} consecutive cycles; \
-                                 force-advancing inbox cursor past seq {} (message remains \
-                                 in inbox.jsonl; it was pasted to the pane but could not \
-                                 be verified)",
-                                VERIFY_FAIL_FORCE_ADVANCE_THRESHOLD,
-                                msg.seq
-                            );
-                            if let Err(e) = inbox::mark_delivered(&seq_path, msg.seq) {
-                                errors.push(format!("mark_delivered error: {e}"));
-                            } else {
-                                verify_fail_counts.remove(&target);
+                    if let Some(path) = app.editor_path.take() {
+                        match run_editor(&mut terminal, &path) {
+                            Ok(()) => {
+                                app.reload_notes();
+                                app.set_status("Notes reloaded from disk".to_string());
+                            }
+                            Err(e) => {
+                                tracing::error!(error = %e, "failed to open notes in editor");
+                                app.set_status(format!("Error: {e}"));
+                            }
+                        }
+                    }
+
+                    if should_attach {
+                        // Session picker sets attach_session_name directly
+                        if let Some(session) = app.attach_session_name.take() {
+                            attach_session = Some(session);
+                        } else if let Some(task) = app.selected_task() {
+                            if task.meta.has_repos() {
+                                attach_session =
+                                    Some(task.meta.primary_repo().tmux_session.clone());
+                            } else if task.meta.is_multi_repo() {
+                                // Multi-repo with no repos yet — attach to parent session
+                                attach_session = Some(task.meta.parent_tmux_session());
                             }
-                        } else {
-                            // Skipped on the force-advance cycle: the WARN
-                            // above already tells the whole story, and this
-                            // error would otherwise be logged after it.
-                            errors.push(format!(
-                                "delivery failed after {} attempts for seq {}",
-                                MAX_RETRIES, msg.seq
-                            ));
                         }
                         break;
                     }
-                    results.push(InboxPollResult {
-                        target,
-                        delivered,
-                        errors,
-                    });
-                }
-                InboxPollOutput {
-                    results,
-                    stuck_skip_counts,
-                    first_ready_at,
-                    verify_fail_counts,
                 }
-            })
-            .await
-            .unwrap_or_else(|_| InboxPollOutput {
-                results: Vec::new(),
-                stuck_skip_counts: std::collections::HashMap::new(),
-                first_ready_at: std::collections::HashMap::new(),
-                verify_fail_counts: std::collections::HashMap::new(),
-            });
-            let _ = tx.send(output);
-        });
-    }
-
-    fn apply_inbox_poll_results(&mut self) {
-        let output = match self.inbox_poll_rx.try_recv() {
-            Ok(r) => r,
-            Err(_) => return,
-        };
-        self.inbox_poll_active = false;
+                // While a popup is open, tmux owns input. Any crossterm events
+                // that leak through (e.g. SIGWINCH resize) target stale pane
+                // geometry and must be discarded rather than dispatched.
+            }
 
-        self.stuck_skip_counts = output.stuck_skip_counts;
-        self.first_ready_at = output.first_ready_at;
-        self.verify_fail_counts = output.verify_fail_counts;
+            if app.should_quit || app.should_restart {
+                break;
+            }
 
-        // One-shot warn the first time a target crosses STALL_THRESHOLD; clear
-        // the warned marker when it recovers, so a later stall episode warns again.
-        let currently_stalled: std::collections::HashSet<String> = self
-            .stuck_skip_counts
-            .iter()
-            .filter(|(_, n)| **n >= STALL_THRESHOLD)
-            .map(|(t, _)| t.clone())
-            .collect();
-        for target in &currently_stalled {
-            if self.stall_warned.insert(target.clone()) {
-                tracing::warn!(
-                    target = %target,
-                    threshold = STALL_THRESHOLD,
-                    "message delivery stalled: session not ready for {} consecutive cycles",
-                    STALL_THRESHOLD
-                );
+            // Periodic refresh (drives visible project data and agent activity)
+            if last_refresh.elapsed() >= refresh_interval {
+                // While paused the lists stay exactly as they are.
+                let paused = app.auto_refresh_paused;
+                if app.view == View::ProjectList && !paused {
+                    app.start_project_refresh();
+                } else if app.view == View::TaskList && !paused {
+                    app.refresh_tasks_for_project();
+                    app.refresh_agents();
+                } else if app.view == View::Dashboard {
+                    app.refresh_tasks();
+                    app.refresh_agents();
+                }
+                app.refresh_stalled_tasks();
+                app.refresh_input_needed_tasks();
+                app.maybe_checkpoint_tasks();
+                last_refresh = Instant::now();
             }
-        }
-        self.stall_warned.retain(|t| currently_stalled.contains(t));
+            app.apply_project_refresh_result();
+            app.apply_input_check_result();
 
-        for result in &output.results {
-            if result.delivered > 0 {
-                tracing::info!(
-                    target = %result.target,
-                    delivered = result.delivered,
-                    "delivered inbox messages"
-                );
-                self.stuck_skip_counts.remove(&result.target);
+            // Live engineer view in the preview refreshes every tick
+            app.refresh_preview_live();
+            app.apply_preview_live_result();
+
+            // Re-scan agent logs for usage while the COST column is shown
+            if app.usage_settings.show_cost_column
+                && app.last_usage_scan.elapsed() >= USAGE_SCAN_INTERVAL
+            {
+                app.scan_task_usage();
+                app.last_usage_scan = Instant::now();
             }
-            for err in &result.errors {
-                tracing::warn!(target = %result.target, error = %err, "inbox poll error");
+            app.apply_usage_scan_result();
+
+            // Poll GitHub notifications every 60 seconds (regardless of view),
+            // unless a rate limit has paused polling
+            if app.last_gh_notif_poll.elapsed() >= Duration::from_secs(60)
+                && !app.gh_polling_backed_off()
+            {
+                app.start_gh_notif_poll();
+                app.last_gh_notif_poll = Instant::now();
             }
-        }
-    }
 
-    fn apply_respawn_results(&mut self) {
-        let result = match self.respawn_rx.try_recv() {
-            Ok(r) => r,
-            Err(_) => return,
-        };
-        let target = self.respawn_in_progress.take().unwrap_or_default();
-        match result {
-            Ok(msg) if msg.starts_with("cos+pms:") => {
-                let pm_count = msg.strip_prefix("cos+pms:").unwrap_or("0");
-                tracing::info!(target = %target, pm_count = %pm_count, "cos + all pms respawned successfully");
-                self.set_status(format!("respawned chief-of-staff + {pm_count} pms"));
+            // Check for completed notification poll results (non-blocking)
+            app.apply_gh_notif_results();
+
+            // Poll Show PRs data every 60 seconds (regardless of view)
+            if app.last_show_prs_poll.elapsed() >= Duration::from_secs(60)
+                && !app.gh_polling_backed_off()
+            {
+                app.start_show_prs_poll();
+                app.last_show_prs_poll = Instant::now();
             }
-            Ok(_) => {
-                tracing::info!(target = %target, "agent respawned successfully");
-                self.set_status(format!("respawned {target}"));
+
+            // Check for completed Show PRs poll results (non-blocking)
+            app.apply_show_prs_results();
+
+            // Poll agent inboxes every 2 seconds (deliver messages via tmux send-keys)
+            if app.last_inbox_poll.elapsed() >= Duration::from_secs(2) {
+                app.start_inbox_poll();
+                app.last_inbox_poll = Instant::now();
             }
-            Err(e) => {
-                tracing::error!(target = %target, error = %e, "failed to respawn agent");
-                self.set_status(format!("respawn failed: {e}"));
+
+            // Check for completed inbox poll results (non-blocking)
+            app.apply_inbox_poll_results();
+
+            // Check for completed respawn results (non-blocking)
+            app.apply_respawn_results();
+
+            // Check for completed task archive results (non-blocking)
+            app.apply_archive_results();
+            app.apply_task_create_results();
+            app.apply_issue_list_results();
+            app.apply_clone_events()?;
+            app.apply_add_repo_results();
+
+            if app.last_telegram_watchdog.elapsed() >= TELEGRAM_WATCHDOG_INTERVAL {
+                app.check_telegram_watchdog();
+                app.last_telegram_watchdog = Instant::now();
             }
-        }
-    }
 
-    /// Drain any completed background archive jobs. The worker reports the
-    /// task id and either `Ok(saved)` or an error string. On success we clear
-    /// the in-progress entry so subsequent refreshes treat the task as
-    /// archived; on failure we still clear the entry (so the task can re-
-    /// appear from disk if the worker only partially completed), surface the
-    /// error to the status line, and log it.
-    fn apply_archive_results(&mut self) {
-        loop {
-            let (task_id, result) = match self.archive_rx.try_recv() {
-                Ok(r) => r,
-                Err(_) => return,
-            };
-            self.archive_in_progress.remove(&task_id);
-            match result {
-                Ok(saved) => {
-                    let label = if saved {
-                        "Archived & saved"
-                    } else {
-                        "Archived"
-                    };
-                    tracing::info!(task_id = %task_id, saved, "TUI: archive task completed");
-                    self.log_output(format!("  Archived task {task_id}"));
-                    self.set_status(format!("{label}: {task_id}"));
-                }
-                Err(e) => {
-                    tracing::error!(task_id = %task_id, error = %e, "TUI: archive task failed");
-                    self.log_output(format!("  Archive failed for {task_id}: {e}"));
-                    self.set_status(format!("archive failed: {task_id}: {e}"));
+            // Check for restart signal (written by release.sh or `agman restart`)
+            #[cfg(unix)]
+            {
+                let restart_signal = dirs::home_dir()
+                    .unwrap_or_default()
+                    .join(".agman/.agman-restart");
+                if restart_signal.exists() {
+                    tracing::info!("detected .agman-restart signal file, restarting immediately");
+                    let _ = std::fs::remove_file(&restart_signal);
+                    app.should_restart = true;
+                    break;
                 }
             }
-        }
-    }
 
-    /// Spawn a background task to poll GitHub notifications.
-    fn start_gh_notif_poll(&mut self) {
-        if self.gh_notif_poll_active {
-            return;
+            // Clear old status messages
+            app.clear_old_status();
+            app.clear_old_output();
         }
 
-        self.gh_notif_poll_active = true;
-        let tx = self.gh_notif_tx.clone();
-
-        tracing::debug!("starting github notification poll");
-        self.rt.spawn(async move {
-            let result = tokio::task::spawn_blocking(use_cases::fetch_github_notifications)
-                .await
-                .unwrap_or_else(|_| use_cases::NotifPollResult {
-                    notifications: Vec::new(),
-                });
-            let _ = tx.send(result);
-        });
-    }
+        // Restore terminal
+        disable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        )?;
+        terminal.show_cursor()?;
 
-    /// Check for completed notification poll results (non-blocking) and apply.
-    fn apply_gh_notif_results(&mut self) {
-        let result = match self.gh_notif_rx.try_recv() {
-            Ok(r) => r,
-            Err(_) => return,
-        };
-        self.gh_notif_poll_active = false;
+        // Signal Telegram bot to stop
+        if let Some(ref h) = app.telegram {
+            h.cancel.store(true, Ordering::Relaxed);
+        }
 
-        if !self.gh_notif_first_poll_done {
-            self.gh_notif_first_poll_done = true;
-            tracing::debug!("first github notification poll completed");
+        // If user confirmed restart, exec the new binary
+        #[cfg(unix)]
+        if app.should_restart {
+            app.stop_caffeinate();
+            eprintln!("agman updated — restarting...");
+            let err = Command::new("agman").exec();
+            // exec only returns on error
+            eprintln!("Failed to restart: {err}");
+            std::process::exit(1);
         }
 
-        self.notifications = result.notifications;
+        // If user requested quit, exit the outer loop
+        if app.should_quit {
+            break;
+        }
 
-        // Auto-dismiss CI/workagent failure notifications
-        let ci_notifs: Vec<(String, String, String)> = self
-            .notifications
-            .iter()
-            .filter(|n| n.reason == "ci_activity")
-            .map(|n| (n.id.clone(), n.updated_at.clone(), n.title.clone()))
-            .collect();
-        if !ci_notifs.is_empty() {
-            tracing::info!(
-                count = ci_notifs.len(),
-                titles = ?ci_notifs.iter().map(|(_, _, t)| t.as_str()).collect::<Vec<_>>(),
-                "auto-dismissing ci_activity notifications"
-            );
-            for (thread_id, updated_at, _title) in &ci_notifs {
-                self.dismissed_notifs
-                    .insert(thread_id.clone(), updated_at.clone());
-                let tid = thread_id.clone();
-                self.rt.spawn(async move {
-                    let _ = tokio::task::spawn_blocking(move || {
-                        if let Err(e) = use_cases::dismiss_github_notification(&tid) {
-                            tracing::warn!(thread_id = %tid, error = %e, "failed to auto-dismiss ci_activity notification");
-                        }
-                    })
-                    .await;
-                });
-            }
-            self.dismissed_notifs
-                .save(&self.config.dismissed_notifications_path());
+        // Attach to tmux if requested, then loop back to restart TUI
+        if let Some(session) = attach_session {
+            Tmux::attach_session(&session)?;
+            // After detaching or switching back, the loop continues and TUI restarts
         }
+    }
 
-        // Filter out notifications that were dismissed but may not yet be reflected by the API.
-        // Dismissed entries are only removed by retention-based pruning or explicit un-dismiss
-        // when there's genuine new activity (unread + newer updated_at).
-        if !self.dismissed_notifs.ids.is_empty() {
-            // Prune entries older than the retention window
-            let retention = chrono::Duration::weeks(
-                agman::dismissed_notifications::NOTIFICATION_RETENTION_WEEKS,
-            );
-            let pruned = self.dismissed_notifs.prune_older_than(retention);
+    Ok(())
+}
 
-            // Un-dismiss threads that have new activity since they were dismissed
-            let mut undismissed: Vec<(String, String, String)> = Vec::new();
-            for notif in &self.notifications {
-                if self.dismissed_notifs.should_undismiss(
-                    &notif.id,
-                    &notif.updated_at,
-                    notif.unread,
-                ) {
-                    let old_updated_at = self
-                        .dismissed_notifs
-                        .ids
-                        .get(&notif.id)
-                        .map(|e| e.updated_at.clone())
-                        .unwrap_or_default();
-                    undismissed.push((notif.id.clone(), old_updated_at, notif.updated_at.clone()));
-                }
-            }
-            for (thread_id, old_updated_at, new_updated_at) in &undismissed {
-                tracing::info!(
-                    thread_id = %thread_id,
-                    old_updated_at = %old_updated_at,
-                    new_updated_at = %new_updated_at,
-                    "un-dismissing notification due to new activity"
-                );
-                self.dismissed_notifs.remove(thread_id);
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agman::agent_model::AgentAttachment;
+    use agman::task::{LinkedPr, TaskMeta};
 
-            if pruned > 0 || !undismissed.is_empty() {
-                self.dismissed_notifs
-                    .save(&self.config.dismissed_notifications_path());
-                tracing::debug!(
-                    pruned,
-                    undismissed = undismissed.len(),
-                    "cleaned up dismissed notification entries"
-                );
-            }
-            // Filter out still-dismissed notifications from the displayed list
-            let before = self.notifications.len();
-            self.notifications
-                .retain(|n| !self.dismissed_notifs.contains(&n.id));
-            let filtered = before - self.notifications.len();
-            if filtered > 0 {
-                tracing::debug!(
-                    filtered_count = filtered,
-                    "filtered dismissed notifications from poll results"
-                );
-            }
-        }
+    #[test]
+    fn apply_project_refresh_snapshot_preserves_counts_and_clamps_selection() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        Project::create(&config, "alpha", "Alpha project").unwrap();
+        Project::create(&config, "beta", "Beta project").unwrap();
+        let projects = use_cases::list_projects(&config).unwrap();
+        let mut app = App::new_for_test(config).unwrap();
+        app.selected_project_index = 99;
 
-        // Clamp selection index
-        if self.selected_notif_index >= self.notifications.len() && !self.notifications.is_empty() {
-            self.selected_notif_index = self.notifications.len() - 1;
-        }
+        let snapshot = ProjectRefreshSnapshot {
+            projects,
+            project_task_counts: HashMap::from([("alpha".to_string(), 3)]),
+            project_agent_counts: HashMap::from([("alpha".to_string(), 2)]),
+            project_active_agent_counts: HashMap::from([("alpha".to_string(), 1)]),
+            unassigned_task_count: 1,
+            agent_activity: HashMap::from([(
+                "agent-session".to_string(),
+                AgentActivitySample {
+                    last_tmux_activity_epoch: Some(100),
+                    last_observed_work_at: Some(Instant::now()),
+                    foreground_command: "nvim".to_string(),
+                    pane_dead: false,
+                    query_ok: true,
+                },
+            )]),
+            project_list_error: None,
+            agent_list_error: None,
+            agent_activity_error: None,
+        };
 
-        tracing::debug!(
-            notification_count = self.notifications.len(),
-            "applied github notification poll results"
-        );
-    }
+        app.apply_project_refresh_snapshot(snapshot);
 
-    /// Spawn a background task to poll GitHub issues & PRs for the Show PRs view.
-    fn start_show_prs_poll(&mut self) {
-        if self.show_prs_poll_active {
-            return;
-        }
+        assert_eq!(app.selected_project_index, app.project_list_len() - 1);
+        assert_eq!(app.project_task_counts.get("alpha"), Some(&3));
+        assert_eq!(app.project_agent_counts.get("alpha"), Some(&2));
+        assert_eq!(app.project_active_agent_counts.get("alpha"), Some(&1));
+        assert_eq!(app.unassigned_task_count, 1);
+        assert!(app.agent_activity.contains_key("agent-session"));
+    }
 
-        self.show_prs_poll_active = true;
-        let tx = self.show_prs_poll_tx.clone();
+    #[test]
+    fn apply_project_refresh_snapshot_keeps_empty_selection_behavior() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let mut app = App::new_for_test(config).unwrap();
+        app.selected_project_index = 7;
 
-        tracing::debug!("starting show-prs poll");
-        self.rt.spawn(async move {
-            let result = tokio::task::spawn_blocking(use_cases::fetch_show_prs_data)
-                .await
-                .unwrap_or_else(|_| use_cases::ShowPrsData::default());
-            let _ = tx.send(result);
+        app.apply_project_refresh_snapshot(ProjectRefreshSnapshot {
+            projects: Vec::new(),
+            project_task_counts: HashMap::new(),
+            project_agent_counts: HashMap::new(),
+            project_active_agent_counts: HashMap::new(),
+            unassigned_task_count: 0,
+            agent_activity: HashMap::new(),
+            project_list_error: None,
+            agent_list_error: None,
+            agent_activity_error: None,
         });
-    }
 
-    /// Check for completed Show PRs poll results (non-blocking) and apply.
-    fn apply_show_prs_results(&mut self) {
-        let result = match self.show_prs_poll_rx.try_recv() {
-            Ok(r) => r,
-            Err(_) => return,
-        };
-        self.show_prs_poll_active = false;
+        assert_eq!(app.project_list_len(), 0);
+        assert_eq!(app.selected_project_index, 7);
+    }
 
-        if !self.show_prs_first_poll_done {
-            self.show_prs_first_poll_done = true;
-            tracing::debug!("first show-prs poll completed");
-        }
+    #[test]
+    fn stale_project_refresh_result_does_not_overwrite_current_state() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        Project::create(&config, "alpha", "Alpha project").unwrap();
+        let projects = use_cases::list_projects(&config).unwrap();
+        let mut app = App::new_for_test(config).unwrap();
+        app.project_refresh_generation = 2;
+        app.project_refresh_active = true;
+        app.project_task_counts = HashMap::from([("fresh".to_string(), 1)]);
+        app.unassigned_task_count = 4;
 
-        self.show_prs_data = result;
+        let stale_snapshot = ProjectRefreshSnapshot {
+            projects,
+            project_task_counts: HashMap::from([("stale".to_string(), 99)]),
+            project_agent_counts: HashMap::new(),
+            project_active_agent_counts: HashMap::new(),
+            unassigned_task_count: 99,
+            agent_activity: HashMap::new(),
+            project_list_error: None,
+            agent_list_error: None,
+            agent_activity_error: None,
+        };
+        app.project_refresh_tx.send((1, stale_snapshot)).unwrap();
 
-        // Clamp selection index
-        let total = self.show_prs_total_items();
-        if self.show_prs_selected >= total && total > 0 {
-            self.show_prs_selected = total - 1;
-        }
+        app.apply_project_refresh_result();
 
-        tracing::debug!(
-            issues = self.show_prs_data.issues.len(),
-            my_prs = self.show_prs_data.my_prs.len(),
-            review_requests = self.show_prs_data.review_requests.len(),
-            "applied show-prs poll results"
+        assert!(!app.project_refresh_active);
+        assert_eq!(
+            app.project_task_counts,
+            HashMap::from([("fresh".to_string(), 1)])
         );
+        assert_eq!(app.unassigned_task_count, 4);
     }
 
-    fn show_prs_total_items(&self) -> usize {
-        self.show_prs_data.issues.len()
-            + self.show_prs_data.my_prs.len()
-            + self.show_prs_data.review_requests.len()
-    }
+    #[test]
+    fn project_list_opens_global_notes_and_returns_to_project_list() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        Project::create(&config, "alpha", "Alpha project").unwrap();
+        Project::create(&config, "beta", "Beta project").unwrap();
+        let mut app = App::new_for_test(config.clone()).unwrap();
+        app.refresh_projects();
+        app.selected_project_index = app
+            .projects
+            .iter()
+            .position(|project| project.meta.name == "beta")
+            .unwrap();
 
-    fn show_prs_selected_item(&self) -> Option<&use_cases::GithubItem> {
-        let idx = self.show_prs_selected;
-        let issues_len = self.show_prs_data.issues.len();
-        let my_prs_len = self.show_prs_data.my_prs.len();
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('o'),
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
 
-        if idx < issues_len {
-            self.show_prs_data.issues.get(idx)
-        } else if idx < issues_len + my_prs_len {
-            self.show_prs_data.my_prs.get(idx - issues_len)
-        } else {
-            self.show_prs_data
-                .review_requests
-                .get(idx - issues_len - my_prs_len)
-        }
+        assert_eq!(app.view, View::Notes);
+        assert_eq!(app.notes_return_view, View::ProjectList);
+        assert_eq!(app.notes_view.as_ref().unwrap().root_dir, config.notes_dir);
+
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Esc,
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
+
+        assert_eq!(app.view, View::ProjectList);
+        assert!(app.notes_view.is_none());
     }
-}
 
-impl Drop for App {
-    fn drop(&mut self) {
-        self.stop_caffeinate();
+    #[test]
+    fn project_list_opens_global_notes_for_unassigned() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        Project::create(&config, "alpha", "Alpha project").unwrap();
+        let mut app = App::new_for_test(config.clone()).unwrap();
+        app.refresh_projects();
+        app.unassigned_task_count = 1;
+        app.selected_project_index = app.projects.len();
+
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('o'),
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
+
+        assert_eq!(app.view, View::Notes);
+        assert_eq!(app.notes_return_view, View::ProjectList);
+        assert_eq!(app.notes_view.as_ref().unwrap().root_dir, config.notes_dir);
     }
-}
 
-pub fn run_tui(config: Config) -> Result<()> {
-    // Remove any stale restart signal files left over from a previous run.
-    // This prevents a "double restart" if the TUI missed the signal (e.g. it
-    // crashed or was not running when release.sh created the file).
-    #[cfg(unix)]
-    {
-        let base = dirs::home_dir().unwrap_or_default().join(".agman");
-        let name = ".agman-restart";
-        let path = base.join(name);
-        if path.exists() {
-            tracing::info!(file = name, "removing stale restart signal file at startup");
-            let _ = std::fs::remove_file(&path);
-        }
+    #[test]
+    fn task_list_lowercase_o_opens_project_notes_and_returns_to_task_list() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        Project::create(&config, "alpha", "Alpha project").unwrap();
+        let mut app = App::new_for_test(config.clone()).unwrap();
+        app.current_project = Some("alpha".to_string());
+        app.view = View::TaskList;
+
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('o'),
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
+
+        assert_eq!(app.view, View::Notes);
+        assert_eq!(app.notes_return_view, View::TaskList);
+        assert_eq!(
+            app.notes_view.as_ref().unwrap().root_dir,
+            config.project_notes_dir("alpha")
+        );
+
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('q'),
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
+
+        assert_eq!(app.view, View::TaskList);
+        assert!(app.notes_view.is_none());
     }
 
-    // Create app once (persists across attach/return cycles)
-    let mut app = App::new(config)?;
+    #[test]
+    fn task_list_lowercase_o_opens_project_notes_even_when_task_has_linked_pr() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let unique = unique_name();
+        let project = format!("repo-{unique}");
+        let branch = format!("branch-{unique}");
+        let mut task = create_test_task(&config, &project, &branch);
+        task.meta.linked_pr = Some(LinkedPr {
+            number: 42,
+            url: "https://github.com/example/repo/pull/42".to_string(),
+            owned: true,
+            author: None,
+        });
+        task.save_meta().unwrap();
+
+        let mut app = App::new_for_test(config.clone()).unwrap();
+        app.current_project = Some(project.clone());
+        app.view = View::TaskList;
+        app.refresh_tasks_for_project();
+
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('o'),
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
 
-    loop {
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+        assert_eq!(app.view, View::Notes);
+        assert_eq!(app.notes_return_view, View::TaskList);
+        assert_eq!(
+            app.notes_view.as_ref().unwrap().root_dir,
+            config.project_notes_dir(&project)
+        );
+    }
 
-        // Reset view state when returning from attach
-        app.current_project = None;
-        app.view = View::ProjectList;
-        app.should_quit = false;
-        app.refresh_projects();
-        app.refresh_tasks();
+    #[test]
+    fn refresh_tasks_for_project_preserves_attached_agent_row_selection() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let unique = unique_name();
+        let project = format!("repo-{unique}");
+        let branch = format!("branch-{unique}");
+        let task = create_test_task(&config, &project, &branch);
+        let task_id = task.meta.task_id();
+        let researcher = AgentRecord::create(
+            &config,
+            &project,
+            "research-agent-window-names",
+            "test researcher",
+            AgentKind::Researcher {
+                repo: None,
+                branch: None,
+                task_id: None,
+            },
+        )
+        .unwrap();
+        use_cases::attach_agent_to_task(&config, &project, &researcher.meta.name, &task_id, None)
+            .unwrap();
 
-        // Main loop
-        let mut attach_session: Option<String> = None;
-        let mut last_refresh = Instant::now();
-        let refresh_interval = Duration::from_secs(1);
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project.clone());
+        app.refresh_tasks_for_project();
+        app.selected_index = app
+            .project_detail_rows()
+            .iter()
+            .position(|row| {
+                matches!(
+                    row,
+                    ProjectDetailRow::AttachedAgent(ProjectTaskRow::Agent { agent, .. })
+                        if agent.meta.name == "research-agent-window-names"
+                )
+            })
+            .expect("attached researcher row exists");
 
-        loop {
-            // Poll any active tmux popup so inbox and PR polling keep ticking
-            // while the user is interacting with the popup. When the popup
-            // process exits, dispatch close side-effects via `on_popup_closed`.
-            let popup_open = match app.popup.as_mut() {
-                Some(p) => match p.child.try_wait() {
-                    Ok(None) => true,
-                    Ok(Some(_)) => {
-                        app.on_popup_closed();
-                        false
-                    }
-                    Err(e) => {
-                        tracing::warn!(error = %e, "popup try_wait failed, treating as closed");
-                        app.on_popup_closed();
-                        false
-                    }
-                },
-                None => false,
-            };
+        app.refresh_tasks_for_project();
 
-            if !popup_open {
-                terminal.draw(|f| ui::draw(f, &mut app))?;
-            }
+        assert!(matches!(
+            app.selected_project_task_row(),
+            Some(ProjectTaskRow::Agent { agent, .. })
+                if agent.meta.name == "research-agent-window-names"
+        ));
+    }
 
-            if event::poll(Duration::from_millis(250))? {
-                let event = event::read()?;
-                if !popup_open {
-                    let should_attach = app.handle_event(event)?;
+    #[test]
+    fn refresh_tasks_for_project_falls_back_to_parent_task_when_agent_row_disappears() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let unique = unique_name();
+        let project = format!("repo-{unique}");
+        let branch = format!("branch-{unique}");
+        let task = create_test_task(&config, &project, &branch);
+        let task_id = task.meta.task_id();
+        let researcher = AgentRecord::create(
+            &config,
+            &project,
+            "research-agent-window-names",
+            "test researcher",
+            AgentKind::Researcher {
+                repo: None,
+                branch: None,
+                task_id: None,
+            },
+        )
+        .unwrap();
+        use_cases::attach_agent_to_task(&config, &project, &researcher.meta.name, &task_id, None)
+            .unwrap();
 
-                    if should_attach {
-                        // Session picker sets attach_session_name directly
-                        if let Some(session) = app.attach_session_name.take() {
-                            attach_session = Some(session);
-                        } else if let Some(task) = app.selected_task() {
-                            if task.meta.has_repos() {
-                                attach_session =
-                                    Some(task.meta.primary_repo().tmux_session.clone());
-                            } else if task.meta.is_multi_repo() {
-                                // Multi-repo with no repos yet — attach to parent session
-                                attach_session = Some(Config::tmux_session_name(
-                                    &task.meta.name,
-                                    &task.meta.branch_name,
-                                ));
-                            }
-                        }
-                        break;
-                    }
-                }
-                // While a popup is open, tmux owns input. Any crossterm events
-                // that leak through (e.g. SIGWINCH resize) target stale pane
-                // geometry and must be discarded rather than dispatched.
-            }
+        let mut app = App::new_for_test(config.clone()).unwrap();
+        app.current_project = Some(project.clone());
+        app.refresh_tasks_for_project();
+        app.selected_index = app
+            .project_detail_rows()
+            .iter()
+            .position(|row| {
+                matches!(
+                    row,
+                    ProjectDetailRow::AttachedAgent(ProjectTaskRow::Agent { agent, .. })
+                        if agent.meta.name == "research-agent-window-names"
+                )
+            })
+            .expect("attached researcher row exists");
 
-            if app.should_quit || app.should_restart {
-                break;
-            }
+        use_cases::detach_agent_from_task(&config, &project, &researcher.meta.name).unwrap();
+        app.refresh_tasks_for_project();
 
-            // Periodic refresh (drives visible project data and agent activity)
-            if last_refresh.elapsed() >= refresh_interval {
-                if app.view == View::ProjectList {
-                    app.start_project_refresh();
-                } else if app.view == View::TaskList {
-                    app.refresh_tasks_for_project();
-                    app.refresh_agents();
-                }
-                last_refresh = Instant::now();
-            }
-            app.apply_project_refresh_result();
+        assert!(matches!(
+            app.selected_project_task_row(),
+            Some(ProjectTaskRow::Task { task, .. }) if task.meta.task_id() == task_id
+        ));
+    }
 
-            // Poll GitHub notifications every 60 seconds (regardless of view)
-            if app.last_gh_notif_poll.elapsed() >= Duration::from_secs(60) {
-                app.start_gh_notif_poll();
-                app.last_gh_notif_poll = Instant::now();
-            }
+    #[test]
+    fn refresh_tasks_preserves_attached_agent_row_selection() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let unique = unique_name();
+        let project = format!("repo-{unique}");
+        let branch = format!("branch-{unique}");
+        let task = create_test_task(&config, &project, &branch);
+        let task_id = task.meta.task_id();
+        let reviewer = AgentRecord::create(
+            &config,
+            &project,
+            "reviewer-pr5590-ltv",
+            "test reviewer",
+            AgentKind::Reviewer {
+                worktrees: Vec::new(),
+            },
+        )
+        .unwrap();
+        use_cases::attach_agent_to_task(&config, &project, &reviewer.meta.name, &task_id, None)
+            .unwrap();
 
-            // Check for completed notification poll results (non-blocking)
-            app.apply_gh_notif_results();
+        let mut app = App::new_for_test(config).unwrap();
+        app.refresh_tasks();
+        app.selected_index = app
+            .project_detail_rows()
+            .iter()
+            .position(|row| {
+                matches!(
+                    row,
+                    ProjectDetailRow::AttachedAgent(ProjectTaskRow::Agent { agent, .. })
+                        if agent.meta.name == "reviewer-pr5590-ltv"
+                )
+            })
+            .expect("attached reviewer row exists");
 
-            // Poll Show PRs data every 60 seconds (regardless of view)
-            if app.last_show_prs_poll.elapsed() >= Duration::from_secs(60) {
-                app.start_show_prs_poll();
-                app.last_show_prs_poll = Instant::now();
-            }
+        app.refresh_tasks();
 
-            // Check for completed Show PRs poll results (non-blocking)
-            app.apply_show_prs_results();
+        assert!(matches!(
+            app.selected_project_task_row(),
+            Some(ProjectTaskRow::Agent { agent, .. }) if agent.meta.name == "reviewer-pr5590-ltv"
+        ));
+    }
 
-            // Poll agent inboxes every 2 seconds (deliver messages via tmux send-keys)
-            if app.last_inbox_poll.elapsed() >= Duration::from_secs(2) {
-                app.start_inbox_poll();
-                app.last_inbox_poll = Instant::now();
-            }
+    #[test]
+    fn find_log_matches_is_case_insensitive_and_reports_char_columns() {
+        let lines: Vec<String> = ["Error: boom", "no match", "é error and ERROR"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            find_log_matches(&lines, "error"),
+            vec![(0, 0), (2, 2), (2, 12)]
+        );
+        assert!(find_log_matches(&lines, "").is_empty());
+        assert!(find_log_matches(&lines, "missing").is_empty());
+    }
 
-            // Check for completed inbox poll results (non-blocking)
-            app.apply_inbox_poll_results();
+    #[test]
+    fn quit_without_live_tasks_skips_confirmation_and_cancel_restores_view() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        create_test_task(&config, &format!("repo-{}", unique_name()), "branch");
+        let mut app = App::new_for_test(config).unwrap();
 
-            // Check for completed respawn results (non-blocking)
-            app.apply_respawn_results();
+        // No tmux sessions exist for the task, so Ctrl+C quits straight away.
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL,
+        )))
+        .unwrap();
+        assert!(app.should_quit);
+        assert_eq!(app.view, View::ProjectList);
+
+        app.should_quit = false;
+        app.quit_live_tasks = vec!["repo--branch".to_string()];
+        app.quit_return_view = View::Settings;
+        app.view = View::QuitConfirm;
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('n'),
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
+        assert!(!app.should_quit);
+        assert_eq!(app.view, View::Settings);
+        assert!(app.quit_live_tasks.is_empty());
+    }
 
-            // Check for completed task archive results (non-blocking)
-            app.apply_archive_results();
+    #[test]
+    fn question_mark_toggles_help_for_current_view_but_not_in_prompts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut app = App::new_for_test(test_config(tmp.path())).unwrap();
+        let question = || Event::Key(event::KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE));
 
-            if app.last_telegram_watchdog.elapsed() >= TELEGRAM_WATCHDOG_INTERVAL {
-                app.check_telegram_watchdog();
-                app.last_telegram_watchdog = Instant::now();
-            }
+        app.view = View::TaskList;
+        app.handle_event(question()).unwrap();
+        assert_eq!(app.view, View::Help);
+        assert_eq!(app.help_return_view, View::TaskList);
+        app.handle_event(question()).unwrap();
+        assert_eq!(app.view, View::TaskList);
 
-            // Check for restart signal (written by release.sh or `agman restart`)
-            #[cfg(unix)]
-            {
-                let restart_signal = dirs::home_dir()
-                    .unwrap_or_default()
-                    .join(".agman/.agman-restart");
-                if restart_signal.exists() {
-                    tracing::info!("detected .agman-restart signal file, restarting immediately");
-                    let _ = std::fs::remove_file(&restart_signal);
-                    app.should_restart = true;
-                    break;
-                }
-            }
+        // Typing `?` into the log search prompt must not open help
+        app.view = View::Preview;
+        app.log_search_editor = Some(TextArea::default());
+        app.handle_event(question()).unwrap();
+        assert_eq!(app.view, View::Preview);
+    }
 
-            // Clear old status messages
-            app.clear_old_status();
-            app.clear_old_output();
-        }
+    #[test]
+    fn ctrl_p_finder_opens_nested_note_and_moves_explorer_to_it() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let root = config.notes_dir.clone();
+        std::fs::create_dir_all(root.join("work")).unwrap();
+        std::fs::write(root.join("work/roadmap.md"), "ship it").unwrap();
+        std::fs::write(root.join("inbox.md"), "").unwrap();
 
-        // Restore terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
+        let mut app = App::new_for_test(config).unwrap();
+        app.open_global_notes(View::ProjectList);
+        let key = |code, modifiers| Event::Key(event::KeyEvent::new(code, modifiers));
 
-        // Signal Telegram bot to stop
-        if let Some(ref h) = app.telegram {
-            h.cancel.store(true, Ordering::Relaxed);
+        app.handle_event(key(KeyCode::Char('p'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(
+            app.notes_view
+                .as_ref()
+                .unwrap()
+                .finder
+                .as_ref()
+                .unwrap()
+                .matches
+                .len(),
+            2
+        );
+        for c in "road".chars() {
+            app.handle_event(key(KeyCode::Char(c), KeyModifiers::NONE))
+                .unwrap();
         }
+        app.handle_event(key(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
 
-        // If user confirmed restart, exec the new binary
-        #[cfg(unix)]
-        if app.should_restart {
-            app.stop_caffeinate();
-            eprintln!("agman updated — restarting...");
-            let err = Command::new("agman").exec();
-            // exec only returns on error
-            eprintln!("Failed to restart: {err}");
-            std::process::exit(1);
-        }
+        let nv = app.notes_view.as_ref().unwrap();
+        assert!(nv.finder.is_none());
+        assert_eq!(
+            nv.open_file.as_deref(),
+            Some(root.join("work/roadmap.md").as_path())
+        );
+        assert_eq!(nv.current_dir, root.join("work"));
+        assert_eq!(nv.entries[nv.selected_index].file_name, "roadmap.md");
+        assert_eq!(nv.focus, NotesFocus::Editor);
+    }
 
-        // If user requested quit, exit the outer loop
-        if app.should_quit {
-            break;
-        }
+    #[test]
+    fn live_preview_stays_off_without_a_running_engineer() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let project = format!("repo-{}", unique_name());
+        create_test_task(&config, &project, "live");
 
-        // Attach to tmux if requested, then loop back to restart TUI
-        if let Some(session) = attach_session {
-            Tmux::attach_session(&session)?;
-            // After detaching or switching back, the loop continues and TUI restarts
-        }
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project);
+        app.refresh_tasks_for_project();
+        app.view = View::Preview;
+        app.preview_pane = PreviewPane::Logs;
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('L'),
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
+
+        assert!(app.preview_live.is_none());
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Engineer is not running");
     }
 
-    Ok(())
-}
+    #[test]
+    fn live_preview_capture_is_applied_only_for_the_watched_session() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let project = format!("repo-{}", unique_name());
+        create_test_task(&config, &project, "live");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use agman::agent_model::AgentAttachment;
-    use agman::task::{LinkedPr, TaskMeta};
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project);
+        app.refresh_tasks_for_project();
+        app.view = View::Preview;
+        app.preview_pane = PreviewPane::Logs;
+        app.preview_live = Some("watched".to_string());
+        app.live_capture_active = true;
+
+        app.live_capture_tx
+            .send(("stale".to_string(), Ok("old pane".to_string())))
+            .unwrap();
+        app.apply_preview_live_result();
+        assert!(!app.live_capture_active);
+        assert_ne!(app.logs_editor.textarea.lines(), ["old pane"]);
+
+        app.live_capture_tx
+            .send(("watched".to_string(), Ok("engineer pane\n\n".to_string())))
+            .unwrap();
+        app.apply_preview_live_result();
+        assert_eq!(app.logs_editor.textarea.lines(), ["engineer pane"]);
+        assert_eq!(app.preview_live.as_deref(), Some("watched"));
+    }
 
     #[test]
-    fn apply_project_refresh_snapshot_preserves_counts_and_clamps_selection() {
+    fn external_notes_edit_is_requested_and_reloaded() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
-        Project::create(&config, "alpha", "Alpha project").unwrap();
-        Project::create(&config, "beta", "Beta project").unwrap();
-        let projects = use_cases::list_projects(&config).unwrap();
+        let project = format!("repo-{}", unique_name());
+        let task = create_test_task(&config, &project, "notes");
+
         let mut app = App::new_for_test(config).unwrap();
-        app.selected_project_index = 99;
+        app.current_project = Some(project);
+        app.refresh_tasks_for_project();
+        app.load_preview();
+        app.view = View::Preview;
+        app.preview_pane = PreviewPane::Notes;
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('E'),
+            KeyModifiers::NONE,
+        )))
+        .unwrap();
 
-        let snapshot = ProjectRefreshSnapshot {
-            projects,
-            project_task_counts: HashMap::from([("alpha".to_string(), 3)]),
-            project_agent_counts: HashMap::from([("alpha".to_string(), 2)]),
-            project_active_agent_counts: HashMap::from([("alpha".to_string(), 1)]),
-            unassigned_task_count: 1,
-            agent_activity: HashMap::from([(
-                "agent-session".to_string(),
-                AgentActivitySample {
-                    last_tmux_activity_epoch: Some(100),
-                    last_observed_work_at: Some(Instant::now()),
-                    foreground_command: "nvim".to_string(),
-                    pane_dead: false,
-                    query_ok: true,
-                },
-            )]),
-            project_list_error: None,
-            agent_list_error: None,
-            agent_activity_error: None,
-        };
+        let path = app.editor_path.take().unwrap();
+        assert_eq!(path, task.dir.join("notes.md"));
+        std::fs::write(&path, "written elsewhere").unwrap();
+        app.reload_notes();
+        assert_eq!(app.notes_content, "written elsewhere");
+        assert!(!app.notes_editing);
+    }
 
-        app.apply_project_refresh_snapshot(snapshot);
+    #[test]
+    fn repo_picker_reopens_where_it_was_cancelled() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let nested = config.repos_dir.join("group");
+        for name in ["alpha", "beta", "gamma"] {
+            std::fs::create_dir_all(nested.join(name)).unwrap();
+        }
 
-        assert_eq!(app.selected_project_index, app.project_list_len() - 1);
-        assert_eq!(app.project_task_counts.get("alpha"), Some(&3));
-        assert_eq!(app.project_agent_counts.get("alpha"), Some(&2));
-        assert_eq!(app.project_active_agent_counts.get("alpha"), Some(&1));
-        assert_eq!(app.unassigned_task_count, 1);
-        assert!(app.agent_activity.contains_key("agent-session"));
+        let mut app = App::new_for_test(config).unwrap();
+        let key = |code| Event::Key(event::KeyEvent::new(code, KeyModifiers::NONE));
+        app.start_wizard().unwrap();
+        let picker = app.dir_picker.as_mut().unwrap();
+        picker.current_dir = nested.clone();
+        picker.refresh_entries();
+        picker.selected_index = 2;
+        app.handle_event(key(KeyCode::Esc)).unwrap();
+        assert!(app.dir_picker.is_none());
+
+        app.start_wizard().unwrap();
+        let picker = app.dir_picker.as_ref().unwrap();
+        assert_eq!(picker.current_dir, nested);
+        assert_eq!(picker.selected_name().as_deref(), Some("gamma"));
     }
 
     #[test]
-    fn apply_project_refresh_snapshot_keeps_empty_selection_behavior() {
+    fn new_branch_that_already_exists_is_reused_after_confirming() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
+        let repo_path = config.repos_dir.join("repo");
+        std::fs::create_dir_all(&repo_path).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .current_dir(&repo_path)
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "init",
+        ]);
+        git(&["branch", "leftover"]);
+
         let mut app = App::new_for_test(config).unwrap();
-        app.selected_project_index = 7;
+        app.create_wizard_from_picker("repo".to_string(), repo_path.clone(), false)
+            .unwrap();
+        let wizard = app.wizard.as_mut().unwrap();
+        wizard.new_branch_editor.insert_str("leftover");
+        app.wizard_next_step().unwrap();
+        let wizard = app.wizard.as_ref().unwrap();
+        assert_eq!(wizard.step, WizardStep::SelectBranch);
+        assert!(wizard
+            .error_message
+            .as_deref()
+            .is_some_and(|m| m.contains("already exists")));
+
+        app.wizard_next_step().unwrap();
+        let wizard = app.wizard.as_ref().unwrap();
+        assert_eq!(wizard.step, WizardStep::EnterFirstPrompt);
+        assert_eq!(wizard.branch_source, BranchSource::ExistingBranch);
+        assert_eq!(
+            wizard.existing_branches[wizard.selected_branch_index],
+            "leftover"
+        );
+    }
 
-        app.apply_project_refresh_snapshot(ProjectRefreshSnapshot {
-            projects: Vec::new(),
-            project_task_counts: HashMap::new(),
-            project_agent_counts: HashMap::new(),
-            project_active_agent_counts: HashMap::new(),
-            unassigned_task_count: 0,
-            agent_activity: HashMap::new(),
-            project_list_error: None,
-            agent_list_error: None,
-            agent_activity_error: None,
+    #[test]
+    fn new_branch_owned_by_a_live_task_is_not_offered_for_reuse() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let repo_path = config.repos_dir.join("repo");
+        std::fs::create_dir_all(&repo_path).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .current_dir(&repo_path)
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "init",
+        ]);
+        git(&["branch", "shared"]);
+
+        // A multi-repo task named after another repo has `repo` on `shared`.
+        let mut owner = create_test_task(&config, "api", "shared");
+        owner.meta.repos.push(RepoEntry {
+            repo_name: "repo".to_string(),
+            worktree_path: config.worktree_path("repo", "shared"),
+            tmux_session: Config::tmux_session_name("repo", "shared"),
         });
+        owner.save_meta().unwrap();
 
-        assert_eq!(app.project_list_len(), 0);
-        assert_eq!(app.selected_project_index, 7);
+        let mut app = App::new_for_test(config).unwrap();
+        app.refresh_tasks_for_project();
+        app.create_wizard_from_picker("repo".to_string(), repo_path.clone(), false)
+            .unwrap();
+        let wizard = app.wizard.as_mut().unwrap();
+        assert!(!wizard.existing_branches.contains(&"shared".to_string()));
+        wizard.new_branch_editor.insert_str("shared");
+        for _ in 0..2 {
+            app.wizard_next_step().unwrap();
+            let wizard = app.wizard.as_ref().unwrap();
+            assert_eq!(wizard.step, WizardStep::SelectBranch);
+            assert!(wizard
+                .error_message
+                .as_deref()
+                .is_some_and(|m| m.contains("belongs to task 'api--shared'")));
+        }
     }
 
     #[test]
-    fn stale_project_refresh_result_does_not_overwrite_current_state() {
+    fn full_log_toggle_keeps_search_and_reanchors_to_first_match() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
-        Project::create(&config, "alpha", "Alpha project").unwrap();
-        let projects = use_cases::list_projects(&config).unwrap();
+        let project = format!("repo-{}", unique_name());
+        let task = create_test_task(&config, &project, "logs");
+        let filler = "filler\n".repeat(100);
+        std::fs::write(
+            task.dir.join("agent.log"),
+            format!("early needle\n{filler}late needle\n"),
+        )
+        .unwrap();
+
         let mut app = App::new_for_test(config).unwrap();
-        app.project_refresh_generation = 2;
-        app.project_refresh_active = true;
-        app.project_task_counts = HashMap::from([("fresh".to_string(), 1)]);
-        app.unassigned_task_count = 4;
+        app.current_project = Some(project);
+        app.refresh_tasks_for_project();
+        app.load_preview();
+        app.view = View::Preview;
+        app.log_search = Some(LogSearch {
+            term: "needle".to_string(),
+            matches: Vec::new(),
+            current: None,
+        });
+        app.jump_to_log_match(true);
+        assert_eq!(app.log_search.as_ref().unwrap().matches.len(), 1);
 
-        let stale_snapshot = ProjectRefreshSnapshot {
-            projects,
-            project_task_counts: HashMap::from([("stale".to_string(), 99)]),
-            project_agent_counts: HashMap::new(),
-            project_active_agent_counts: HashMap::new(),
-            unassigned_task_count: 99,
-            agent_activity: HashMap::new(),
-            project_list_error: None,
-            agent_list_error: None,
-            agent_activity_error: None,
-        };
-        app.project_refresh_tx.send((1, stale_snapshot)).unwrap();
+        let toggle = Event::Key(event::KeyEvent::new(KeyCode::Char('T'), KeyModifiers::NONE));
+        app.handle_event(toggle.clone()).unwrap();
+        assert!(app.preview_full_log);
+        let search = app.log_search.as_ref().unwrap();
+        assert_eq!(search.matches, vec![(0, 6), (101, 5)]);
+        assert_eq!(search.current, Some(0));
+        assert_eq!(app.logs_editor.textarea.cursor(), (0, 12));
 
-        app.apply_project_refresh_result();
+        app.handle_event(toggle).unwrap();
+        assert!(!app.preview_full_log);
+        assert_eq!(app.log_search.as_ref().unwrap().matches.len(), 1);
+        assert!(app.preview_content.contains("lines trimmed"));
+    }
 
-        assert!(!app.project_refresh_active);
+    #[test]
+    fn dashboard_counts_pending_actions_and_jumps_into_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let repo = format!("repo-{}", unique_name());
+        let idle = create_test_task(&config, &repo, "idle");
+        let mut review = create_test_task(&config, &repo, "review");
+        review.meta.linked_pr = Some(LinkedPr {
+            number: 7,
+            url: "https://github.com/o/r/pull/7".to_string(),
+            owned: false,
+            author: None,
+        });
+        review.save_meta().unwrap();
+
+        let mut app = App::new_for_test(config).unwrap();
+        app.show_prs_first_poll_done = true;
+        app.notifications = [true, false]
+            .into_iter()
+            .enumerate()
+            .map(|(i, unread)| use_cases::GithubNotification {
+                id: i.to_string(),
+                repo_full_name: "o/r".to_string(),
+                title: "t".to_string(),
+                reason: "mention".to_string(),
+                subject_type: "PullRequest".to_string(),
+                updated_at: String::new(),
+                unread,
+                browser_url: String::new(),
+            })
+            .collect();
+        app.stalled_tasks.insert(idle.meta.task_id());
+        app.open_dashboard();
+        assert_eq!(app.view, View::Dashboard);
         assert_eq!(
-            app.project_task_counts,
-            HashMap::from([("fresh".to_string(), 1)])
+            app.dashboard_counts(),
+            DashboardCounts {
+                idle_tasks: 1,
+                review_tasks: 1,
+                review_requests: 0,
+                unread_notifications: 1,
+            }
         );
-        assert_eq!(app.unassigned_task_count, 4);
+
+        let key = |c| Event::Key(event::KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        app.handle_event(key('w')).unwrap();
+        assert_eq!(app.view, View::TaskList);
+        assert_eq!(
+            app.selected_task().map(|t| t.meta.task_id()),
+            Some(idle.meta.task_id())
+        );
+
+        app.open_dashboard();
+        app.handle_event(key('r')).unwrap();
+        assert!(app.review_queue_only);
+        let branches: Vec<&str> = app
+            .tasks
+            .iter()
+            .map(|t| t.meta.branch_name.as_str())
+            .collect();
+        assert_eq!(branches, vec!["review"]);
     }
 
     #[test]
-    fn project_list_opens_global_notes_and_returns_to_project_list() {
+    fn preview_position_is_restored_per_task_unless_left_at_bottom() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
-        Project::create(&config, "alpha", "Alpha project").unwrap();
-        Project::create(&config, "beta", "Beta project").unwrap();
-        let mut app = App::new_for_test(config.clone()).unwrap();
-        app.refresh_projects();
-        app.selected_project_index = app
-            .projects
-            .iter()
-            .position(|project| project.meta.name == "beta")
-            .unwrap();
+        let project = format!("repo-{}", unique_name());
+        let task = create_test_task(&config, &project, "logs");
+        std::fs::write(task.dir.join("agent.log"), "line\n".repeat(20)).unwrap();
 
-        app.handle_event(Event::Key(event::KeyEvent::new(
-            KeyCode::Char('o'),
-            KeyModifiers::NONE,
-        )))
-        .unwrap();
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project);
+        app.refresh_tasks_for_project();
+        app.load_preview();
+        app.view = View::Preview;
+        app.logs_editor.move_cursor(CursorMove::Jump(3, 2));
+        let quit = Event::Key(event::KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        app.handle_event(quit.clone()).unwrap();
+        assert_eq!(app.view, View::TaskList);
+        assert_eq!(app.preview_positions.len(), 1);
 
-        assert_eq!(app.view, View::Notes);
-        assert_eq!(app.notes_return_view, View::ProjectList);
-        assert_eq!(app.notes_view.as_ref().unwrap().root_dir, config.notes_dir);
+        app.load_preview();
+        assert_eq!(app.logs_editor.textarea.cursor().0, 19);
+        app.restore_preview_position();
+        assert_eq!(app.logs_editor.textarea.cursor(), (3, 2));
 
-        app.handle_event(Event::Key(event::KeyEvent::new(
-            KeyCode::Esc,
-            KeyModifiers::NONE,
-        )))
-        .unwrap();
+        // Leaving from the bottom forgets the position again.
+        app.view = View::Preview;
+        app.logs_editor.move_cursor(CursorMove::Bottom);
+        app.handle_event(quit).unwrap();
+        assert!(app.preview_positions.is_empty());
+    }
+
+    #[test]
+    fn last_error_outlives_output_and_pins_until_dismissed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut app = App::new_for_test(test_config(tmp.path())).unwrap();
+
+        app.log_output("Creating task repo--feat...".to_string());
+        assert_eq!(app.last_error, None);
+        app.log_output("  Error: worktree already exists".to_string());
+        app.log_output("  Launching engineer via supervisor...".to_string());
+        app.output_log.clear();
+        assert_eq!(
+            app.last_error.as_deref(),
+            Some("Error: worktree already exists")
+        );
 
-        assert_eq!(app.view, View::ProjectList);
-        assert!(app.notes_view.is_none());
+        app.copy_last_error();
+        assert_eq!(
+            app.pinned_error.as_deref(),
+            Some("Error: worktree already exists")
+        );
+        app.copy_last_error();
+        assert_eq!(app.pinned_error, None);
     }
 
     #[test]
-    fn project_list_opens_global_notes_for_unassigned() {
+    fn paste_fills_single_line_inputs_without_line_breaks() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
-        Project::create(&config, "alpha", "Alpha project").unwrap();
-        let mut app = App::new_for_test(config.clone()).unwrap();
-        app.refresh_projects();
-        app.unassigned_task_count = 1;
-        app.selected_project_index = app.projects.len();
+        let mut app = App::new_for_test(config).unwrap();
 
-        app.handle_event(Event::Key(event::KeyEvent::new(
-            KeyCode::Char('o'),
-            KeyModifiers::NONE,
-        )))
-        .unwrap();
+        app.view = View::EditLabel;
+        app.handle_event(Event::Paste("fix login\n".to_string()))
+            .unwrap();
+        assert_eq!(app.label_editor.lines(), ["fix login"]);
 
-        assert_eq!(app.view, View::Notes);
-        assert_eq!(app.notes_return_view, View::ProjectList);
-        assert_eq!(app.notes_view.as_ref().unwrap().root_dir, config.notes_dir);
+        app.view = View::CloneRepo;
+        app.handle_event(Event::Paste("git@github.com:acme/api.git".to_string()))
+            .unwrap();
+        assert_eq!(
+            app.clone_url_editor.lines(),
+            ["git@github.com:acme/api.git"]
+        );
     }
 
     #[test]
-    fn task_list_lowercase_o_opens_project_notes_and_returns_to_task_list() {
+    fn task_attention_counts_idle_and_stopped_engineers() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
-        Project::create(&config, "alpha", "Alpha project").unwrap();
-        let mut app = App::new_for_test(config.clone()).unwrap();
-        app.current_project = Some("alpha".to_string());
-        app.view = View::TaskList;
-
-        app.handle_event(Event::Key(event::KeyEvent::new(
-            KeyCode::Char('o'),
-            KeyModifiers::NONE,
-        )))
-        .unwrap();
+        let project = format!("repo-{}", unique_name());
+        let task = create_test_task(&config, &project, "attn");
 
-        assert_eq!(app.view, View::Notes);
-        assert_eq!(app.notes_return_view, View::TaskList);
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project);
+        app.refresh_tasks_for_project();
+        let engineer = app.attached_task_agents[&task.meta.task_id()]
+            .iter()
+            .find(|a| a.is_engineer())
+            .unwrap()
+            .clone();
+        let session = App::agent_session_name(&engineer);
+
+        app.agent_activity.remove(&session);
+        assert_eq!(app.task_attention(), TaskAttention::default());
+
+        app.agent_activity.insert(
+            session.clone(),
+            AgentActivitySample {
+                last_tmux_activity_epoch: Some(1),
+                last_observed_work_at: Instant::now().checked_sub(Duration::from_secs(600)),
+                foreground_command: "claude".to_string(),
+                pane_dead: false,
+                query_ok: true,
+            },
+        );
         assert_eq!(
-            app.notes_view.as_ref().unwrap().root_dir,
-            config.project_notes_dir("alpha")
+            app.task_attention(),
+            TaskAttention {
+                waiting: 1,
+                stopped: 0
+            }
         );
 
-        app.handle_event(Event::Key(event::KeyEvent::new(
-            KeyCode::Char('q'),
-            KeyModifiers::NONE,
-        )))
-        .unwrap();
+        app.agent_activity
+            .get_mut(&session)
+            .unwrap()
+            .foreground_command = "zsh".to_string();
+        assert_eq!(app.task_attention().stopped, 1);
 
-        assert_eq!(app.view, View::TaskList);
-        assert!(app.notes_view.is_none());
+        app.agent_activity.get_mut(&session).unwrap().query_ok = false;
+        assert_eq!(app.task_attention(), TaskAttention::default());
     }
 
     #[test]
-    fn task_list_lowercase_o_opens_project_notes_even_when_task_has_linked_pr() {
+    fn silent_engineer_flags_task_idle_only_past_threshold() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
-        let unique = unique_name();
-        let project = format!("repo-{unique}");
-        let branch = format!("branch-{unique}");
-        let mut task = create_test_task(&config, &project, &branch);
-        task.meta.linked_pr = Some(LinkedPr {
-            number: 42,
-            url: "https://github.com/example/repo/pull/42".to_string(),
-            owned: true,
-            author: None,
-        });
-        task.save_meta().unwrap();
+        let project = format!("repo-{}", unique_name());
+        let task = create_test_task(&config, &project, "quiet");
+        use_cases::set_config_value(&config, "agent_idle_secs", "0").unwrap();
+        assert_eq!(use_cases::load_idle_settings(&config).threshold, None);
+        use_cases::set_config_value(&config, "agent_idle_secs", "300").unwrap();
 
-        let mut app = App::new_for_test(config.clone()).unwrap();
-        app.current_project = Some(project.clone());
-        app.view = View::TaskList;
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project);
         app.refresh_tasks_for_project();
+        let session = app.task_engineer_session(&task.meta.task_id()).unwrap();
+        let silent_for = |secs| AgentActivitySample {
+            last_tmux_activity_epoch: Some(1),
+            last_observed_work_at: Instant::now().checked_sub(Duration::from_secs(secs)),
+            foreground_command: "claude".to_string(),
+            pane_dead: false,
+            query_ok: true,
+        };
 
-        app.handle_event(Event::Key(event::KeyEvent::new(
-            KeyCode::Char('o'),
-            KeyModifiers::NONE,
-        )))
-        .unwrap();
+        app.agent_activity.insert(session.clone(), silent_for(60));
+        app.refresh_stalled_tasks();
+        assert!(app.stalled_tasks.is_empty());
 
-        assert_eq!(app.view, View::Notes);
-        assert_eq!(app.notes_return_view, View::TaskList);
-        assert_eq!(
-            app.notes_view.as_ref().unwrap().root_dir,
-            config.project_notes_dir(&project)
-        );
+        app.agent_activity.insert(session.clone(), silent_for(600));
+        app.refresh_stalled_tasks();
+        assert!(app.stalled_tasks.contains(&task.meta.task_id()));
+
+        // Filtered out of the visible list (another project selected).
+        app.tasks.clear();
+        app.refresh_stalled_tasks();
+        assert!(app.stalled_tasks.contains(&task.meta.task_id()));
+
+        app.agent_activity.insert(session, silent_for(1));
+        app.refresh_stalled_tasks();
+        assert!(app.stalled_tasks.is_empty());
     }
 
     #[test]
-    fn refresh_tasks_for_project_preserves_attached_agent_row_selection() {
+    fn pinned_tasks_render_in_their_own_section_first() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
-        let unique = unique_name();
-        let project = format!("repo-{unique}");
-        let branch = format!("branch-{unique}");
-        let task = create_test_task(&config, &project, &branch);
-        let task_id = task.meta.task_id();
-        let researcher = AgentRecord::create(
-            &config,
-            &project,
-            "research-agent-window-names",
-            "test researcher",
-            AgentKind::Researcher {
-                repo: None,
-                branch: None,
-                task_id: None,
-            },
-        )
-        .unwrap();
-        use_cases::attach_agent_to_task(&config, &project, &researcher.meta.name, &task_id, None)
+        let project = format!("repo-{}", unique_name());
+        create_test_task(&config, &project, "a-first");
+        let mut pinned = create_test_task(&config, &project, "z-pinned");
+        pinned.meta.pinned = true;
+        pinned.save_meta().unwrap();
+
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project);
+        app.refresh_tasks_for_project();
+
+        assert_eq!(app.tasks[0].meta.branch_name, "z-pinned");
+        let rows = app.project_detail_rows();
+        let pinned_header = rows
+            .iter()
+            .position(|row| matches!(row, ProjectDetailRow::PinnedTasksHeader))
+            .unwrap();
+        let other_header = rows
+            .iter()
+            .position(|row| matches!(row, ProjectDetailRow::OtherTasksHeader))
             .unwrap();
+        assert!(matches!(
+            rows[pinned_header + 1],
+            ProjectDetailRow::Task(ProjectTaskRow::Task { task, .. }) if task.meta.pinned
+        ));
+        assert!(matches!(
+            rows[other_header + 1],
+            ProjectDetailRow::Task(ProjectTaskRow::Task { task, .. }) if !task.meta.pinned
+        ));
+    }
+
+    #[test]
+    fn missing_worktree_is_flagged_and_blocks_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let project = format!("repo-{}", unique_name());
+        create_test_task(&config, &project, "intact");
+        let gone = create_test_task(&config, &project, "gone");
+        std::fs::remove_dir_all(&gone.meta.primary_repo().worktree_path).unwrap();
 
         let mut app = App::new_for_test(config).unwrap();
-        app.current_project = Some(project.clone());
+        app.current_project = Some(project);
         app.refresh_tasks_for_project();
+        assert_eq!(app.missing_worktrees, HashSet::from([gone.meta.task_id()]));
+
         app.selected_index = app
             .project_detail_rows()
             .iter()
             .position(|row| {
                 matches!(
                     row,
-                    ProjectDetailRow::AttachedAgent(ProjectTaskRow::Agent { agent, .. })
-                        if agent.meta.name == "research-agent-window-names"
+                    ProjectDetailRow::Task(ProjectTaskRow::Task { task, .. })
+                        if task.meta.branch_name == "gone"
                 )
             })
-            .expect("attached researcher row exists");
-
-        app.refresh_tasks_for_project();
-
-        assert!(matches!(
-            app.selected_project_task_row(),
-            Some(ProjectTaskRow::Agent { agent, .. })
-                if agent.meta.name == "research-agent-window-names"
-        ));
+            .unwrap();
+        assert!(app.selected_task_worktree_missing());
+        let (status, _) = app.status_message.clone().unwrap();
+        assert!(status.contains("press W"), "{status}");
     }
 
     #[test]
-    fn refresh_tasks_for_project_falls_back_to_parent_task_when_agent_row_disappears() {
+    fn snoozed_task_is_hidden_until_shown_or_woken() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
-        let unique = unique_name();
-        let project = format!("repo-{unique}");
-        let branch = format!("branch-{unique}");
-        let task = create_test_task(&config, &project, &branch);
-        let task_id = task.meta.task_id();
-        let researcher = AgentRecord::create(
-            &config,
-            &project,
-            "research-agent-window-names",
-            "test researcher",
-            AgentKind::Researcher {
-                repo: None,
-                branch: None,
-                task_id: None,
-            },
-        )
-        .unwrap();
-        use_cases::attach_agent_to_task(&config, &project, &researcher.meta.name, &task_id, None)
-            .unwrap();
+        let project = format!("repo-{}", unique_name());
+        create_test_task(&config, &project, "awake");
+        let sleepy = create_test_task(&config, &project, "sleepy");
 
-        let mut app = App::new_for_test(config.clone()).unwrap();
-        app.current_project = Some(project.clone());
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project);
         app.refresh_tasks_for_project();
         app.selected_index = app
             .project_detail_rows()
@@ -6183,63 +10068,119 @@ mod tests {
             .position(|row| {
                 matches!(
                     row,
-                    ProjectDetailRow::AttachedAgent(ProjectTaskRow::Agent { agent, .. })
-                        if agent.meta.name == "research-agent-window-names"
+                    ProjectDetailRow::Task(ProjectTaskRow::Task { task, .. })
+                        if task.meta.branch_name == "sleepy"
                 )
             })
-            .expect("attached researcher row exists");
+            .unwrap();
 
-        use_cases::detach_agent_from_task(&config, &project, &researcher.meta.name).unwrap();
-        app.refresh_tasks_for_project();
+        app.toggle_selected_task_snooze();
+        assert_eq!(app.snoozed_task_count, 1);
+        assert!(app.tasks.iter().all(|t| t.meta.branch_name != "sleepy"));
+        let stored = Task::load_by_id(&app.config, &sleepy.meta.task_id()).unwrap();
+        assert!(stored.meta.is_snoozed(chrono::Utc::now()));
 
-        assert!(matches!(
-            app.selected_project_task_row(),
-            Some(ProjectTaskRow::Task { task, .. }) if task.meta.task_id() == task_id
-        ));
+        app.show_snoozed = true;
+        app.refresh_tasks_for_project();
+        assert!(app.tasks.iter().any(|t| t.meta.branch_name == "sleepy"));
     }
 
     #[test]
-    fn refresh_tasks_preserves_attached_agent_row_selection() {
+    fn repo_filter_scopes_task_list_by_exact_repo_and_toggles_off() {
         let tmp = tempfile::tempdir().unwrap();
         let config = test_config(tmp.path());
         let unique = unique_name();
-        let project = format!("repo-{unique}");
-        let branch = format!("branch-{unique}");
-        let task = create_test_task(&config, &project, &branch);
-        let task_id = task.meta.task_id();
-        let reviewer = AgentRecord::create(
-            &config,
-            &project,
-            "reviewer-pr5590-ltv",
-            "test reviewer",
-            AgentKind::Reviewer {
-                worktrees: Vec::new(),
-            },
-        )
-        .unwrap();
-        use_cases::attach_agent_to_task(&config, &project, &reviewer.meta.name, &task_id, None)
-            .unwrap();
+        let repo = format!("api-{unique}");
+        create_test_task(&config, &repo, "one");
+        create_test_task(&config, &repo, "two");
+        create_test_task(&config, &format!("{repo}-gateway"), &repo);
 
         let mut app = App::new_for_test(config).unwrap();
-        app.refresh_tasks();
-        app.selected_index = app
+        app.current_project = None;
+        app.refresh_tasks_for_project();
+        assert_eq!(app.tasks.len(), 3);
+        let task_row = app
             .project_detail_rows()
             .iter()
             .position(|row| {
                 matches!(
                     row,
-                    ProjectDetailRow::AttachedAgent(ProjectTaskRow::Agent { agent, .. })
-                        if agent.meta.name == "reviewer-pr5590-ltv"
+                    ProjectDetailRow::Task(ProjectTaskRow::Task { task, .. }) if task.meta.name == repo
                 )
             })
-            .expect("attached reviewer row exists");
+            .unwrap();
+        app.selected_index = task_row;
 
-        app.refresh_tasks();
+        app.toggle_repo_filter();
+        assert_eq!(app.repo_filter.as_deref(), Some(repo.as_str()));
+        assert_eq!(app.tasks.len(), 2);
+        assert!(app.tasks.iter().all(|t| t.meta.name == repo));
+        assert_eq!(app.all_tasks.len(), 3);
 
-        assert!(matches!(
-            app.selected_project_task_row(),
-            Some(ProjectTaskRow::Agent { agent, .. }) if agent.meta.name == "reviewer-pr5590-ltv"
-        ));
+        app.toggle_repo_filter();
+        assert!(app.repo_filter.is_none());
+        assert_eq!(app.tasks.len(), 3);
+    }
+
+    #[test]
+    fn review_queue_lists_only_tasks_with_others_prs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let repo = format!("repo-{}", unique_name());
+        for (branch, owned) in [
+            ("mine", Some(true)),
+            ("theirs", Some(false)),
+            ("bare", None),
+        ] {
+            let mut task = create_test_task(&config, &repo, branch);
+            task.meta.linked_pr = owned.map(|owned| LinkedPr {
+                number: 7,
+                url: "https://github.com/o/r/pull/7".to_string(),
+                owned,
+                author: None,
+            });
+            task.save_meta().unwrap();
+        }
+
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(repo);
+        app.refresh_tasks_for_project();
+        assert_eq!(app.tasks.len(), 3);
+
+        app.toggle_review_queue();
+        let branches: Vec<&str> = app
+            .tasks
+            .iter()
+            .map(|t| t.meta.branch_name.as_str())
+            .collect();
+        assert_eq!(branches, vec!["theirs"]);
+
+        app.toggle_review_queue();
+        assert_eq!(app.tasks.len(), 3);
+    }
+
+    #[test]
+    fn resuming_auto_refresh_picks_up_new_tasks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        use_cases::set_config_value(&config, "refresh_secs", "5").unwrap();
+        let repo = format!("repo-{}", unique_name());
+        create_test_task(&config, &repo, "first");
+
+        let mut app = App::new_for_test(config.clone()).unwrap();
+        assert_eq!(app.refresh_interval, Duration::from_secs(5));
+        app.current_project = Some(repo.clone());
+        app.view = View::TaskList;
+        app.refresh_tasks_for_project();
+
+        app.toggle_auto_refresh();
+        assert!(app.auto_refresh_paused);
+        create_test_task(&config, &repo, "second");
+        assert_eq!(app.tasks.len(), 1);
+
+        app.toggle_auto_refresh();
+        assert!(!app.auto_refresh_paused);
+        assert_eq!(app.tasks.len(), 2);
     }
 
     #[test]
@@ -6597,6 +10538,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shift_a_archives_selected_task_without_confirm() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        let unique = unique_name();
+        let project = format!("repo-{unique}");
+        let task = create_test_task(&config, &project, &format!("branch-{unique}"));
+        let task_id = task.meta.task_id();
+
+        let mut app = App::new_for_test(config).unwrap();
+        app.current_project = Some(project);
+        app.view = View::TaskList;
+        app.refresh_tasks_for_project();
+        app.refresh_agents();
+        app.selected_index = app
+            .project_detail_rows()
+            .iter()
+            .position(|row| matches!(row, ProjectDetailRow::Task(ProjectTaskRow::Task { .. })))
+            .unwrap();
+
+        app.handle_event(Event::Key(event::KeyEvent::new(
+            KeyCode::Char('A'),
+            KeyModifiers::SHIFT,
+        )))
+        .unwrap();
+
+        assert_eq!(app.view, View::TaskList);
+        assert!(app.archive_in_progress.contains(&task_id));
+        assert!(!app.tasks.iter().any(|t| t.meta.task_id() == task_id));
+    }
+
     #[test]
     fn archive_task_inserts_task_id_into_archive_in_progress() {
         let tmp = tempfile::tempdir().unwrap();
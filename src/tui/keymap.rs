@@ -0,0 +1,405 @@
+//! Per-view keybinding reference. The `?` help overlay lists every key; the
+//! status bar shows the ones carrying a `hint`.
+
+use super::app::View;
+
+/// Status-bar colour class of a hinted key.
+#[derive(Clone, Copy)]
+pub enum HintKind {
+    /// Movement and leaving the view.
+    Nav,
+    /// Opening or creating something.
+    Open,
+    Misc,
+    /// Acting on an engineer or agent.
+    Agent,
+    /// Destructive.
+    Danger,
+}
+
+/// One key of a view.
+pub struct KeyBinding {
+    pub key: &'static str,
+    /// Description shown in the help overlay.
+    pub action: &'static str,
+    /// Short status-bar label; `None` keeps the key overlay-only.
+    pub hint: Option<(&'static str, HintKind)>,
+}
+
+const fn key(key: &'static str, action: &'static str) -> KeyBinding {
+    KeyBinding {
+        key,
+        action,
+        hint: None,
+    }
+}
+
+const fn hint(
+    key: &'static str,
+    action: &'static str,
+    label: &'static str,
+    kind: HintKind,
+) -> KeyBinding {
+    KeyBinding {
+        key,
+        action,
+        hint: Some((label, kind)),
+    }
+}
+
+/// A titled group of keys.
+pub struct HelpSection {
+    pub title: &'static str,
+    pub keys: &'static [KeyBinding],
+}
+
+static GLOBAL: HelpSection = HelpSection {
+    title: "Global",
+    keys: &[key("?", "toggle this help"), key("Ctrl+C", "quit agman")],
+};
+
+const PROJECT_LIST: &[HelpSection] = &[
+    HelpSection {
+        title: "Navigation",
+        keys: &[
+            hint("j/k", "move selection", "nav", HintKind::Nav),
+            key("g/G", "first / last project"),
+            hint("Enter/l", "open project", "open", HintKind::Open),
+        ],
+    },
+    HelpSection {
+        title: "Projects",
+        keys: &[
+            hint("n", "new project", "new", HintKind::Open),
+            hint("d", "delete project", "delete", HintKind::Danger),
+            hint("h", "put on hold / resume", "hold", HintKind::Misc),
+            hint("m", "migrate unassigned tasks", "migrate", HintKind::Agent),
+        ],
+    },
+    HelpSection {
+        title: "Chief of Staff",
+        keys: &[
+            hint("c", "chat", "CoS chat", HintKind::Misc),
+            hint("e", "respawn", "respawn", HintKind::Agent),
+        ],
+    },
+    HelpSection {
+        title: "Views",
+        keys: &[
+            hint("o", "global notes", "notes", HintKind::Misc),
+            hint("i", "GitHub notifications", "inbox", HintKind::Misc),
+            hint("p", "my issues & PRs", "prs", HintKind::Misc),
+            hint("r", "repo stats", "repos", HintKind::Misc),
+            hint(",", "settings", "settings", HintKind::Misc),
+            hint("b", "reset break timer", "break reset", HintKind::Misc),
+            key("Space", "pause / resume auto-refresh"),
+            key("Y", "copy & pin last error / dismiss"),
+        ],
+    },
+];
+
+const TASK_LIST: &[HelpSection] = &[
+    HelpSection {
+        title: "Navigation",
+        keys: &[
+            hint("j/k", "move selection", "nav", HintKind::Nav),
+            key("g/G", "first / last row"),
+            hint(
+                "Tab/S-Tab",
+                "next / previous section",
+                "section",
+                HintKind::Nav,
+            ),
+            hint(
+                "Enter",
+                "preview task / chat with agent",
+                "preview",
+                HintKind::Open,
+            ),
+            hint("q/Esc", "back to projects", "back", HintKind::Nav),
+        ],
+    },
+    HelpSection {
+        title: "Create",
+        keys: &[
+            hint("n", "new task", "new task", HintKind::Open),
+            hint("a", "new agent", "new agent", HintKind::Open),
+        ],
+    },
+    HelpSection {
+        title: "Selected task",
+        keys: &[
+            hint("r", "relaunch engineer", "rerun", HintKind::Agent),
+            hint("R", "rename branch", "rename branch", HintKind::Agent),
+            hint("L", "set / clear display label", "label", HintKind::Agent),
+            hint(
+                "H",
+                "messages sent to the engineer",
+                "messages",
+                HintKind::Agent,
+            ),
+            key("m", "quick answer to the engineer (empty: carry on)"),
+            key("K", "compact the engineer's context"),
+            key("+", "add a sibling repo (makes it multi-repo)"),
+            hint("p", "open linked PR", "open pr", HintKind::Misc),
+            hint(
+                "C",
+                "mark / compare with marked",
+                "mark compare",
+                HintKind::Agent,
+            ),
+            hint("P", "pin / unpin", "pin", HintKind::Misc),
+            hint(
+                "F",
+                "only this repo / clear filter",
+                "this repo only",
+                HintKind::Misc,
+            ),
+            hint("s", "snooze for 4h / wake", "snooze", HintKind::Misc),
+            key("S", "show / hide snoozed tasks"),
+            key("O", "cycle task sort order"),
+            key("V", "only tasks reviewing others' PRs"),
+            hint(
+                "W",
+                "recreate missing worktree / relocate moved repo",
+                "recreate worktree",
+                HintKind::Danger,
+            ),
+            key("Z", "snapshot HEAD to a tag"),
+            key("U", "snapshots / restore"),
+            hint("d", "archive", "archive", HintKind::Danger),
+            key("A", "archive now (no confirm)"),
+        ],
+    },
+    HelpSection {
+        title: "Project",
+        keys: &[
+            hint("c", "PM chat", "PM chat", HintKind::Misc),
+            hint("o", "project notes", "notes", HintKind::Misc),
+            hint("e", "respawn PM", "respawn", HintKind::Agent),
+            hint("z", "archived tasks / agents", "archived", HintKind::Misc),
+            key("b", "reset break timer"),
+            key("Space", "pause / resume auto-refresh"),
+            key("Y", "copy & pin last error / dismiss"),
+        ],
+    },
+];
+
+const PREVIEW: &[HelpSection] = &[
+    HelpSection {
+        title: "Panes",
+        keys: &[
+            hint("Tab", "switch logs / notes", "pane", HintKind::Nav),
+            key("j/k", "scroll"),
+            hint("Enter", "attach to tmux session", "attach", HintKind::Nav),
+            hint("q/Esc", "back to task list", "back", HintKind::Nav),
+        ],
+    },
+    HelpSection {
+        title: "Logs",
+        keys: &[
+            hint("/", "search", "search", HintKind::Misc),
+            hint("n/N", "next / previous match", "next/prev", HintKind::Misc),
+            hint("O", "open in $PAGER", "pager", HintKind::Misc),
+            hint(
+                "L",
+                "live view of the engineer pane",
+                "live",
+                HintKind::Misc,
+            ),
+            hint(
+                "T",
+                "toggle log tail / full log (keeps search)",
+                "full log",
+                HintKind::Misc,
+            ),
+            hint(
+                "M",
+                "cycle filter: all / no tools / errors / final",
+                "filter",
+                HintKind::Misc,
+            ),
+            hint("F", "only this repo", "this repo only", HintKind::Misc),
+            hint("B", "edit base ref", "base ref", HintKind::Misc),
+            key("A", "archive task (no confirm)"),
+        ],
+    },
+    HelpSection {
+        title: "Notes",
+        keys: &[
+            key("Enter/i", "edit notes"),
+            hint(
+                "Esc",
+                "save & stop editing",
+                "save & exit editing",
+                HintKind::Open,
+            ),
+            key("Ctrl+S", "save"),
+            hint("E", "edit in $EDITOR", "$EDITOR", HintKind::Misc),
+        ],
+    },
+    HelpSection {
+        title: "Task",
+        keys: &[
+            hint("r", "relaunch engineer", "rerun", HintKind::Agent),
+            hint("p", "open linked PR", "open pr", HintKind::Misc),
+        ],
+    },
+];
+
+const NOTIFICATIONS: &[HelpSection] = &[HelpSection {
+    title: "Notifications",
+    keys: &[
+        hint("j/k", "move selection", "nav", HintKind::Nav),
+        hint("o", "open in browser", "open", HintKind::Open),
+        hint("d", "mark done", "done", HintKind::Danger),
+        hint("D", "mark all done", "done all", HintKind::Danger),
+        hint("s", "snooze for 4h", "snooze", HintKind::Misc),
+        hint("m", "mark all read", "read all", HintKind::Misc),
+        hint("f", "cycle reason filter", "filter", HintKind::Agent),
+        hint("q/Esc", "back", "back", HintKind::Nav),
+    ],
+}];
+
+const SHOW_PRS: &[HelpSection] = &[HelpSection {
+    title: "Issues & PRs",
+    keys: &[
+        hint("j/k", "move selection", "nav", HintKind::Nav),
+        hint("o", "open in browser", "open", HintKind::Open),
+        hint("r", "refresh", "refresh", HintKind::Misc),
+        hint("q/Esc", "back", "back", HintKind::Nav),
+    ],
+}];
+
+const NOTES: &[HelpSection] = &[
+    HelpSection {
+        title: "Explorer",
+        keys: &[
+            hint("j/k", "move selection", "nav", HintKind::Nav),
+            hint("J/K", "reorder", "reorder", HintKind::Nav),
+            hint("l", "open file / directory", "open", HintKind::Open),
+            hint("h", "parent directory", "back", HintKind::Nav),
+            hint("a", "new note", "new", HintKind::Open),
+            hint("A", "new directory", "dir", HintKind::Open),
+            hint("r", "rename", "rename", HintKind::Misc),
+            hint("x", "cut", "cut", HintKind::Misc),
+            hint("p", "paste", "paste", HintKind::Open),
+            hint("d", "delete", "del", HintKind::Danger),
+            hint("Ctrl+P", "fuzzy find a note", "find", HintKind::Nav),
+            hint("Tab", "focus editor", "editor", HintKind::Nav),
+            hint("q/Esc", "back", "back", HintKind::Nav),
+        ],
+    },
+    HelpSection {
+        title: "Editor",
+        keys: &[
+            hint(
+                "Tab",
+                "focus explorer (Normal mode)",
+                "explorer",
+                HintKind::Nav,
+            ),
+            key("Ctrl+P", "fuzzy find a note (Normal mode)"),
+            hint("Ctrl+S", "save", "save", HintKind::Open),
+            key("Ctrl+V", "paste clipboard (Insert mode)"),
+        ],
+    },
+];
+
+const COMPARE: &[HelpSection] = &[HelpSection {
+    title: "Compare",
+    keys: &[
+        hint("Tab", "switch pane", "switch pane", HintKind::Nav),
+        hint("j/k", "scroll", "scroll", HintKind::Nav),
+        hint("d/u", "page down / up", "page", HintKind::Nav),
+        hint("g/G", "top / bottom", "top/bottom", HintKind::Nav),
+        hint("q/Esc", "back", "back", HintKind::Nav),
+    ],
+}];
+
+const REPO_STATS: &[HelpSection] = &[HelpSection {
+    title: "Repo stats",
+    keys: &[
+        hint("j/k", "move selection", "nav", HintKind::Nav),
+        hint("s", "cycle sort column", "sort", HintKind::Misc),
+        hint("r", "refresh", "refresh", HintKind::Misc),
+        hint("q/Esc", "back", "back", HintKind::Nav),
+    ],
+}];
+
+const MESSAGE_HISTORY: &[HelpSection] = &[HelpSection {
+    title: "Message history",
+    keys: &[
+        hint("j/k", "scroll", "scroll", HintKind::Nav),
+        hint("g/G", "oldest / newest", "oldest/newest", HintKind::Nav),
+        hint("q/Esc", "back", "back", HintKind::Nav),
+    ],
+}];
+
+const SNAPSHOTS: &[HelpSection] = &[HelpSection {
+    title: "Snapshots",
+    keys: &[
+        hint("j/k", "move selection", "nav", HintKind::Nav),
+        hint("n", "snapshot HEAD now", "new snapshot", HintKind::Open),
+        hint(
+            "Enter",
+            "restore (git reset --hard, asks first)",
+            "restore",
+            HintKind::Misc,
+        ),
+        hint("q/Esc", "back", "back", HintKind::Nav),
+    ],
+}];
+
+const DASHBOARD: &[HelpSection] = &[HelpSection {
+    title: "Pending actions",
+    keys: &[
+        hint(
+            "w",
+            "task list at the first idle engineer",
+            "idle",
+            HintKind::Misc,
+        ),
+        hint(
+            "r",
+            "task list narrowed to the review queue",
+            "review",
+            HintKind::Misc,
+        ),
+        hint("p", "Show PRs", "prs", HintKind::Misc),
+        hint("i", "GitHub notifications", "inbox", HintKind::Misc),
+        hint(
+            "Enter/Esc",
+            "continue to projects",
+            "projects",
+            HintKind::Nav,
+        ),
+    ],
+}];
+
+/// Views that can open the help overlay with `?`.
+pub fn has_help(view: View) -> bool {
+    !sections_for(view).is_empty()
+}
+
+/// Sections for `view`, followed by the global keys.
+pub fn help_sections(view: View) -> Vec<&'static HelpSection> {
+    sections_for(view).iter().chain([&GLOBAL]).collect()
+}
+
+fn sections_for(view: View) -> &'static [HelpSection] {
+    match view {
+        View::ProjectList => PROJECT_LIST,
+        View::TaskList => TASK_LIST,
+        View::Preview => PREVIEW,
+        View::Notifications => NOTIFICATIONS,
+        View::ShowPrs => SHOW_PRS,
+        View::Notes => NOTES,
+        View::Compare => COMPARE,
+        View::RepoStats => REPO_STATS,
+        View::MessageHistory => MESSAGE_HISTORY,
+        View::Snapshots => SNAPSHOTS,
+        View::Dashboard => DASHBOARD,
+        _ => &[],
+    }
+}
@@ -1,4 +1,5 @@
 mod app;
+mod keymap;
 mod ui;
 mod vim;
 
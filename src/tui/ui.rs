@@ -8,8 +8,11 @@ use ratatui::{
 };
 
 use agman::agent_model::AgentKind;
-use agman::use_cases::{self, TelegramHealth};
+use agman::repo_stats::RepoStatsSort;
+use agman::usage;
+use agman::use_cases::{self, BreakState, TaskSort, TelegramHealth};
 
+use std::borrow::Cow;
 use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
@@ -17,6 +20,7 @@ use super::app::{
     AgentActivitySample, App, ArchiveKind, BranchSource, DirKind, DirPickerOrigin, NotesFocus,
     PreviewPane, ProjectDetailRow, ProjectTaskRow, View, WizardStep,
 };
+use super::keymap::{self, HintKind};
 use super::vim::VimMode;
 
 const PROJECT_TASK_COUNT_WIDTH: usize = 8;
@@ -113,12 +117,61 @@ fn clock_title(app: &App) -> Line<'static> {
         vec![]
     };
 
+    let break_spans =
+        match use_cases::classify_break(&app.break_settings, app.last_break_reset.elapsed()) {
+            BreakState::Hidden => vec![],
+            BreakState::Warning { remaining_secs } => vec![Span::styled(
+                format!(" ☕ {}:{:02} ", remaining_secs / 60, remaining_secs % 60),
+                Style::default().fg(Color::Rgb(255, 180, 40)),
+            )],
+            BreakState::Due => vec![Span::styled(
+                " ☕ BREAK ",
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
+            )],
+        };
+
+    let attention = app.task_attention();
+    let mut task_spans = Vec::new();
+    if attention.waiting > 0 {
+        task_spans.push(Span::styled(
+            format!(" {} WAITING ", attention.waiting),
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if attention.stopped > 0 {
+        task_spans.push(Span::styled(
+            format!(" {} STOPPED ", attention.stopped),
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if app.auto_refresh_paused {
+        task_spans.push(Span::styled(
+            " PAUSED ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let clock_span = Span::styled(
         format!(" {} ", Local::now().format("%H:%M")),
         Style::default().fg(Color::DarkGray),
     );
 
-    let mut spans = notif_spans;
+    let mut spans = task_spans;
+    spans.extend(notif_spans);
+    spans.extend(break_spans);
     spans.push(clock_span);
 
     Line::from(spans).alignment(Alignment::Right)
@@ -137,6 +190,15 @@ pub fn draw(f: &mut Frame, app: &mut App) {
             | View::ProjectDeleteConfirm
             | View::AgentWizard
             | View::RespawnConfirm
+            | View::RenameBranch
+            | View::EditBaseRef
+            | View::EditLabel
+            | View::RelocateRepo
+            | View::QuickAnswer
+            | View::AddRepo
+            | View::CloneRepo
+            | View::QuitConfirm
+            | View::Help
     );
 
     // Determine output pane height based on content (hide during modals)
@@ -171,6 +233,20 @@ pub fn draw(f: &mut Frame, app: &mut App) {
             draw_project_detail(f, app, chunks[0]);
             draw_directory_picker(f, app);
         }
+        View::Compare => draw_compare(f, app, chunks[0]),
+        View::QuitConfirm => {
+            if app.current_project.is_some() {
+                draw_project_detail(f, app, chunks[0]);
+            } else {
+                draw_project_list(f, app, chunks[0]);
+            }
+            draw_quit_confirm(f, app);
+        }
+        View::CloneRepo => {
+            draw_project_detail(f, app, chunks[0]);
+            draw_directory_picker(f, app);
+            draw_clone_repo(f, app);
+        }
         View::SessionPicker => {
             draw_preview(f, app, chunks[0]);
             draw_session_picker(f, app);
@@ -219,6 +295,51 @@ pub fn draw(f: &mut Frame, app: &mut App) {
             }
             draw_respawn_confirm(f, app);
         }
+        View::RepoStats => draw_repo_stats(f, app, chunks[0]),
+        View::MessageHistory => draw_message_history(f, app, chunks[0]),
+        View::Snapshots => draw_snapshots(f, app, chunks[0]),
+        View::Dashboard => draw_dashboard(f, app, chunks[0]),
+        View::Help => {
+            // Draw the view the help describes behind the overlay
+            match app.help_return_view {
+                View::ProjectList => draw_project_list(f, app, chunks[0]),
+                View::Preview => draw_preview(f, app, chunks[0]),
+                View::Notifications => draw_notifications(f, app, chunks[0]),
+                View::ShowPrs => draw_show_prs(f, app, chunks[0]),
+                View::Notes => draw_notes(f, app, chunks[0]),
+                View::Compare => draw_compare(f, app, chunks[0]),
+                View::RepoStats => draw_repo_stats(f, app, chunks[0]),
+                View::MessageHistory => draw_message_history(f, app, chunks[0]),
+                View::Snapshots => draw_snapshots(f, app, chunks[0]),
+                View::Dashboard => draw_dashboard(f, app, chunks[0]),
+                _ => draw_project_detail(f, app, chunks[0]),
+            }
+            draw_help(f, app);
+        }
+        View::RenameBranch => {
+            draw_project_detail(f, app, chunks[0]);
+            draw_rename_branch(f, app);
+        }
+        View::EditBaseRef => {
+            draw_preview(f, app, chunks[0]);
+            draw_edit_base_ref(f, app);
+        }
+        View::EditLabel => {
+            draw_project_detail(f, app, chunks[0]);
+            draw_edit_label(f, app);
+        }
+        View::RelocateRepo => {
+            draw_project_detail(f, app, chunks[0]);
+            draw_relocate_repo(f, app);
+        }
+        View::QuickAnswer => {
+            draw_project_detail(f, app, chunks[0]);
+            draw_quick_answer(f, app);
+        }
+        View::AddRepo => {
+            draw_project_detail(f, app, chunks[0]);
+            draw_add_repo(f, app);
+        }
     }
 
     if output_height > 0 {
@@ -1132,6 +1253,8 @@ struct TaskColumnWidths {
     repo: usize,
     branch: usize,
     pr: usize,
+    /// Zero hides the COST column (`show_cost_column = false`).
+    cost: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -1179,6 +1302,7 @@ fn project_task_column_widths(app: &App, area: Rect) -> TaskColumnWidths {
     const MIN_BRANCH_WIDTH: usize = 6;
     const PR_WIDTH: usize = 25;
     const UPDATED_WIDTH: usize = 10;
+    const COST_WIDTH: usize = 10;
 
     let max_repo_len = app
         .tasks
@@ -1197,17 +1321,25 @@ fn project_task_column_widths(app: &App, area: Rect) -> TaskColumnWidths {
     let max_branch_len = app
         .tasks
         .iter()
-        .map(|t| t.meta.branch_name.len())
+        .map(|t| t.meta.display_name().len())
         .max()
         .unwrap_or(MIN_BRANCH_WIDTH);
     let branch_width = max_branch_len.max(MIN_BRANCH_WIDTH);
-    let fixed_cols_width = (5 + 12 + repo_width + PR_WIDTH + UPDATED_WIDTH) as u16;
+    let cost_width = if app.usage_settings.show_cost_column {
+        COST_WIDTH
+    } else {
+        0
+    };
+    let cost_cols_width = if cost_width > 0 { cost_width + 4 } else { 0 };
+    let fixed_cols_width =
+        (5 + 12 + repo_width + PR_WIDTH + UPDATED_WIDTH + cost_cols_width) as u16;
     let available_width = area.width.saturating_sub(fixed_cols_width) as usize;
 
     TaskColumnWidths {
         repo: repo_width,
         branch: branch_width.min(available_width.max(MIN_BRANCH_WIDTH)),
         pr: PR_WIDTH,
+        cost: cost_width,
     }
 }
 
@@ -1270,7 +1402,39 @@ fn project_detail_list_item(
             ListItem::new(project_section_separator(list_width))
         }
         ProjectDetailRow::SectionSpacer | ProjectDetailRow::TaskGroupSpacer => ListItem::new(""),
-        ProjectDetailRow::TasksSectionHeader => ListItem::new(project_tasks_section_header()),
+        ProjectDetailRow::TasksSectionHeader => {
+            let mut header = project_tasks_section_header();
+            if let Some(repo) = &app.repo_filter {
+                header.spans.push(Span::styled(
+                    format!("  [repo: {}]", repo),
+                    Style::default().fg(Color::LightYellow),
+                ));
+            }
+            if app.review_queue_only {
+                header.spans.push(Span::styled(
+                    "  [review queue]",
+                    Style::default().fg(Color::LightYellow),
+                ));
+            }
+            if app.task_sort != TaskSort::default() {
+                header.spans.push(Span::styled(
+                    format!("  [sort: {}]", app.task_sort.label()),
+                    Style::default().fg(Color::LightYellow),
+                ));
+            }
+            if app.snoozed_task_count > 0 {
+                header.spans.push(Span::styled(
+                    format!("  [{} snoozed, S to show]", app.snoozed_task_count),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            } else if app.show_snoozed {
+                header.spans.push(Span::styled(
+                    "  [showing snoozed]",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(header)
+        }
         ProjectDetailRow::TasksColumnsHeader => {
             ListItem::new(project_tasks_columns_header(task_widths))
         }
@@ -1278,9 +1442,22 @@ fn project_detail_list_item(
             "  No tasks",
             Style::default().fg(Color::DarkGray),
         ))),
-        ProjectDetailRow::Task(ProjectTaskRow::Task { task, .. }) => {
-            project_task_row(task, row_index == app.selected_index, task_widths)
-        }
+        ProjectDetailRow::Task(ProjectTaskRow::Task { task, .. }) => project_task_row(
+            task,
+            row_index == app.selected_index,
+            app.missing_worktrees.contains(&task.meta.task_id()),
+            app.stalled_tasks.contains(&task.meta.task_id()),
+            app.input_needed_tasks.contains_key(&task.meta.task_id()),
+            task_widths,
+        ),
+        ProjectDetailRow::PinnedTasksHeader => ListItem::new(Line::from(Span::styled(
+            "  PINNED",
+            Style::default().fg(Color::LightYellow),
+        ))),
+        ProjectDetailRow::OtherTasksHeader => ListItem::new(Line::from(Span::styled(
+            "  OTHER",
+            Style::default().fg(Color::DarkGray),
+        ))),
         ProjectDetailRow::AttachedAgentsHeader => {
             ListItem::new(project_attached_agents_header(attached_agent_widths))
         }
@@ -1367,7 +1544,7 @@ fn project_tasks_columns_header(widths: TaskColumnWidths) -> Line<'static> {
     let header_style = Style::default()
         .fg(Color::White)
         .add_modifier(Modifier::BOLD);
-    Line::from(vec![
+    let mut spans = vec![
         Span::raw(" ".repeat(PROJECT_TASK_PREFIX_WIDTH)),
         Span::styled(
             format!("{:<width$}", "REPO", width = widths.repo),
@@ -1381,8 +1558,16 @@ fn project_tasks_columns_header(widths: TaskColumnWidths) -> Line<'static> {
         Span::raw(COL_GAP),
         Span::styled(format!("{:<width$}", "PR", width = widths.pr), header_style),
         Span::raw(COL_GAP),
-        Span::styled("UPDATED", header_style),
-    ])
+    ];
+    if widths.cost > 0 {
+        spans.push(Span::styled(
+            format!("{:<width$}", "COST", width = widths.cost),
+            header_style,
+        ));
+        spans.push(Span::raw(COL_GAP));
+    }
+    spans.push(Span::styled("UPDATED", header_style));
+    Line::from(spans)
 }
 
 fn project_attached_agents_header(widths: AttachedAgentColumnWidths) -> Line<'static> {
@@ -1553,9 +1738,35 @@ fn project_attached_agent_line(
 fn project_task_row(
     task: &agman::task::Task,
     is_selected: bool,
+    worktree_missing: bool,
+    stalled: bool,
+    input_needed: bool,
     widths: TaskColumnWidths,
 ) -> ListItem<'static> {
-    let line = project_task_line(task, is_selected, widths);
+    let mut line = project_task_line(task, is_selected, widths);
+    if worktree_missing {
+        line.spans.push(Span::styled(
+            "  ⚠ no worktree",
+            Style::default().fg(Color::LightRed),
+        ));
+    }
+    if input_needed {
+        line.spans.push(Span::styled(
+            "  input?",
+            Style::default().fg(Color::LightMagenta),
+        ));
+    } else if stalled {
+        line.spans.push(Span::styled(
+            "  idle?",
+            Style::default().fg(Color::LightYellow),
+        ));
+    }
+    if let Some(until) = task.meta.snoozed_until.filter(|u| *u > Utc::now()) {
+        line.spans.push(Span::styled(
+            format!("  zz until {}", until.with_timezone(&Local).format("%H:%M")),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
     let style = if is_selected {
         Style::default().bg(Color::Rgb(40, 40, 50))
     } else {
@@ -1576,7 +1787,7 @@ fn project_task_line(
         task.meta.name.clone()
     };
     let display_repo = truncate_to_width(&repo_label, widths.repo);
-    let display_branch = truncate_to_width(&task.meta.branch_name, widths.branch);
+    let display_branch = truncate_to_width(task.meta.display_name(), widths.branch);
     let text_color = if is_selected {
         Color::White
     } else {
@@ -1610,7 +1821,7 @@ fn project_task_line(
             .fg(Color::White)
             .add_modifier(Modifier::BOLD)
     };
-    Line::from(vec![
+    let mut spans = vec![
         Span::styled(PROJECT_TASK_PREFIX, Style::default().fg(Color::DarkGray)),
         Span::styled(
             format!("{:<width$}", display_repo, width = widths.repo),
@@ -1639,11 +1850,20 @@ fn project_task_line(
             },
         ),
         Span::raw(COL_GAP),
-        Span::styled(
-            task.time_since_update(),
-            Style::default().fg(Color::DarkGray),
-        ),
-    ])
+    ];
+    if widths.cost > 0 {
+        let cost = truncate_to_width(&usage::format_usage(&task.meta.usage()), widths.cost);
+        spans.push(Span::styled(
+            format!("{:<width$}", cost, width = widths.cost),
+            Style::default().fg(Color::LightYellow),
+        ));
+        spans.push(Span::raw(COL_GAP));
+    }
+    spans.push(Span::styled(
+        task.time_since_update(),
+        Style::default().fg(Color::DarkGray),
+    ));
+    Line::from(spans)
 }
 
 fn agent_kind_label(kind: &AgentKind) -> &'static str {
@@ -1738,14 +1958,17 @@ fn truncate_to_width(value: &str, width: usize) -> String {
 }
 
 fn draw_preview(f: &mut Frame, app: &mut App, area: Rect) {
+    // The branch/PR line only shows when the panels keep a usable height.
+    let detail_line = area.height >= 20;
+    let header_height = if detail_line { 4 } else { 3 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .constraints([Constraint::Length(header_height), Constraint::Min(0)])
         .split(area);
 
     // Task info header
     if let Some(task) = app.selected_task() {
-        let header_spans = vec![
+        let mut header_spans = vec![
             Span::styled("Task: ", Style::default().fg(Color::DarkGray)),
             Span::styled(
                 task.meta.task_id(),
@@ -1754,8 +1977,29 @@ fn draw_preview(f: &mut Frame, app: &mut App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
         ];
+        if let Some(label) = &task.meta.label {
+            header_spans.push(Span::styled(
+                format!("  \u{201c}{label}\u{201d}"),
+                Style::default().fg(Color::LightYellow),
+            ));
+        }
+        header_spans.extend([
+            Span::styled("  Base: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                task.meta
+                    .base_ref
+                    .clone()
+                    .unwrap_or_else(|| "auto".to_string()),
+                Style::default().fg(Color::LightBlue),
+            ),
+        ]);
+
+        let mut header_lines = vec![Line::from(header_spans)];
+        if detail_line {
+            header_lines.push(preview_detail_line(app, task));
+        }
 
-        let header = Paragraph::new(Line::from(header_spans)).block(
+        let header = Paragraph::new(header_lines).block(
             Block::default()
                 .title(Span::styled(
                     " Task Info ",
@@ -1780,14 +2024,74 @@ fn draw_preview(f: &mut Frame, app: &mut App, area: Rect) {
     draw_notes_panel(f, app, panels[1]);
 }
 
+/// `branch → base`, ahead/behind counts and the linked PR for the preview
+/// header.
+fn preview_detail_line(app: &App, task: &agman::task::Task) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let base = task
+        .meta
+        .base_ref
+        .clone()
+        .unwrap_or_else(|| "auto".to_string());
+    let mut spans = vec![
+        Span::styled(
+            task.meta.branch_name.clone(),
+            Style::default().fg(Color::LightGreen),
+        ),
+        Span::styled(" \u{2192} ", dim),
+        Span::styled(base, Style::default().fg(Color::LightBlue)),
+    ];
+    if let Some((ahead, behind)) = app.preview_ahead_behind {
+        spans.push(Span::styled(
+            format!("  \u{2191}{ahead} \u{2193}{behind}"),
+            Style::default().fg(if behind > 0 {
+                Color::LightYellow
+            } else {
+                Color::Gray
+            }),
+        ));
+    }
+    match &task.meta.linked_pr {
+        Some(pr) => {
+            spans.push(Span::styled("  PR ", dim));
+            spans.push(Span::styled(
+                format!("#{}", pr.number),
+                Style::default().fg(Color::LightMagenta),
+            ));
+            if let Some(author) = &pr.author {
+                spans.push(Span::styled(format!(" by @{author}"), dim));
+            }
+            if !pr.owned {
+                spans.push(Span::styled(
+                    " (not owned)",
+                    Style::default().fg(Color::LightYellow),
+                ));
+            }
+        }
+        None => spans.push(Span::styled("  no PR", dim)),
+    }
+    if let Some(at) = &task.meta.last_compacted_at {
+        spans.push(Span::styled(
+            format!("  compacted {}", time_since_datetime(at)),
+            dim,
+        ));
+    }
+    Line::from(spans)
+}
+
 fn draw_logs_panel(f: &mut Frame, app: &mut App, area: Rect) {
     let is_focused = app.preview_pane == PreviewPane::Logs;
 
     let (title, title_style, border_color) = if is_focused {
         let mode = app.logs_editor.mode();
         let color = vim_mode_color(mode);
+        let live = if app.preview_live.is_some() {
+            " live"
+        } else {
+            ""
+        };
         (
-            format!(" Logs [{}] ", mode.indicator()),
+            format!(" Logs [{}]{live} ", mode.indicator()),
             Style::default().fg(color).add_modifier(Modifier::BOLD),
             color,
         )
@@ -1799,12 +2103,26 @@ fn draw_logs_panel(f: &mut Frame, app: &mut App, area: Rect) {
         )
     };
 
-    app.logs_editor.textarea.set_block(
-        Block::default()
-            .title(Span::styled(title, title_style))
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(border_color)),
-    );
+    let mut block = Block::default()
+        .title(Span::styled(title, title_style))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+    if let Some(editor) = &app.log_search_editor {
+        block = block.title_bottom(Span::styled(
+            format!(" /{}\u{2588} ", editor.lines().join("")),
+            Style::default().fg(Color::LightYellow),
+        ));
+    } else if let Some(search) = &app.log_search {
+        let position = match search.current {
+            Some(i) => format!("{}/{}", i + 1, search.matches.len()),
+            None => "0/0".to_string(),
+        };
+        block = block.title_bottom(Span::styled(
+            format!(" /{} [{}] ", search.term, position),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    app.logs_editor.textarea.set_block(block);
     app.logs_editor
         .textarea
         .set_cursor_style(Style::default().bg(Color::DarkGray).fg(Color::White));
@@ -1848,10 +2166,14 @@ fn draw_notes_panel(f: &mut Frame, app: &mut App, area: Rect) {
 }
 
 fn draw_delete_confirm(f: &mut Frame, app: &App) {
-    let area = centered_rect(52, 28, f.area());
+    let area = centered_rect(60, 28, f.area());
 
     f.render_widget(Clear, area);
 
+    let is_task = matches!(
+        app.selected_project_detail_row(),
+        Some(ProjectDetailRow::Task(_))
+    );
     let (question, subject) = match app.selected_project_detail_row() {
         Some(ProjectDetailRow::Task(_)) => (
             "Archive this task?",
@@ -1891,7 +2213,11 @@ fn draw_delete_confirm(f: &mut Frame, app: &App) {
         )),
         Line::from(""),
         Line::from(Span::styled(
-            "  [Enter] archive   [Esc] cancel",
+            if is_task {
+                "  [Enter] archive   [s] archive, keep session   [Esc] cancel"
+            } else {
+                "  [Enter] archive   [Esc] cancel"
+            },
             Style::default().fg(Color::DarkGray),
         )),
     ];
@@ -1957,78 +2283,579 @@ fn draw_project_delete_confirm(f: &mut Frame, app: &App) {
     f.render_widget(popup, area);
 }
 
-fn draw_respawn_confirm(f: &mut Frame, app: &App) {
-    let area = centered_rect(50, 35, f.area());
+fn draw_quit_confirm(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 40, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Quit agman? ",
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightYellow));
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "  {} task(s) still have live tmux sessions:",
+                app.quit_live_tasks.len()
+            ),
+            Style::default().fg(Color::White),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(app.quit_live_tasks.iter().map(|id| {
+        Line::from(Span::styled(
+            format!("    {id}"),
+            Style::default().fg(Color::LightCyan),
+        ))
+    }));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Sessions keep running after quit unless stopped.",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
 
+fn draw_help(f: &mut Frame, app: &mut App) {
+    let area = centered_rect(60, 80, f.area());
     f.render_widget(Clear, area);
 
-    let sel = app.respawn_confirm_index;
+    let block = Block::default()
+        .title(Span::styled(
+            " Keys ",
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
 
-    let option0_style = if sel == 0 {
-        Style::default()
-            .fg(Color::White)
-            .bg(Color::Rgb(30, 40, 60))
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Gray)
-    };
-    let option1_style = if sel == 1 {
-        Style::default()
-            .fg(Color::White)
-            .bg(Color::Rgb(30, 40, 60))
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Gray)
-    };
+    let sections = keymap::help_sections(app.help_return_view);
+    let key_width = sections
+        .iter()
+        .flat_map(|s| s.keys.iter())
+        .map(|binding| binding.key.len())
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    for section in sections {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            format!("  {}", section.title),
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for binding in section.keys {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<key_width$}  ", binding.key),
+                    Style::default().fg(Color::LightCyan),
+                ),
+                Span::styled(binding.action, Style::default().fg(Color::White)),
+            ]));
+        }
+    }
 
-    let prefix0 = if sel == 0 { "▸ " } else { "  " };
-    let prefix1 = if sel == 1 { "▸ " } else { "  " };
+    let visible = area.height.saturating_sub(2);
+    let max_scroll = (lines.len() as u16).saturating_sub(visible);
+    app.help_scroll = app.help_scroll.min(max_scroll);
 
-    let (title, text) = if app.respawn_confirm_is_chief_of_staff {
-        (
-            " Respawn Chief of Staff ",
-            vec![
-                Line::from(""),
-                Line::from(Span::styled(format!("{prefix0}CoS only"), option0_style)),
-                Line::from(""),
-                Line::from(Span::styled(
-                    format!("{prefix1}CoS + all PMs"),
-                    option1_style,
-                )),
-            ],
-        )
-    } else {
-        let target = app.respawn_confirm_target.as_deref().unwrap_or("unknown");
-        (
-            " Respawn PM ",
-            vec![
-                Line::from(""),
-                Line::from(Span::styled(
-                    format!("  Respawn PM for '{target}'?"),
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                )),
-                Line::from(""),
-                Line::from(Span::styled(format!("{prefix0}Respawn"), option0_style)),
-                Line::from(""),
-                Line::from(Span::styled(format!("{prefix1}Cancel"), option1_style)),
-            ],
-        )
-    };
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((app.help_scroll, 0)),
+        area,
+    );
+}
 
-    let popup = Paragraph::new(text).block(
-        Block::default()
+fn draw_compare(f: &mut Frame, app: &App, area: Rect) {
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    for (i, pane) in app.compare_panes.iter().enumerate().take(2) {
+        let focused = i == app.compare_focus;
+        let color = if focused {
+            Color::LightCyan
+        } else {
+            Color::DarkGray
+        };
+        let mut block = Block::default()
             .title(Span::styled(
-                title,
-                Style::default()
-                    .fg(Color::LightMagenta)
-                    .add_modifier(Modifier::BOLD),
+                format!(" {} ", pane.task_id),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
             ))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::LightMagenta)),
-    );
-
+            .border_style(Style::default().fg(color));
+        if i == 1 {
+            block = block.title_bottom(clock_title(app));
+        }
+        let log = Paragraph::new(pane.log.as_str())
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: false })
+            .scroll((pane.scroll, 0))
+            .block(block);
+        f.render_widget(log, panels[i]);
+    }
+}
+
+fn draw_clone_repo(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 25, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Clone Repo ",
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .split(inner);
+
+    let input_block = Block::default()
+        .title(" Git URL ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    let input_inner = input_block.inner(chunks[0]);
+    f.render_widget(input_block, chunks[0]);
+    f.render_widget(&app.clone_url_editor, input_inner);
+
+    let footer = match &app.clone_error {
+        None if app.clone_in_progress => Line::from(Span::styled(
+            "  Cloning... progress is shown in the status line.",
+            Style::default().fg(Color::Yellow),
+        )),
+        Some(err) => Line::from(Span::styled(
+            format!("  {err}"),
+            Style::default().fg(Color::LightRed),
+        )),
+        None => Line::from(Span::styled(
+            format!(
+                "  Clones into {} and continues to the new-task wizard.",
+                app.config.repos_dir.display()
+            ),
+            Style::default().fg(Color::DarkGray),
+        )),
+    };
+    f.render_widget(Paragraph::new(footer).wrap(Wrap { trim: false }), chunks[1]);
+}
+
+fn draw_rename_branch(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 30, f.area());
+    f.render_widget(Clear, area);
+
+    let task_id = app
+        .selected_task()
+        .map(|t| t.meta.task_id())
+        .unwrap_or_default();
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Rename Branch ",
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("  Task: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(task_id, Style::default().fg(Color::White)),
+    ]));
+    f.render_widget(header, chunks[0]);
+
+    let input_block = Block::default()
+        .title(" New branch name ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    let input_inner = input_block.inner(chunks[1]);
+    f.render_widget(input_block, chunks[1]);
+    f.render_widget(&app.branch_rename_editor, input_inner);
+
+    let footer = match &app.branch_rename_error {
+        Some(err) => Line::from(Span::styled(
+            format!("  {err}"),
+            Style::default().fg(Color::LightRed),
+        )),
+        None => Line::from(Span::styled(
+            "  Renames the branch, worktree, task dir, and tmux sessions.",
+            Style::default().fg(Color::DarkGray),
+        )),
+    };
+    f.render_widget(Paragraph::new(footer).wrap(Wrap { trim: false }), chunks[2]);
+}
+
+fn draw_edit_base_ref(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 30, f.area());
+    f.render_widget(Clear, area);
+
+    let task_id = app
+        .selected_task()
+        .map(|t| t.meta.task_id())
+        .unwrap_or_default();
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Base Ref ",
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("  Task: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(task_id, Style::default().fg(Color::White)),
+    ]));
+    f.render_widget(header, chunks[0]);
+
+    let input_block = Block::default()
+        .title(" Branched from (e.g. origin/main) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    let input_inner = input_block.inner(chunks[1]);
+    f.render_widget(input_block, chunks[1]);
+    f.render_widget(&app.base_ref_editor, input_inner);
+
+    let footer = match &app.base_ref_error {
+        Some(err) => Line::from(Span::styled(
+            format!("  {err}"),
+            Style::default().fg(Color::LightRed),
+        )),
+        None => Line::from(Span::styled(
+            "  Used by auto-rebase. Leave empty to auto-detect.",
+            Style::default().fg(Color::DarkGray),
+        )),
+    };
+    f.render_widget(Paragraph::new(footer).wrap(Wrap { trim: false }), chunks[2]);
+}
+
+fn draw_edit_label(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 30, f.area());
+    f.render_widget(Clear, area);
+
+    let task_id = app
+        .selected_task()
+        .map(|t| t.meta.task_id())
+        .unwrap_or_default();
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Task Label ",
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("  Task: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(task_id, Style::default().fg(Color::White)),
+    ]));
+    f.render_widget(header, chunks[0]);
+
+    let input_block = Block::default()
+        .title(" Label ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    let input_inner = input_block.inner(chunks[1]);
+    f.render_widget(input_block, chunks[1]);
+    f.render_widget(&app.label_editor, input_inner);
+
+    let footer = Line::from(Span::styled(
+        "  Shown instead of the branch. Leave empty to clear.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    f.render_widget(Paragraph::new(footer).wrap(Wrap { trim: false }), chunks[2]);
+}
+
+fn draw_relocate_repo(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 30, f.area());
+    f.render_widget(Clear, area);
+
+    let (task_id, multi_repo) = app
+        .selected_task()
+        .map(|t| (t.meta.task_id(), t.meta.is_multi_repo()))
+        .unwrap_or_default();
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Repo Moved ",
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightYellow));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Min(1),
+        ])
+        .split(inner);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("  Task: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(task_id, Style::default().fg(Color::White)),
+    ]));
+    f.render_widget(header, chunks[0]);
+
+    let input_block = Block::default()
+        .title(if multi_repo {
+            " New parent directory "
+        } else {
+            " New repo path "
+        })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    let input_inner = input_block.inner(chunks[1]);
+    f.render_widget(input_block, chunks[1]);
+    f.render_widget(&app.relocate_editor, input_inner);
+
+    let footer = Line::from(Span::styled(
+        "  The task's repo is gone from where it was. Worktrees that moved with it are re-linked.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    f.render_widget(Paragraph::new(footer).wrap(Wrap { trim: false }), chunks[2]);
+}
+
+fn draw_quick_answer(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 35, f.area());
+    f.render_widget(Clear, area);
+
+    let task_id = app
+        .selected_task()
+        .map(|t| t.meta.task_id())
+        .unwrap_or_default();
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Answer Engineer ",
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(2),
+            Constraint::Length(3),
+            Constraint::Length(2),
+        ])
+        .split(inner);
+
+    let header = Paragraph::new(Line::from(vec![
+        Span::styled("  Task: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(task_id, Style::default().fg(Color::White)),
+    ]));
+    f.render_widget(header, chunks[0]);
+
+    let question = match &app.answer_question {
+        Some(q) => Line::from(vec![
+            Span::styled("  Q: ", Style::default().fg(Color::LightYellow)),
+            Span::styled(q.clone(), Style::default().fg(Color::White)),
+        ]),
+        None => Line::from(Span::styled(
+            "  No question found at the bottom of the engineer's pane",
+            Style::default().fg(Color::DarkGray),
+        )),
+    };
+    f.render_widget(
+        Paragraph::new(question).wrap(Wrap { trim: false }),
+        chunks[1],
+    );
+
+    let input_block = Block::default()
+        .title(" Answer ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    let input_inner = input_block.inner(chunks[2]);
+    f.render_widget(input_block, chunks[2]);
+    f.render_widget(&app.answer_editor, input_inner);
+
+    let footer = Line::from(Span::styled(
+        "  Delivered through the engineer's inbox. Attach for longer replies.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    f.render_widget(Paragraph::new(footer).wrap(Wrap { trim: false }), chunks[3]);
+}
+
+fn draw_add_repo(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let task_id = app
+        .selected_task()
+        .map(|t| t.meta.task_id())
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Add Repo to {task_id} "),
+            Style::default()
+                .fg(Color::LightMagenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightMagenta));
+
+    let items: Vec<ListItem> = app
+        .add_repo_choices
+        .iter()
+        .enumerate()
+        .map(|(i, repo)| {
+            if i == app.add_repo_selected {
+                ListItem::new(format!("▸ {repo}")).style(
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::Rgb(30, 40, 60))
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ListItem::new(format!("  {repo}")).style(Style::default().fg(Color::Gray))
+            }
+        })
+        .collect();
+
+    f.render_widget(List::new(items).block(block), area);
+}
+
+fn draw_respawn_confirm(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 35, f.area());
+
+    f.render_widget(Clear, area);
+
+    let sel = app.respawn_confirm_index;
+
+    let option0_style = if sel == 0 {
+        Style::default()
+            .fg(Color::White)
+            .bg(Color::Rgb(30, 40, 60))
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+    let option1_style = if sel == 1 {
+        Style::default()
+            .fg(Color::White)
+            .bg(Color::Rgb(30, 40, 60))
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Gray)
+    };
+
+    let prefix0 = if sel == 0 { "▸ " } else { "  " };
+    let prefix1 = if sel == 1 { "▸ " } else { "  " };
+
+    let (title, text) = if app.respawn_confirm_is_chief_of_staff {
+        (
+            " Respawn Chief of Staff ",
+            vec![
+                Line::from(""),
+                Line::from(Span::styled(format!("{prefix0}CoS only"), option0_style)),
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!("{prefix1}CoS + all PMs"),
+                    option1_style,
+                )),
+            ],
+        )
+    } else {
+        let target = app.respawn_confirm_target.as_deref().unwrap_or("unknown");
+        (
+            " Respawn PM ",
+            vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    format!("  Respawn PM for '{target}'?"),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(format!("{prefix0}Respawn"), option0_style)),
+                Line::from(""),
+                Line::from(Span::styled(format!("{prefix1}Cancel"), option1_style)),
+            ],
+        )
+    };
+
+    let popup = Paragraph::new(text).block(
+        Block::default()
+            .title(Span::styled(
+                title,
+                Style::default()
+                    .fg(Color::LightMagenta)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightMagenta)),
+    );
+
     f.render_widget(popup, area);
 }
 
@@ -2094,197 +2921,231 @@ fn telegram_health_spans(app: &App) -> Vec<Span<'static>> {
     ]
 }
 
-fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
-    let help_text = match app.view {
-        View::ProjectList => {
-            let mut spans = vec![
-                Span::styled("j/k", Style::default().fg(Color::LightCyan)),
-                Span::styled(" nav  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("enter", Style::default().fg(Color::LightGreen)),
-                Span::styled(" open  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("n", Style::default().fg(Color::LightGreen)),
-                Span::styled(" new  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("c", Style::default().fg(Color::LightYellow)),
-                Span::styled(" CoS chat  ", Style::default().fg(Color::DarkGray)),
-            ];
-            // Show migrate hint when (unassigned) is selected
-            let is_unassigned =
-                app.selected_project_index >= app.projects.len() && app.unassigned_task_count > 0;
-            if is_unassigned {
-                spans.extend([
-                    Span::styled("m", Style::default().fg(Color::LightMagenta)),
-                    Span::styled(" migrate  ", Style::default().fg(Color::DarkGray)),
-                ]);
-            }
-            // Show delete and hold hints when a real project is selected
-            if app.selected_project_index < app.projects.len() {
-                spans.extend([
-                    Span::styled("d", Style::default().fg(Color::LightRed)),
-                    Span::styled(" delete  ", Style::default().fg(Color::DarkGray)),
-                ]);
-                let hold_label = if app.projects[app.selected_project_index].meta.held {
-                    " unhold  "
-                } else {
-                    " hold  "
-                };
-                spans.extend([
-                    Span::styled("h", Style::default().fg(Color::LightYellow)),
-                    Span::styled(hold_label, Style::default().fg(Color::DarkGray)),
-                ]);
-            }
-            spans.extend([
-                Span::styled("o", Style::default().fg(Color::LightYellow)),
-                Span::styled(" notes  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("e", Style::default().fg(Color::LightMagenta)),
-                Span::styled(" respawn  ", Style::default().fg(Color::DarkGray)),
-            ]);
-            let unread_count = app.notifications.iter().filter(|n| n.unread).count();
-            let inbox_label = if unread_count > 0 {
-                format!(" inbox({})  ", unread_count)
-            } else if !app.gh_notif_first_poll_done {
-                " inbox(...)  ".to_string()
-            } else {
-                " inbox  ".to_string()
+/// Status-bar hints for a view with a keymap table: every hinted key in
+/// table order, minus the ones `status_hint_label` hides right now.
+fn keymap_status_spans(app: &App) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for section in keymap::help_sections(app.view) {
+        for binding in section.keys {
+            let Some((label, kind)) = binding.hint else {
+                continue;
             };
-            spans.extend([
-                Span::styled("i", Style::default().fg(Color::LightYellow)),
-                Span::styled(inbox_label, Style::default().fg(Color::DarkGray)),
-                Span::styled("p", Style::default().fg(Color::LightYellow)),
-                Span::styled(" prs  ", Style::default().fg(Color::DarkGray)),
-                Span::styled(",", Style::default().fg(Color::LightYellow)),
-                Span::styled(" settings  ", Style::default().fg(Color::DarkGray)),
-            ]);
-            spans
+            let Some(label) = status_hint_label(app, section.title, binding.key, label) else {
+                continue;
+            };
+            let color = match kind {
+                HintKind::Nav => Color::LightCyan,
+                HintKind::Open => Color::LightGreen,
+                HintKind::Misc => Color::LightYellow,
+                HintKind::Agent => Color::LightMagenta,
+                HintKind::Danger => Color::LightRed,
+            };
+            spans.push(Span::styled(binding.key, Style::default().fg(color)));
+            spans.push(Span::styled(
+                format!(" {label}  "),
+                Style::default().fg(Color::DarkGray),
+            ));
         }
-        View::TaskList => {
-            let mut spans = vec![
-                Span::styled("j/k", Style::default().fg(Color::LightCyan)),
-                Span::styled(" nav  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Tab", Style::default().fg(Color::LightCyan)),
-                Span::styled(" section  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("n", Style::default().fg(Color::LightGreen)),
-                Span::styled(" new task  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("a", Style::default().fg(Color::LightGreen)),
-                Span::styled(" new agent  ", Style::default().fg(Color::DarkGray)),
-            ];
-            if app
-                .current_project
-                .as_deref()
-                .is_some_and(|p| p != "(unassigned)")
-            {
-                spans.extend([
-                    Span::styled("c", Style::default().fg(Color::LightYellow)),
-                    Span::styled(" PM chat  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("o", Style::default().fg(Color::LightYellow)),
-                    Span::styled(" notes  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("e", Style::default().fg(Color::LightMagenta)),
-                    Span::styled(" respawn  ", Style::default().fg(Color::DarkGray)),
-                ]);
-            }
+    }
+    if let Some(last) = spans.last_mut() {
+        last.content = last.content.trim_end().to_string().into();
+    }
+    spans
+}
 
-            match app.selected_project_detail_row() {
-                Some(ProjectDetailRow::Task(_)) => {
-                    if app
-                        .selected_task()
-                        .is_some_and(|task| task.meta.linked_pr.is_some())
-                    {
-                        spans.push(Span::styled("p", Style::default().fg(Color::LightYellow)));
-                        spans.push(Span::styled(
-                            " open pr  ",
-                            Style::default().fg(Color::DarkGray),
-                        ));
+/// The status-bar label for a hinted key given the current state, or `None`
+/// when the key does nothing right now.
+fn status_hint_label(
+    app: &App,
+    section: &str,
+    key: &str,
+    label: &'static str,
+) -> Option<Cow<'static, str>> {
+    let shown = |show: bool| show.then_some(Cow::Borrowed(label));
+    match app.view {
+        View::ProjectList => {
+            let project = app.projects.get(app.selected_project_index);
+            match key {
+                "m" => shown(project.is_none() && app.unassigned_task_count > 0),
+                "d" => shown(project.is_some()),
+                "h" => project.map(|p| {
+                    if p.meta.held {
+                        "unhold".into()
+                    } else {
+                        label.into()
                     }
-                    spans.extend([
-                        Span::styled("enter", Style::default().fg(Color::LightGreen)),
-                        Span::styled(" preview  ", Style::default().fg(Color::DarkGray)),
-                        Span::styled("r", Style::default().fg(Color::LightMagenta)),
-                        Span::styled(" rerun  ", Style::default().fg(Color::DarkGray)),
-                        Span::styled("d", Style::default().fg(Color::LightRed)),
-                        Span::styled(" archive  ", Style::default().fg(Color::DarkGray)),
-                    ]);
+                }),
+                "i" => {
+                    let unread = app.notifications.iter().filter(|n| n.unread).count();
+                    Some(if unread > 0 {
+                        format!("{label}({unread})").into()
+                    } else if !app.gh_notif_first_poll_done {
+                        format!("{label}(...)").into()
+                    } else {
+                        label.into()
+                    })
                 }
+                "b" => shown(app.break_settings.enabled),
+                _ => Some(label.into()),
+            }
+        }
+        View::TaskList => {
+            let row = app.selected_project_detail_row();
+            let on_task = matches!(row, Some(ProjectDetailRow::Task(_)));
+            let on_agent = matches!(
+                row,
                 Some(ProjectDetailRow::UnattachedAgent { .. })
-                | Some(ProjectDetailRow::AttachedAgent(_)) => {
-                    spans.extend([
-                        Span::styled("enter", Style::default().fg(Color::LightGreen)),
-                        Span::styled(" chat  ", Style::default().fg(Color::DarkGray)),
-                        Span::styled("d", Style::default().fg(Color::LightRed)),
-                        Span::styled(" archive  ", Style::default().fg(Color::DarkGray)),
-                    ]);
+                    | Some(ProjectDetailRow::AttachedAgent(_))
+            );
+            let task = app.selected_task().filter(|_| on_task);
+            match (section, key) {
+                ("Navigation", "Enter") => {
+                    if on_task {
+                        Some(label.into())
+                    } else {
+                        on_agent.then_some("chat".into())
+                    }
                 }
-                _ => {}
-            }
-
-            spans.extend([
-                Span::styled("z", Style::default().fg(Color::LightYellow)),
-                Span::styled(" archived  ", Style::default().fg(Color::DarkGray)),
-            ]);
-            if app.current_project.is_some() {
-                spans.extend([
-                    Span::styled("q", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" back", Style::default().fg(Color::DarkGray)),
-                ]);
+                ("Navigation", "q/Esc") => shown(app.current_project.is_some()),
+                ("Selected task", "d") => shown(on_task || on_agent),
+                ("Selected task", _) => {
+                    let task = task?;
+                    match key {
+                        "p" => shown(task.meta.linked_pr.is_some()),
+                        "W" => shown(app.missing_worktrees.contains(&task.meta.task_id())),
+                        "C" if app.compare_mark.is_some() => Some("compare with marked".into()),
+                        "P" if task.meta.pinned => Some("unpin".into()),
+                        "F" if app.repo_filter.is_some() => Some("clear repo filter".into()),
+                        "s" if task.meta.is_snoozed(Utc::now()) => Some("wake".into()),
+                        _ => Some(label.into()),
+                    }
+                }
+                ("Project", "c" | "o" | "e") => shown(
+                    app.current_project
+                        .as_deref()
+                        .is_some_and(|p| p != "(unassigned)"),
+                ),
+                _ => Some(label.into()),
             }
-            spans
         }
         View::Preview => {
             if app.notes_editing {
-                vec![
-                    Span::styled("Esc", Style::default().fg(Color::LightGreen)),
-                    Span::styled(" save & exit editing", Style::default().fg(Color::DarkGray)),
-                ]
-            } else {
-                let mut spans = vec![
-                    Span::styled("Tab", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" pane  ", Style::default().fg(Color::DarkGray)),
-                ];
-                if let Some(task) = app.selected_task() {
-                    if task.meta.linked_pr.is_some() {
-                        spans.push(Span::styled("p", Style::default().fg(Color::LightYellow)));
-                        spans.push(Span::styled(
-                            " open pr  ",
-                            Style::default().fg(Color::DarkGray),
-                        ));
+                return shown(section == "Notes" && key == "Esc");
+            }
+            let task = app.selected_task();
+            match section {
+                "Logs" => {
+                    if task.is_none() || app.preview_pane != PreviewPane::Logs {
+                        return None;
+                    }
+                    match key {
+                        "n/N" => shown(app.log_search.is_some()),
+                        "L" if app.preview_live.is_some() => Some("stop live".into()),
+                        "T" if app.preview_full_log => Some("tail".into()),
+                        "M" => Some(format!("{label} ({})", app.log_filter.label()).into()),
+                        _ => Some(label.into()),
                     }
-                    // Task-selected hints (always shown when a task is selected)
-                    spans.extend([
-                        Span::styled("r", Style::default().fg(Color::LightMagenta)),
-                        Span::styled(" rerun  ", Style::default().fg(Color::DarkGray)),
-                    ]);
                 }
-                spans.extend([
-                    Span::styled("Enter", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" attach  ", Style::default().fg(Color::DarkGray)),
-                ]);
-                spans.extend([
-                    Span::styled("q", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" back", Style::default().fg(Color::DarkGray)),
-                ]);
-                spans
+                "Notes" => {
+                    shown(key != "Esc" && task.is_some() && app.preview_pane == PreviewPane::Notes)
+                }
+                "Task" => {
+                    let task = task?;
+                    shown(key != "p" || task.meta.linked_pr.is_some())
+                }
+                _ => Some(label.into()),
             }
         }
+        View::Notes => {
+            let in_editor = app
+                .notes_view
+                .as_ref()
+                .is_some_and(|nv| nv.focus == NotesFocus::Editor);
+            shown(in_editor == (section == "Editor"))
+        }
+        View::Notifications if key == "f" => {
+            Some(format!("{label} ({})", app.notif_filter.label()).into())
+        }
+        View::RepoStats if key == "s" => {
+            Some(format!("{label} ({})", app.repo_stats_sort.label()).into())
+        }
+        _ => Some(label.into()),
+    }
+}
+
+fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
+    let help_text = match app.view {
         View::DeleteConfirm => {
-            vec![
+            let mut spans = vec![
                 Span::styled("Enter", Style::default().fg(Color::LightGreen)),
                 Span::styled(" archive  ", Style::default().fg(Color::DarkGray)),
+            ];
+            if matches!(
+                app.selected_project_detail_row(),
+                Some(ProjectDetailRow::Task(_))
+            ) {
+                spans.extend([
+                    Span::styled("s", Style::default().fg(Color::LightYellow)),
+                    Span::styled(" keep session  ", Style::default().fg(Color::DarkGray)),
+                ]);
+            }
+            spans.extend([
                 Span::styled("Esc/q", Style::default().fg(Color::LightRed)),
                 Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
-            ]
+            ]);
+            spans
         }
         View::NewTaskWizard => {
             if let Some(wizard) = &app.wizard {
                 match wizard.step {
-                    WizardStep::SelectBranch => {
+                    WizardStep::SelectBranch if wizard.issue_picker.is_some() => {
                         vec![
+                            Span::styled("j/k", Style::default().fg(Color::LightCyan)),
+                            Span::styled(" nav  ", Style::default().fg(Color::DarkGray)),
+                            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+                            Span::styled(" use issue  ", Style::default().fg(Color::DarkGray)),
+                            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+                            Span::styled(" close", Style::default().fg(Color::DarkGray)),
+                        ]
+                    }
+                    WizardStep::SelectBranch => {
+                        let mut spans = vec![
                             Span::styled("Tab", Style::default().fg(Color::LightCyan)),
                             Span::styled(" mode  ", Style::default().fg(Color::DarkGray)),
                             Span::styled("j/k", Style::default().fg(Color::LightCyan)),
                             Span::styled(" nav  ", Style::default().fg(Color::DarkGray)),
+                            Span::styled("Ctrl+N", Style::default().fg(Color::LightCyan)),
+                            Span::styled(" complete  ", Style::default().fg(Color::DarkGray)),
+                            Span::styled("Ctrl+R", Style::default().fg(Color::LightCyan)),
+                            Span::styled(
+                                if wizard.auto_rebase {
+                                    " auto-rebase: on  "
+                                } else {
+                                    " auto-rebase: off  "
+                                },
+                                Style::default().fg(Color::DarkGray),
+                            ),
+                        ];
+                        if !wizard.start_repo_choices.is_empty() {
+                            let start = wizard
+                                .start_repo
+                                .and_then(|i| wizard.start_repo_choices.get(i))
+                                .map_or("parent dir", String::as_str);
+                            spans.extend([
+                                Span::styled("Ctrl+O", Style::default().fg(Color::LightCyan)),
+                                Span::styled(
+                                    format!(" start in: {start}  "),
+                                    Style::default().fg(Color::DarkGray),
+                                ),
+                            ]);
+                        }
+                        spans.extend([
                             Span::styled("Enter", Style::default().fg(Color::LightGreen)),
                             Span::styled(" next  ", Style::default().fg(Color::DarkGray)),
                             Span::styled("Esc", Style::default().fg(Color::LightRed)),
                             Span::styled(" back", Style::default().fg(Color::DarkGray)),
-                        ]
+                        ]);
+                        spans
                     }
                     WizardStep::EnterFirstPrompt => {
                         vec![
@@ -2319,86 +3180,48 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
                 Span::styled(" up  ", Style::default().fg(Color::DarkGray)),
                 Span::styled("s", Style::default().fg(Color::LightGreen)),
                 Span::styled(" select  ", Style::default().fg(Color::DarkGray)),
+                Span::styled("c", Style::default().fg(Color::LightMagenta)),
+                Span::styled(" clone url  ", Style::default().fg(Color::DarkGray)),
                 Span::styled("Esc", Style::default().fg(Color::LightRed)),
                 Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
             ]
         }
-        View::Notifications => {
-            let mut spans = vec![
-                Span::styled("j/k", Style::default().fg(Color::LightCyan)),
-                Span::styled(" nav  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("d", Style::default().fg(Color::LightRed)),
-                Span::styled(" done  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("o", Style::default().fg(Color::LightGreen)),
-                Span::styled(" open  ", Style::default().fg(Color::DarkGray)),
-            ];
-            spans.extend([
-                Span::styled("q", Style::default().fg(Color::LightCyan)),
-                Span::styled(" back", Style::default().fg(Color::DarkGray)),
-            ]);
-            spans
-        }
-        View::ShowPrs => {
-            let mut spans = vec![
-                Span::styled("j/k", Style::default().fg(Color::LightCyan)),
-                Span::styled(" nav  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("o", Style::default().fg(Color::LightGreen)),
-                Span::styled(" open  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("r", Style::default().fg(Color::LightYellow)),
-                Span::styled(" refresh  ", Style::default().fg(Color::DarkGray)),
-            ];
-            spans.extend([
-                Span::styled("q", Style::default().fg(Color::LightCyan)),
-                Span::styled(" back", Style::default().fg(Color::DarkGray)),
-            ]);
-            spans
-        }
-        View::Notes => {
-            let is_editor = app
+        View::Help => vec![
+            Span::styled("j/k", Style::default().fg(Color::LightCyan)),
+            Span::styled(" scroll  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("?/Esc", Style::default().fg(Color::LightCyan)),
+            Span::styled(" close", Style::default().fg(Color::DarkGray)),
+        ],
+        View::QuitConfirm => vec![
+            Span::styled("y", Style::default().fg(Color::LightGreen)),
+            Span::styled(" quit  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("s", Style::default().fg(Color::LightRed)),
+            Span::styled(" stop all & quit  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("n/Esc", Style::default().fg(Color::LightCyan)),
+            Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
+        ],
+        View::CloneRepo => vec![
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::styled(" clone  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::styled(" back", Style::default().fg(Color::DarkGray)),
+        ],
+        View::Notes
+            if app
                 .notes_view
                 .as_ref()
-                .map(|nv| nv.focus == NotesFocus::Editor)
-                .unwrap_or(false);
-            if is_editor {
-                vec![
-                    Span::styled("Tab", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" explorer  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("Ctrl+s", Style::default().fg(Color::LightGreen)),
-                    Span::styled(" save  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("q", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" back", Style::default().fg(Color::DarkGray)),
-                ]
-            } else {
-                let mut spans = vec![
-                    Span::styled("j/k", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" nav  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("J/K", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" reorder  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("l", Style::default().fg(Color::LightGreen)),
-                    Span::styled(" open  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("h", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" back  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("a", Style::default().fg(Color::LightGreen)),
-                    Span::styled(" new  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("A", Style::default().fg(Color::LightGreen)),
-                    Span::styled(" dir  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("d", Style::default().fg(Color::LightRed)),
-                    Span::styled(" del  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("x", Style::default().fg(Color::LightYellow)),
-                    Span::styled(" cut  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("p", Style::default().fg(Color::LightGreen)),
-                    Span::styled(" paste  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("r", Style::default().fg(Color::LightYellow)),
-                    Span::styled(" rename  ", Style::default().fg(Color::DarkGray)),
-                    Span::styled("Tab", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" editor  ", Style::default().fg(Color::DarkGray)),
-                ];
-                spans.extend([
-                    Span::styled("q", Style::default().fg(Color::LightCyan)),
-                    Span::styled(" back", Style::default().fg(Color::DarkGray)),
-                ]);
-                spans
-            }
+                .is_some_and(|nv| nv.finder.is_some()) =>
+        {
+            vec![
+                Span::styled("type", Style::default().fg(Color::LightCyan)),
+                Span::styled(" filter  ", Style::default().fg(Color::DarkGray)),
+                Span::styled("↑/↓", Style::default().fg(Color::LightCyan)),
+                Span::styled(" select  ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+                Span::styled(" open  ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Esc", Style::default().fg(Color::LightCyan)),
+                Span::styled(" close", Style::default().fg(Color::DarkGray)),
+            ]
         }
         View::Settings => {
             if app.settings_editing {
@@ -2509,6 +3332,70 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
             ]
         }
         View::RespawnConfirm => vec![],
+        View::RenameBranch => vec![
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::styled(" rename  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
+        ],
+        View::EditLabel => vec![
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::styled(" save  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
+        ],
+        View::RelocateRepo => vec![
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::styled(" relocate  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
+        ],
+        View::QuickAnswer => vec![
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::styled(
+                " send (empty: carry on)  ",
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
+        ],
+        View::AddRepo => vec![
+            Span::styled("j/k", Style::default().fg(Color::LightCyan)),
+            Span::styled(" nav  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::styled(" add to task  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
+        ],
+        View::EditBaseRef => vec![
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::styled(" save  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Ctrl+t", Style::default().fg(Color::LightGreen)),
+            Span::styled(
+                " save & set upstream  ",
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
+        ],
+        View::Snapshots if app.snapshot_restore_confirm => vec![
+            Span::styled("y", Style::default().fg(Color::LightRed)),
+            Span::styled(" reset --hard  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("any other key", Style::default().fg(Color::LightCyan)),
+            Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
+        ],
+        // Views with a keymap table take their hints from it.
+        View::ProjectList
+        | View::TaskList
+        | View::Preview
+        | View::Compare
+        | View::Notifications
+        | View::ShowPrs
+        | View::Notes
+        | View::MessageHistory
+        | View::Snapshots
+        | View::Dashboard
+        | View::RepoStats => keymap_status_spans(app),
         View::AgentWizard => vec![
             Span::styled("Tab", Style::default().fg(Color::LightCyan)),
             Span::styled(" next  ", Style::default().fg(Color::DarkGray)),
@@ -2536,6 +3423,14 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
         line_spans.push(Span::styled(msg, Style::default().fg(Color::LightYellow)));
     }
 
+    if let Some(err) = &app.pinned_error {
+        line_spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+        line_spans.push(Span::styled(
+            format!("⚠ {err} (Y to dismiss)"),
+            Style::default().fg(Color::LightRed),
+        ));
+    }
+
     if matches!(app.view, View::ProjectList | View::TaskList) {
         let tg = telegram_health_spans(app);
         if !tg.is_empty() {
@@ -2558,7 +3453,7 @@ fn draw_wizard(f: &mut Frame, app: &mut App) {
     f.render_widget(Clear, area);
 
     // Extract data we need before mutable borrows
-    let (step, step_num, total_steps, step_title, repo_name, error_message) = {
+    let (step, step_num, total_steps, step_title, repo_name, error_message, busy) = {
         let wizard = match &app.wizard {
             Some(w) => w,
             None => return,
@@ -2575,6 +3470,13 @@ fn draw_wizard(f: &mut Frame, app: &mut App) {
             step_title,
             wizard.selected_repo.clone(),
             wizard.error_message.clone(),
+            if wizard.creating {
+                Some("Creating task (running worktree setup)...")
+            } else if wizard.loading_issues {
+                Some("Loading open issues...")
+            } else {
+                None
+            },
         )
     };
 
@@ -2616,7 +3518,7 @@ fn draw_wizard(f: &mut Frame, app: &mut App) {
     }
 
     // Draw error message or help text
-    draw_wizard_footer_direct(f, step, error_message, chunks[1]);
+    draw_wizard_footer_direct(f, step, error_message, busy, chunks[1]);
 }
 
 fn draw_wizard_branch(f: &mut Frame, app: &mut App, area: Rect) {
@@ -2639,6 +3541,48 @@ fn draw_wizard_branch(f: &mut Frame, app: &mut App, area: Rect) {
         " Enter branch name (creates new branch + worktree) ",
         area,
     );
+
+    if let Some(issues) = &wizard.issue_picker {
+        draw_issue_picker(f, issues, wizard.selected_issue_index, area);
+    }
+}
+
+fn draw_issue_picker(
+    f: &mut Frame,
+    issues: &[use_cases::GithubIssue],
+    selected: usize,
+    area: Rect,
+) {
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = issues
+        .iter()
+        .map(|issue| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("#{:<6}", issue.number),
+                    Style::default().fg(Color::LightYellow),
+                ),
+                Span::styled(issue.title.clone(), Style::default().fg(Color::White)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" Open Issues ({}) ", issues.len()),
+                    Style::default().fg(Color::LightCyan),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::LightCyan)),
+        )
+        .highlight_style(Style::default().bg(Color::Rgb(40, 40, 50)));
+
+    let mut state = ratatui::widgets::ListState::default();
+    state.select(Some(selected));
+    f.render_stateful_widget(list, area, &mut state);
 }
 
 fn draw_wizard_first_prompt(f: &mut Frame, app: &mut App, area: Rect) {
@@ -2678,6 +3622,7 @@ fn draw_wizard_footer_direct(
     f: &mut Frame,
     step: WizardStep,
     error_message: Option<String>,
+    busy: Option<&str>,
     area: Rect,
 ) {
     let content = if let Some(err) = &error_message {
@@ -2685,10 +3630,14 @@ fn draw_wizard_footer_direct(
             Span::styled("Error: ", Style::default().fg(Color::LightRed)),
             Span::styled(err, Style::default().fg(Color::LightRed)),
         ])
+    } else if let Some(busy) = busy {
+        Line::from(Span::styled(busy, Style::default().fg(Color::Yellow)))
     } else {
         // Show contextual help
         let help = match step {
-            WizardStep::SelectBranch => "Tab: switch mode  j/k: navigate  Enter: next  Esc: back",
+            WizardStep::SelectBranch => {
+                "Tab: switch mode  j/k: navigate  Ctrl+N: complete  Ctrl+G: from issue  Enter: next  Esc: back"
+            }
             WizardStep::EnterFirstPrompt => "Ctrl+S: create task  Esc: back",
         };
         Line::from(Span::styled(help, Style::default().fg(Color::DarkGray)))
@@ -3102,9 +4051,42 @@ fn draw_directory_picker(f: &mut Frame, app: &App) {
 
     // Build list items
     let is_repo_select = picker.is_repo_select_mode();
+    let recent_len = picker.recent_repos.len();
     let fav_len = picker.favorites_len();
     let mut items: Vec<ListItem> = Vec::new();
 
+    // Recently used section
+    if recent_len > 0 {
+        let header_line = Line::from(vec![
+            Span::styled(
+                format!("── Recent ({}) ", recent_len),
+                Style::default()
+                    .fg(Color::LightMagenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("─".repeat(40), Style::default().fg(Color::Rgb(60, 60, 60))),
+        ]);
+        items.push(ListItem::new(header_line));
+
+        for (idx, repo) in picker.recent_repos.iter().enumerate() {
+            let is_selected = idx == picker.selected_index;
+            let style = if is_selected {
+                Style::default()
+                    .fg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let prefix = if is_selected { "> " } else { "  " };
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(format!("{}{}  ", prefix, repo), style),
+                Span::styled("[git]", Style::default().fg(Color::LightGreen)),
+            ])));
+        }
+
+        items.push(ListItem::new(Line::from("")));
+    }
+
     // Favourites section
     if fav_len > 0 {
         // Header line (non-selectable)
@@ -3120,7 +4102,7 @@ fn draw_directory_picker(f: &mut Frame, app: &App) {
         items.push(ListItem::new(header_line));
 
         for (idx, (repo, count)) in picker.favorite_repos.iter().enumerate() {
-            let is_selected = idx == picker.selected_index;
+            let is_selected = recent_len + idx == picker.selected_index;
             let style = if is_selected {
                 Style::default()
                     .fg(Color::LightCyan)
@@ -3143,7 +4125,7 @@ fn draw_directory_picker(f: &mut Frame, app: &App) {
 
     // Directory entries
     for (i, name) in picker.entries.iter().enumerate() {
-        let flat_index = fav_len + i;
+        let flat_index = picker.quick_pick_len() + i;
         let is_selected = flat_index == picker.selected_index;
         let kind = picker.entry_kinds.get(i).copied();
 
@@ -3241,16 +4223,27 @@ fn relative_time(iso_str: &str) -> String {
 }
 
 fn draw_notifications(f: &mut Frame, app: &App, area: Rect) {
-    let count = app.notifications.len();
-    let title = format!(" Notifications ({}) ", count);
+    let visible = app.visible_notif_indices();
+    let title = if app.notif_filter == use_cases::NotifReasonFilter::All {
+        format!(" Notifications ({}) ", visible.len())
+    } else {
+        format!(
+            " Notifications ({}/{}) · {} ",
+            visible.len(),
+            app.notifications.len(),
+            app.notif_filter.label()
+        )
+    };
 
-    if app.notifications.is_empty() {
+    if visible.is_empty() {
         let block = Block::default()
             .title(title)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::DarkGray))
             .title_bottom(clock_title(app));
-        let empty_text = if app.gh_notif_first_poll_done {
+        let empty_text = if !app.notifications.is_empty() {
+            "No notifications match the filter"
+        } else if app.gh_notif_first_poll_done {
             "No notifications"
         } else {
             "Fetching notifications..."
@@ -3262,9 +4255,9 @@ fn draw_notifications(f: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let items: Vec<ListItem> = app
-        .notifications
+    let items: Vec<ListItem> = visible
         .iter()
+        .map(|&idx| &app.notifications[idx])
         .enumerate()
         .map(|(i, notif)| {
             let style = if i == app.selected_notif_index {
@@ -3531,6 +4524,278 @@ fn format_time_ago(archived_at: &chrono::DateTime<Utc>) -> String {
     }
 }
 
+/// Compact human duration for the repo-stats table ("3d 4h", "5h 12m", "8m").
+fn format_duration_short(duration: chrono::Duration) -> String {
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    let mins = duration.num_minutes() % 60;
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{}m", mins.max(0))
+    }
+}
+
+fn draw_repo_stats(f: &mut Frame, app: &App, area: Rect) {
+    const COUNT_WIDTH: usize = 10;
+    const TTM_WIDTH: usize = 16;
+    const COST_WIDTH: usize = 12;
+
+    let title = format!(" Repo Stats ({}) ", app.repo_stats_rows.len());
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title_bottom(clock_title(app));
+
+    if app.repo_stats_rows.is_empty() {
+        let content = Paragraph::new("No tasks yet")
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(content, area);
+        return;
+    }
+
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let name_width = inner_width
+        .saturating_sub(3 * COUNT_WIDTH + TTM_WIDTH + COST_WIDTH + 1)
+        .max(12);
+
+    let header_style = |sort: RepoStatsSort| {
+        if app.repo_stats_sort == sort {
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    };
+    let header = Line::from(vec![
+        Span::styled(
+            format!(" {:<name_width$}", "REPO"),
+            header_style(RepoStatsSort::Name),
+        ),
+        Span::styled(
+            format!("{:>COUNT_WIDTH$}", "ACTIVE"),
+            header_style(RepoStatsSort::Active),
+        ),
+        Span::styled(
+            format!("{:>COUNT_WIDTH$}", "ARCHIVED"),
+            header_style(RepoStatsSort::Archived),
+        ),
+        Span::styled(
+            format!("{:>COUNT_WIDTH$}", "MERGED"),
+            header_style(RepoStatsSort::Merged),
+        ),
+        Span::styled(
+            format!("{:>TTM_WIDTH$}", "AVG TO MERGE"),
+            header_style(RepoStatsSort::TimeToMerge),
+        ),
+        Span::styled(
+            format!("{:>COST_WIDTH$}", "COST"),
+            header_style(RepoStatsSort::Cost),
+        ),
+    ]);
+
+    let mut items = vec![ListItem::new(header)];
+    items.extend(app.repo_stats_rows.iter().enumerate().map(|(i, row)| {
+        let style = if i == app.repo_stats_selected {
+            Style::default().bg(Color::Rgb(40, 40, 50))
+        } else {
+            Style::default()
+        };
+        let ttm = row
+            .avg_time_to_merge
+            .map(format_duration_short)
+            .unwrap_or_else(|| "-".to_string());
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                format!(
+                    " {:<name_width$}",
+                    truncate_with_ellipsis(&row.repo_name, name_width)
+                ),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!("{:>COUNT_WIDTH$}", row.active),
+                active_count_style(row.active as usize),
+            ),
+            Span::styled(format!("{:>COUNT_WIDTH$}", row.archived), dim_count_style()),
+            Span::styled(
+                format!("{:>COUNT_WIDTH$}", row.merged),
+                Style::default().fg(Color::LightMagenta),
+            ),
+            Span::styled(format!("{ttm:>TTM_WIDTH$}"), dim_count_style()),
+            Span::styled(
+                format!("{:>COST_WIDTH$}", usage::format_usage(&row.usage)),
+                Style::default().fg(Color::LightYellow),
+            ),
+        ]))
+        .style(style)
+    }));
+
+    let list = List::new(items).block(block);
+    f.render_widget(list, area);
+}
+
+fn draw_message_history(f: &mut Frame, app: &mut App, area: Rect) {
+    let title = format!(
+        " Messages: {} ({}) ",
+        app.message_history_title,
+        app.message_history.len()
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan))
+        .title_bottom(clock_title(app));
+
+    if app.message_history.is_empty() {
+        let content = Paragraph::new("No messages sent to this task's engineer yet")
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(content, area);
+        return;
+    }
+
+    let mut lines: Vec<Line> = Vec::new();
+    for msg in &app.message_history {
+        let when = msg.timestamp.with_timezone(&Local);
+        lines.push(Line::from(vec![
+            Span::styled(
+                when.format("%Y-%m-%d %H:%M").to_string(),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                msg.from.clone(),
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        lines.extend(
+            msg.message
+                .lines()
+                .map(|line| Line::from(format!("  {line}"))),
+        );
+        lines.push(Line::from(""));
+    }
+
+    // Approximate wrapped height so the bottom clamp holds for long lines.
+    let inner_width = area.width.saturating_sub(2).max(1) as usize;
+    let total_lines: usize = lines
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(inner_width))
+        .sum();
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let max_scroll = total_lines
+        .saturating_sub(inner_height)
+        .min(u16::MAX as usize) as u16;
+    if app.message_history_scroll > max_scroll {
+        app.message_history_scroll = max_scroll;
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((app.message_history_scroll, 0))
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(paragraph, area);
+}
+
+fn draw_snapshots(f: &mut Frame, app: &App, area: Rect) {
+    let title = format!(
+        " Snapshots: {} ({}) ",
+        app.snapshot_task_id.as_deref().unwrap_or(""),
+        app.snapshots.len()
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan))
+        .title_bottom(clock_title(app));
+
+    if app.snapshots.is_empty() {
+        let content = Paragraph::new("No snapshots yet (press n to tag the current HEAD)")
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(content, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .snapshots
+        .iter()
+        .enumerate()
+        .map(|(i, tag)| {
+            let selected = i == app.snapshot_selected;
+            let style = if selected {
+                Style::default().bg(Color::Rgb(40, 40, 50))
+            } else {
+                Style::default()
+            };
+            let mut spans = vec![Span::styled(
+                format!(" {tag}"),
+                Style::default().fg(Color::White),
+            )];
+            if selected && app.snapshot_restore_confirm {
+                spans.push(Span::styled(
+                    "  reset --hard here? uncommitted changes are lost (y/N)",
+                    Style::default()
+                        .fg(Color::LightRed)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            ListItem::new(Line::from(spans)).style(style)
+        })
+        .collect();
+
+    f.render_widget(List::new(items).block(block), area);
+}
+
+fn draw_dashboard(f: &mut Frame, app: &App, area: Rect) {
+    let counts = app.dashboard_counts();
+    let block = Block::default()
+        .title(" Pending actions ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan))
+        .title_bottom(clock_title(app));
+
+    let rows = [
+        ("w", counts.idle_tasks, "idle engineers"),
+        ("r", counts.review_tasks, "tasks reviewing others' PRs"),
+        ("p", counts.review_requests, "PRs requesting your review"),
+        ("i", counts.unread_notifications, "unread notifications"),
+    ];
+    let mut lines = vec![Line::from("")];
+    for (key, count, label) in rows {
+        let count_style = if count > 0 {
+            Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {key}  "), Style::default().fg(Color::LightCyan)),
+            Span::styled(format!("{count:>4}  "), count_style),
+            Span::styled(label, Style::default().fg(Color::White)),
+        ]));
+    }
+    if !app.show_prs_first_poll_done {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  Fetching PRs from GitHub…",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 fn draw_archive(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -3752,6 +5017,7 @@ fn draw_archive_preview(f: &mut Frame, app: &mut App) {
 fn draw_settings(f: &mut Frame, app: &mut App, area: Rect) {
     let retention_days = app.archive_retention_days;
     let harness_kind = app.config.harness_kind();
+    let merge_cleanup = use_cases::load_merge_cleanup(&app.config);
 
     let selected_style = Style::default().bg(Color::Rgb(40, 40, 50));
 
@@ -3763,6 +5029,10 @@ fn draw_settings(f: &mut Frame, app: &mut App, area: Rect) {
         "  Harness             \u{25C0}  {:<6} \u{25B6}",
         harness_kind.as_str()
     );
+    let cleanup_display = format!(
+        "  Cleanup on merge    \u{25C0}  {:<19} \u{25B6}",
+        merge_cleanup.as_str()
+    );
 
     // Telegram token display: mask all but last 4 chars
     let token_text: String = app.telegram_token_editor.lines().join("");
@@ -3854,6 +5124,18 @@ fn draw_settings(f: &mut Frame, app: &mut App, area: Rect) {
         } else {
             Style::default()
         }),
+        ListItem::new(Line::from(vec![
+            Span::styled(&cleanup_display, Style::default().fg(Color::White)),
+            Span::styled(
+                "    (h/l to switch \u{2014} applies when archiving tasks with a PR)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
+        .style(if app.settings_selected == 4 {
+            selected_style
+        } else {
+            Style::default()
+        }),
     ];
 
     let block = Block::default()
@@ -3928,6 +5210,71 @@ fn draw_notes(f: &mut Frame, app: &mut App, area: Rect) {
 
     draw_notes_explorer(f, app.notes_view.as_ref().unwrap(), chunks[0]);
     draw_notes_editor(f, app, chunks[1]);
+
+    if let Some(finder) = app.notes_view.as_ref().and_then(|nv| nv.finder.as_ref()) {
+        draw_note_finder(f, finder, area);
+    }
+}
+
+fn draw_note_finder(f: &mut Frame, finder: &super::app::NoteFinder, area: Rect) {
+    let popup = centered_rect(60, 60, area);
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Find note ({}) ", finder.matches.len()),
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightCyan));
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+    if inner.height < 2 {
+        return;
+    }
+
+    let input_area = Rect { height: 1, ..inner };
+    let list_area = Rect {
+        y: inner.y + 2,
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+
+    let query = Paragraph::new(format!("> {}", finder.input.lines()[0]))
+        .style(Style::default().fg(Color::LightGreen));
+    f.render_widget(query, input_area);
+
+    if finder.matches.is_empty() {
+        f.render_widget(
+            Paragraph::new("  (no matching notes)").style(Style::default().fg(Color::DarkGray)),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection visible by scrolling the window over the matches.
+    let visible = list_area.height as usize;
+    let start = finder.selected.saturating_sub(visible.saturating_sub(1));
+    let items: Vec<ListItem> = finder
+        .matches
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(visible)
+        .map(|(i, path)| {
+            let display = path.to_string_lossy();
+            let display = display.strip_suffix(".md").unwrap_or(&display);
+            let style = if i == finder.selected {
+                Style::default().fg(Color::White).bg(Color::Rgb(40, 40, 50))
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            ListItem::new(format!("  {display}")).style(style)
+        })
+        .collect();
+    f.render_widget(List::new(items), list_area);
 }
 
 fn draw_notes_explorer(f: &mut Frame, nv: &super::app::NotesView, area: Rect) {
@@ -4309,6 +5656,7 @@ mod project_count_cell_tests {
             repo: 12,
             branch: 10,
             pr: 8,
+            cost: 0,
         };
 
         assert_eq!(
@@ -4333,6 +5681,7 @@ mod project_count_cell_tests {
             repo: 20,
             branch: 12,
             pr: 8,
+            cost: 0,
         };
 
         let line = project_task_line(&task, false, widths);
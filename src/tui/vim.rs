@@ -551,6 +551,24 @@ impl<'a> VimTextArea<'a> {
 
     /// Process an input event, syncing with system clipboard
     pub fn input(&mut self, input: Input) {
+        // Ctrl+V in Insert mode: paste the system clipboard in one go
+        if matches!(
+            input,
+            Input {
+                key: Key::Char('v'),
+                ctrl: true,
+                ..
+            }
+        ) && self.vim.mode == VimMode::Insert
+        {
+            if let Ok(mut clipboard) = arboard::Clipboard::new() {
+                if let Ok(text) = clipboard.get_text() {
+                    self.paste_text(&text);
+                }
+            }
+            return;
+        }
+
         // Before paste: pull from system clipboard
         if matches!(
             input,
@@ -584,6 +602,16 @@ impl<'a> VimTextArea<'a> {
         }
     }
 
+    /// Insert pasted text at the cursor as a single edit rather than a
+    /// stream of key events. Line endings are normalised to `\n`.
+    pub fn paste_text(&mut self, text: &str) {
+        if self.vim.read_only || text.is_empty() {
+            return;
+        }
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        self.textarea.insert_str(text);
+    }
+
     /// Get all lines as a joined string
     pub fn lines_joined(&self) -> String {
         self.textarea.lines().join("\n")
@@ -754,4 +782,20 @@ mod tests {
 
         assert_eq!(editor.lines_joined(), original);
     }
+
+    #[test]
+    fn paste_text_inserts_multiline_text_at_cursor() {
+        let mut editor = VimTextArea::from_lines(["ab"]);
+        editor.set_insert_mode();
+        editor.move_cursor(CursorMove::Forward);
+
+        editor.paste_text("one\r\ntwo\rthree");
+
+        assert_eq!(editor.lines_joined(), "aone\ntwo\nthreeb");
+        assert_eq!(editor.cursor(), (2, 5));
+
+        editor.set_read_only(true);
+        editor.paste_text("ignored");
+        assert_eq!(editor.lines_joined(), "aone\ntwo\nthreeb");
+    }
 }
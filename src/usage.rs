@@ -0,0 +1,83 @@
+//! Token / cost accounting scraped from a task's agent.log.
+//!
+//! Agents report usage in wildly different formats, so the pattern is a
+//! user-configurable regex. Any of the named groups `input`, `output` and
+//! `cost` that participate in a match are summed; `cost` is in dollars and
+//! stored as cents.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+/// Default usage pattern. Matches lines like `input_tokens: 1200`,
+/// `output tokens=340` or `cost: $0.42`.
+pub const DEFAULT_USAGE_PATTERN: &str = r"(?i)input[_ ]tokens[=: ]+(?P<input>\d+)|output[_ ]tokens[=: ]+(?P<output>\d+)|cost[=: ]+\$?(?P<cost>\d+(?:\.\d+)?)";
+
+/// Aggregated usage for one task.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsageTotals {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_cents: u64,
+}
+
+impl UsageTotals {
+    pub fn is_empty(&self) -> bool {
+        self.input_tokens == 0 && self.output_tokens == 0 && self.cost_cents == 0
+    }
+}
+
+/// Compile a user-supplied pattern, falling back to [`DEFAULT_USAGE_PATTERN`]
+/// when none is configured.
+pub fn compile_pattern(pattern: Option<&str>) -> Result<Regex> {
+    let pattern = pattern.unwrap_or(DEFAULT_USAGE_PATTERN);
+    Regex::new(pattern).with_context(|| format!("invalid usage pattern '{}'", pattern))
+}
+
+/// Sum every match of `pattern` in `log`.
+pub fn parse_usage(log: &str, pattern: &Regex) -> UsageTotals {
+    let mut totals = UsageTotals::default();
+    for caps in pattern.captures_iter(log) {
+        if let Some(n) = caps
+            .name("input")
+            .and_then(|m| m.as_str().parse::<u64>().ok())
+        {
+            totals.input_tokens += n;
+        }
+        if let Some(n) = caps
+            .name("output")
+            .and_then(|m| m.as_str().parse::<u64>().ok())
+        {
+            totals.output_tokens += n;
+        }
+        if let Some(dollars) = caps
+            .name("cost")
+            .and_then(|m| m.as_str().parse::<f64>().ok())
+        {
+            totals.cost_cents += (dollars * 100.0).round() as u64;
+        }
+    }
+    totals
+}
+
+/// Compact cost label for table cells: `$1.23`, or a token count when the
+/// agent only reports tokens, or `-` when nothing was recorded.
+pub fn format_usage(totals: &UsageTotals) -> String {
+    if totals.cost_cents > 0 {
+        format!(
+            "${}.{:02}",
+            totals.cost_cents / 100,
+            totals.cost_cents % 100
+        )
+    } else {
+        let tokens = totals.input_tokens + totals.output_tokens;
+        if tokens >= 1_000_000 {
+            format!("{:.1}M tok", tokens as f64 / 1_000_000.0)
+        } else if tokens >= 1_000 {
+            format!("{:.1}k tok", tokens as f64 / 1_000.0)
+        } else if tokens > 0 {
+            format!("{} tok", tokens)
+        } else {
+            "-".to_string()
+        }
+    }
+}
@@ -16,9 +16,10 @@ use crate::harness::{
 };
 use crate::inbox;
 use crate::project::Project;
-use crate::repo_stats::RepoStats;
-use crate::task::{LinkedPr, Task};
+use crate::repo_stats::{self, RepoStats, RepoTaskTotals};
+use crate::task::{LinkedPr, RepoEntry, Task};
 use crate::tmux::Tmux;
+use crate::usage;
 
 /// Required external tools that must be on $PATH (harness binary excluded —
 /// it's resolved per-config via [`Config::default_harness`] and prepended
@@ -73,6 +74,80 @@ pub fn install_hint(config: &Config, tool: &str) -> String {
     }
 }
 
+/// Run the repo's setup script (see [`Config::setup_script`]) in a freshly
+/// created worktree, appending its output to the task's agent.log. Fails
+/// when the script exits non-zero so the task isn't handed to an engineer
+/// in a half-bootstrapped worktree; the error carries the output's tail
+/// because callers roll the worktree (and a new task's dir) back.
+pub fn run_setup_script(
+    config: &Config,
+    task: &Task,
+    repo_name: &str,
+    worktree_path: &Path,
+) -> Result<()> {
+    let Some(script) = config.setup_script(repo_name, worktree_path) else {
+        return Ok(());
+    };
+    let task_id = task.meta.task_id();
+    tracing::info!(task_id = %task_id, repo = repo_name, script = %script, "running setup script");
+    let marker = format!("--- setup ({repo_name}): {script} ---");
+    task.append_agent_log(&marker)?;
+
+    let log_path = task.dir.join("agent.log");
+    let log = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&log_path)
+        .context("Failed to open agent.log for the setup script")?;
+    let status = Command::new("sh")
+        .args(["-c", &script])
+        .current_dir(worktree_path)
+        .stdin(std::process::Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .status()
+        .with_context(|| format!("Failed to run setup script for {repo_name}"))?;
+
+    if !status.success() {
+        tracing::error!(task_id = %task_id, repo = repo_name, %status, "setup script failed");
+        let log = task.read_agent_log().unwrap_or_default();
+        let output = log.rsplit_once(&marker).map(|(_, out)| out).unwrap_or("");
+        let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+        let tail = lines[lines.len().saturating_sub(5)..].join("\n");
+        bail!(
+            "Setup script for {} failed ({}):\n{}",
+            repo_name,
+            status,
+            tail
+        );
+    }
+    Ok(())
+}
+
+/// Undo a fresh worktree whose setup script failed: remove it and, when
+/// this operation created the branch, delete the branch too. Best-effort.
+fn discard_fresh_worktree(
+    config: &Config,
+    parent_dir: Option<&Path>,
+    repo_name: &str,
+    branch_name: &str,
+    worktree_path: &Path,
+    created_branch: bool,
+) {
+    tracing::info!(
+        repo = repo_name,
+        branch = branch_name,
+        created_branch,
+        "rolling back fresh worktree"
+    );
+    let repo_path = config.repo_path_for(parent_dir, repo_name);
+    if let Err(e) = Git::remove_worktree(&repo_path, worktree_path) {
+        tracing::warn!(repo = repo_name, error = %e, "failed to remove worktree during rollback");
+    }
+    if created_branch {
+        let _ = Git::delete_branch(&repo_path, branch_name);
+    }
+}
+
 /// Copy `.env` from the main repo to a new worktree if it exists.
 ///
 /// Best-effort: logs a warning on failure, never fails task creation.
@@ -146,6 +221,8 @@ pub fn create_task(
     config.init_default_files(false)?;
 
     // Set up or reuse worktree
+    let mut base_ref = None;
+    let mut fresh_worktree = false;
     let worktree_path = match worktree_source {
         WorktreeSource::ExistingWorktree(path) => {
             let _ = Git::direnv_allow(&path);
@@ -162,6 +239,7 @@ pub fn create_task(
                 let _ = Git::direnv_allow(&candidate);
                 candidate
             } else {
+                let repo_path = config.repo_path_for(parent_dir_ref, repo_name);
                 let path = Git::create_worktree_quiet(
                     config,
                     repo_name,
@@ -170,6 +248,8 @@ pub fn create_task(
                     parent_dir_ref,
                 )?;
                 let _ = Git::direnv_allow(&path);
+                base_ref = Some(base_branch.unwrap_or_else(|| Git::find_base_ref(&repo_path)));
+                fresh_worktree = true;
                 path
             }
         }
@@ -191,6 +271,7 @@ pub fn create_task(
                     parent_dir_ref,
                 )?;
                 let _ = Git::direnv_allow(&path);
+                fresh_worktree = true;
                 path
             }
         }
@@ -221,26 +302,120 @@ pub fn create_task(
         task.meta.project = project;
     }
 
+    task.meta.base_ref = base_ref;
+
+    let derived_session = task.meta.primary_repo().tmux_session.clone();
+    let session = unique_tmux_session(config, &task.meta.task_id(), derived_session.clone());
+    let session_renamed = session != derived_session;
+    task.meta.repos[0].tmux_session = session;
+
     // Save if any optional fields were set after creation
-    if task.meta.parent_dir.is_some() || task.meta.project.is_some() {
+    if task.meta.parent_dir.is_some()
+        || task.meta.project.is_some()
+        || task.meta.base_ref.is_some()
+        || session_renamed
+    {
         task.save_meta()?;
     }
 
+    if fresh_worktree {
+        let worktree_path = task.meta.primary_repo().worktree_path.clone();
+        if let Err(e) = run_setup_script(config, &task, repo_name, &worktree_path) {
+            // No engineer exists yet, so nothing else references the task.
+            discard_fresh_worktree(
+                config,
+                task.meta.parent_dir.as_deref(),
+                repo_name,
+                branch_name,
+                &worktree_path,
+                task.meta.base_ref.is_some(),
+            );
+            if let Err(del) = task.delete(config) {
+                tracing::warn!(repo = repo_name, branch = branch_name, error = %del, "failed to remove task dir after setup failure");
+            }
+            return Err(e);
+        }
+    }
+
     create_task_engineer(config, &task, first_prompt)?;
 
     // Increment repo usage stats
     let stats_path = config.repo_stats_path();
     let mut stats = RepoStats::load(&stats_path);
     stats.increment(repo_name);
+    stats.touch(repo_name, Utc::now());
     stats.save(&stats_path);
 
     Ok(task)
 }
 
+/// `base`, or `base-2`, `base-3`, ... when a repo of another task already
+/// owns that tmux session. Distinct branches can sanitize to the same name
+/// (`v1.2` and `v1_2`), and sharing a session would let stopping or
+/// attaching one task hit the other.
+fn unique_tmux_session(config: &Config, task_id: &str, base: String) -> String {
+    let taken: std::collections::HashSet<String> = Task::list_all(config)
+        .into_iter()
+        .filter(|t| t.meta.task_id() != task_id)
+        .flat_map(|t| {
+            let parent = t.meta.is_multi_repo().then(|| t.meta.parent_tmux_session());
+            t.meta
+                .repos
+                .into_iter()
+                .map(|r| r.tmux_session)
+                .chain(parent)
+        })
+        .collect();
+    if !taken.contains(&base) {
+        return base;
+    }
+    let unique = (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|name| !taken.contains(name))
+        .expect("some suffix is free");
+    tracing::warn!(
+        task_id,
+        session = %base,
+        unique = %unique,
+        "tmux session name already belongs to another task; using a suffixed name"
+    );
+    unique
+}
+
+/// User-facing note for each of the task's tmux sessions that had to be
+/// suffixed because another task already owned the derived name.
+pub fn tmux_session_collision_warnings(task: &Task) -> Vec<String> {
+    let meta = &task.meta;
+    let mut sessions: Vec<(String, String)> = meta
+        .repos
+        .iter()
+        .map(|r| {
+            (
+                Config::tmux_session_name(&r.repo_name, &meta.branch_name),
+                r.tmux_session.clone(),
+            )
+        })
+        .collect();
+    if meta.is_multi_repo() {
+        sessions.push((
+            Config::tmux_session_name(&meta.name, &meta.branch_name),
+            meta.parent_tmux_session(),
+        ));
+    }
+    sessions
+        .into_iter()
+        .filter(|(derived, actual)| derived != actual)
+        .map(|(derived, actual)| {
+            format!("tmux session '{derived}' belongs to another task; using '{actual}'")
+        })
+        .collect()
+}
+
 /// Create a multi-repo task: set up task dir, but no worktrees yet.
 ///
 /// This is the pure business logic behind multi-repo task creation in the wizard.
-/// It does not create tmux sessions; launch is handled by the caller.
+/// It does not create tmux sessions; launch is handled by the caller. Repos
+/// join later through [`add_repo_to_task`], which runs their setup script.
 pub fn create_multi_repo_task(
     config: &Config,
     name: &str,
@@ -276,8 +451,14 @@ pub fn create_multi_repo_task(
         task.meta.project = project;
     }
 
+    let derived_session = task.meta.parent_tmux_session();
+    let session = unique_tmux_session(config, &task.meta.task_id(), derived_session.clone());
+    if session != derived_session {
+        task.meta.parent_tmux_session = Some(session);
+    }
+
     // Save if any optional fields were set after creation
-    if task.meta.project.is_some() {
+    if task.meta.project.is_some() || task.meta.parent_tmux_session.is_some() {
         task.save_meta()?;
     }
 
@@ -286,26 +467,199 @@ pub fn create_multi_repo_task(
     Ok(task)
 }
 
+/// Directory holding the task's repos.
+fn task_parent_dir(config: &Config, task: &Task) -> PathBuf {
+    task.meta
+        .parent_dir
+        .clone()
+        .unwrap_or_else(|| config.repos_dir.clone())
+}
+
+/// Git repos next to the task's repos that it does not include yet, sorted.
+pub fn addable_repos_for_task(config: &Config, task: &Task) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(task_parent_dir(config, task)) else {
+        return Vec::new();
+    };
+    let mut repos: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().join(".git").exists())
+        .filter_map(|e| e.file_name().to_str().map(str::to_string))
+        .filter(|name| !task.meta.repos.iter().any(|r| &r.repo_name == name))
+        .collect();
+    repos.sort();
+    repos
+}
+
+/// Check `repo_name` out on the task's branch and add it to the task,
+/// turning a single-repo task into a multi-repo one. The new repo must sit
+/// next to the task's existing repos. The engineer is told about the new
+/// worktree through its inbox; the repo's tmux session is left to the caller.
+pub fn add_repo_to_task(config: &Config, task: &mut Task, repo_name: &str) -> Result<RepoEntry> {
+    let task_id = task.meta.task_id();
+    if task.meta.repos.iter().any(|r| r.repo_name == repo_name) {
+        bail!("{} is already part of task {}", repo_name, task_id);
+    }
+    let parent_dir = task_parent_dir(config, task);
+    let repo_path = parent_dir.join(repo_name);
+    if !repo_path.join(".git").exists() {
+        bail!("{} is not a git repo", repo_path.display());
+    }
+    tracing::info!(task_id = %task_id, repo = repo_name, "adding repo to task");
+
+    let branch = task.meta.branch_name.clone();
+    let parent_dir_ref = Some(parent_dir.as_path());
+    let candidate = config.worktree_path_for(parent_dir_ref, repo_name, &branch);
+    let fresh_worktree = !candidate.exists();
+    let mut created_branch = false;
+    let worktree_path = if !fresh_worktree {
+        candidate
+    } else if Git::local_branch_exists(&repo_path, &branch)
+        || Git::remote_branch_exists(&repo_path, &branch)
+    {
+        Git::create_worktree_for_existing_branch_quiet(config, repo_name, &branch, parent_dir_ref)?
+    } else {
+        created_branch = true;
+        Git::create_worktree_quiet(config, repo_name, &branch, None, parent_dir_ref)?
+    };
+    let _ = Git::direnv_allow(&worktree_path);
+    if let Err(e) = copy_repo_files_to_worktree(config, repo_name, &worktree_path, parent_dir_ref) {
+        tracing::warn!(repo = repo_name, branch = %branch, error = %e, "failed to copy repo files to worktree");
+    }
+    if fresh_worktree {
+        if let Err(e) = run_setup_script(config, task, repo_name, &worktree_path) {
+            discard_fresh_worktree(
+                config,
+                parent_dir_ref,
+                repo_name,
+                &branch,
+                &worktree_path,
+                created_branch,
+            );
+            return Err(e);
+        }
+    }
+
+    let entry = RepoEntry {
+        repo_name: repo_name.to_string(),
+        worktree_path,
+        tmux_session: unique_tmux_session(
+            config,
+            &task_id,
+            Config::tmux_session_name(repo_name, &branch),
+        ),
+    };
+    task.meta.repos.push(entry.clone());
+    task.meta.multi_repo = Some(true);
+    task.meta.parent_dir = Some(parent_dir);
+    task.meta.updated_at = Utc::now();
+    task.save_meta()?;
+
+    match attached_engineer_for_task(config, &task_id) {
+        Ok(engineer) => {
+            let inbox_path = config.agent_inbox(&engineer.meta.project, &engineer.meta.name);
+            let message = format!(
+                "{} was added to task {}. Its worktree is {} on branch {}.",
+                repo_name,
+                task_id,
+                entry.worktree_path.display(),
+                branch
+            );
+            inbox::append_message(&inbox_path, "system", &message)?;
+        }
+        Err(e) => {
+            tracing::warn!(task_id = %task_id, error = %e, "no engineer to tell about the new repo");
+        }
+    }
+
+    Ok(entry)
+}
+
+/// How much of a task's workspace `archive_task_with` tears down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchiveMode {
+    /// Kill the task's tmux sessions and clean up worktrees per policy.
+    #[default]
+    Full,
+    /// Leave the task's tmux sessions, and the worktrees they sit in, for
+    /// manual use. Attached agents are still archived.
+    KeepSession,
+}
+
 /// Archive a task: remove worktrees, set archived_at and saved, save meta.
 ///
-/// Branches are preserved so the user can revisit them later. They are cleaned
-/// up when the task is permanently deleted (see `permanently_delete_archived_task`).
+/// Branches are preserved so the user can revisit them later, and cleaned up
+/// when the task is permanently deleted (see `permanently_delete_archived_task`).
+/// The exception is a task with a linked PR under the `branch_and_worktree`
+/// cleanup policy: its branch is deleted right away in each repo where it is
+/// already merged into the base.
 ///
 /// This is the pure business logic behind archiving from the task list.
 /// It archives and stops attached agents and task tmux sessions.
 /// It does NOT remove the task directory — the directory is kept as the archive.
 pub fn archive_task(config: &Config, task: &mut Task, saved: bool) -> Result<()> {
+    archive_task_with(config, task, saved, ArchiveMode::Full)
+}
+
+/// [`archive_task`] with an explicit [`ArchiveMode`].
+pub fn archive_task_with(
+    config: &Config,
+    task: &mut Task,
+    saved: bool,
+    mode: ArchiveMode,
+) -> Result<()> {
     let task_id = task.meta.task_id();
-    tracing::info!(task_id = %task_id, saved, "archiving task");
+    tracing::info!(task_id = %task_id, saved, ?mode, "archiving task");
 
     archive_agents_attached_to_task(config, &task_id)?;
+    if mode == ArchiveMode::KeepSession {
+        task.meta.archived_at = Some(chrono::Utc::now());
+        task.meta.saved = saved;
+        return task.save_meta();
+    }
     kill_task_tmux_sessions(task);
 
-    // Remove worktrees (branches are kept for later reference)
-    let parent_dir = task.meta.parent_dir.as_deref();
-    for repo in &task.meta.repos {
-        let repo_path = config.repo_path_for(parent_dir, &repo.repo_name);
-        let _ = Git::remove_worktree(&repo_path, &repo.worktree_path);
+    // Tasks with a PR follow the configured cleanup policy; everything else
+    // keeps the default of removing worktrees and keeping branches.
+    let cleanup = if task.meta.linked_pr.is_some() {
+        load_merge_cleanup(config)
+    } else {
+        MergeCleanup::WorktreeOnly
+    };
+    tracing::debug!(task_id = %task_id, cleanup = cleanup.as_str(), "archive cleanup policy");
+
+    if cleanup != MergeCleanup::KeepAll {
+        let parent_dir = task.meta.parent_dir.as_deref();
+        for repo in &task.meta.repos {
+            let repo_path = config.repo_path_for(parent_dir, &repo.repo_name);
+            let _ = Git::remove_worktree(&repo_path, &repo.worktree_path);
+            if cleanup == MergeCleanup::BranchAndWorktree {
+                // A linked PR may still be open or closed unmerged; only
+                // drop branches whose commits all reached the base.
+                let base = task
+                    .meta
+                    .base_ref
+                    .clone()
+                    .unwrap_or_else(|| Git::find_base_ref(&repo_path));
+                let branch_ref = format!("refs/heads/{}", task.meta.branch_name);
+                if !matches!(Git::commits_ahead(&repo_path, &base, &branch_ref), Ok(0)) {
+                    tracing::info!(
+                        task_id = %task_id,
+                        repo = %repo.repo_name,
+                        base = %base,
+                        "task branch not merged; keeping it"
+                    );
+                    continue;
+                }
+                if let Err(e) = Git::delete_branch(&repo_path, &task.meta.branch_name) {
+                    tracing::warn!(
+                        task_id = %task_id,
+                        repo = %repo.repo_name,
+                        error = %e,
+                        "failed to delete task branch"
+                    );
+                }
+            }
+        }
     }
 
     // Mark as archived
@@ -316,6 +670,52 @@ pub fn archive_task(config: &Config, task: &mut Task, saved: bool) -> Result<()>
     Ok(())
 }
 
+/// Fetch origin and rebase every worktree of `task` onto its base ref.
+/// Stops at the first repo that fails; that repo's rebase is aborted.
+pub fn rebase_task_onto_base(config: &Config, task: &Task) -> Result<()> {
+    let parent_dir = task.meta.parent_dir.as_deref();
+    for repo in &task.meta.repos {
+        let repo_path = config.repo_path_for(parent_dir, &repo.repo_name);
+        Git::fetch_origin(&repo_path)?;
+        let base = task
+            .meta
+            .base_ref
+            .clone()
+            .unwrap_or_else(|| Git::find_base_ref(&repo_path));
+        Git::rebase_onto(&repo.worktree_path, &base)
+            .with_context(|| format!("failed to rebase '{}'", repo.repo_name))?;
+    }
+    Ok(())
+}
+
+/// IDs of active tasks that still have a live tmux session, i.e. whose
+/// agents may be mid-flight.
+pub fn live_task_ids(config: &Config) -> Vec<String> {
+    Task::list_all(config)
+        .into_iter()
+        .filter(|t| t.meta.archived_at.is_none())
+        .filter(|t| {
+            t.meta
+                .repos
+                .iter()
+                .any(|r| Tmux::session_exists(&r.tmux_session))
+        })
+        .map(|t| t.meta.task_id())
+        .collect()
+}
+
+/// Kill a task's tmux sessions and those of its attached agents without
+/// archiving anything. The agents can be resumed later with restart.
+pub fn stop_task_sessions(config: &Config, task_id: &str) -> Result<()> {
+    let task = Task::load_by_id(config, task_id)?;
+    tracing::info!(task_id = %task_id, "stopping task sessions");
+    for agent in attached_agents_for_task(config, task_id)? {
+        kill_agent_tmux_session(&agent);
+    }
+    kill_task_tmux_sessions(&task);
+    Ok(())
+}
+
 fn kill_task_tmux_sessions(task: &Task) {
     for repo in &task.meta.repos {
         if let Err(e) = Tmux::kill_session(&repo.tmux_session) {
@@ -329,7 +729,7 @@ fn kill_task_tmux_sessions(task: &Task) {
     }
 
     if task.meta.is_multi_repo() {
-        let parent_session = Config::tmux_session_name(&task.meta.name, &task.meta.branch_name);
+        let parent_session = task.meta.parent_tmux_session();
         if let Err(e) = Tmux::kill_session(&parent_session) {
             tracing::warn!(
                 task_id = %task.meta.task_id(),
@@ -375,8 +775,17 @@ pub fn permanently_delete_archived_task(config: &Config, task: Task) -> Result<(
     archive_agents_attached_to_task(config, &task_id)?;
     kill_task_tmux_sessions(&task);
 
-    // Delete branches for all repos (best-effort)
+    // Tasks archived with `ArchiveMode::KeepSession` still have their
+    // worktrees, and git won't delete a branch a worktree has checked out.
     let parent_dir = task.meta.parent_dir.as_deref();
+    for repo in &task.meta.repos {
+        if repo.worktree_path.exists() {
+            let repo_path = config.repo_path_for(parent_dir, &repo.repo_name);
+            let _ = Git::remove_worktree(&repo_path, &repo.worktree_path);
+        }
+    }
+
+    // Delete branches for all repos (best-effort)
     for repo in &task.meta.repos {
         let repo_path = config.repo_path_for(parent_dir, &repo.repo_name);
         let _ = Git::delete_branch(&repo_path, &task.meta.branch_name);
@@ -461,6 +870,23 @@ pub fn list_archived_tasks(config: &Config, project: &str) -> Vec<(Task, String)
         .collect()
 }
 
+/// Per-repo totals across all active and archived tasks, for the repo-stats
+/// dashboard.
+pub fn repo_dashboard(config: &Config) -> Vec<RepoTaskTotals> {
+    let pattern = load_usage_settings(config).pattern;
+    let metas: Vec<_> = Task::list_all(config)
+        .into_iter()
+        .chain(Task::list_archived(config))
+        .map(|mut task| {
+            if let Err(e) = refresh_task_usage(&mut task, &pattern) {
+                tracing::warn!(task_id = %task.meta.task_id(), error = %e, "failed to refresh task usage");
+            }
+            task.meta
+        })
+        .collect();
+    repo_stats::repo_task_totals(&metas)
+}
+
 /// Toggle hold status on a project.
 pub fn toggle_project_hold(config: &Config, project_name: &str) -> Result<()> {
     let mut project = Project::load_by_name(config, project_name)?;
@@ -481,6 +907,276 @@ pub fn list_tasks(config: &Config) -> Vec<Task> {
     Task::list_all(config)
 }
 
+/// Validate a branch name typed by the user. Mirrors the new-task wizard rules.
+pub fn validate_branch_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        bail!("Branch name cannot be empty");
+    }
+    if name.contains(' ') || name.contains("..") || name.starts_with('/') || name.ends_with('/') {
+        bail!("Invalid branch name format");
+    }
+    Ok(())
+}
+
+/// Complete a partially typed branch name against `branches`.
+///
+/// `press` counts consecutive completion presses starting at 0. The first
+/// press extends `typed` to the longest common prefix of the matches; later
+/// presses (or the first, when the prefix can't grow) cycle through the
+/// matches themselves. Returns `None` when nothing matches.
+pub fn complete_branch_name(typed: &str, branches: &[String], press: usize) -> Option<String> {
+    let matches: Vec<&str> = branches
+        .iter()
+        .map(String::as_str)
+        .filter(|b| b.starts_with(typed))
+        .collect();
+    let first = *matches.first()?;
+    if matches.len() == 1 {
+        return Some(first.to_string());
+    }
+
+    let mut prefix_len = first.len();
+    for m in &matches[1..] {
+        prefix_len = first
+            .char_indices()
+            .zip(m.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map(|((i, a), _)| i + a.len_utf8())
+            .unwrap_or(0)
+            .min(prefix_len);
+    }
+    let grows = prefix_len > typed.len();
+    if press == 0 && grows {
+        return Some(first[..prefix_len].to_string());
+    }
+    let offset = if grows { press - 1 } else { press };
+    Some(matches[offset % matches.len()].to_string())
+}
+
+/// Rename a task's branch in place.
+///
+/// Renames the branch in every repo, moves worktrees that live at the
+/// canonical `<repo>-wt/<branch>` path, renames the task tmux sessions, moves
+/// the task directory to the new task ID, and re-points attached agents at
+/// it. Errors before touching anything if the new name collides with an
+/// existing task or local branch. Running agents keep their current cwd until
+/// they are respawned.
+pub fn rename_task_branch(config: &Config, task: &mut Task, new_branch: &str) -> Result<()> {
+    let new_branch = new_branch.trim();
+    validate_branch_name(new_branch)?;
+
+    let old_branch = task.meta.branch_name.clone();
+    if new_branch == old_branch {
+        bail!("Task is already on branch '{new_branch}'");
+    }
+    let old_id = task.meta.task_id();
+    let new_id = Config::task_id(&task.meta.name, new_branch);
+    let new_dir = config.task_dir(&task.meta.name, new_branch);
+    if new_dir.exists() {
+        bail!("Task '{new_id}' already exists");
+    }
+
+    let parent_dir = task.meta.parent_dir.clone();
+    for repo in &task.meta.repos {
+        let repo_path = config.repo_path_for(parent_dir.as_deref(), &repo.repo_name);
+        if Git::local_branch_exists(&repo_path, new_branch) {
+            bail!(
+                "Branch '{}' already exists in {}",
+                new_branch,
+                repo.repo_name
+            );
+        }
+    }
+
+    tracing::info!(old_id = %old_id, new_id = %new_id, "renaming task branch");
+
+    // (repo index, worktree path before the move) per renamed repo, so a
+    // failure part-way through a multi-repo task can be undone.
+    let mut renamed: Vec<(usize, Option<PathBuf>)> = Vec::new();
+    for index in 0..task.meta.repos.len() {
+        let repo = &mut task.meta.repos[index];
+        match rename_repo_branch(config, parent_dir.as_deref(), repo, &old_branch, new_branch) {
+            Ok(moved_from) => renamed.push((index, moved_from)),
+            Err(e) => {
+                undo_repo_branch_renames(config, task, &renamed, &old_branch, new_branch);
+                return Err(e);
+            }
+        }
+    }
+
+    if let Err(e) = std::fs::rename(&task.dir, &new_dir) {
+        undo_repo_branch_renames(config, task, &renamed, &old_branch, new_branch);
+        return Err(e).with_context(|| {
+            format!(
+                "failed to move task directory {} to {}",
+                task.dir.display(),
+                new_dir.display()
+            )
+        });
+    }
+
+    for repo in &mut task.meta.repos {
+        let new_session = unique_tmux_session(
+            config,
+            &old_id,
+            Config::tmux_session_name(&repo.repo_name, new_branch),
+        );
+        if let Err(e) = Tmux::rename_session(&repo.tmux_session, &new_session) {
+            tracing::warn!(session = %repo.tmux_session, error = %e, "failed to rename task tmux session");
+        }
+        repo.tmux_session = new_session;
+    }
+
+    if task.meta.is_multi_repo() {
+        let old_session = task.meta.parent_tmux_session();
+        let derived_session = Config::tmux_session_name(&task.meta.name, new_branch);
+        let new_session = unique_tmux_session(config, &old_id, derived_session.clone());
+        if let Err(e) = Tmux::rename_session(&old_session, &new_session) {
+            tracing::warn!(session = %old_session, error = %e, "failed to rename task parent tmux session");
+        }
+        task.meta.parent_tmux_session = (new_session != derived_session).then_some(new_session);
+    }
+
+    task.dir = new_dir;
+    task.meta.branch_name = new_branch.to_string();
+    task.meta.updated_at = Utc::now();
+    task.save_meta()?;
+
+    for mut agent in AgentRecord::list_all(config)? {
+        let role_label = match &agent.meta.attachment {
+            AgentAttachment::Task {
+                task_id,
+                role_label,
+            } if task_id == &old_id => role_label.clone(),
+            _ => continue,
+        };
+        agent.set_attachment(AgentAttachment::Task {
+            task_id: new_id.clone(),
+            role_label,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Rename one repo's task branch and, when agman laid the worktree out
+/// itself, move the worktree to match; adopted worktrees stay put. Returns
+/// the worktree's previous path if it moved. Leaves the repo as it was on
+/// error.
+fn rename_repo_branch(
+    config: &Config,
+    parent_dir: Option<&Path>,
+    repo: &mut RepoEntry,
+    old_branch: &str,
+    new_branch: &str,
+) -> Result<Option<PathBuf>> {
+    let repo_path = config.repo_path_for(parent_dir, &repo.repo_name);
+    Git::rename_branch(&repo_path, old_branch, new_branch)?;
+
+    let old_wt = config.worktree_path_for(parent_dir, &repo.repo_name, old_branch);
+    let new_wt = config.worktree_path_for(parent_dir, &repo.repo_name, new_branch);
+    if repo.worktree_path != old_wt || old_wt == new_wt || !old_wt.exists() {
+        return Ok(None);
+    }
+    if let Err(e) = Git::move_worktree(&repo_path, &old_wt, &new_wt) {
+        if let Err(undo) = Git::rename_branch(&repo_path, new_branch, old_branch) {
+            tracing::warn!(repo = %repo.repo_name, error = %undo, "failed to restore branch name");
+        }
+        return Err(e);
+    }
+    repo.worktree_path = new_wt;
+    Ok(Some(old_wt))
+}
+
+/// Undo [`rename_repo_branch`] for the given `(repo index, previous
+/// worktree path)` entries, newest first. Best-effort: failures are logged.
+fn undo_repo_branch_renames(
+    config: &Config,
+    task: &mut Task,
+    renamed: &[(usize, Option<PathBuf>)],
+    old_branch: &str,
+    new_branch: &str,
+) {
+    let parent_dir = task.meta.parent_dir.clone();
+    for (index, moved_from) in renamed.iter().rev() {
+        let repo = &mut task.meta.repos[*index];
+        let repo_path = config.repo_path_for(parent_dir.as_deref(), &repo.repo_name);
+        if let Some(old_wt) = moved_from {
+            match Git::move_worktree(&repo_path, &repo.worktree_path, old_wt) {
+                Ok(()) => repo.worktree_path = old_wt.clone(),
+                Err(e) => {
+                    tracing::warn!(repo = %repo.repo_name, error = %e, "failed to move worktree back")
+                }
+            }
+        }
+        if let Err(e) = Git::rename_branch(&repo_path, new_branch, old_branch) {
+            tracing::warn!(repo = %repo.repo_name, error = %e, "failed to restore branch name");
+        }
+        tracing::info!(repo = %repo.repo_name, branch = old_branch, "rolled back branch rename");
+    }
+}
+
+/// Correct the base ref a task branch was created from.
+///
+/// An empty `base_ref` clears it, so auto-rebase falls back to the repo's
+/// detected base. Otherwise the ref must resolve in every task repo. With
+/// `set_upstream`, each worktree's branch also tracks the new base.
+pub fn set_task_base_ref(
+    config: &Config,
+    task: &mut Task,
+    base_ref: &str,
+    set_upstream: bool,
+) -> Result<()> {
+    let base_ref = base_ref.trim();
+    let parent_dir = task.meta.parent_dir.clone();
+    if !base_ref.is_empty() {
+        for repo in &task.meta.repos {
+            let repo_path = config.repo_path_for(parent_dir.as_deref(), &repo.repo_name);
+            if !Git::commit_exists(&repo_path, base_ref) {
+                bail!("'{}' does not resolve in {}", base_ref, repo.repo_name);
+            }
+        }
+        if set_upstream {
+            for repo in &task.meta.repos {
+                Git::set_upstream(&repo.worktree_path, base_ref)?;
+            }
+        }
+    }
+
+    tracing::info!(task_id = %task.meta.task_id(), base_ref, set_upstream, "setting task base ref");
+    task.meta.base_ref = (!base_ref.is_empty()).then(|| base_ref.to_string());
+    task.meta.updated_at = Utc::now();
+    task.save_meta()
+}
+
+/// Set or clear the display label for a task. An empty label clears it.
+pub fn set_task_label(task: &mut Task, label: &str) -> Result<()> {
+    let label = label.trim();
+    tracing::info!(task_id = %task.meta.task_id(), label, "setting task label");
+    task.meta.label = (!label.is_empty()).then(|| label.to_string());
+    task.meta.updated_at = Utc::now();
+    task.save_meta()
+}
+
+/// Commits the task's primary worktree is `(ahead, behind)` its base: the
+/// task's `base_ref` when set, else the repo's default base. `None` when the
+/// worktree is missing or git can't answer.
+pub fn task_ahead_behind(task: &Task) -> Option<(u64, u64)> {
+    let worktree = &task.meta.repos.first()?.worktree_path;
+    if !worktree.exists() {
+        return None;
+    }
+    let base = task
+        .meta
+        .base_ref
+        .clone()
+        .unwrap_or_else(|| Git::find_base_ref(worktree));
+    let ahead = Git::commits_ahead(worktree, &base, "HEAD").ok()?;
+    let behind = Git::commits_ahead(worktree, "HEAD", &base).ok()?;
+    Some((ahead, behind))
+}
+
 /// Save notes for a task.
 pub fn save_notes(task: &Task, notes: &str) -> Result<()> {
     tracing::info!(task_id = %task.meta.task_id(), "saving notes");
@@ -874,31 +1570,1013 @@ pub enum DirKind {
     Plain,
 }
 
-// ---------------------------------------------------------------------------
-// Archive Retention Settings
-// ---------------------------------------------------------------------------
-
-const DEFAULT_ARCHIVE_RETENTION_DAYS: u64 = 30;
-
-/// Load the archive retention period from config, defaulting to 30 days.
-pub fn load_archive_retention(config: &Config) -> u64 {
-    let cf = crate::config::load_config_file(&config.base_dir);
-    cf.archive_retention_days
-        .unwrap_or(DEFAULT_ARCHIVE_RETENTION_DAYS)
+/// Glob patterns from `~/.agman/repos-ignore`, one per line. Blank lines and
+/// `#` comments are skipped; a missing file means no patterns.
+pub fn load_repos_ignore(config: &Config) -> Vec<String> {
+    std::fs::read_to_string(config.repos_ignore_path())
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.trim_end_matches('/').to_string())
+        .collect()
 }
 
-/// Save the archive retention period to config, preserving other config fields.
-pub fn save_archive_retention(config: &Config, days: u64) -> Result<()> {
-    let mut cf = crate::config::load_config_file(&config.base_dir);
-    cf.archive_retention_days = Some(days);
-    crate::config::save_config_file(&config.base_dir, &cf)
+/// Whether a directory name matches any repos-ignore pattern. Patterns
+/// support `*` (any run of characters) and `?` (one character).
+pub fn is_repo_ignored(name: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|pattern| glob_match(pattern, name))
 }
 
-// ---------------------------------------------------------------------------
-// Telegram Config
-// ---------------------------------------------------------------------------
-
-/// Load the Telegram bot token and chat ID from config.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name index it was tried at.
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    backtrack = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Derive the local directory name for a git URL, the way `git clone` does:
+/// last path segment with any trailing `/` and `.git` stripped. Handles both
+/// `https://host/owner/repo.git` and `git@host:owner/repo.git`.
+pub fn repo_name_from_git_url(url: &str) -> Option<String> {
+    let trimmed = url.trim().trim_end_matches('/');
+    let last = trimmed.rsplit(['/', ':']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    if name.is_empty() || name == "." || name == ".." {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Clone `url` into `repos_dir` so it can be picked for a new task.
+///
+/// Returns the repo name and path. Refuses to clobber an existing directory
+/// and double-checks the result classifies as a [`DirKind::GitRepo`].
+/// `on_progress` receives git's progress lines.
+pub fn clone_repo_into_repos_dir(
+    config: &Config,
+    url: &str,
+    on_progress: impl FnMut(&str),
+) -> Result<(String, PathBuf)> {
+    let url = url.trim();
+    // git would read a leading dash as an option (e.g. `--upload-pack=...`).
+    if url.starts_with('-') {
+        bail!("'{}' is not a git URL", url);
+    }
+    let name = repo_name_from_git_url(url)
+        .ok_or_else(|| anyhow::anyhow!("Cannot derive a repo name from '{}'", url))?;
+    let dest = config.repos_dir.join(&name);
+    if dest.exists() {
+        bail!("{} already exists", dest.display());
+    }
+    std::fs::create_dir_all(&config.repos_dir)
+        .with_context(|| format!("failed to create {}", config.repos_dir.display()))?;
+
+    Git::clone_repo(url, &dest, on_progress)?;
+
+    if classify_directory(&dest) != DirKind::GitRepo {
+        bail!("Cloned {} but it is not a git repo", dest.display());
+    }
+    Ok((name, dest))
+}
+
+// ---------------------------------------------------------------------------
+// Archive Retention Settings
+// ---------------------------------------------------------------------------
+
+const DEFAULT_ARCHIVE_RETENTION_DAYS: u64 = 30;
+
+/// Load the archive retention period from config, defaulting to 30 days.
+pub fn load_archive_retention(config: &Config) -> u64 {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    cf.archive_retention_days
+        .unwrap_or(DEFAULT_ARCHIVE_RETENTION_DAYS)
+}
+
+/// Save the archive retention period to config, preserving other config fields.
+pub fn save_archive_retention(config: &Config, days: u64) -> Result<()> {
+    let mut cf = crate::config::load_config_file(&config.base_dir);
+    cf.archive_retention_days = Some(days);
+    crate::config::save_config_file(&config.base_dir, &cf)
+}
+
+// ---------------------------------------------------------------------------
+// Merge Cleanup Settings
+// ---------------------------------------------------------------------------
+
+/// How much git state `archive_task` reclaims for a task with a linked PR.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeCleanup {
+    /// Remove worktrees, keep local branches.
+    #[default]
+    WorktreeOnly,
+    /// Remove worktrees and delete local branches.
+    BranchAndWorktree,
+    /// Leave worktrees and branches untouched; only kill sessions.
+    KeepAll,
+}
+
+impl MergeCleanup {
+    pub const ALL: [MergeCleanup; 3] = [
+        MergeCleanup::WorktreeOnly,
+        MergeCleanup::BranchAndWorktree,
+        MergeCleanup::KeepAll,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MergeCleanup::WorktreeOnly => "worktree_only",
+            MergeCleanup::BranchAndWorktree => "branch_and_worktree",
+            MergeCleanup::KeepAll => "keep_all",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<MergeCleanup> {
+        MergeCleanup::ALL.into_iter().find(|c| c.as_str() == s)
+    }
+}
+
+/// Load the post-merge cleanup policy from config. Falls back to
+/// `worktree_only` when absent or unrecognised.
+pub fn load_merge_cleanup(config: &Config) -> MergeCleanup {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    cf.cleanup_on_merge
+        .as_deref()
+        .and_then(MergeCleanup::parse)
+        .unwrap_or_default()
+}
+
+/// Save the post-merge cleanup policy to config, preserving other config fields.
+pub fn save_merge_cleanup(config: &Config, cleanup: MergeCleanup) -> Result<()> {
+    let mut cf = crate::config::load_config_file(&config.base_dir);
+    cf.cleanup_on_merge = Some(cleanup.as_str().to_string());
+    crate::config::save_config_file(&config.base_dir, &cf)
+}
+
+// ---------------------------------------------------------------------------
+// Task List Sort
+// ---------------------------------------------------------------------------
+
+/// Order of the task list, cycled with `O` in the TUI and persisted as the
+/// `task_sort` config key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TaskSort {
+    /// Pinned tasks in their own section, then most recently updated.
+    #[default]
+    PinnedThenUpdated,
+    /// Flat list, most recently updated first.
+    UpdatedDesc,
+    /// Flat list, oldest task first.
+    CreatedAsc,
+    /// Flat list grouped by repo, then branch.
+    Repo,
+}
+
+impl TaskSort {
+    pub const ALL: [TaskSort; 4] = [
+        TaskSort::PinnedThenUpdated,
+        TaskSort::UpdatedDesc,
+        TaskSort::CreatedAsc,
+        TaskSort::Repo,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskSort::PinnedThenUpdated => "pinned_then_updated",
+            TaskSort::UpdatedDesc => "updated_desc",
+            TaskSort::CreatedAsc => "created_asc",
+            TaskSort::Repo => "repo",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<TaskSort> {
+        TaskSort::ALL.into_iter().find(|c| c.as_str() == s)
+    }
+
+    /// Cycle to the next ordering.
+    pub fn next(self) -> Self {
+        let idx = TaskSort::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        TaskSort::ALL[(idx + 1) % TaskSort::ALL.len()]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskSort::PinnedThenUpdated => "pinned, updated",
+            TaskSort::UpdatedDesc => "updated",
+            TaskSort::CreatedAsc => "created",
+            TaskSort::Repo => "repo",
+        }
+    }
+
+    /// Whether pinned tasks get their own section in this ordering.
+    pub fn groups_pinned(self) -> bool {
+        self == TaskSort::PinnedThenUpdated
+    }
+}
+
+/// Sort `tasks` in place. Ties keep their existing relative order.
+pub fn sort_tasks(tasks: &mut [Task], sort: TaskSort) {
+    match sort {
+        TaskSort::PinnedThenUpdated => tasks.sort_by(|a, b| {
+            b.meta
+                .pinned
+                .cmp(&a.meta.pinned)
+                .then(b.meta.updated_at.cmp(&a.meta.updated_at))
+        }),
+        TaskSort::UpdatedDesc => tasks.sort_by(|a, b| b.meta.updated_at.cmp(&a.meta.updated_at)),
+        TaskSort::CreatedAsc => tasks.sort_by(|a, b| a.meta.created_at.cmp(&b.meta.created_at)),
+        TaskSort::Repo => tasks.sort_by(|a, b| {
+            a.meta
+                .name
+                .cmp(&b.meta.name)
+                .then(a.meta.branch_name.cmp(&b.meta.branch_name))
+        }),
+    }
+}
+
+/// Load the default task list order from config. Falls back to
+/// `pinned_then_updated` when absent or unrecognised.
+pub fn load_task_sort(config: &Config) -> TaskSort {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    cf.task_sort
+        .as_deref()
+        .and_then(TaskSort::parse)
+        .unwrap_or_default()
+}
+
+/// Save the default task list order to config, preserving other config fields.
+pub fn save_task_sort(config: &Config, sort: TaskSort) -> Result<()> {
+    let mut cf = crate::config::load_config_file(&config.base_dir);
+    cf.task_sort = Some(sort.as_str().to_string());
+    crate::config::save_config_file(&config.base_dir, &cf)
+}
+
+// ---------------------------------------------------------------------------
+// Break Reminder Settings
+// ---------------------------------------------------------------------------
+
+const DEFAULT_BREAK_INTERVAL_MINS: u64 = 50;
+const DEFAULT_BREAK_WARNING_SECS: u64 = 300;
+
+/// Coffee-break reminder settings, resolved from config with defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakSettings {
+    pub enabled: bool,
+    pub interval: std::time::Duration,
+    pub warning: std::time::Duration,
+}
+
+/// Load the break reminder settings from config. Disabled unless
+/// `break_enabled = true`; interval and warning fall back to 50 minutes and
+/// 300 seconds.
+pub fn load_break_settings(config: &Config) -> BreakSettings {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    BreakSettings {
+        enabled: cf.break_enabled.unwrap_or(false),
+        interval: std::time::Duration::from_secs(
+            cf.break_interval_mins
+                .unwrap_or(DEFAULT_BREAK_INTERVAL_MINS)
+                .max(1)
+                * 60,
+        ),
+        warning: std::time::Duration::from_secs(
+            cf.break_warning_secs.unwrap_or(DEFAULT_BREAK_WARNING_SECS),
+        ),
+    }
+}
+
+/// State of the coffee-break badge in the TUI clock.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BreakState {
+    Hidden,
+    /// The break is due in `remaining_secs`.
+    Warning {
+        remaining_secs: u64,
+    },
+    Due,
+}
+
+/// Classify the break badge given the time elapsed since the last reset.
+pub fn classify_break(settings: &BreakSettings, elapsed: std::time::Duration) -> BreakState {
+    if !settings.enabled {
+        return BreakState::Hidden;
+    }
+    if elapsed >= settings.interval {
+        return BreakState::Due;
+    }
+    let remaining = settings.interval - elapsed;
+    if remaining <= settings.warning {
+        BreakState::Warning {
+            remaining_secs: remaining.as_secs(),
+        }
+    } else {
+        BreakState::Hidden
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Log filtering
+// ---------------------------------------------------------------------------
+
+/// Which agent.log lines the preview's Logs pane shows, cycled with `M`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFilter {
+    #[default]
+    All,
+    /// Drop tool invocations and their output.
+    NoTools,
+    /// Only lines that look like errors, plus section markers.
+    Errors,
+    /// Only each agent section's closing message, plus section markers.
+    Final,
+}
+
+impl LogFilter {
+    pub const ALL: [LogFilter; 4] = [
+        LogFilter::All,
+        LogFilter::NoTools,
+        LogFilter::Errors,
+        LogFilter::Final,
+    ];
+
+    /// Cycle to the next filter.
+    pub fn next(self) -> Self {
+        let idx = LogFilter::ALL.iter().position(|f| *f == self).unwrap_or(0);
+        LogFilter::ALL[(idx + 1) % LogFilter::ALL.len()]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogFilter::All => "all",
+            LogFilter::NoTools => "no tools",
+            LogFilter::Errors => "errors",
+            LogFilter::Final => "final messages",
+        }
+    }
+}
+
+/// Agent start/finish markers and stop conditions, which every filter keeps.
+pub fn is_log_marker(line: &str) -> bool {
+    let trimmed = line.trim();
+    (trimmed.starts_with("--- Agent:") && trimmed.ends_with("---"))
+        || trimmed.starts_with("[... ")
+        || trimmed.contains("AGENT_DONE")
+        || trimmed.contains("TASK_COMPLETE")
+        || trimmed.contains("INPUT_NEEDED")
+}
+
+/// Tool calls (`⏺ Bash(...)`), their indented results (`⎿ ...`) and
+/// bracketed `[tool]` lines as harnesses print them.
+fn is_tool_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('⎿') || trimmed.starts_with("[tool") {
+        return true;
+    }
+    let Some(call) = trimmed.strip_prefix("⏺ ") else {
+        return false;
+    };
+    call.split_once('(')
+        .is_some_and(|(name, _)| !name.is_empty() && name.chars().all(char::is_alphanumeric))
+}
+
+/// Whether an agent-log, output or status line reports a failure.
+pub fn is_error_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    lower.trim_start().starts_with("[stderr]")
+        || lower.contains("error")
+        || lower.contains("failed")
+        || lower.contains("panicked")
+}
+
+/// Apply `filter` to agent.log text, keeping line order.
+pub fn filter_log(content: &str, filter: LogFilter) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let kept: Vec<&str> = match filter {
+        LogFilter::All => return content.to_string(),
+        LogFilter::NoTools => lines.into_iter().filter(|l| !is_tool_line(l)).collect(),
+        LogFilter::Errors => lines
+            .into_iter()
+            .filter(|l| is_log_marker(l) || is_error_line(l))
+            .collect(),
+        LogFilter::Final => {
+            // Keep markers, and for each stretch of output between markers
+            // only its last run of non-tool lines.
+            let mut kept = Vec::new();
+            let mut block: Vec<&str> = Vec::new();
+            for line in lines {
+                if is_log_marker(line) {
+                    kept.append(&mut block);
+                    kept.push(line);
+                } else if is_tool_line(line) {
+                    block.clear();
+                } else if line.trim().is_empty() && !block.is_empty() {
+                    block.clear();
+                } else if !line.trim().is_empty() {
+                    block.push(line);
+                }
+            }
+            kept.append(&mut block);
+            kept
+        }
+    };
+    kept.join("\n")
+}
+
+// ---------------------------------------------------------------------------
+// Transcript export
+// ---------------------------------------------------------------------------
+
+/// Render a task's agent.log and the messages sent to its engineer as
+/// markdown for pasting into a PR description or wiki page.
+pub fn task_transcript(config: &Config, task_id: &str) -> Result<String> {
+    let task = Task::load_by_id(config, task_id)?;
+    let log = task.read_agent_log().unwrap_or_default();
+    let messages = task_message_history(config, &task.meta.task_id())?;
+    Ok(render_transcript(&task, &log, &messages))
+}
+
+/// Markdown for [`task_transcript`]: a heading per log section, tool output
+/// in fenced blocks, and each message to the engineer as a blockquote.
+pub fn render_transcript(task: &Task, log: &str, messages: &[inbox::InboxMessage]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Transcript: {}\n", task.meta.task_id());
+    let _ = writeln!(out, "- Branch: `{}`", task.meta.branch_name);
+    let repos: Vec<&str> = task
+        .meta
+        .repos
+        .iter()
+        .map(|r| r.repo_name.as_str())
+        .collect();
+    if !repos.is_empty() {
+        let _ = writeln!(out, "- Repos: {}", repos.join(", "));
+    }
+    if let Some(pr) = &task.meta.linked_pr {
+        let _ = writeln!(out, "- PR: {}", pr.url);
+    }
+
+    if !messages.is_empty() {
+        out.push_str("\n## Messages to the engineer\n");
+        for msg in messages {
+            let _ = writeln!(
+                out,
+                "\n> **{}** · {}\n>",
+                msg.from,
+                msg.timestamp.format("%Y-%m-%d %H:%M UTC")
+            );
+            for line in msg.message.lines() {
+                let _ = writeln!(out, "> {line}");
+            }
+        }
+    }
+
+    out.push_str("\n## Agent log\n");
+    if log.trim().is_empty() {
+        out.push_str("\n_No agent output._\n");
+        return out;
+    }
+    for section in crate::task::agent_log_sections(log) {
+        let mut tools: Vec<&str> = Vec::new();
+        for line in section {
+            if is_tool_line(line) {
+                tools.push(line);
+                continue;
+            }
+            push_tool_block(&mut out, &mut tools);
+            let trimmed = line.trim();
+            if let Some(title) = trimmed
+                .strip_prefix("--- ")
+                .and_then(|rest| rest.strip_suffix(" ---"))
+            {
+                let _ = writeln!(out, "\n### {title}\n");
+            } else {
+                let _ = writeln!(out, "{line}");
+            }
+        }
+        push_tool_block(&mut out, &mut tools);
+    }
+    out
+}
+
+/// Flush a run of tool lines as one fenced block, picking a fence the
+/// output itself doesn't contain.
+fn push_tool_block(out: &mut String, tools: &mut Vec<&str>) {
+    if tools.is_empty() {
+        return;
+    }
+    let fence = if tools.iter().any(|l| l.contains("```")) {
+        "~~~~"
+    } else {
+        "```"
+    };
+    let _ = writeln!(out, "\n{fence}text");
+    for line in tools.drain(..) {
+        let _ = writeln!(out, "{line}");
+    }
+    let _ = writeln!(out, "{fence}\n");
+}
+
+// ---------------------------------------------------------------------------
+// Idle agent detection
+// ---------------------------------------------------------------------------
+
+/// Stalled-engineer detection settings, resolved from config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdleSettings {
+    /// Silence after which a task is flagged; `None` disables detection.
+    pub threshold: Option<std::time::Duration>,
+    pub notify: bool,
+}
+
+pub fn load_idle_settings(config: &Config) -> IdleSettings {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    IdleSettings {
+        threshold: cf
+            .agent_idle_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs),
+        notify: cf.agent_idle_notify.unwrap_or(false),
+    }
+}
+
+/// Best-effort desktop notification (`notify-send` on Linux, `osascript` on
+/// macOS). Failures are logged, never surfaced.
+pub fn send_desktop_notification(title: &str, body: &str) {
+    let result = if cfg!(target_os = "macos") {
+        let script = format!("display notification {:?} with title {:?}", body, title);
+        std::process::Command::new("osascript")
+            .args(["-e", &script])
+            .spawn()
+    } else {
+        std::process::Command::new("notify-send")
+            .args([title, body])
+            .spawn()
+    };
+    if let Err(e) = result {
+        tracing::debug!(error = %e, "desktop notification failed");
+    }
+}
+
+/// Configured `webhook_url`, if any.
+pub fn load_webhook_url(config: &Config) -> Option<String> {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    cf.webhook_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// JSON body posted to `webhook_url` when a task's engineer goes idle.
+pub fn task_idle_webhook_payload(task_id: &str, idle_secs: u64) -> serde_json::Value {
+    serde_json::json!({
+        "event": "task_idle",
+        "task_id": task_id,
+        "idle_secs": idle_secs,
+        "text": format!("agman: {task_id} has had no engineer output for {idle_secs}s"),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// POST `payload` to `url` with short timeouts. Blocking; callers run it off
+/// the UI thread.
+pub fn post_webhook(url: &str, payload: &serde_json::Value) -> Result<()> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout_read(std::time::Duration::from_secs(5))
+        .timeout_write(std::time::Duration::from_secs(5))
+        .build();
+    agent
+        .post(url)
+        .send_json(payload)
+        .map_err(|e| anyhow::anyhow!("webhook POST failed: {e}"))?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Agent resource limits
+// ---------------------------------------------------------------------------
+
+/// Opt-in limits applied to the agent command typed into its tmux pane.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    pub nice: Option<u64>,
+    pub memory_limit_mb: Option<u64>,
+}
+
+pub fn load_resource_limits(config: &Config) -> ResourceLimits {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    ResourceLimits {
+        nice: cf.agent_nice.filter(|n| *n > 0),
+        memory_limit_mb: cf.agent_memory_limit_mb.filter(|mb| *mb > 0),
+    }
+}
+
+/// Wrap an agent launch command with `nice` and, for a memory cap, a
+/// `sh -c` that runs `ulimit -v` first. Limits are best-effort: `ulimit -v`
+/// is ignored on some platforms (notably macOS).
+///
+/// The command runs through `env` so harness commands that start with
+/// `VAR=value` assignments (goose, pi) still work after `nice`/`exec`.
+pub fn with_resource_limits(cmd: String, limits: ResourceLimits) -> String {
+    let cmd = match limits.nice {
+        Some(nice) => format!("nice -n {nice} env {cmd}"),
+        None => cmd,
+    };
+    match limits.memory_limit_mb {
+        Some(mb) => {
+            let cmd = if limits.nice.is_some() {
+                cmd
+            } else {
+                format!("env {cmd}")
+            };
+            let inner = format!("ulimit -v {}; exec {}", mb * 1024, cmd);
+            format!("sh -c '{}'", inner.replace('\'', "'\\''"))
+        }
+        None => cmd,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Auto-refresh
+// ---------------------------------------------------------------------------
+
+/// How often the TUI rescans the visible project or task list.
+pub fn load_refresh_interval(config: &Config) -> std::time::Duration {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    std::time::Duration::from_secs(cf.refresh_secs.filter(|secs| *secs > 0).unwrap_or(1))
+}
+
+/// Whether the TUI opens on the pending-actions dashboard.
+pub fn load_show_dashboard_on_start(config: &Config) -> bool {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    cf.show_dashboard_on_start.unwrap_or(false)
+}
+
+// ---------------------------------------------------------------------------
+// Work checkpoints
+// ---------------------------------------------------------------------------
+
+/// How often to checkpoint running tasks; `None` disables checkpoints.
+pub fn load_checkpoint_interval(config: &Config) -> Option<std::time::Duration> {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    cf.checkpoint_interval_mins
+        .filter(|mins| *mins > 0)
+        .map(|mins| std::time::Duration::from_secs(mins * 60))
+}
+
+/// Ref that holds a task branch's checkpoint history.
+pub fn checkpoint_ref(branch: &str) -> String {
+    format!("refs/agman/checkpoints/{branch}")
+}
+
+/// Snapshot every worktree of each active task whose engineer session is up.
+/// The branch and index are left alone; snapshots chain on
+/// [`checkpoint_ref`] and are skipped when nothing changed. Returns how many
+/// checkpoints were written.
+pub fn checkpoint_running_tasks(config: &Config) -> usize {
+    let mut written = 0;
+    for task in Task::list_all(config) {
+        let task_id = task.meta.task_id();
+        let running = task_engineer_session(config, &task_id)
+            .is_ok_and(|session| Tmux::session_exists(&session));
+        if !running {
+            continue;
+        }
+        let ref_name = checkpoint_ref(&task.meta.branch_name);
+        for repo in &task.meta.repos {
+            if !repo.worktree_path.exists() {
+                continue;
+            }
+            match Git::checkpoint(&repo.worktree_path, &ref_name) {
+                Ok(Some(_)) => written += 1,
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(task_id = %task_id, repo = %repo.repo_name, error = %e, "checkpoint failed");
+                }
+            }
+        }
+    }
+    written
+}
+
+// ---------------------------------------------------------------------------
+// Worktree snapshots
+// ---------------------------------------------------------------------------
+
+/// Every snapshot tag starts with this, followed by a `%Y%m%d-%H%M%S`
+/// timestamp and the branch name with `/` replaced by `-`.
+pub const SNAPSHOT_TAG_PREFIX: &str = "agman-snapshot-";
+
+const SNAPSHOT_TIMESTAMP_LEN: usize = "20060102-150405".len();
+
+fn snapshot_branch_slug(branch: &str) -> String {
+    branch.replace('/', "-")
+}
+
+/// Snapshot tag name for `branch` taken at `at`.
+pub fn snapshot_tag_name(branch: &str, at: DateTime<Utc>) -> String {
+    format!(
+        "{SNAPSHOT_TAG_PREFIX}{}-{}",
+        at.format("%Y%m%d-%H%M%S"),
+        snapshot_branch_slug(branch)
+    )
+}
+
+/// Tag every existing worktree of the task at its HEAD with one snapshot
+/// name. Only commits are captured; uncommitted changes are not. Returns the
+/// tag name.
+pub fn snapshot_task(task: &Task) -> Result<String> {
+    let tag = snapshot_tag_name(&task.meta.branch_name, Utc::now());
+    let mut tagged = 0;
+    for repo in &task.meta.repos {
+        if !repo.worktree_path.exists() {
+            continue;
+        }
+        Git::create_tag(&repo.worktree_path, &tag)
+            .with_context(|| format!("failed to snapshot {}", repo.repo_name))?;
+        tagged += 1;
+    }
+    if tagged == 0 {
+        bail!("task has no worktree on disk to snapshot");
+    }
+    tracing::info!(task_id = %task.meta.task_id(), tag = %tag, "snapshot created");
+    Ok(tag)
+}
+
+/// Snapshot tags for the task's branch, newest first. The tags are the only
+/// record of snapshots; nothing is kept in the task meta.
+pub fn list_task_snapshots(task: &Task) -> Result<Vec<String>> {
+    let Some(repo) = task.meta.repos.iter().find(|r| r.worktree_path.exists()) else {
+        return Ok(Vec::new());
+    };
+    let slug = snapshot_branch_slug(&task.meta.branch_name);
+    let mut tags: Vec<String> =
+        Git::list_tags(&repo.worktree_path, &format!("{SNAPSHOT_TAG_PREFIX}*"))?
+            .into_iter()
+            .filter(|tag| {
+                tag.strip_prefix(SNAPSHOT_TAG_PREFIX)
+                    .and_then(|rest| rest.get(SNAPSHOT_TIMESTAMP_LEN..))
+                    .and_then(|rest| rest.strip_prefix('-'))
+                    == Some(slug.as_str())
+            })
+            .collect();
+    tags.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(tags)
+}
+
+/// `git reset --hard` every worktree of the task that has `tag`, discarding
+/// uncommitted changes there.
+pub fn restore_task_snapshot(task: &Task, tag: &str) -> Result<()> {
+    let mut restored = 0;
+    for repo in &task.meta.repos {
+        if !repo.worktree_path.exists() || !Git::commit_exists(&repo.worktree_path, tag) {
+            continue;
+        }
+        Git::reset_hard(&repo.worktree_path, tag)
+            .with_context(|| format!("failed to restore {}", repo.repo_name))?;
+        restored += 1;
+    }
+    if restored == 0 {
+        bail!("snapshot '{}' not found in any worktree of the task", tag);
+    }
+    tracing::info!(task_id = %task.meta.task_id(), tag, "snapshot restored");
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Store maintenance
+// ---------------------------------------------------------------------------
+
+/// Task `agent.log` files larger than this are cut down to their most recent
+/// half by [`gc_store`].
+pub const GC_LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Outcome of one [`gc_store`] pass.
+#[derive(Debug, Default)]
+pub struct GcReport {
+    pub archives_purged: usize,
+    pub notifications_pruned: usize,
+    pub logs_truncated: usize,
+    /// `(repo, path)` of worktrees in agman's layout that no task or agent
+    /// owns. Only removed when `gc_store` is asked to.
+    pub orphan_worktrees: Vec<(String, PathBuf)>,
+    pub orphans_removed: bool,
+    /// Orphans left in place because they have uncommitted changes.
+    pub dirty_orphans: Vec<PathBuf>,
+    pub reclaimed_bytes: u64,
+}
+
+/// Tidy the whole store in one go: purge expired archives, prune stale
+/// dismissed notifications, truncate oversized task logs and find orphan
+/// worktrees, removing them only when `remove_orphans` is set.
+pub fn gc_store(config: &Config, remove_orphans: bool) -> Result<GcReport> {
+    let mut report = GcReport::default();
+    let store_before = dir_size(&config.base_dir);
+
+    report.archives_purged = purge_old_archives(config)?;
+
+    let dismissed_path = config.dismissed_notifications_path();
+    let mut dismissed =
+        crate::dismissed_notifications::DismissedNotifications::load(&dismissed_path);
+    let retention =
+        chrono::Duration::weeks(crate::dismissed_notifications::NOTIFICATION_RETENTION_WEEKS);
+    report.notifications_pruned =
+        dismissed.prune_older_than(retention) + dismissed.prune_expired_snoozes();
+    if report.notifications_pruned > 0 {
+        dismissed.save(&dismissed_path);
+    }
+
+    for task in Task::list_all(config)
+        .into_iter()
+        .chain(Task::list_archived(config))
+    {
+        let log_path = task.dir.join("agent.log");
+        if truncate_log_tail(&log_path, GC_LOG_MAX_BYTES)? {
+            report.logs_truncated += 1;
+        }
+    }
+
+    report.orphan_worktrees = find_orphan_worktrees(config);
+    let mut orphan_bytes = 0;
+    if remove_orphans {
+        for (repo, path) in &report.orphan_worktrees {
+            // `remove_worktree` forces removal; never throw away work.
+            if Git::is_dirty(path).unwrap_or(true) {
+                tracing::warn!(repo = %repo, path = %path.display(), "keeping dirty orphan worktree");
+                report.dirty_orphans.push(path.clone());
+                continue;
+            }
+            let size = dir_size(path);
+            Git::remove_worktree(&config.repo_path(repo), path)?;
+            if !path.exists() {
+                orphan_bytes += size;
+            }
+        }
+        report.orphans_removed = true;
+    }
+
+    let store_after = dir_size(&config.base_dir);
+    report.reclaimed_bytes = store_before.saturating_sub(store_after) + orphan_bytes;
+    Ok(report)
+}
+
+/// Worktrees under [`Config::worktree_base`] of any repo in `repos_dir` that
+/// are not referenced by an active or archived task, or by a reviewer or
+/// tester agent. Paths are compared canonicalized, so a symlinked or
+/// relative base dir doesn't make a live worktree look orphaned.
+pub fn find_orphan_worktrees(config: &Config) -> Vec<(String, PathBuf)> {
+    let canonical = |path: PathBuf| path.canonicalize().unwrap_or(path);
+    let mut owned: std::collections::HashSet<PathBuf> = Task::list_all(config)
+        .into_iter()
+        .chain(Task::list_archived(config))
+        .flat_map(|task| task.meta.repos.into_iter().map(|r| r.worktree_path))
+        .map(canonical)
+        .collect();
+    for agent in AgentRecord::list_all(config).unwrap_or_default() {
+        if let AgentKind::Reviewer { worktrees } | AgentKind::Tester { worktrees, .. } =
+            agent.meta.kind
+        {
+            owned.extend(worktrees.into_iter().map(|w| canonical(w.path)));
+        }
+    }
+
+    let Ok(entries) = std::fs::read_dir(&config.repos_dir) else {
+        return Vec::new();
+    };
+    let mut orphans = Vec::new();
+    for entry in entries.flatten() {
+        let repo_path = entry.path();
+        if !repo_path.join(".git").is_dir() {
+            continue;
+        }
+        let Some(repo) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let base = canonical(config.worktree_base(&repo));
+        let worktrees = match Git::list_worktrees(&repo_path) {
+            Ok(worktrees) => worktrees,
+            Err(e) => {
+                tracing::warn!(repo = %repo, error = %e, "failed to list worktrees");
+                continue;
+            }
+        };
+        for (_branch, path) in worktrees {
+            let resolved = canonical(path.clone());
+            if resolved.starts_with(&base) && !owned.contains(&resolved) {
+                orphans.push((repo.clone(), path));
+            }
+        }
+    }
+    orphans.sort();
+    orphans
+}
+
+/// Keep only the most recent half of `path` once it grows past `max_bytes`,
+/// starting at a line boundary. Returns whether the file was truncated.
+fn truncate_log_tail(path: &Path, max_bytes: u64) -> Result<bool> {
+    let len = match std::fs::metadata(path) {
+        Ok(meta) => meta.len(),
+        Err(_) => return Ok(false),
+    };
+    if len <= max_bytes {
+        return Ok(false);
+    }
+    let content =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let start = content.len() - (max_bytes / 2) as usize;
+    let start = content[start..]
+        .iter()
+        .position(|b| *b == b'\n')
+        .map_or(start, |i| start + i + 1);
+    std::fs::write(path, &content[start..])
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(true)
+}
+
+/// Total size of the regular files under `path`, without following symlinks.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| dir_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// Usage / Cost Accounting
+// ---------------------------------------------------------------------------
+
+/// Usage accounting settings, resolved from config with defaults.
+#[derive(Debug, Clone)]
+pub struct UsageSettings {
+    pub show_cost_column: bool,
+    pub pattern: regex::Regex,
+}
+
+/// Load usage settings. An invalid `usage_pattern` is logged and replaced
+/// by [`usage::DEFAULT_USAGE_PATTERN`] so a typo never breaks the TUI.
+pub fn load_usage_settings(config: &Config) -> UsageSettings {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    let pattern = match usage::compile_pattern(cf.usage_pattern.as_deref()) {
+        Ok(re) => re,
+        Err(e) => {
+            tracing::warn!(error = %e, "invalid usage_pattern in config, using default");
+            usage::compile_pattern(None).expect("default usage pattern compiles")
+        }
+    };
+    UsageSettings {
+        show_cost_column: cf.show_cost_column.unwrap_or(false),
+        pattern,
+    }
+}
+
+/// Re-scan a task's agent.log and store the totals on its meta. Only writes
+/// meta.json when the totals changed. Returns whether they did.
+pub fn refresh_task_usage(task: &mut Task, pattern: &regex::Regex) -> Result<bool> {
+    let log = match task.read_agent_log() {
+        Ok(log) => log,
+        Err(_) => return Ok(false),
+    };
+    let totals = usage::parse_usage(&log, pattern);
+    if totals == task.meta.usage() {
+        return Ok(false);
+    }
+    task.meta.total_input_tokens = totals.input_tokens;
+    task.meta.total_output_tokens = totals.output_tokens;
+    task.meta.total_cost_cents = totals.cost_cents;
+    task.save_meta()?;
+    Ok(true)
+}
+
+// ---------------------------------------------------------------------------
+// Telegram Config
+// ---------------------------------------------------------------------------
+
+/// Load the Telegram bot token and chat ID from config.
 pub fn load_telegram_config(config: &Config) -> (Option<String>, Option<String>) {
     let cf = crate::config::load_config_file(&config.base_dir);
     (
@@ -973,6 +2651,47 @@ pub struct GithubNotification {
     pub browser_url: String,
 }
 
+/// Reason filter for the Notifications view, cycled with `f`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NotifReasonFilter {
+    #[default]
+    All,
+    ReviewRequested,
+    Mention,
+    Assign,
+}
+
+impl NotifReasonFilter {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::ReviewRequested,
+            Self::ReviewRequested => Self::Mention,
+            Self::Mention => Self::Assign,
+            Self::Assign => Self::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::ReviewRequested => "review requested",
+            Self::Mention => "mentions",
+            Self::Assign => "assigned",
+        }
+    }
+
+    /// Whether a notification with the given API `reason` passes this filter.
+    /// `Mention` covers both direct and team mentions.
+    pub fn matches(self, reason: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::ReviewRequested => reason == "review_requested",
+            Self::Mention => reason == "mention" || reason == "team_mention",
+            Self::Assign => reason == "assign",
+        }
+    }
+}
+
 /// Raw JSON shape returned by `GET /notifications` (subset of fields we care about).
 #[derive(Deserialize)]
 struct RawNotification {
@@ -1047,6 +2766,29 @@ pub fn parse_notifications_json(json_str: &str) -> Vec<GithubNotification> {
 /// Result of a GitHub notifications poll.
 pub struct NotifPollResult {
     pub notifications: Vec<GithubNotification>,
+    /// `gh` reported a (secondary) rate limit; `notifications` is incomplete.
+    pub rate_limited: bool,
+}
+
+/// True if `gh` stderr indicates GitHub rejected the call for rate limiting,
+/// including secondary (abuse) limits.
+pub fn is_gh_rate_limited(stderr: &str) -> bool {
+    let lower = stderr.to_ascii_lowercase();
+    lower.contains("rate limit") || lower.contains("abuse detection")
+}
+
+/// Initial pause after the first rate-limited GitHub poll.
+pub const GH_BACKOFF_INITIAL: std::time::Duration = std::time::Duration::from_secs(120);
+/// Upper bound for the GitHub poll backoff.
+pub const GH_BACKOFF_MAX: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+/// Next backoff after another rate-limited poll: start at
+/// `GH_BACKOFF_INITIAL`, then double up to `GH_BACKOFF_MAX`.
+pub fn next_gh_backoff(current: Option<std::time::Duration>) -> std::time::Duration {
+    match current {
+        None => GH_BACKOFF_INITIAL,
+        Some(d) => (d * 2).min(GH_BACKOFF_MAX),
+    }
 }
 
 /// Fetch all GitHub notifications via paginated `gh api /notifications?all=true` calls.
@@ -1062,6 +2804,7 @@ pub fn fetch_github_notifications() -> NotifPollResult {
     tracing::info!(since = %since, "fetching github notifications with time bound");
 
     let mut all_notifications = Vec::new();
+    let mut rate_limited = false;
 
     for page in 1..=10 {
         let url = format!("/notifications?all=true&per_page=50&page={page}&since={since}");
@@ -1075,7 +2818,8 @@ pub fn fetch_github_notifications() -> NotifPollResult {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            tracing::warn!(stderr = %stderr, page, "gh api /notifications returned error");
+            rate_limited = is_gh_rate_limited(&stderr);
+            tracing::warn!(stderr = %stderr, page, rate_limited, "gh api /notifications returned error");
             break;
         }
 
@@ -1096,6 +2840,7 @@ pub fn fetch_github_notifications() -> NotifPollResult {
     );
     NotifPollResult {
         notifications: all_notifications,
+        rate_limited,
     }
 }
 
@@ -1118,6 +2863,102 @@ pub fn dismiss_github_notification(thread_id: &str) -> Result<()> {
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// GitHub issue import (seed a task from an issue)
+// ---------------------------------------------------------------------------
+
+/// An open GitHub issue as returned by `gh issue list/view --json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubIssue {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+/// List open issues for the repo checked out at `repo_path`.
+pub fn list_open_issues(repo_path: &Path) -> Result<Vec<GithubIssue>> {
+    let output = Command::new("gh")
+        .current_dir(repo_path)
+        .args([
+            "issue",
+            "list",
+            "--state",
+            "open",
+            "--limit",
+            "100",
+            "--json",
+            "number,title,body",
+        ])
+        .output()
+        .context("failed to run gh issue list")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("gh issue list failed: {}", stderr.trim());
+    }
+    serde_json::from_slice(&output.stdout).context("failed to parse gh issue list output")
+}
+
+/// Fetch a single issue by number for the repo checked out at `repo_path`.
+pub fn fetch_issue(repo_path: &Path, number: u64) -> Result<GithubIssue> {
+    let output = Command::new("gh")
+        .current_dir(repo_path)
+        .args([
+            "issue",
+            "view",
+            &number.to_string(),
+            "--json",
+            "number,title,body",
+        ])
+        .output()
+        .context("failed to run gh issue view")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("gh issue view #{} failed: {}", number, stderr.trim());
+    }
+    serde_json::from_slice(&output.stdout).context("failed to parse gh issue view output")
+}
+
+/// Slugify an issue into a branch name, e.g. `42-fix-login-redirect`.
+///
+/// Keeps lowercase ASCII alphanumerics, collapses everything else into single
+/// hyphens, and caps the title part at 50 characters. The result always
+/// passes [`validate_branch_name`].
+pub fn issue_branch_name(issue: &GithubIssue) -> String {
+    let mut slug = String::new();
+    for c in issue.title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= 50 {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        format!("issue-{}", issue.number)
+    } else {
+        format!("{}-{}", issue.number, slug)
+    }
+}
+
+/// First prompt for a task seeded from an issue: title header plus body.
+pub fn issue_first_prompt(issue: &GithubIssue) -> String {
+    let body = issue.body.trim();
+    if body.is_empty() {
+        format!("GitHub issue #{}: {}", issue.number, issue.title)
+    } else {
+        format!(
+            "GitHub issue #{}: {}\n\n{}",
+            issue.number, issue.title, body
+        )
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Notes (standalone markdown files in ~/.agman/notes/)
 // ---------------------------------------------------------------------------
@@ -1208,15 +3049,101 @@ pub fn list_notes(dir: &Path) -> Result<Vec<NoteEntry>> {
         result.extend(remaining_dirs);
         result.extend(remaining_files);
 
-        return Ok(result);
+        return Ok(result);
+    }
+
+    // Default: dirs first, then files, each group sorted alphabetically
+    dirs.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    files.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    dirs.extend(files);
+    Ok(dirs)
+}
+
+/// Every `.md` note under `root`, as paths relative to `root`, sorted
+/// case-insensitively. Hidden directories are skipped.
+pub fn list_notes_recursive(root: &Path) -> Result<Vec<PathBuf>> {
+    fn walk(root: &Path, dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+        let read_dir = std::fs::read_dir(dir)
+            .with_context(|| format!("failed to read notes directory: {}", dir.display()))?;
+        for entry in read_dir {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            if file_type.is_dir() && !file_name.starts_with('.') {
+                walk(root, &path, out)?;
+            } else if file_type.is_file() && file_name.ends_with(".md") {
+                if let Ok(rel) = path.strip_prefix(root) {
+                    out.push(rel.to_path_buf());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    let mut notes = Vec::new();
+    walk(root, root, &mut notes)?;
+    notes.sort_by_key(|p| p.to_string_lossy().to_lowercase());
+    Ok(notes)
+}
+
+/// Filter note paths for the fuzzy finder: keeps paths containing every
+/// character of `query` in order (case-insensitive), best matches first.
+/// Matches that are contiguous or land in the file name rank higher.
+pub fn fuzzy_filter_notes(paths: &[PathBuf], query: &str) -> Vec<PathBuf> {
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return paths.to_vec();
+    }
+
+    let mut scored: Vec<(i64, &PathBuf)> = paths
+        .iter()
+        .filter_map(|path| fuzzy_note_score(&path.to_string_lossy(), &query).map(|s| (s, path)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, p)| p.clone()).collect()
+}
+
+fn fuzzy_note_score(path: &str, query: &[char]) -> Option<i64> {
+    let haystack: Vec<char> = path.to_lowercase().chars().collect();
+    let name_start = haystack
+        .iter()
+        .rposition(|c| *c == '/')
+        .map_or(0, |i| i + 1);
+
+    let mut score = 0i64;
+    let mut qi = 0;
+    let mut prev: Option<usize> = None;
+    for (i, c) in haystack.iter().enumerate() {
+        if qi == query.len() {
+            break;
+        }
+        if *c != query[qi] {
+            continue;
+        }
+        score += 1;
+        if prev == Some(i.wrapping_sub(1)) {
+            score += 5;
+        }
+        if i >= name_start {
+            score += 2;
+        }
+        if i == 0 || matches!(haystack[i - 1], '/' | '-' | '_' | ' ' | '.') {
+            score += 3;
+        }
+        prev = Some(i);
+        qi += 1;
     }
-
-    // Default: dirs first, then files, each group sorted alphabetically
-    dirs.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-    files.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-
-    dirs.extend(files);
-    Ok(dirs)
+    if qi < query.len() {
+        return None;
+    }
+    // Prefer shorter paths among equally good matches.
+    Some(score * 100 - haystack.len() as i64)
 }
 
 /// Create a new `.md` note file in the given directory.
@@ -1467,6 +3394,8 @@ pub struct ShowPrsData {
     pub issues: Vec<GithubItem>,
     pub my_prs: Vec<GithubItem>,
     pub review_requests: Vec<GithubItem>,
+    /// `gh` reported a (secondary) rate limit; the lists are incomplete.
+    pub rate_limited: bool,
 }
 
 /// Raw JSON shape from `gh search issues/prs --json ...`.
@@ -1525,7 +3454,12 @@ pub fn parse_search_items_json(json_str: &str, kind: GithubItemKind) -> Vec<Gith
 }
 
 /// Run a `gh search` command and return stdout on success, or None on failure.
-fn run_gh_search(args: &[&str]) -> Option<String> {
+/// Sets `rate_limited` when GitHub throttles the call; once set, later calls
+/// are skipped so a throttled poll doesn't keep hitting the API.
+fn run_gh_search(args: &[&str], rate_limited: &mut bool) -> Option<String> {
+    if *rate_limited {
+        return None;
+    }
     let output = match Command::new("gh").args(args).output() {
         Ok(o) => o,
         Err(e) => {
@@ -1536,7 +3470,8 @@ fn run_gh_search(args: &[&str]) -> Option<String> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        tracing::warn!(stderr = %stderr, cmd = ?args, "gh search returned error");
+        *rate_limited = is_gh_rate_limited(&stderr);
+        tracing::warn!(stderr = %stderr, cmd = ?args, rate_limited = *rate_limited, "gh search returned error");
         return None;
     }
 
@@ -1555,65 +3490,81 @@ const ISSUE_JSON_FIELDS: &str = "number,title,repository,state,url,updatedAt,aut
 /// Fetch all GitHub issues and PRs relevant to the current user.
 pub fn fetch_show_prs_data() -> ShowPrsData {
     tracing::info!("fetching show-prs data");
+    let mut rate_limited = false;
 
     // 1. My Issues (assigned to me)
-    let issues = run_gh_search(&[
-        "search",
-        "issues",
-        "--assignee=@me",
-        "--state=open",
-        &format!("--json={}", ISSUE_JSON_FIELDS),
-        "--limit=50",
-    ])
+    let issues = run_gh_search(
+        &[
+            "search",
+            "issues",
+            "--assignee=@me",
+            "--state=open",
+            &format!("--json={}", ISSUE_JSON_FIELDS),
+            "--limit=50",
+        ],
+        &mut rate_limited,
+    )
     .map(|json| parse_search_items_json(&json, GithubItemKind::Issue))
     .unwrap_or_default();
 
     // 2. My PRs (authored by me)
-    let mut my_prs = run_gh_search(&[
-        "search",
-        "prs",
-        "--author=@me",
-        "--state=open",
-        &format!("--json={}", PR_JSON_FIELDS),
-        "--limit=50",
-    ])
+    let mut my_prs = run_gh_search(
+        &[
+            "search",
+            "prs",
+            "--author=@me",
+            "--state=open",
+            &format!("--json={}", PR_JSON_FIELDS),
+            "--limit=50",
+        ],
+        &mut rate_limited,
+    )
     .map(|json| parse_search_items_json(&json, GithubItemKind::PullRequest))
     .unwrap_or_default();
 
     // 3. PRs assigned to me (merge into my_prs)
-    if let Some(json) = run_gh_search(&[
-        "search",
-        "prs",
-        "--assignee=@me",
-        "--state=open",
-        &format!("--json={}", PR_JSON_FIELDS),
-        "--limit=50",
-    ]) {
+    if let Some(json) = run_gh_search(
+        &[
+            "search",
+            "prs",
+            "--assignee=@me",
+            "--state=open",
+            &format!("--json={}", PR_JSON_FIELDS),
+            "--limit=50",
+        ],
+        &mut rate_limited,
+    ) {
         my_prs.extend(parse_search_items_json(&json, GithubItemKind::PullRequest));
         dedup_github_items(&mut my_prs);
     }
 
     // 4. Review requests
-    let mut review_requests = run_gh_search(&[
-        "search",
-        "prs",
-        "--review-requested=@me",
-        "--state=open",
-        &format!("--json={}", PR_JSON_FIELDS),
-        "--limit=50",
-    ])
+    let mut review_requests = run_gh_search(
+        &[
+            "search",
+            "prs",
+            "--review-requested=@me",
+            "--state=open",
+            &format!("--json={}", PR_JSON_FIELDS),
+            "--limit=50",
+        ],
+        &mut rate_limited,
+    )
     .map(|json| parse_search_items_json(&json, GithubItemKind::PullRequest))
     .unwrap_or_default();
 
     // 5. PRs mentioning me (merge into review_requests)
-    if let Some(json) = run_gh_search(&[
-        "search",
-        "prs",
-        "--mentions=@me",
-        "--state=open",
-        &format!("--json={}", PR_JSON_FIELDS),
-        "--limit=50",
-    ]) {
+    if let Some(json) = run_gh_search(
+        &[
+            "search",
+            "prs",
+            "--mentions=@me",
+            "--state=open",
+            &format!("--json={}", PR_JSON_FIELDS),
+            "--limit=50",
+        ],
+        &mut rate_limited,
+    ) {
         review_requests.extend(parse_search_items_json(&json, GithubItemKind::PullRequest));
         dedup_github_items(&mut review_requests);
     }
@@ -1629,6 +3580,7 @@ pub fn fetch_show_prs_data() -> ShowPrsData {
         issues,
         my_prs,
         review_requests,
+        rate_limited,
     }
 }
 
@@ -2061,6 +4013,121 @@ pub fn attached_engineer_for_task(config: &Config, task_id: &str) -> Result<Agen
     }
 }
 
+/// Every message delivered to the task's engineers, oldest first. Stopped
+/// engineers are included so the history survives a respawn.
+pub fn task_message_history(config: &Config, task_id: &str) -> Result<Vec<inbox::InboxMessage>> {
+    let mut messages = Vec::new();
+    for agent in AgentRecord::list_all(config)? {
+        let attached = matches!(
+            &agent.meta.attachment,
+            AgentAttachment::Task { task_id: attached, .. } if attached == task_id
+        );
+        if agent.is_engineer() && attached {
+            let inbox_path = config.agent_inbox(&agent.meta.project, &agent.meta.name);
+            messages.extend(inbox::read_messages(&inbox_path)?);
+        }
+    }
+    messages.sort_by_key(|m| m.timestamp);
+    Ok(messages)
+}
+
+/// How far back from the bottom of the engineer pane [`pending_question`]
+/// looks, in non-empty lines.
+const QUESTION_SCAN_LINES: usize = 15;
+
+/// The engineer's open question, if any: the last line ending in `?` among
+/// the final non-empty lines of a `capture-pane` of its session.
+pub fn pending_question(pane: &str) -> Option<String> {
+    last_pane_line(pane, |line| line.ends_with('?'))
+}
+
+/// The last of the pane's final non-empty lines matching `input_pattern`:
+/// an engineer asking for input in free text.
+pub fn input_request(pane: &str, pattern: &regex::Regex) -> Option<String> {
+    last_pane_line(pane, |line| pattern.is_match(line))
+}
+
+fn last_pane_line(pane: &str, matches: impl Fn(&str) -> bool) -> Option<String> {
+    pane.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .rev()
+        .take(QUESTION_SCAN_LINES)
+        .find(|line| matches(line))
+        .map(str::to_string)
+}
+
+/// Compiled `input_pattern`, if set. An invalid pattern is logged and
+/// treated as unset.
+pub fn load_input_pattern(config: &Config) -> Option<regex::Regex> {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    let pattern = cf.input_pattern.filter(|p| !p.is_empty())?;
+    match regex::Regex::new(&pattern) {
+        Ok(re) => Some(re),
+        Err(e) => {
+            tracing::warn!(error = %e, "invalid input_pattern in config, ignoring");
+            None
+        }
+    }
+}
+
+/// Deliver a short answer to the task's running engineer through its inbox.
+pub fn answer_engineer(config: &Config, task_id: &str, answer: &str) -> Result<()> {
+    let answer = answer.trim();
+    if answer.is_empty() {
+        bail!("answer is empty");
+    }
+    let engineer = attached_engineer_for_task(config, task_id)?;
+    let inbox_path = config.agent_inbox(&engineer.meta.project, &engineer.meta.name);
+    tracing::info!(task_id, engineer = %engineer.meta.name, "sending quick answer");
+    inbox::append_message(&inbox_path, "user", answer)?;
+    Ok(())
+}
+
+/// Sent by [`continue_engineer`] when the user has nothing to add.
+pub const CONTINUE_MESSAGE: &str =
+    "No new feedback: you were on the right track, carry on where you left off.";
+
+/// Tell the task's engineer to keep going without new instructions.
+pub fn continue_engineer(config: &Config, task_id: &str) -> Result<()> {
+    answer_engineer(config, task_id, CONTINUE_MESSAGE)
+}
+
+/// Ask the task's running engineer to compact its context: the configured
+/// `compact_command`, else the harness's own. Records the time on the task
+/// and returns the command that was sent.
+pub fn compact_engineer(config: &Config, task_id: &str) -> Result<String> {
+    let mut task = Task::load_by_id(config, task_id)?;
+    let engineer = attached_engineer_for_task(config, task_id)?;
+    let session = agent_tmux_session(&engineer.meta);
+    if !Tmux::session_exists(&session) {
+        bail!("engineer session '{session}' is not running");
+    }
+    let cf = crate::config::load_config_file(&config.base_dir);
+    let command = match cf.compact_command.filter(|c| !c.trim().is_empty()) {
+        Some(command) => command,
+        None => {
+            let repo = task.meta.repos.first().map(|r| r.repo_name.as_str());
+            let kind = harness::read_or_stamp(&engineer.dir, config.harness_kind_for_repo(repo))?;
+            match kind.select().compact_command() {
+                Some(command) => command.to_string(),
+                None => bail!("{kind} has no compact command; set compact_command in config"),
+            }
+        }
+    };
+    tracing::info!(task_id, engineer = %engineer.meta.name, command, "compacting engineer context");
+    Tmux::send_keys_to_session(&session, &command)?;
+    task.meta.last_compacted_at = Some(Utc::now());
+    task.save_meta()?;
+    Ok(command)
+}
+
+/// tmux session of the task's running engineer.
+pub fn task_engineer_session(config: &Config, task_id: &str) -> Result<String> {
+    let engineer = attached_engineer_for_task(config, task_id)?;
+    Ok(agent_tmux_session(&engineer.meta))
+}
+
 fn unique_agent_name(config: &Config, project: &str, base: &str) -> String {
     if !config.agent_dir(project, base).exists() {
         return base.to_string();
@@ -2962,6 +5029,7 @@ pub fn start_chief_of_staff_session(config: &Config, force_fresh: bool) -> Resul
         capabilities: Default::default(),
         session_key: prep.session_key(),
     });
+    let cmd = with_resource_limits(cmd, load_resource_limits(config));
 
     let already_existed = Tmux::session_exists(session_name);
     Tmux::create_agent_session(session_name, &cmd, Some(&prep.cwd))?;
@@ -3062,6 +5130,7 @@ pub fn start_pm_session(config: &Config, project_name: &str, force_fresh: bool)
         capabilities: Default::default(),
         session_key: prep.session_key(),
     });
+    let cmd = with_resource_limits(cmd, load_resource_limits(config));
 
     let already_existed = Tmux::session_exists(&session_name);
     Tmux::create_agent_session(&session_name, &cmd, Some(&prep.cwd))?;
@@ -3243,10 +5312,7 @@ fn base36_ordinal(mut ordinal: usize) -> String {
 
 fn task_tmux_session(task: &Task) -> Result<String> {
     if task.meta.is_multi_repo() {
-        Ok(Config::tmux_session_name(
-            &task.meta.name,
-            &task.meta.branch_name,
-        ))
+        Ok(task.meta.parent_tmux_session())
     } else if task.meta.has_repos() {
         Ok(task.meta.primary_repo().tmux_session.clone())
     } else {
@@ -3374,6 +5440,7 @@ pub fn create_reviewer(
     if spec.branches.is_empty() {
         anyhow::bail!("reviewer requires at least one --branch <repo>:<branch>");
     }
+    ensure_branches_have_commits(config, &spec)?;
     let worktrees = resolve_agent_worktrees(config, &spec)?;
     let kind = AgentKind::Reviewer { worktrees };
     create_agent(config, project, name, first_prompt, kind)
@@ -3391,7 +5458,7 @@ pub fn create_tester(
         anyhow::bail!("tester requires at least one --branch <repo>:<branch>");
     }
     let worktrees = resolve_agent_worktrees(config, &spec)?;
-    let harness_kind = config.harness_kind();
+    let harness_kind = config.harness_kind_for_repo(worktrees.first().map(|w| w.repo.as_str()));
     if capabilities.browser && matches!(harness_kind, HarnessKind::Goose | HarnessKind::Pi) {
         let note = format!(
             "note: browser capability not available on harness {}; tester spawned without it",
@@ -3407,6 +5474,37 @@ pub fn create_tester(
     create_agent(config, project, name, first_prompt, kind)
 }
 
+/// Refuse to review a branch that has nothing on top of its base — the
+/// reviewer would otherwise spin up against an empty diff. Branches that are
+/// not known locally or on origin are left for `resolve_agent_worktrees` to
+/// report.
+fn ensure_branches_have_commits(config: &Config, spec: &WorktreeSpec) -> Result<()> {
+    let parent_dir = spec.parent_dir.as_deref();
+    for (repo, branch) in &spec.branches {
+        let repo_path = config.repo_path_for(parent_dir, repo);
+        if !repo_path.exists() {
+            continue;
+        }
+        let branch_ref = if Git::local_branch_exists(&repo_path, branch) {
+            format!("refs/heads/{}", branch)
+        } else if Git::remote_branch_exists(&repo_path, branch) {
+            format!("refs/remotes/origin/{}", branch)
+        } else {
+            continue;
+        };
+        let base = Git::find_base_ref(&repo_path);
+        if Git::commits_ahead(&repo_path, &base, &branch_ref)? == 0 {
+            anyhow::bail!(
+                "branch '{}' in {} has no commits to review (nothing ahead of {})",
+                branch,
+                repo,
+                base
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Walk the `(repo, branch)` list applying the three-step decision tree.
 /// Returns the resolved `AgentWorktree` entries on success. On failure,
 /// returns the first error encountered without creating any worktrees that
@@ -3538,7 +5636,24 @@ pub fn start_agent_session(
     let dir = config.agent_dir(project, name);
     let agent = AgentRecord::load(dir.clone())?;
 
-    let kind = harness::read_or_stamp(&dir, config.harness_kind())?;
+    // The repo the agent works in drives both the harness override and the
+    // system preamble override.
+    let agent_repo = match &agent.meta.kind {
+        AgentKind::Engineer => match &agent.meta.attachment {
+            crate::agent_model::AgentAttachment::Task { task_id, .. } => {
+                Task::load_by_id(config, task_id)
+                    .ok()
+                    .and_then(|t| t.meta.repos.first().map(|r| r.repo_name.clone()))
+            }
+            crate::agent_model::AgentAttachment::Unattached => None,
+        },
+        AgentKind::Researcher { repo, .. } | AgentKind::Operator { repo, .. } => repo.clone(),
+        AgentKind::Reviewer { worktrees } | AgentKind::Tester { worktrees, .. } => {
+            worktrees.first().map(|w| w.repo.clone())
+        }
+    };
+
+    let kind = harness::read_or_stamp(&dir, config.harness_kind_for_repo(agent_repo.as_deref()))?;
     let harness = kind.select();
 
     let (token, chat_id) = load_telegram_config(config);
@@ -3643,6 +5758,8 @@ pub fn start_agent_session(
         }
     };
 
+    let prompt = with_system_preamble(prompt, config.system_preamble(agent_repo.as_deref()));
+
     let agent_kind = match &agent.meta.kind {
         AgentKind::Engineer => "engineer",
         AgentKind::Researcher { .. } => "researcher",
@@ -3699,6 +5816,7 @@ pub fn start_agent_session(
         capabilities,
         session_key: prep.session_key(),
     });
+    let cmd = with_resource_limits(cmd, load_resource_limits(config));
 
     let already_existed = Tmux::session_exists(&session_name);
     Tmux::create_agent_session(&session_name, &cmd, Some(&prep.cwd))?;
@@ -3709,6 +5827,15 @@ pub fn start_agent_session(
     Ok(())
 }
 
+/// Prepend the user's standing preamble (see `Config::system_preamble`) to an
+/// agent system prompt.
+pub fn with_system_preamble(prompt: String, preamble: Option<String>) -> String {
+    match preamble {
+        Some(preamble) => format!("# House Rules\n\n{}\n\n---\n\n{}", preamble.trim(), prompt),
+        None => prompt,
+    }
+}
+
 /// Resolve the working directory for agent kinds with repo/branch/task hints.
 /// Worktree-backed agents use the first worktree path directly.
 fn resolve_repo_hinted_work_dir(
@@ -3734,11 +5861,7 @@ fn resolve_repo_hinted_work_dir(
 
     // If repo + branch, resolve to worktree
     if let (Some(repo), Some(branch)) = (repo, branch) {
-        let wt_dir = config
-            .repos_dir
-            .parent()?
-            .join(format!("{repo}-wt"))
-            .join(branch);
+        let wt_dir = config.worktree_path(repo, branch);
         if wt_dir.exists() {
             return Some(wt_dir);
         }
@@ -4360,6 +6483,546 @@ pub fn save_harness(config: &Config, kind: HarnessKind) -> Result<()> {
     crate::config::save_config_file(&config.base_dir, &cf)
 }
 
+// ---------------------------------------------------------------------------
+// Config Keys (`agman config`)
+// ---------------------------------------------------------------------------
+
+/// Every key `agman config` understands, in `config.toml` order.
+pub const CONFIG_KEYS: &[&str] = &[
+    "repos_dir",
+    "worktrees_dir",
+    "archive_retention_days",
+    "telegram_bot_token",
+    "telegram_chat_id",
+    "harness",
+    "repo_harness",
+    "break_enabled",
+    "break_interval_mins",
+    "break_warning_secs",
+    "usage_pattern",
+    "show_cost_column",
+    "cleanup_on_merge",
+    "session_windows",
+    "repo_session_windows",
+    "system_preamble",
+    "repo_system_preamble",
+    "agent_idle_secs",
+    "agent_idle_notify",
+    "task_sort",
+    "agent_nice",
+    "agent_memory_limit_mb",
+    "checkpoint_interval_mins",
+    "webhook_url",
+    "refresh_secs",
+    "show_dashboard_on_start",
+    "compact_command",
+    "input_pattern",
+    "repo_setup_script",
+    "allow_setup_file",
+];
+
+/// Keys holding lists or maps. `get` and `list` render them as JSON; they
+/// can only be changed by editing `config.toml`.
+const STRUCTURED_CONFIG_KEYS: &[&str] = &[
+    "session_windows",
+    "repo_session_windows",
+    "repo_harness",
+    "repo_system_preamble",
+    "repo_setup_script",
+];
+
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn unknown_config_key(key: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Unknown config key '{}'. Valid keys: {}",
+        key,
+        CONFIG_KEYS.join(", ")
+    )
+}
+
+/// Read one key from `config.toml`. `Ok(None)` means the key is valid but unset.
+pub fn get_config_value(config: &Config, key: &str) -> Result<Option<String>> {
+    let cf = crate::config::load_config_file(&config.base_dir);
+    let value = match key {
+        "repos_dir" => cf.repos_dir,
+        "worktrees_dir" => cf.worktrees_dir,
+        "archive_retention_days" => cf.archive_retention_days.map(|v| v.to_string()),
+        "telegram_bot_token" => cf.telegram_bot_token,
+        "telegram_chat_id" => cf.telegram_chat_id,
+        "harness" => cf.harness,
+        "repo_harness" => cf.repo_harness.as_ref().map(to_json),
+        "break_enabled" => cf.break_enabled.map(|v| v.to_string()),
+        "break_interval_mins" => cf.break_interval_mins.map(|v| v.to_string()),
+        "break_warning_secs" => cf.break_warning_secs.map(|v| v.to_string()),
+        "usage_pattern" => cf.usage_pattern,
+        "show_cost_column" => cf.show_cost_column.map(|v| v.to_string()),
+        "cleanup_on_merge" => cf.cleanup_on_merge,
+        "session_windows" => cf.session_windows.as_ref().map(to_json),
+        "repo_session_windows" => cf.repo_session_windows.as_ref().map(to_json),
+        "system_preamble" => cf.system_preamble,
+        "repo_system_preamble" => cf.repo_system_preamble.as_ref().map(to_json),
+        "agent_idle_secs" => cf.agent_idle_secs.map(|v| v.to_string()),
+        "agent_idle_notify" => cf.agent_idle_notify.map(|v| v.to_string()),
+        "task_sort" => cf.task_sort,
+        "agent_nice" => cf.agent_nice.map(|v| v.to_string()),
+        "agent_memory_limit_mb" => cf.agent_memory_limit_mb.map(|v| v.to_string()),
+        "checkpoint_interval_mins" => cf.checkpoint_interval_mins.map(|v| v.to_string()),
+        "webhook_url" => cf.webhook_url,
+        "refresh_secs" => cf.refresh_secs.map(|v| v.to_string()),
+        "show_dashboard_on_start" => cf.show_dashboard_on_start.map(|v| v.to_string()),
+        "compact_command" => cf.compact_command,
+        "input_pattern" => cf.input_pattern,
+        "repo_setup_script" => cf.repo_setup_script.as_ref().map(to_json),
+        "allow_setup_file" => cf.allow_setup_file.map(|v| v.to_string()),
+        _ => return Err(unknown_config_key(key)),
+    };
+    Ok(value)
+}
+
+/// All known keys with their current values (`None` when unset).
+pub fn list_config_values(config: &Config) -> Vec<(&'static str, Option<String>)> {
+    CONFIG_KEYS
+        .iter()
+        .map(|key| (*key, get_config_value(config, key).ok().flatten()))
+        .collect()
+}
+
+/// Validate and write one key to `config.toml`, preserving the others.
+pub fn set_config_value(config: &Config, key: &str, value: &str) -> Result<()> {
+    fn parse_u64(key: &str, value: &str) -> Result<u64> {
+        value
+            .parse()
+            .with_context(|| format!("{key} must be a non-negative integer"))
+    }
+    fn parse_bool(key: &str, value: &str) -> Result<bool> {
+        value
+            .parse()
+            .with_context(|| format!("{key} must be true or false"))
+    }
+
+    if STRUCTURED_CONFIG_KEYS.contains(&key) {
+        bail!("{key} is a map/list key; edit config.toml to change it");
+    }
+    let mut cf = crate::config::load_config_file(&config.base_dir);
+    match key {
+        "repos_dir" => {
+            if !Path::new(value).is_dir() {
+                bail!("repos_dir '{}' is not an existing directory", value);
+            }
+            cf.repos_dir = Some(value.to_string());
+        }
+        "worktrees_dir" => {
+            if !Path::new(value).is_dir() {
+                bail!("worktrees_dir '{}' is not an existing directory", value);
+            }
+            cf.worktrees_dir = Some(value.to_string());
+        }
+        "archive_retention_days" => {
+            let days = parse_u64(key, value)?;
+            if !(7..=365).contains(&days) {
+                bail!("archive_retention_days must be between 7 and 365");
+            }
+            cf.archive_retention_days = Some(days);
+        }
+        "telegram_bot_token" => cf.telegram_bot_token = Some(value.to_string()),
+        "telegram_chat_id" => cf.telegram_chat_id = Some(value.to_string()),
+        "harness" => {
+            let Ok(kind) = value.parse::<HarnessKind>() else {
+                bail!(
+                    "harness must be one of: {}",
+                    HarnessKind::ALL
+                        .iter()
+                        .map(|k| k.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            };
+            cf.harness = Some(kind.as_str().to_string());
+        }
+        "break_enabled" => cf.break_enabled = Some(parse_bool(key, value)?),
+        "break_interval_mins" => cf.break_interval_mins = Some(parse_u64(key, value)?),
+        "break_warning_secs" => cf.break_warning_secs = Some(parse_u64(key, value)?),
+        "usage_pattern" => {
+            usage::compile_pattern(Some(value))?;
+            cf.usage_pattern = Some(value.to_string());
+        }
+        "show_cost_column" => cf.show_cost_column = Some(parse_bool(key, value)?),
+        "cleanup_on_merge" => {
+            let Some(cleanup) = MergeCleanup::parse(value) else {
+                bail!(
+                    "cleanup_on_merge must be one of: {}",
+                    MergeCleanup::ALL.map(|c| c.as_str()).join(", ")
+                );
+            };
+            cf.cleanup_on_merge = Some(cleanup.as_str().to_string());
+        }
+        "system_preamble" => cf.system_preamble = Some(value.to_string()),
+        "agent_idle_secs" => cf.agent_idle_secs = Some(parse_u64(key, value)?),
+        "agent_idle_notify" => cf.agent_idle_notify = Some(parse_bool(key, value)?),
+        "task_sort" => {
+            let Some(sort) = TaskSort::parse(value) else {
+                bail!(
+                    "task_sort must be one of: {}",
+                    TaskSort::ALL.map(|s| s.as_str()).join(", ")
+                );
+            };
+            cf.task_sort = Some(sort.as_str().to_string());
+        }
+        "agent_nice" => {
+            let nice = parse_u64(key, value)?;
+            if nice > 19 {
+                bail!("agent_nice must be between 0 and 19");
+            }
+            cf.agent_nice = Some(nice);
+        }
+        "agent_memory_limit_mb" => cf.agent_memory_limit_mb = Some(parse_u64(key, value)?),
+        "checkpoint_interval_mins" => cf.checkpoint_interval_mins = Some(parse_u64(key, value)?),
+        "webhook_url" => {
+            if !(value.starts_with("http://") || value.starts_with("https://")) {
+                bail!("webhook_url must start with http:// or https://");
+            }
+            cf.webhook_url = Some(value.to_string());
+        }
+        "refresh_secs" => {
+            let secs = parse_u64(key, value)?;
+            if !(1..=300).contains(&secs) {
+                bail!("refresh_secs must be between 1 and 300");
+            }
+            cf.refresh_secs = Some(secs);
+        }
+        "show_dashboard_on_start" => cf.show_dashboard_on_start = Some(parse_bool(key, value)?),
+        "compact_command" => cf.compact_command = Some(value.to_string()),
+        "input_pattern" => {
+            if let Err(e) = regex::Regex::new(value) {
+                bail!("input_pattern is not a valid regex: {e}");
+            }
+            cf.input_pattern = Some(value.to_string());
+        }
+        "allow_setup_file" => cf.allow_setup_file = Some(parse_bool(key, value)?),
+        _ => return Err(unknown_config_key(key)),
+    }
+    tracing::info!(key, "config value set via CLI");
+    crate::config::save_config_file(&config.base_dir, &cf)
+}
+
+// ---------------------------------------------------------------------------
+// Moved repo / missing worktree repair
+// ---------------------------------------------------------------------------
+
+/// Whether any of the task's repos no longer exists where agman looks for
+/// it, e.g. because it was moved on disk.
+pub fn task_repo_missing(config: &Config, task: &Task) -> bool {
+    task.meta.repos.iter().any(|repo| {
+        !config
+            .repo_path_for(task.meta.parent_dir.as_deref(), &repo.repo_name)
+            .exists()
+    })
+}
+
+/// Point a task at its repo's new location after the repo moved on disk.
+/// `new_location` is the repo itself for single-repo tasks and the
+/// directory holding the repos for multi-repo tasks. Worktrees that moved
+/// along with it are rebased onto the new location and re-linked with
+/// `git worktree repair`; ones still missing can be recreated with `W`.
+/// Returns how many worktrees resolve afterwards.
+pub fn relocate_task_repo(config: &Config, task_id: &str, new_location: &Path) -> Result<usize> {
+    let mut task = Task::load_by_id(config, task_id)?;
+    let old_parent = task
+        .meta
+        .parent_dir
+        .clone()
+        .unwrap_or_else(|| config.repos_dir.clone());
+
+    let new_parent_dir = if task.meta.is_multi_repo() {
+        if classify_directory(new_location) != DirKind::MultiRepoParent {
+            bail!("{} contains no git repos", new_location.display());
+        }
+        Some(new_location.to_path_buf())
+    } else {
+        if classify_directory(new_location) != DirKind::GitRepo {
+            bail!("{} is not a git repo", new_location.display());
+        }
+        let repo_name = &task.meta.primary_repo().repo_name;
+        if new_location.file_name().and_then(|n| n.to_str()) != Some(repo_name.as_str()) {
+            bail!("agman finds repos by name: the directory must still be called '{repo_name}'");
+        }
+        let parent = new_location
+            .parent()
+            .context("repo path has no parent directory")?;
+        (parent != config.repos_dir).then(|| parent.to_path_buf())
+    };
+    let new_parent = new_parent_dir
+        .clone()
+        .unwrap_or_else(|| config.repos_dir.clone());
+
+    for repo in &task.meta.repos {
+        let repo_path = config.repo_path_for(Some(&new_parent), &repo.repo_name);
+        if classify_directory(&repo_path) != DirKind::GitRepo {
+            bail!(
+                "repo '{}' not found at {}",
+                repo.repo_name,
+                repo_path.display()
+            );
+        }
+    }
+
+    tracing::info!(
+        task_id,
+        from = %old_parent.display(),
+        to = %new_parent.display(),
+        "relocating task repo"
+    );
+    task.meta.parent_dir = new_parent_dir;
+    let mut resolved = 0;
+    for repo in task.meta.repos.iter_mut() {
+        if !repo.worktree_path.exists() {
+            if let Ok(rest) = repo.worktree_path.strip_prefix(&old_parent) {
+                let moved = new_parent.join(rest);
+                if moved.exists() {
+                    repo.worktree_path = moved;
+                }
+            }
+        }
+        if !repo.worktree_path.exists() {
+            continue;
+        }
+        let repo_path = config.repo_path_for(Some(&new_parent), &repo.repo_name);
+        if let Err(e) = Git::repair_worktree(&repo_path, &repo.worktree_path) {
+            tracing::warn!(task_id, repo = %repo.repo_name, error = %e, "worktree repair failed");
+            continue;
+        }
+        resolved += 1;
+    }
+    task.meta.updated_at = Utc::now();
+    task.save_meta()?;
+    Ok(resolved)
+}
+
+/// Re-create any worktrees of a task that no longer exist on disk by
+/// checking the recorded branch out again. Returns the number recreated.
+pub fn recreate_task_worktrees(config: &Config, task_id: &str) -> Result<usize> {
+    let mut task = Task::load_by_id(config, task_id)?;
+    let branch = task.meta.branch_name.clone();
+    let parent_dir = task.meta.parent_dir.clone();
+    let mut recreated = 0;
+    for repo in task.meta.repos.iter_mut() {
+        if repo.worktree_path.exists() {
+            continue;
+        }
+        let repo_path = config.repo_path_for(parent_dir.as_deref(), &repo.repo_name);
+        if !repo_path.exists() {
+            bail!(
+                "repo '{}' not found at {}",
+                repo.repo_name,
+                repo_path.display()
+            );
+        }
+        // Drop git's stale registration so the branch can be checked out again
+        Git::remove_worktree(&repo_path, &repo.worktree_path)?;
+        let path = Git::create_worktree_for_existing_branch_quiet(
+            config,
+            &repo.repo_name,
+            &branch,
+            parent_dir.as_deref(),
+        )?;
+        let _ = Git::direnv_allow(&path);
+        tracing::info!(task_id, repo = %repo.repo_name, path = %path.display(), "recreated missing worktree");
+        repo.worktree_path = path;
+        recreated += 1;
+    }
+    if recreated > 0 {
+        task.save_meta()?;
+    }
+    Ok(recreated)
+}
+
+// ---------------------------------------------------------------------------
+// Task export / import
+// ---------------------------------------------------------------------------
+
+/// Bundle a task's directory (meta.json, notes, logs, inbox) into a gzipped
+/// tarball at `dest`. Worktrees live under the repo, not the task dir, so
+/// they are never included.
+pub fn export_task(config: &Config, task_id: &str, dest: &Path) -> Result<()> {
+    let task = Task::load_by_id(config, task_id)?;
+    let dir_name = task
+        .dir
+        .file_name()
+        .context("task directory has no name")?
+        .to_owned();
+
+    let output = Command::new("tar")
+        .arg("-czf")
+        .arg(dest)
+        .arg("-C")
+        .arg(&config.tasks_dir)
+        .arg(&dir_name)
+        .output()
+        .context("failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    tracing::info!(task_id = %task.meta.task_id(), dest = %dest.display(), "exported task");
+    Ok(())
+}
+
+/// Result of `import_task`: the imported task id plus any repos whose
+/// worktree could not be re-established.
+pub struct ImportedTask {
+    pub task_id: String,
+    pub warnings: Vec<String>,
+}
+
+/// Unpack a tarball written by `export_task` into the tasks directory and,
+/// for every repo that exists locally, re-create the worktree from the
+/// recorded branch. Missing repos or branches are reported as warnings
+/// rather than failing the import.
+pub fn import_task(config: &Config, archive: &Path) -> Result<ImportedTask> {
+    let output = Command::new("tar")
+        .arg("-tzf")
+        .arg(archive)
+        .output()
+        .context("failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "not a readable task archive: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let mut dir_name: Option<&str> = None;
+    for entry in listing.lines().filter(|l| !l.is_empty()) {
+        let path = Path::new(entry);
+        if path.is_absolute()
+            || path
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        {
+            bail!("refusing to import archive with unsafe path '{}'", entry);
+        }
+        let top = entry.split('/').next().unwrap_or(entry);
+        match dir_name {
+            None => dir_name = Some(top),
+            Some(existing) if existing != top => {
+                bail!("archive must contain exactly one task directory")
+            }
+            Some(_) => {}
+        }
+    }
+    let Some(dir_name) = dir_name else {
+        bail!("archive is empty");
+    };
+    if !listing
+        .lines()
+        .any(|l| l.trim_end_matches('/') == format!("{}/meta.json", dir_name))
+    {
+        bail!("archive has no {}/meta.json", dir_name);
+    }
+
+    // A task directory only holds plain files and directories. A symlink or
+    // hard link could point extraction (or later writes) outside it, so the
+    // type column of the verbose listing must be `-` or `d` for every entry.
+    let output = Command::new("tar")
+        .arg("-tvzf")
+        .arg(archive)
+        .output()
+        .context("failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "not a readable task archive: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let verbose = String::from_utf8_lossy(&output.stdout);
+    if let Some(entry) = verbose
+        .lines()
+        .filter(|l| !l.is_empty())
+        .find(|l| !l.starts_with(['-', 'd']))
+    {
+        bail!(
+            "refusing to import archive with a link or special file: {}",
+            entry
+        );
+    }
+
+    let task_dir = config.tasks_dir.join(dir_name);
+    if task_dir.exists() {
+        bail!("task '{}' already exists", dir_name);
+    }
+    std::fs::create_dir_all(&config.tasks_dir)?;
+
+    let output = Command::new("tar")
+        .arg("-xzf")
+        .arg(archive)
+        .arg("-C")
+        .arg(&config.tasks_dir)
+        .output()
+        .context("failed to run tar")?;
+    if !output.status.success() {
+        let _ = std::fs::remove_dir_all(&task_dir);
+        bail!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let mut task = match Task::load_by_id(config, dir_name) {
+        Ok(task) => task,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&task_dir);
+            return Err(e.context("imported archive has invalid task metadata"));
+        }
+    };
+
+    let branch = task.meta.branch_name.clone();
+    let parent_dir = task.meta.parent_dir.clone();
+    let mut warnings = Vec::new();
+    for repo in task.meta.repos.iter_mut() {
+        let repo_path = config.repo_path_for(parent_dir.as_deref(), &repo.repo_name);
+        if !repo_path.exists() {
+            warnings.push(format!(
+                "repo '{}' not found at {}; worktree not created",
+                repo.repo_name,
+                repo_path.display()
+            ));
+            continue;
+        }
+        let existing = Git::find_worktree_for_branch(&repo_path, &branch).unwrap_or(None);
+        let result = match existing {
+            Some(path) => Ok(path),
+            None => Git::create_worktree_for_existing_branch_quiet(
+                config,
+                &repo.repo_name,
+                &branch,
+                parent_dir.as_deref(),
+            ),
+        };
+        match result {
+            Ok(path) => {
+                let _ = Git::direnv_allow(&path);
+                repo.worktree_path = path;
+            }
+            Err(e) => warnings.push(format!(
+                "repo '{}': could not check out branch '{}': {}",
+                repo.repo_name, branch, e
+            )),
+        }
+    }
+    task.save_meta()?;
+
+    let task_id = task.meta.task_id();
+    tracing::info!(task_id = %task_id, warnings = warnings.len(), "imported task");
+    Ok(ImportedTask { task_id, warnings })
+}
+
 // ---------------------------------------------------------------------------
 // Default system prompts
 // ---------------------------------------------------------------------------
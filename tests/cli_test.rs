@@ -112,3 +112,32 @@ fn cli_role_agent_help_exposes_first_prompt_not_description() {
         );
     }
 }
+
+#[test]
+fn cli_help_exposes_restart_task() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_agman"))
+        .args(["restart-task", "--help"])
+        .output()
+        .expect("failed to run agman restart-task --help");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("help output should be utf8");
+
+    assert!(stdout.contains("Relaunch a task's engineer"));
+    assert!(stdout.contains("<TASK_ID>"));
+}
+
+#[test]
+fn cli_help_exposes_config_subcommands() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_agman"))
+        .args(["config", "--help"])
+        .output()
+        .expect("failed to run agman config --help");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("help output should be utf8");
+
+    for sub in ["list", "get", "set"] {
+        assert!(stdout.contains(sub), "missing {sub} in config help");
+    }
+}
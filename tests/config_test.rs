@@ -2,7 +2,9 @@ mod helpers;
 
 use agman::config::Config;
 use agman::harness::HarnessKind;
+use agman::use_cases::{self, BreakState};
 use helpers::test_config;
+use std::time::Duration;
 
 #[test]
 fn config_new_sets_paths() {
@@ -51,6 +53,23 @@ fn config_worktree_path_with_slash() {
     assert_eq!(path, tmp.path().join("repos/myrepo-wt/feat-foo"));
 }
 
+#[test]
+fn worktrees_dir_relocates_worktrees_namespaced_by_repo() {
+    let tmp = tempfile::tempdir().unwrap();
+    let mut config = test_config(&tmp);
+    config.worktrees_dir = Some(tmp.path().join("fast-disk"));
+
+    assert_eq!(
+        config.worktree_path("myrepo", "feat/foo"),
+        tmp.path().join("fast-disk/myrepo/feat-foo")
+    );
+    // Overrides the beside-the-repo layout for repos outside repos_dir too
+    assert_eq!(
+        config.worktree_path_for(Some(&tmp.path().join("elsewhere")), "other", "main"),
+        tmp.path().join("fast-disk/other/main")
+    );
+}
+
 #[test]
 fn config_task_id_with_slash() {
     assert_eq!(Config::task_id("repo", "feat/foo"), "repo--feat-foo");
@@ -166,3 +185,190 @@ fn config_init_default_files() {
         .unwrap()
         .contains("long-lived task-attached engineer"));
 }
+
+#[test]
+fn customized_default_files_lists_only_edited_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    assert!(config.customized_default_files().is_empty());
+
+    config.init_default_files(false).unwrap();
+    assert!(config.customized_default_files().is_empty());
+
+    let engineer = config.prompt_path("engineer");
+    std::fs::write(&engineer, "my own prompt\n").unwrap();
+    let customized: Vec<_> = config
+        .customized_default_files()
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(customized, vec![engineer.clone()]);
+
+    config.init_default_files(false).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&engineer).unwrap(),
+        "my own prompt\n"
+    );
+    config.init_default_files(true).unwrap();
+    assert!(config.customized_default_files().is_empty());
+}
+
+#[test]
+fn break_settings_default_disabled_and_read_from_config() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    config.ensure_dirs().unwrap();
+
+    let defaults = use_cases::load_break_settings(&config);
+    assert!(!defaults.enabled);
+    assert_eq!(defaults.interval, Duration::from_secs(50 * 60));
+
+    std::fs::write(
+        config.base_dir.join("config.toml"),
+        "break_enabled = true\nbreak_interval_mins = 25\nbreak_warning_secs = 60\n",
+    )
+    .unwrap();
+    let settings = use_cases::load_break_settings(&config);
+    assert!(settings.enabled);
+    assert_eq!(settings.interval, Duration::from_secs(25 * 60));
+
+    assert_eq!(
+        use_cases::classify_break(&settings, Duration::from_secs(60)),
+        BreakState::Hidden
+    );
+    assert_eq!(
+        use_cases::classify_break(&settings, Duration::from_secs(24 * 60 + 30)),
+        BreakState::Warning { remaining_secs: 30 }
+    );
+    assert_eq!(
+        use_cases::classify_break(&settings, Duration::from_secs(25 * 60)),
+        BreakState::Due
+    );
+    assert_eq!(
+        use_cases::classify_break(&defaults, Duration::from_secs(24 * 60 * 60)),
+        BreakState::Hidden
+    );
+}
+
+#[test]
+fn session_windows_prefer_repo_override_then_global_then_default() {
+    use agman::tmux::SessionWindow;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    config.ensure_dirs().unwrap();
+
+    assert_eq!(
+        config.session_windows("backend"),
+        SessionWindow::default_layout()
+    );
+
+    std::fs::write(
+        config.base_dir.join("config.toml"),
+        r#"
+[[session_windows]]
+name = "editor"
+command = "hx"
+
+[[session_windows]]
+name = "shell"
+
+[repo_session_windows]
+backend = [{ name = "review", command = "gh pr view" }]
+"#,
+    )
+    .unwrap();
+
+    let global = config.session_windows("frontend");
+    assert_eq!(
+        global
+            .iter()
+            .map(|w| (w.name.as_str(), w.command.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![("editor", Some("hx")), ("shell", None)]
+    );
+    let backend = config.session_windows("backend");
+    assert_eq!(backend.len(), 1);
+    assert_eq!(backend[0].name, "review");
+}
+
+#[test]
+fn system_preamble_prefers_repo_override_over_global() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    config.ensure_dirs().unwrap();
+
+    assert_eq!(config.system_preamble(Some("backend")), None);
+
+    std::fs::write(
+        config.base_dir.join("config.toml"),
+        r#"
+system_preamble = "Always run the tests."
+
+[repo_system_preamble]
+backend = "Use sqlx, never raw SQL strings."
+blank = "  "
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.system_preamble(Some("backend")).as_deref(),
+        Some("Use sqlx, never raw SQL strings.")
+    );
+    assert_eq!(
+        config.system_preamble(Some("frontend")).as_deref(),
+        Some("Always run the tests.")
+    );
+    assert_eq!(
+        config.system_preamble(None).as_deref(),
+        Some("Always run the tests.")
+    );
+    // A blank repo override suppresses the preamble for that repo
+    assert_eq!(config.system_preamble(Some("blank")), None);
+
+    let prompt = agman::use_cases::with_system_preamble(
+        "You are an engineer.".to_string(),
+        config.system_preamble(Some("backend")),
+    );
+    assert!(prompt.starts_with("# House Rules\n\nUse sqlx"));
+    assert!(prompt.ends_with("You are an engineer."));
+}
+
+#[test]
+fn harness_prefers_repo_override_over_global() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    config.ensure_dirs().unwrap();
+
+    assert_eq!(
+        config.harness_kind_for_repo(Some("backend")),
+        HarnessKind::Claude
+    );
+
+    std::fs::write(
+        config.base_dir.join("config.toml"),
+        r#"
+harness = "codex"
+
+[repo_harness]
+backend = "goose"
+typo = "not-a-harness"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.harness_kind_for_repo(Some("backend")),
+        HarnessKind::Goose
+    );
+    assert_eq!(
+        config.harness_kind_for_repo(Some("frontend")),
+        HarnessKind::Codex
+    );
+    assert_eq!(
+        config.harness_kind_for_repo(Some("typo")),
+        HarnessKind::Codex
+    );
+    assert_eq!(config.harness_kind(), HarnessKind::Codex);
+}
@@ -87,6 +87,7 @@ fn backwards_compatible_load_from_legacy_map_format() {
 fn prune_older_than_removes_old_entries() {
     let mut dn = DismissedNotifications {
         ids: std::collections::HashMap::new(),
+        snoozed: std::collections::HashMap::new(),
     };
 
     // Insert an entry with a timestamp from 4 weeks ago
@@ -119,6 +120,7 @@ fn prune_older_than_removes_old_entries() {
 fn prune_older_than_keeps_all_when_none_expired() {
     let mut dn = DismissedNotifications {
         ids: std::collections::HashMap::new(),
+        snoozed: std::collections::HashMap::new(),
     };
 
     dn.insert("thread-1".to_string(), "2025-06-01T00:00:00Z".to_string());
@@ -134,6 +136,7 @@ fn prune_older_than_keeps_all_when_none_expired() {
 fn should_undismiss_when_notification_has_new_activity() {
     let mut dn = DismissedNotifications {
         ids: std::collections::HashMap::new(),
+        snoozed: std::collections::HashMap::new(),
     };
 
     // Dismiss a notification with updated_at = T1
@@ -154,3 +157,26 @@ fn should_undismiss_when_notification_has_new_activity() {
     // Unknown thread, unread — should NOT un-dismiss
     assert!(!dn.should_undismiss("thread-unknown", "2025-06-01T11:00:00Z", true));
 }
+
+#[test]
+fn snooze_hides_until_expiry_and_survives_reload() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("dismissed.json");
+
+    let mut dn = DismissedNotifications::load(&path);
+    dn.snooze("later".to_string(), Utc::now() + Duration::hours(4));
+    dn.snooze("expired".to_string(), Utc::now() - Duration::minutes(1));
+    assert!(dn.is_snoozed("later"));
+    assert!(!dn.is_snoozed("expired"));
+    // Snoozing is not dismissal
+    assert!(!dn.contains("later"));
+
+    dn.save(&path);
+    let mut loaded = DismissedNotifications::load(&path);
+    assert!(loaded.is_snoozed("later"));
+    assert_eq!(loaded.prune_expired_snoozes(), 1);
+    assert_eq!(loaded.snoozed.len(), 1);
+    // Retention pruning leaves snoozes alone
+    loaded.prune_older_than(Duration::weeks(3));
+    assert!(loaded.is_snoozed("later"));
+}
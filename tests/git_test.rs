@@ -198,3 +198,112 @@ fn git_create_worktree_with_custom_base_ref() {
     // side.txt should be present (inherited from the side branch)
     assert!(worktree_path.join("side.txt").exists());
 }
+
+#[test]
+fn git_rebase_onto_moves_branch_and_aborts_on_conflict() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo_path = init_test_repo(&tmp, "myrepo");
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        let out = std::process::Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+
+    let worktree =
+        Git::create_worktree_quiet(&config, "myrepo", "feat", Some("main"), None).unwrap();
+    std::fs::write(worktree.join("feature.txt"), "feature\n").unwrap();
+    git(&worktree, &["add", "."]);
+    git(&worktree, &["commit", "-m", "feature"]);
+
+    std::fs::write(repo_path.join("upstream.txt"), "upstream\n").unwrap();
+    git(&repo_path, &["add", "."]);
+    git(&repo_path, &["commit", "-m", "upstream"]);
+
+    Git::rebase_onto(&worktree, "main").unwrap();
+    assert!(worktree.join("upstream.txt").exists());
+    let main_head = git(&repo_path, &["rev-parse", "main"]);
+    assert_eq!(git(&worktree, &["rev-parse", "HEAD~1"]), main_head);
+
+    // Conflicting edits to the same file: rebase fails and is aborted.
+    std::fs::write(worktree.join("README.md"), "feature side\n").unwrap();
+    git(&worktree, &["commit", "-am", "feature readme"]);
+    std::fs::write(repo_path.join("README.md"), "main side\n").unwrap();
+    git(&repo_path, &["commit", "-am", "main readme"]);
+    let before = git(&worktree, &["rev-parse", "HEAD"]);
+
+    assert!(Git::rebase_onto(&worktree, "main").is_err());
+    assert_eq!(git(&worktree, &["rev-parse", "HEAD"]), before);
+    assert_eq!(git(&worktree, &["status", "--porcelain"]), "");
+}
+
+#[test]
+fn checkpoint_snapshots_worktree_without_touching_branch_or_index() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_path = init_test_repo(&tmp, "myrepo");
+    let git = |args: &[&str]| {
+        let out = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+    let ref_name = "refs/agman/checkpoints/main";
+    let head = git(&["rev-parse", "HEAD"]);
+
+    assert_eq!(Git::checkpoint(&repo_path, ref_name).unwrap(), None);
+
+    std::fs::write(repo_path.join("README.md"), "edited\n").unwrap();
+    std::fs::write(repo_path.join("new.txt"), "untracked\n").unwrap();
+    let first = Git::checkpoint(&repo_path, ref_name).unwrap().unwrap();
+    assert_eq!(git(&["rev-parse", ref_name]), first);
+    assert_eq!(git(&["rev-parse", &format!("{first}^")]), head);
+    assert_eq!(git(&["show", &format!("{first}:new.txt")]), "untracked");
+
+    // Branch, index and working tree are untouched.
+    assert_eq!(git(&["rev-parse", "HEAD"]), head);
+    assert_eq!(git(&["diff", "--cached", "--name-only"]), "");
+    assert_eq!(git(&["status", "--porcelain"]), "M README.md\n?? new.txt");
+
+    assert_eq!(Git::checkpoint(&repo_path, ref_name).unwrap(), None);
+    std::fs::write(repo_path.join("new.txt"), "changed\n").unwrap();
+    let second = Git::checkpoint(&repo_path, ref_name).unwrap().unwrap();
+    assert_eq!(git(&["rev-parse", &format!("{second}^")]), first);
+}
+
+#[test]
+fn commits_ahead_counts_commits_on_branch_only() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_path = init_test_repo(&tmp, "myrepo");
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo_path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    git(&["branch", "feature"]);
+    assert_eq!(
+        Git::commits_ahead(&repo_path, "main", "feature").unwrap(),
+        0
+    );
+
+    git(&["checkout", "-q", "feature"]);
+    std::fs::write(repo_path.join("feature.txt"), "work\n").unwrap();
+    git(&["add", "feature.txt"]);
+    git(&["commit", "-q", "-m", "feature work"]);
+    assert_eq!(
+        Git::commits_ahead(&repo_path, "main", "feature").unwrap(),
+        1
+    );
+    assert_eq!(
+        Git::commits_ahead(&repo_path, "feature", "main").unwrap(),
+        0
+    );
+}
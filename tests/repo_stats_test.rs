@@ -1,4 +1,7 @@
-use agman::repo_stats::RepoStats;
+use agman::repo_stats::{repo_task_totals, sort_repo_task_totals, RepoStats, RepoStatsSort};
+use agman::task::{LinkedPr, TaskMeta};
+use chrono::{Duration, Utc};
+use std::path::PathBuf;
 
 #[test]
 fn repo_stats_load_missing_file() {
@@ -24,6 +27,27 @@ fn repo_stats_increment_and_save_roundtrip() {
     assert_eq!(loaded.counts.get("myrepo"), Some(&3));
 }
 
+#[test]
+fn repo_stats_recent_orders_by_last_use_and_loads_old_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("stats.json");
+    std::fs::write(&path, r#"{"counts":{"big":40,"small":1}}"#).unwrap();
+
+    let mut stats = RepoStats::load(&path);
+    assert_eq!(stats.counts.get("big"), Some(&40));
+    assert!(stats.recent(5).is_empty());
+
+    let now = Utc::now();
+    stats.touch("big", now - Duration::days(3));
+    stats.touch("small", now - Duration::hours(1));
+    stats.touch("fresh", now);
+    stats.save(&path);
+
+    let loaded = RepoStats::load(&path);
+    assert_eq!(loaded.recent(5), vec!["fresh", "small", "big"]);
+    assert_eq!(loaded.recent(2), vec!["fresh", "small"]);
+}
+
 #[test]
 fn repo_stats_favorites_sorted() {
     let tmp = tempfile::tempdir().unwrap();
@@ -43,3 +67,47 @@ fn repo_stats_favorites_sorted() {
     assert_eq!(favs[1], ("c".to_string(), 2));
     assert_eq!(favs[2], ("a".to_string(), 1));
 }
+
+#[test]
+fn repo_task_totals_counts_active_archived_and_merged() {
+    let now = Utc::now();
+    let meta = |repo: &str, branch: &str| {
+        let mut meta = TaskMeta::new(
+            repo.to_string(),
+            branch.to_string(),
+            PathBuf::from("/tmp/wt"),
+            "new".to_string(),
+        );
+        meta.created_at = now - Duration::hours(10);
+        meta
+    };
+
+    let active = meta("a", "one");
+    let mut archived = meta("a", "two");
+    archived.archived_at = Some(now);
+    let mut merged_a = meta("a", "three");
+    merged_a.archived_at = Some(now - Duration::hours(6));
+    merged_a.linked_pr = Some(LinkedPr {
+        number: 1,
+        url: "https://github.com/o/a/pull/1".to_string(),
+        owned: true,
+        author: None,
+    });
+    let mut merged_b = meta("b", "four");
+    merged_b.archived_at = Some(now);
+    merged_b.linked_pr = merged_a.linked_pr.clone();
+
+    let mut rows = repo_task_totals(&[active, archived, merged_a, merged_b]);
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].repo_name, "a");
+    assert_eq!(rows[0].active, 1);
+    assert_eq!(rows[0].archived, 2);
+    assert_eq!(rows[0].merged, 1);
+    assert_eq!(rows[0].avg_time_to_merge, Some(Duration::hours(4)));
+    assert_eq!(rows[1].repo_name, "b");
+    assert_eq!(rows[1].active, 0);
+    assert_eq!(rows[1].avg_time_to_merge, Some(Duration::hours(10)));
+
+    sort_repo_task_totals(&mut rows, RepoStatsSort::TimeToMerge);
+    assert_eq!(rows[0].repo_name, "b");
+}
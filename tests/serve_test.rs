@@ -0,0 +1,40 @@
+mod helpers;
+
+use agman::serve::route;
+use helpers::{create_test_task, test_config};
+
+#[test]
+fn route_serves_task_list_detail_and_log_tail() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let task = create_test_task(&config, "repo", "feature");
+    let task_id = task.meta.task_id();
+    std::fs::write(task.dir.join("agent.log"), "one\ntwo\nthree\n").unwrap();
+
+    let list = route(&config, "GET", "/tasks");
+    assert_eq!(list.status, 200);
+    let ids: Vec<&str> = list
+        .body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec![task_id.as_str()]);
+
+    let detail = route(&config, "GET", &format!("/tasks/{task_id}"));
+    assert_eq!(detail.status, 200);
+    assert_eq!(detail.body["branch_name"], "feature");
+
+    let log = route(&config, "GET", &format!("/tasks/{task_id}/log?tail=2"));
+    assert_eq!(log.status, 200);
+    assert_eq!(log.body["lines"], serde_json::json!(["two", "three"]));
+
+    assert_eq!(route(&config, "GET", "/tasks/nope--missing").status, 404);
+    assert_eq!(
+        route(&config, "GET", &format!("/tasks/{task_id}/log?tail=x")).status,
+        400
+    );
+    assert_eq!(route(&config, "POST", "/tasks").status, 405);
+    assert_eq!(route(&config, "GET", "/elsewhere").status, 404);
+}
@@ -0,0 +1,69 @@
+mod helpers;
+
+use agman::usage::{self, UsageTotals};
+use agman::use_cases;
+use helpers::{create_test_task, test_config};
+
+#[test]
+fn parse_usage_sums_default_pattern_matches() {
+    let log = "\
+step one
+input_tokens: 1200 output_tokens: 300
+Total cost: $0.42
+input tokens=800
+output_tokens: 200
+cost: 1.05
+";
+    let pattern = usage::compile_pattern(None).unwrap();
+    let totals = usage::parse_usage(log, &pattern);
+    assert_eq!(
+        totals,
+        UsageTotals {
+            input_tokens: 2000,
+            output_tokens: 500,
+            cost_cents: 147,
+        }
+    );
+    assert_eq!(usage::format_usage(&totals), "$1.47");
+}
+
+#[test]
+fn parse_usage_with_custom_pattern_and_token_only_label() {
+    let pattern =
+        usage::compile_pattern(Some(r"used (?P<input>\d+) in / (?P<output>\d+) out")).unwrap();
+    let totals = usage::parse_usage("used 1000 in / 500 out\nused 10 in / 5 out\n", &pattern);
+    assert_eq!(totals.input_tokens, 1010);
+    assert_eq!(totals.output_tokens, 505);
+    assert_eq!(totals.cost_cents, 0);
+    assert_eq!(usage::format_usage(&totals), "1.5k tok");
+    assert_eq!(usage::format_usage(&UsageTotals::default()), "-");
+
+    assert!(usage::compile_pattern(Some("(unclosed")).is_err());
+}
+
+#[test]
+fn refresh_task_usage_persists_totals_and_uses_config_pattern() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let mut task = create_test_task(&config, "repo", "feat");
+    task.append_agent_log("input_tokens: 100\ncost: $0.10\n")
+        .unwrap();
+
+    let settings = use_cases::load_usage_settings(&config);
+    assert!(!settings.show_cost_column);
+    assert!(use_cases::refresh_task_usage(&mut task, &settings.pattern).unwrap());
+    assert!(!use_cases::refresh_task_usage(&mut task, &settings.pattern).unwrap());
+
+    let reloaded = agman::task::Task::load_by_id(&config, "repo--feat").unwrap();
+    assert_eq!(reloaded.meta.total_input_tokens, 100);
+    assert_eq!(reloaded.meta.total_cost_cents, 10);
+
+    std::fs::write(
+        config.base_dir.join("config.toml"),
+        "show_cost_column = true\nusage_pattern = \"(unclosed\"\n",
+    )
+    .unwrap();
+    let settings = use_cases::load_usage_settings(&config);
+    assert!(settings.show_cost_column);
+    assert_eq!(settings.pattern.as_str(), usage::DEFAULT_USAGE_PATTERN);
+}
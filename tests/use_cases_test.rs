@@ -10,7 +10,7 @@ use agman::use_cases::{self, WorktreeSource};
 use chrono::{Duration, Utc};
 use helpers::{
     create_test_agent, create_test_project, create_test_researcher, create_test_task,
-    init_test_repo, test_config,
+    init_test_repo, init_test_repo_at, test_config,
 };
 
 #[test]
@@ -57,6 +57,205 @@ fn create_task_creates_one_attached_engineer_and_initial_inbox_message() {
     assert!(!messages[0].message.contains("Task goal for"));
 }
 
+#[test]
+fn create_task_suffixes_a_tmux_session_another_task_owns() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _repo = init_test_repo(&tmp, "repo");
+
+    let create = |branch: &str| {
+        use_cases::create_task(
+            &config,
+            "repo",
+            branch,
+            None,
+            "new",
+            WorktreeSource::NewBranch { base_branch: None },
+            None,
+            None,
+        )
+        .unwrap()
+    };
+    let dotted = create("v1.2");
+    let underscored = create("v1_2");
+
+    let first = &dotted.meta.primary_repo().tmux_session;
+    assert_eq!(
+        first,
+        &agman::config::Config::tmux_session_name("repo", "v1_2")
+    );
+    assert_eq!(
+        underscored.meta.primary_repo().tmux_session,
+        format!("{first}-2")
+    );
+    let reloaded = agman::task::Task::load_by_id(&config, "repo--v1_2").unwrap();
+    assert_eq!(
+        reloaded.meta.primary_repo().tmux_session,
+        format!("{first}-2")
+    );
+}
+
+#[test]
+fn create_task_runs_repo_setup_script_in_fresh_worktree() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _repo = init_test_repo(&tmp, "repo");
+    config.ensure_dirs().unwrap();
+    std::fs::write(
+        config.base_dir.join("config.toml"),
+        r#"
+[repo_setup_script]
+repo = "echo bootstrapped && touch .ready"
+"#,
+    )
+    .unwrap();
+
+    let task = use_cases::create_task(
+        &config,
+        "repo",
+        "setup",
+        None,
+        "new",
+        WorktreeSource::NewBranch { base_branch: None },
+        None,
+        None,
+    )
+    .unwrap();
+
+    assert!(task
+        .meta
+        .primary_repo()
+        .worktree_path
+        .join(".ready")
+        .exists());
+    assert!(task.read_agent_log().unwrap().contains("bootstrapped"));
+}
+
+#[test]
+fn relocate_task_repo_rewrites_paths_and_relinks_worktree() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _repo = init_test_repo(&tmp, "repo");
+    config.ensure_dirs().unwrap();
+    let task = use_cases::create_task(
+        &config,
+        "repo",
+        "moving",
+        None,
+        "new",
+        WorktreeSource::NewBranch { base_branch: None },
+        None,
+        None,
+    )
+    .unwrap();
+    let task_id = task.meta.task_id();
+
+    let moved = tmp.path().join("moved");
+    std::fs::create_dir_all(&moved).unwrap();
+    std::fs::rename(config.repos_dir.join("repo"), moved.join("repo")).unwrap();
+    std::fs::rename(config.repos_dir.join("repo-wt"), moved.join("repo-wt")).unwrap();
+    let task = agman::task::Task::load_by_id(&config, &task_id).unwrap();
+    assert!(use_cases::task_repo_missing(&config, &task));
+
+    assert!(use_cases::relocate_task_repo(&config, &task_id, &moved).is_err());
+    let resolved = use_cases::relocate_task_repo(&config, &task_id, &moved.join("repo")).unwrap();
+    assert_eq!(resolved, 1);
+
+    let task = agman::task::Task::load_by_id(&config, &task_id).unwrap();
+    assert!(!use_cases::task_repo_missing(&config, &task));
+    assert_eq!(task.meta.parent_dir.as_deref(), Some(moved.as_path()));
+    let worktree = &task.meta.primary_repo().worktree_path;
+    assert!(worktree.starts_with(&moved));
+    let status = std::process::Command::new("git")
+        .args(["status", "--short"])
+        .current_dir(worktree)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn create_task_fails_when_setup_script_fails() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo = init_test_repo(&tmp, "repo");
+    config.ensure_dirs().unwrap();
+    std::fs::write(
+        config.base_dir.join("config.toml"),
+        r#"
+[repo_setup_script]
+repo = "echo missing node >&2; exit 3"
+"#,
+    )
+    .unwrap();
+
+    let err = use_cases::create_task(
+        &config,
+        "repo",
+        "broken-setup",
+        None,
+        "new",
+        WorktreeSource::NewBranch { base_branch: None },
+        None,
+        None,
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().contains("Setup script for repo failed"));
+    assert!(err.to_string().contains("missing node"));
+    assert!(
+        use_cases::attached_agents_for_task(&config, "repo--broken-setup")
+            .unwrap_or_default()
+            .is_empty()
+    );
+    // Rolled back: no task dir, worktree or new branch left behind.
+    assert!(!config.task_dir("repo", "broken-setup").exists());
+    assert!(!config
+        .worktree_path_for(None, "repo", "broken-setup")
+        .exists());
+    assert!(!agman::git::Git::local_branch_exists(&repo, "broken-setup"));
+}
+
+#[test]
+fn setup_file_from_the_branch_runs_only_when_allowed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo = init_test_repo(&tmp, "repo");
+    std::fs::write(repo.join(".agman-setup.sh"), "touch .ran\n").unwrap();
+    for args in [
+        &["add", ".agman-setup.sh"][..],
+        &["commit", "-m", "setup file"][..],
+    ] {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo)
+            .output()
+            .unwrap();
+    }
+    config.ensure_dirs().unwrap();
+
+    let create = |branch: &str| {
+        use_cases::create_task(
+            &config,
+            "repo",
+            branch,
+            None,
+            "new",
+            WorktreeSource::NewBranch { base_branch: None },
+            None,
+            None,
+        )
+        .unwrap()
+    };
+
+    let task = create("untrusted");
+    assert!(!task.meta.primary_repo().worktree_path.join(".ran").exists());
+
+    use_cases::set_config_value(&config, "allow_setup_file", "true").unwrap();
+    let task = create("trusted");
+    assert!(task.meta.primary_repo().worktree_path.join(".ran").exists());
+}
+
 #[test]
 fn visible_fresh_inbox_message_is_deferred() {
     let now = Utc::now();
@@ -342,6 +541,42 @@ fn multi_repo_no_first_prompt_creates_idle_engineer_with_empty_inbox() {
     assert!(messages.is_empty());
 }
 
+#[test]
+fn multi_repo_task_suffixes_a_parent_session_another_task_owns() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let parent_dir = tmp.path().join("repos");
+    std::fs::create_dir_all(&parent_dir).unwrap();
+
+    let create = |branch: &str| {
+        use_cases::create_multi_repo_task(
+            &config,
+            "repos",
+            branch,
+            None,
+            "new-multi",
+            parent_dir.clone(),
+            None,
+        )
+        .unwrap()
+    };
+    let dotted = create("v1.2");
+    let underscored = create("v1_2");
+
+    let first = dotted.meta.parent_tmux_session();
+    assert!(use_cases::tmux_session_collision_warnings(&dotted).is_empty());
+    let reloaded = agman::task::Task::load_by_id(&config, "repos--v1_2").unwrap();
+    assert_eq!(reloaded.meta.parent_tmux_session(), format!("{first}-2"));
+    assert_eq!(
+        agman::supervisor::supervisor_session(&underscored).unwrap(),
+        format!("{first}-2")
+    );
+    assert_eq!(
+        use_cases::tmux_session_collision_warnings(&underscored).len(),
+        1
+    );
+}
+
 #[test]
 fn create_researcher_with_first_prompt_seeds_one_inbox_message() {
     let tmp = tempfile::tempdir().unwrap();
@@ -429,6 +664,122 @@ fn send_message_targets_specific_attached_engineer() {
     assert!(task_target_err.contains("unknown target"));
 }
 
+#[test]
+fn task_message_history_lists_engineer_messages_oldest_first() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _task = create_test_task(&config, "repo", "branch");
+    let _other = create_test_task(&config, "repo", "other");
+    let engineer = use_cases::attached_engineer_for_task(&config, "repo--branch").unwrap();
+    let other = use_cases::attached_engineer_for_task(&config, "repo--other").unwrap();
+
+    for (name, text) in [
+        (&engineer.meta.name, "first round"),
+        (&other.meta.name, "not this task"),
+        (&engineer.meta.name, "second round"),
+    ] {
+        use_cases::send_message(&config, &format!("engineer:repo--{name}"), "repo", text).unwrap();
+    }
+
+    let history = use_cases::task_message_history(&config, "repo--branch").unwrap();
+    let texts: Vec<&str> = history.iter().map(|m| m.message.as_str()).collect();
+    assert_eq!(texts, vec!["first round", "second round"]);
+}
+
+#[test]
+fn quick_answer_finds_latest_question_and_reaches_engineer_inbox() {
+    let pane = "Should I keep the old API?\nworking...\nUse tabs or spaces?\n\n> \n";
+    assert_eq!(
+        use_cases::pending_question(pane).as_deref(),
+        Some("Use tabs or spaces?")
+    );
+    assert_eq!(use_cases::pending_question("all done\n> "), None);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _task = create_test_task(&config, "repo", "branch");
+    assert!(use_cases::answer_engineer(&config, "repo--branch", "  ").is_err());
+    use_cases::answer_engineer(&config, "repo--branch", "spaces").unwrap();
+
+    let history = use_cases::task_message_history(&config, "repo--branch").unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].from, "user");
+    assert_eq!(history[0].message, "spaces");
+}
+
+#[test]
+fn input_pattern_matches_free_text_prompts_in_pane_tail() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    assert!(use_cases::load_input_pattern(&config).is_none());
+    assert!(use_cases::set_config_value(&config, "input_pattern", "(unclosed").is_err());
+    use_cases::set_config_value(
+        &config,
+        "input_pattern",
+        r"(?i)^(please confirm|waiting for)",
+    )
+    .unwrap();
+
+    let pattern = use_cases::load_input_pattern(&config).unwrap();
+    let pane = "Ran the tests.\nPlease confirm the schema change\n\n> \n";
+    assert_eq!(
+        use_cases::input_request(pane, &pattern).as_deref(),
+        Some("Please confirm the schema change")
+    );
+    assert_eq!(use_cases::input_request("all done\n> ", &pattern), None);
+}
+
+#[test]
+fn transcript_renders_sections_tool_output_and_messages_as_markdown() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let task = create_test_task(&config, "repo", "branch");
+    std::fs::write(
+        task.dir.join("agent.log"),
+        "--- Agent: engineer started at 10:00 ---\n\
+         Looking at the parser.\n\
+         ⏺ Bash(cargo test)\n\
+         \u{20}\u{20}⎿ test result: ok\n\
+         All green.\n\
+         --- Agent: engineer finished at 10:05 ---\n",
+    )
+    .unwrap();
+    use_cases::answer_engineer(&config, "repo--branch", "Ship it\nthen tidy up").unwrap();
+
+    let md = use_cases::task_transcript(&config, "repo--branch").unwrap();
+    assert!(md.starts_with("# Transcript: repo--branch\n"));
+    assert!(md.contains("### Agent: engineer started at 10:00"));
+    assert!(md.contains("```text\n⏺ Bash(cargo test)\n  ⎿ test result: ok\n```"));
+    assert!(md.contains("Looking at the parser.\n"));
+    assert!(md.contains("> **user**"));
+    assert!(md.contains("> Ship it\n> then tidy up\n"));
+}
+
+#[test]
+fn continue_engineer_sends_carry_on_without_feedback() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _task = create_test_task(&config, "repo", "branch");
+    use_cases::continue_engineer(&config, "repo--branch").unwrap();
+
+    let history = use_cases::task_message_history(&config, "repo--branch").unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].from, "user");
+    assert_eq!(history[0].message, use_cases::CONTINUE_MESSAGE);
+}
+
+#[test]
+fn compact_engineer_without_a_running_session_leaves_task_untouched() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _task = create_test_task(&config, "repo", "branch");
+
+    let err = use_cases::compact_engineer(&config, "repo--branch").unwrap_err();
+    assert!(err.to_string().contains("is not running"), "{err}");
+    let task = agman::task::Task::load_by_id(&config, "repo--branch").unwrap();
+    assert!(task.meta.last_compacted_at.is_none());
+}
+
 #[test]
 fn attach_detach_and_move_non_engineer_agents_preserve_single_engineer() {
     let tmp = tempfile::tempdir().unwrap();
@@ -1115,3 +1466,989 @@ fn stale_agent_switch_token_returns_none() {
         None
     );
 }
+
+#[test]
+fn rename_task_branch_moves_worktree_task_dir_and_attachments() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo = init_test_repo(&tmp, "repo");
+
+    let mut task = use_cases::create_task(
+        &config,
+        "repo",
+        "feature",
+        None,
+        "engineer",
+        WorktreeSource::NewBranch { base_branch: None },
+        None,
+        None,
+    )
+    .unwrap();
+    let old_dir = task.dir.clone();
+    let old_worktree = task.meta.primary_repo().worktree_path.clone();
+
+    use_cases::rename_task_branch(&config, &mut task, "feature-renamed").unwrap();
+
+    assert_eq!(task.meta.branch_name, "feature-renamed");
+    assert_eq!(task.meta.task_id(), "repo--feature-renamed");
+    assert!(!old_dir.exists());
+    assert!(config
+        .task_dir("repo", "feature-renamed")
+        .join("meta.json")
+        .exists());
+    assert!(!old_worktree.exists());
+    assert_eq!(
+        task.meta.primary_repo().worktree_path,
+        config.worktree_path("repo", "feature-renamed")
+    );
+    assert!(task.meta.primary_repo().worktree_path.exists());
+    assert!(agman::git::Git::local_branch_exists(
+        &repo,
+        "feature-renamed"
+    ));
+    assert!(!agman::git::Git::local_branch_exists(&repo, "feature"));
+
+    let agents = use_cases::attached_agents_for_task(&config, "repo--feature-renamed").unwrap();
+    assert_eq!(agents.len(), 1);
+    assert!(agents[0].is_engineer());
+
+    let other = create_test_task(&config, "repo", "other");
+    assert!(use_cases::rename_task_branch(&config, &mut task, &other.meta.branch_name).is_err());
+    assert!(use_cases::rename_task_branch(&config, &mut task, "bad name").is_err());
+}
+
+#[test]
+fn rename_task_branch_rolls_back_earlier_repos_when_a_later_one_fails() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let api = init_test_repo(&tmp, "api");
+    let web = init_test_repo(&tmp, "web");
+    let mut task = create_test_task(&config, "api", "feat");
+    use_cases::add_repo_to_task(&config, &mut task, "web").unwrap();
+    let api_worktree = task.meta.repos[0].worktree_path.clone();
+
+    // Something already sits where web's worktree would move to.
+    let blocker = config.worktree_path("web", "feat-renamed");
+    std::fs::create_dir_all(&blocker).unwrap();
+    std::fs::write(blocker.join("file"), "x").unwrap();
+
+    assert!(use_cases::rename_task_branch(&config, &mut task, "feat-renamed").is_err());
+
+    assert_eq!(task.meta.task_id(), "api--feat");
+    assert_eq!(task.meta.repos[0].worktree_path, api_worktree);
+    assert!(api_worktree.exists());
+    for repo in [&api, &web] {
+        assert!(agman::git::Git::local_branch_exists(repo, "feat"));
+        assert!(!agman::git::Git::local_branch_exists(repo, "feat-renamed"));
+    }
+    assert!(config.task_dir("api", "feat").join("meta.json").exists());
+}
+
+#[test]
+fn issue_branch_name_slugifies_title_into_valid_branch() {
+    let issue = use_cases::GithubIssue {
+        number: 42,
+        title: "Fix: login redirect loops on /auth (Safari)!".to_string(),
+        body: "Steps to reproduce".to_string(),
+    };
+    let branch = use_cases::issue_branch_name(&issue);
+    assert_eq!(branch, "42-fix-login-redirect-loops-on-auth-safari");
+    assert!(use_cases::validate_branch_name(&branch).is_ok());
+
+    let prompt = use_cases::issue_first_prompt(&issue);
+    assert!(prompt.starts_with("GitHub issue #42: Fix: login redirect"));
+    assert!(prompt.ends_with("Steps to reproduce"));
+
+    let untitled = use_cases::GithubIssue {
+        number: 7,
+        title: "???".to_string(),
+        body: String::new(),
+    };
+    assert_eq!(use_cases::issue_branch_name(&untitled), "issue-7");
+    assert_eq!(
+        use_cases::issue_first_prompt(&untitled),
+        "GitHub issue #7: ???"
+    );
+}
+
+#[test]
+fn notif_reason_filter_cycles_and_matches_reasons() {
+    use use_cases::NotifReasonFilter;
+
+    let mut filter = NotifReasonFilter::default();
+    assert!(filter.matches("subscribed"));
+
+    filter = filter.next();
+    assert_eq!(filter, NotifReasonFilter::ReviewRequested);
+    assert!(filter.matches("review_requested"));
+    assert!(!filter.matches("mention"));
+
+    filter = filter.next();
+    assert!(filter.matches("mention"));
+    assert!(filter.matches("team_mention"));
+    assert!(!filter.matches("assign"));
+
+    filter = filter.next();
+    assert!(filter.matches("assign"));
+    assert_eq!(filter.next(), NotifReasonFilter::All);
+}
+
+#[test]
+fn repo_name_from_git_url_handles_https_and_ssh() {
+    assert_eq!(
+        use_cases::repo_name_from_git_url("https://github.com/owner/widget.git"),
+        Some("widget".to_string())
+    );
+    assert_eq!(
+        use_cases::repo_name_from_git_url("git@github.com:owner/widget.git"),
+        Some("widget".to_string())
+    );
+    assert_eq!(
+        use_cases::repo_name_from_git_url("https://github.com/owner/widget/"),
+        Some("widget".to_string())
+    );
+    assert_eq!(use_cases::repo_name_from_git_url(""), None);
+}
+
+#[test]
+fn clone_repo_into_repos_dir_clones_and_refuses_existing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let upstream = init_test_repo_at(&tmp.path().join("upstream"), "widget");
+    let url = upstream.to_string_lossy().to_string();
+
+    let mut progress = Vec::new();
+    let (name, path) =
+        use_cases::clone_repo_into_repos_dir(&config, &url, |line| progress.push(line.to_string()))
+            .unwrap();
+    assert_eq!(name, "widget");
+    assert_eq!(path, config.repos_dir.join("widget"));
+    assert_eq!(
+        use_cases::classify_directory(&path),
+        use_cases::DirKind::GitRepo
+    );
+    assert!(path.join("README.md").exists());
+    assert!(progress.iter().any(|l| l.starts_with("Cloning into")));
+
+    assert!(use_cases::clone_repo_into_repos_dir(&config, &url, |_| {}).is_err());
+
+    let marker = tmp.path().join("pwned");
+    let option_url = format!("--upload-pack=touch {}", marker.display());
+    assert!(use_cases::clone_repo_into_repos_dir(&config, &option_url, |_| {}).is_err());
+    assert!(!marker.exists());
+}
+
+#[test]
+fn archive_task_honours_cleanup_on_merge_policy() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo = init_test_repo(&tmp, "repo");
+    assert_eq!(
+        use_cases::load_merge_cleanup(&config),
+        use_cases::MergeCleanup::WorktreeOnly
+    );
+
+    let create = |branch: &str| {
+        let mut task = use_cases::create_task(
+            &config,
+            "repo",
+            branch,
+            None,
+            "engineer",
+            WorktreeSource::NewBranch { base_branch: None },
+            None,
+            None,
+        )
+        .unwrap();
+        task.set_linked_pr(1, "https://github.com/o/r/pull/1".to_string(), true, None)
+            .unwrap();
+        task
+    };
+
+    use_cases::save_merge_cleanup(&config, use_cases::MergeCleanup::KeepAll).unwrap();
+    let mut kept = create("kept");
+    let kept_worktree = kept.meta.primary_repo().worktree_path.clone();
+    use_cases::archive_task(&config, &mut kept, false).unwrap();
+    assert!(kept.meta.archived_at.is_some());
+    assert!(kept_worktree.exists());
+    assert!(agman::git::Git::local_branch_exists(&repo, "kept"));
+
+    use_cases::save_merge_cleanup(&config, use_cases::MergeCleanup::BranchAndWorktree).unwrap();
+    let mut gone = create("gone");
+    let gone_worktree = gone.meta.primary_repo().worktree_path.clone();
+    use_cases::archive_task(&config, &mut gone, false).unwrap();
+    assert!(!gone_worktree.exists());
+    assert!(!agman::git::Git::local_branch_exists(&repo, "gone"));
+
+    // Unmerged work keeps its branch even under branch_and_worktree.
+    let mut open = create("open");
+    let open_worktree = open.meta.primary_repo().worktree_path.clone();
+    let status = std::process::Command::new("git")
+        .current_dir(&open_worktree)
+        .args([
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@t",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "wip",
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    use_cases::archive_task(&config, &mut open, false).unwrap();
+    assert!(!open_worktree.exists());
+    assert!(agman::git::Git::local_branch_exists(&repo, "open"));
+}
+
+#[test]
+fn complete_branch_name_extends_common_prefix_then_cycles_matches() {
+    let branches: Vec<String> = ["feat/login-form", "feat/login-api", "fix/typo"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    assert_eq!(
+        use_cases::complete_branch_name("fe", &branches, 0).as_deref(),
+        Some("feat/login-")
+    );
+    assert_eq!(
+        use_cases::complete_branch_name("fe", &branches, 1).as_deref(),
+        Some("feat/login-form")
+    );
+    assert_eq!(
+        use_cases::complete_branch_name("fe", &branches, 2).as_deref(),
+        Some("feat/login-api")
+    );
+    assert_eq!(
+        use_cases::complete_branch_name("fe", &branches, 3).as_deref(),
+        Some("feat/login-form")
+    );
+    // Prefix can't grow: the first press already cycles.
+    assert_eq!(
+        use_cases::complete_branch_name("feat/login-", &branches, 0).as_deref(),
+        Some("feat/login-form")
+    );
+    assert_eq!(
+        use_cases::complete_branch_name("fi", &branches, 0).as_deref(),
+        Some("fix/typo")
+    );
+    assert_eq!(use_cases::complete_branch_name("nope", &branches, 0), None);
+}
+
+#[test]
+fn config_values_round_trip_and_reject_invalid_input() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+
+    assert_eq!(
+        use_cases::get_config_value(&config, "harness").unwrap(),
+        None
+    );
+    use_cases::set_config_value(&config, "harness", "codex").unwrap();
+    use_cases::set_config_value(&config, "archive_retention_days", "14").unwrap();
+    use_cases::set_config_value(&config, "cleanup_on_merge", "keep_all").unwrap();
+    assert_eq!(
+        use_cases::get_config_value(&config, "harness")
+            .unwrap()
+            .as_deref(),
+        Some("codex")
+    );
+    assert_eq!(use_cases::load_archive_retention(&config), 14);
+    assert_eq!(
+        use_cases::load_merge_cleanup(&config),
+        use_cases::MergeCleanup::KeepAll
+    );
+
+    let listed = use_cases::list_config_values(&config);
+    assert_eq!(listed.len(), use_cases::CONFIG_KEYS.len());
+    assert!(listed.contains(&("harness", Some("codex".to_string()))));
+
+    assert!(use_cases::set_config_value(&config, "harness", "vim").is_err());
+    assert!(use_cases::set_config_value(&config, "archive_retention_days", "3").is_err());
+    assert!(use_cases::set_config_value(&config, "break_enabled", "yes").is_err());
+    assert!(use_cases::set_config_value(
+        &config,
+        "repos_dir",
+        tmp.path().join("missing").to_str().unwrap()
+    )
+    .is_err());
+    let err = use_cases::get_config_value(&config, "nope").unwrap_err();
+    assert!(err.to_string().contains("Valid keys: repos_dir"));
+}
+
+#[test]
+fn structured_config_keys_are_listed_as_json_but_not_settable() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    config.ensure_dirs().unwrap();
+    std::fs::write(
+        config.base_dir.join("config.toml"),
+        r#"
+[[session_windows]]
+name = "shell"
+
+[repo_session_windows]
+backend = [{ name = "review", command = "gh pr view" }]
+
+[repo_harness]
+backend = "codex"
+
+[repo_system_preamble]
+backend = "Run cargo test."
+
+[repo_setup_script]
+backend = "npm install"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        use_cases::get_config_value(&config, "repo_harness")
+            .unwrap()
+            .as_deref(),
+        Some(r#"{"backend":"codex"}"#)
+    );
+    assert_eq!(
+        use_cases::get_config_value(&config, "repo_system_preamble")
+            .unwrap()
+            .as_deref(),
+        Some(r#"{"backend":"Run cargo test."}"#)
+    );
+    assert_eq!(
+        use_cases::get_config_value(&config, "repo_setup_script")
+            .unwrap()
+            .as_deref(),
+        Some(r#"{"backend":"npm install"}"#)
+    );
+    assert_eq!(
+        use_cases::get_config_value(&config, "session_windows")
+            .unwrap()
+            .as_deref(),
+        Some(r#"[{"name":"shell","command":null}]"#)
+    );
+    let listed = use_cases::list_config_values(&config);
+    assert!(listed.contains(&(
+        "repo_session_windows",
+        Some(r#"{"backend":[{"name":"review","command":"gh pr view"}]}"#.to_string())
+    )));
+
+    for key in [
+        "session_windows",
+        "repo_session_windows",
+        "repo_harness",
+        "repo_system_preamble",
+        "repo_setup_script",
+    ] {
+        let err = use_cases::set_config_value(&config, key, "[]").unwrap_err();
+        assert!(err.to_string().contains("edit config.toml"), "{key}: {err}");
+    }
+}
+
+#[test]
+fn webhook_url_is_validated_and_payload_names_the_task() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+
+    assert_eq!(use_cases::load_webhook_url(&config), None);
+    assert!(use_cases::set_config_value(&config, "webhook_url", "hooks.example.com").is_err());
+    use_cases::set_config_value(&config, "webhook_url", "https://hooks.example.com/x").unwrap();
+    assert_eq!(
+        use_cases::load_webhook_url(&config).as_deref(),
+        Some("https://hooks.example.com/x")
+    );
+
+    let payload = use_cases::task_idle_webhook_payload("repo--feat", 300);
+    assert_eq!(payload["event"], "task_idle");
+    assert_eq!(payload["task_id"], "repo--feat");
+    assert_eq!(payload["idle_secs"], 300);
+}
+
+#[test]
+fn export_then_import_task_restores_dir_and_worktree() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo = init_test_repo(&tmp, "repo");
+
+    let task = use_cases::create_task(
+        &config,
+        "repo",
+        "portable",
+        None,
+        "new",
+        WorktreeSource::NewBranch { base_branch: None },
+        None,
+        None,
+    )
+    .unwrap();
+    let task_id = task.meta.task_id();
+    let worktree = task.meta.primary_repo().worktree_path.clone();
+    std::fs::write(task.dir.join("notes.md"), "remember this").unwrap();
+
+    let archive = tmp.path().join("portable.tar.gz");
+    use_cases::export_task(&config, &task_id, &archive).unwrap();
+    assert!(archive.exists());
+
+    agman::git::Git::remove_worktree(&repo, &worktree).unwrap();
+    std::fs::remove_dir_all(&task.dir).unwrap();
+
+    let imported = use_cases::import_task(&config, &archive).unwrap();
+    assert_eq!(imported.task_id, task_id);
+    assert!(imported.warnings.is_empty(), "{:?}", imported.warnings);
+
+    let restored = agman::task::Task::load_by_id(&config, &task_id).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(restored.dir.join("notes.md")).unwrap(),
+        "remember this"
+    );
+    assert!(restored
+        .meta
+        .primary_repo()
+        .worktree_path
+        .join("README.md")
+        .exists());
+
+    let err = use_cases::import_task(&config, &archive).unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+}
+
+#[test]
+fn import_task_refuses_archives_with_links() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let staging = tmp.path().join("staging");
+    let task_dir = staging.join("repo--evil");
+    std::fs::create_dir_all(&task_dir).unwrap();
+    std::fs::write(task_dir.join("meta.json"), "{}").unwrap();
+    std::os::unix::fs::symlink(tmp.path().join("outside"), task_dir.join("notes.md")).unwrap();
+
+    let archive = tmp.path().join("evil.tar.gz");
+    let status = std::process::Command::new("tar")
+        .arg("-czf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&staging)
+        .arg("repo--evil")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let err = use_cases::import_task(&config, &archive).unwrap_err();
+    assert!(err.to_string().contains("link"), "{err}");
+    assert!(!config.tasks_dir.join("repo--evil").exists());
+}
+
+#[test]
+fn gh_rate_limit_detection_and_backoff_doubles_up_to_cap() {
+    assert!(use_cases::is_gh_rate_limited(
+        "gh: You have exceeded a secondary rate limit. Please wait a few minutes."
+    ));
+    assert!(use_cases::is_gh_rate_limited(
+        "API rate limit exceeded for user ID 1"
+    ));
+    assert!(!use_cases::is_gh_rate_limited("HTTP 404: Not Found"));
+
+    let first = use_cases::next_gh_backoff(None);
+    assert_eq!(first, use_cases::GH_BACKOFF_INITIAL);
+    assert_eq!(use_cases::next_gh_backoff(Some(first)), first * 2);
+    assert_eq!(
+        use_cases::next_gh_backoff(Some(use_cases::GH_BACKOFF_MAX)),
+        use_cases::GH_BACKOFF_MAX
+    );
+}
+
+#[test]
+fn note_finder_indexes_nested_notes_and_ranks_fuzzy_matches() {
+    use std::path::PathBuf;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().join("notes");
+    std::fs::create_dir_all(root.join("work/meetings")).unwrap();
+    std::fs::create_dir_all(root.join(".hidden")).unwrap();
+    std::fs::write(root.join("inbox.md"), "").unwrap();
+    std::fs::write(root.join("work/roadmap.md"), "").unwrap();
+    std::fs::write(root.join("work/meetings/standup.md"), "").unwrap();
+    std::fs::write(root.join("work/todo.txt"), "").unwrap();
+    std::fs::write(root.join(".hidden/secret.md"), "").unwrap();
+
+    let index = use_cases::list_notes_recursive(&root).unwrap();
+    assert_eq!(
+        index,
+        vec![
+            PathBuf::from("inbox.md"),
+            PathBuf::from("work/meetings/standup.md"),
+            PathBuf::from("work/roadmap.md"),
+        ]
+    );
+
+    assert_eq!(use_cases::fuzzy_filter_notes(&index, ""), index);
+    assert_eq!(
+        use_cases::fuzzy_filter_notes(&index, "STAND"),
+        vec![PathBuf::from("work/meetings/standup.md")]
+    );
+    // Both match "in", but the contiguous hit at the start of a file name wins.
+    assert_eq!(
+        use_cases::fuzzy_filter_notes(&index, "in"),
+        vec![
+            PathBuf::from("inbox.md"),
+            PathBuf::from("work/meetings/standup.md"),
+        ]
+    );
+    assert!(use_cases::fuzzy_filter_notes(&index, "zzz").is_empty());
+}
+
+#[test]
+fn set_task_base_ref_validates_and_clears() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _repo = init_test_repo(&tmp, "repo");
+    let mut task = create_test_task(&config, "repo", "feat");
+
+    use_cases::set_task_base_ref(&config, &mut task, " main ", false).unwrap();
+    let reloaded = agman::task::Task::load_by_id(&config, &task.meta.task_id()).unwrap();
+    assert_eq!(reloaded.meta.base_ref.as_deref(), Some("main"));
+
+    let err = use_cases::set_task_base_ref(&config, &mut task, "origin/nope", false).unwrap_err();
+    assert!(err.to_string().contains("does not resolve"), "{err}");
+    assert_eq!(task.meta.base_ref.as_deref(), Some("main"));
+
+    use_cases::set_task_base_ref(&config, &mut task, "", false).unwrap();
+    let reloaded = agman::task::Task::load_by_id(&config, &task.meta.task_id()).unwrap();
+    assert_eq!(reloaded.meta.base_ref, None);
+}
+
+#[test]
+fn set_task_label_is_display_only() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let _repo = init_test_repo(&tmp, "repo");
+    let mut task = create_test_task(&config, "repo", "wip-3f9a1c");
+    let task_id = task.meta.task_id();
+
+    use_cases::set_task_label(&mut task, "  Fix login redirect ").unwrap();
+    let reloaded = agman::task::Task::load_by_id(&config, &task_id).unwrap();
+    assert_eq!(reloaded.meta.label.as_deref(), Some("Fix login redirect"));
+    assert_eq!(reloaded.meta.display_name(), "Fix login redirect");
+    assert_eq!(reloaded.meta.task_id(), task_id);
+
+    use_cases::set_task_label(&mut task, "").unwrap();
+    let reloaded = agman::task::Task::load_by_id(&config, &task_id).unwrap();
+    assert_eq!(reloaded.meta.label, None);
+    assert_eq!(reloaded.meta.display_name(), "wip-3f9a1c");
+}
+
+#[test]
+fn sort_tasks_orders_by_each_task_sort_and_persists_default() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let now = chrono::Utc::now();
+    let mut old = create_test_task(&config, "beta", "old");
+    old.meta.created_at = now - chrono::Duration::days(3);
+    old.meta.updated_at = now;
+    let mut pinned = create_test_task(&config, "gamma", "pinned");
+    pinned.meta.created_at = now - chrono::Duration::days(2);
+    pinned.meta.updated_at = now - chrono::Duration::days(2);
+    pinned.meta.pinned = true;
+    let mut fresh = create_test_task(&config, "alpha", "fresh");
+    fresh.meta.created_at = now - chrono::Duration::days(1);
+    fresh.meta.updated_at = now - chrono::Duration::hours(1);
+    let mut tasks = vec![fresh, old, pinned];
+
+    let order = |tasks: &[agman::task::Task]| -> Vec<String> {
+        tasks.iter().map(|t| t.meta.branch_name.clone()).collect()
+    };
+    use_cases::sort_tasks(&mut tasks, use_cases::TaskSort::PinnedThenUpdated);
+    assert_eq!(order(&tasks), ["pinned", "old", "fresh"]);
+    use_cases::sort_tasks(&mut tasks, use_cases::TaskSort::UpdatedDesc);
+    assert_eq!(order(&tasks), ["old", "fresh", "pinned"]);
+    use_cases::sort_tasks(&mut tasks, use_cases::TaskSort::CreatedAsc);
+    assert_eq!(order(&tasks), ["old", "pinned", "fresh"]);
+    use_cases::sort_tasks(&mut tasks, use_cases::TaskSort::Repo);
+    assert_eq!(order(&tasks), ["fresh", "old", "pinned"]);
+
+    assert_eq!(
+        use_cases::load_task_sort(&config),
+        use_cases::TaskSort::PinnedThenUpdated
+    );
+    use_cases::save_task_sort(&config, use_cases::TaskSort::Repo).unwrap();
+    assert_eq!(
+        use_cases::load_task_sort(&config),
+        use_cases::TaskSort::Repo
+    );
+    assert!(use_cases::set_config_value(&config, "task_sort", "priority").is_err());
+}
+
+#[test]
+fn resource_limits_wrap_agent_command_only_when_configured() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let cmd = "claude --system-prompt 'it'\\''s fine'".to_string();
+
+    let limits = use_cases::load_resource_limits(&config);
+    assert_eq!(limits, use_cases::ResourceLimits::default());
+    assert_eq!(use_cases::with_resource_limits(cmd.clone(), limits), cmd);
+
+    use_cases::set_config_value(&config, "agent_nice", "10").unwrap();
+    let limits = use_cases::load_resource_limits(&config);
+    assert_eq!(
+        use_cases::with_resource_limits(cmd.clone(), limits),
+        format!("nice -n 10 env {cmd}")
+    );
+
+    use_cases::set_config_value(&config, "agent_memory_limit_mb", "2048").unwrap();
+    let limits = use_cases::load_resource_limits(&config);
+    let wrapped = use_cases::with_resource_limits(cmd.clone(), limits);
+    assert!(
+        wrapped.starts_with("sh -c 'ulimit -v 2097152; exec nice -n 10 env claude"),
+        "{wrapped}"
+    );
+    let out = std::process::Command::new("sh")
+        .args([
+            "-c",
+            &format!("echo {}", wrapped.trim_start_matches("sh -c ")),
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout).trim(),
+        format!("ulimit -v 2097152; exec nice -n 10 env {cmd}")
+    );
+
+    assert!(use_cases::set_config_value(&config, "agent_nice", "20").is_err());
+}
+
+#[test]
+fn resource_limits_keep_env_prefixed_commands_runnable() {
+    let cmd = "AGMAN_LIMIT_TEST=ok printenv AGMAN_LIMIT_TEST".to_string();
+    let run = |wrapped: String| {
+        let out = std::process::Command::new("sh")
+            .args(["-c", &wrapped])
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+
+    let niced = use_cases::ResourceLimits {
+        nice: Some(5),
+        memory_limit_mb: None,
+    };
+    assert_eq!(
+        run(use_cases::with_resource_limits(cmd.clone(), niced)),
+        "ok"
+    );
+
+    let capped = use_cases::ResourceLimits {
+        nice: None,
+        memory_limit_mb: Some(4096),
+    };
+    assert_eq!(
+        run(use_cases::with_resource_limits(cmd.clone(), capped)),
+        "ok"
+    );
+
+    let both = use_cases::ResourceLimits {
+        nice: Some(5),
+        memory_limit_mb: Some(4096),
+    };
+    assert_eq!(run(use_cases::with_resource_limits(cmd, both)), "ok");
+}
+
+#[test]
+fn repos_ignore_patterns_match_directory_names() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    assert!(use_cases::load_repos_ignore(&config).is_empty());
+
+    std::fs::create_dir_all(&config.base_dir).unwrap();
+    std::fs::write(
+        config.repos_ignore_path(),
+        "# archived stuff\n\narchive-*\nscratch/\nold-v?\n",
+    )
+    .unwrap();
+    let patterns = use_cases::load_repos_ignore(&config);
+    assert_eq!(patterns, ["archive-*", "scratch", "old-v?"]);
+
+    assert!(use_cases::is_repo_ignored("archive-2023", &patterns));
+    assert!(use_cases::is_repo_ignored("scratch", &patterns));
+    assert!(use_cases::is_repo_ignored("old-v1", &patterns));
+    assert!(!use_cases::is_repo_ignored("old-v10", &patterns));
+    assert!(!use_cases::is_repo_ignored("my-archive-tool", &patterns));
+    assert!(!use_cases::is_repo_ignored("agman", &patterns));
+    assert!(use_cases::is_repo_ignored("anything", &["*".to_string()]));
+    assert!(use_cases::is_repo_ignored("a-b-c", &["a*c".to_string()]));
+}
+
+#[test]
+fn gc_store_truncates_big_logs_and_removes_orphans_only_when_asked() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo_path = init_test_repo(&tmp, "repo");
+    let add_worktree = |branch: &str| {
+        let path = config.worktree_path("repo", branch);
+        let status = std::process::Command::new("git")
+            .args(["worktree", "add", "-q", "-b", branch])
+            .arg(&path)
+            .current_dir(&repo_path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        path
+    };
+    let owned = add_worktree("owned");
+    let stray = add_worktree("stray");
+    let dirty = add_worktree("dirty");
+    std::fs::write(dirty.join("wip.txt"), "unsaved").unwrap();
+    let linked = add_worktree("linked");
+    let task = create_test_task(&config, "repo", "owned");
+    // A task that recorded its worktree through a symlinked directory
+    // still owns it.
+    let alias = tmp.path().join("alias");
+    std::os::unix::fs::symlink(linked.parent().unwrap(), &alias).unwrap();
+    let mut linked_task = create_test_task(&config, "repo", "linked");
+    linked_task.meta.repos[0].worktree_path = alias.join(linked.file_name().unwrap());
+    linked_task.save_meta().unwrap();
+    let log_line = "x".repeat(99) + "\n";
+    let big_log = log_line.repeat((use_cases::GC_LOG_MAX_BYTES / 100 + 10) as usize);
+    std::fs::write(task.dir.join("agent.log"), big_log).unwrap();
+
+    let report = use_cases::gc_store(&config, false).unwrap();
+    assert_eq!(report.logs_truncated, 1);
+    let log = std::fs::read_to_string(task.dir.join("agent.log")).unwrap();
+    assert!(log.len() as u64 <= use_cases::GC_LOG_MAX_BYTES);
+    assert!(log.starts_with('x') && log.ends_with('\n'));
+    assert_eq!(
+        report.orphan_worktrees,
+        vec![
+            ("repo".to_string(), dirty.clone()),
+            ("repo".to_string(), stray.clone())
+        ]
+    );
+    assert!(!report.orphans_removed);
+    assert!(stray.exists());
+
+    let report = use_cases::gc_store(&config, true).unwrap();
+    assert_eq!(report.logs_truncated, 0);
+    assert!(report.orphans_removed);
+    assert!(!stray.exists());
+    assert!(owned.exists());
+    assert!(linked.exists());
+    assert_eq!(report.dirty_orphans, vec![dirty.clone()]);
+    assert!(dirty.join("wip.txt").exists());
+    assert_eq!(
+        use_cases::find_orphan_worktrees(&config),
+        vec![("repo".to_string(), dirty)]
+    );
+}
+
+#[test]
+fn task_ahead_behind_counts_against_base() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo_path = init_test_repo(&tmp, "repo");
+    let worktree = config.worktree_path("repo", "feat");
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+    git(
+        &repo_path,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "feat",
+            worktree.to_str().unwrap(),
+        ],
+    );
+    let task = create_test_task(&config, "repo", "feat");
+    assert_eq!(use_cases::task_ahead_behind(&task), Some((0, 0)));
+
+    git(
+        &worktree,
+        &["commit", "-q", "--allow-empty", "-m", "feat work"],
+    );
+    git(
+        &repo_path,
+        &["commit", "-q", "--allow-empty", "-m", "main moved"],
+    );
+    assert_eq!(use_cases::task_ahead_behind(&task), Some((1, 1)));
+}
+
+#[test]
+fn snapshot_tags_list_per_branch_and_restore_head() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo_path = init_test_repo(&tmp, "repo");
+    let worktree = config.worktree_path("repo", "feat");
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+    git(
+        &repo_path,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "feat",
+            worktree.to_str().unwrap(),
+        ],
+    );
+    let task = create_test_task(&config, "repo", "feat");
+    let before = git(&worktree, &["rev-parse", "HEAD"]);
+
+    let tag = use_cases::snapshot_task(&task).unwrap();
+    assert!(tag.starts_with(use_cases::SNAPSHOT_TAG_PREFIX));
+    // A snapshot of another branch whose name extends this one's is not listed.
+    git(
+        &repo_path,
+        &["tag", "agman-snapshot-20260101-000000-feat-x", "HEAD"],
+    );
+    assert_eq!(
+        use_cases::list_task_snapshots(&task).unwrap(),
+        vec![tag.clone()]
+    );
+
+    git(
+        &worktree,
+        &["commit", "-q", "--allow-empty", "-m", "risky agent work"],
+    );
+    assert_ne!(git(&worktree, &["rev-parse", "HEAD"]), before);
+    use_cases::restore_task_snapshot(&task, &tag).unwrap();
+    assert_eq!(git(&worktree, &["rev-parse", "HEAD"]), before);
+}
+
+#[test]
+fn archive_keep_session_leaves_worktree_in_place() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    let repo_path = init_test_repo(&tmp, "repo");
+    let worktree = config.worktree_path("repo", "feat");
+    let status = std::process::Command::new("git")
+        .args(["worktree", "add", "-q", "-b", "feat"])
+        .arg(&worktree)
+        .current_dir(&repo_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let mut task = create_test_task(&config, "repo", "feat");
+
+    use_cases::archive_task_with(
+        &config,
+        &mut task,
+        false,
+        use_cases::ArchiveMode::KeepSession,
+    )
+    .unwrap();
+
+    assert!(task.meta.archived_at.is_some());
+    assert!(worktree.exists());
+    assert!(use_cases::attached_engineer_for_task(&config, "repo--feat").is_err());
+
+    use_cases::permanently_delete_archived_task(&config, task).unwrap();
+    assert!(!worktree.exists());
+    assert!(!agman::git::Git::local_branch_exists(&repo_path, "feat"));
+}
+
+#[test]
+fn log_filters_drop_tools_keep_errors_and_final_messages() {
+    let log = "\
+--- Agent: coder started at 10:00 ---
+Looking at the parser.
+⏺ Bash(cargo test)
+  ⎿  error: 2 tests failed
+Fixed the off-by-one.
+
+All tests pass now.
+--- Agent: coder finished at 10:05 ---";
+
+    let no_tools = use_cases::filter_log(log, use_cases::LogFilter::NoTools);
+    assert!(!no_tools.contains("Bash(cargo test)"));
+    assert!(!no_tools.contains("tests failed"));
+    assert!(no_tools.contains("Looking at the parser."));
+
+    let errors = use_cases::filter_log(log, use_cases::LogFilter::Errors);
+    assert_eq!(errors.lines().count(), 3);
+    assert!(errors.contains("error: 2 tests failed"));
+
+    let last = use_cases::filter_log(log, use_cases::LogFilter::Final);
+    assert_eq!(
+        last.lines().collect::<Vec<_>>(),
+        vec![
+            "--- Agent: coder started at 10:00 ---",
+            "All tests pass now.",
+            "--- Agent: coder finished at 10:05 ---",
+        ]
+    );
+
+    assert_eq!(use_cases::filter_log(log, use_cases::LogFilter::All), log);
+    assert_eq!(
+        use_cases::LogFilter::Final.next(),
+        use_cases::LogFilter::All
+    );
+}
+
+#[test]
+fn add_repo_to_task_checks_out_branch_and_makes_task_multi_repo() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    init_test_repo(&tmp, "api");
+    init_test_repo(&tmp, "web");
+    let mut task = create_test_task(&config, "api", "feat");
+    assert!(!task.meta.is_multi_repo());
+    assert_eq!(
+        use_cases::addable_repos_for_task(&config, &task),
+        vec!["web".to_string()]
+    );
+
+    let entry = use_cases::add_repo_to_task(&config, &mut task, "web").unwrap();
+    assert_eq!(entry.worktree_path, config.worktree_path("web", "feat"));
+    assert!(entry.worktree_path.join("README.md").exists());
+    assert!(use_cases::add_repo_to_task(&config, &mut task, "web").is_err());
+
+    let reloaded = agman::task::Task::load_by_id(&config, "api--feat").unwrap();
+    assert!(reloaded.meta.is_multi_repo());
+    let repos: Vec<&str> = reloaded
+        .meta
+        .repos
+        .iter()
+        .map(|r| r.repo_name.as_str())
+        .collect();
+    assert_eq!(repos, vec!["api", "web"]);
+    assert!(use_cases::addable_repos_for_task(&config, &reloaded).is_empty());
+
+    let history = use_cases::task_message_history(&config, "api--feat").unwrap();
+    assert!(history
+        .iter()
+        .any(|m| m.from == "system" && m.message.contains("web was added")));
+}
+
+#[test]
+fn primary_repo_hint_moves_parent_session_into_the_repo_once_it_joins() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config(&tmp);
+    init_test_repo(&tmp, "api");
+    init_test_repo(&tmp, "web");
+    let mut task = create_test_task(&config, "api", "feat");
+    task.meta.primary_repo_hint = Some("web".to_string());
+    assert_eq!(task.meta.hinted_repo_index(), None);
+    assert_eq!(task.meta.parent_session_dir(), task.meta.parent_dir.clone());
+
+    let entry = use_cases::add_repo_to_task(&config, &mut task, "web").unwrap();
+    assert_eq!(task.meta.hinted_repo_index(), Some(1));
+    assert_eq!(task.meta.parent_session_dir(), Some(entry.worktree_path));
+}